@@ -0,0 +1,95 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+// Files defining the errors listed by the `error_catalog` query
+const SOURCES: &[&str] = &["src/error.rs", "../../packages/manifest-errors/src/lib.rs"];
+
+// Generate the list of every error the contract can fail with, one value per variant of the
+// thiserror enums, so the catalog cannot drift from the definitions
+fn main() {
+    let mut entries = vec![];
+    for source in SOURCES {
+        println!("cargo:rerun-if-changed={source}");
+        let text = fs::read_to_string(source).expect("failed to read error definitions");
+        entries.extend(variants(&text));
+    }
+
+    let mut out = String::from("vec![\n");
+    for entry in entries {
+        out.push_str(&format!("    {entry},\n"));
+    }
+    out.push_str("]\n");
+    let path = Path::new(&env::var("OUT_DIR").unwrap()).join("error_catalog.rs");
+    fs::write(path, out).expect("failed to write error catalog");
+}
+
+// Values of the variants of the enums deriving `Error`, converted to `ContractError`. Variants
+// wrapping a category are skipped, the category being listed on its own, and string fields are
+// set to their `{n}` placeholder
+fn variants(text: &str) -> Vec<String> {
+    let mut entries = vec![];
+    let mut derives_error = false;
+    let mut lines = text.lines();
+    while let Some(line) = lines.next() {
+        let line = line.trim();
+        if line.starts_with("#[derive(") {
+            derives_error = line.contains("Error");
+            continue;
+        }
+        let Some(name) = line
+            .strip_prefix("pub enum ")
+            .and_then(|rest| rest.strip_suffix(" {"))
+        else {
+            continue;
+        };
+        if !derives_error {
+            continue;
+        }
+
+        // Attributes may span several lines, e.g. long `#[error(...)]` messages
+        let mut depth = 0;
+        for line in lines.by_ref() {
+            let line = line.trim();
+            if depth == 0 && line == "}" {
+                break;
+            }
+            if depth > 0 || line.starts_with("#[") {
+                depth += brackets(line);
+                continue;
+            }
+            let Some(variant) = line.strip_suffix(',') else {
+                continue;
+            };
+            let args = match variant.split_once('(') {
+                None => variant.to_string(),
+                Some((_, fields)) if fields.contains("#[from]") => continue,
+                Some((ident, fields)) => {
+                    let fields = fields.trim_end_matches(')').split(',').enumerate();
+                    let args: Vec<_> = fields
+                        .map(|(index, ty)| match ty.trim() {
+                            "String" => format!("\"{{{index}}}\".to_string()"),
+                            _ => "Default::default()".to_string(),
+                        })
+                        .collect();
+                    format!("{ident}({})", args.join(", "))
+                }
+            };
+            entries.push(match name {
+                "ContractError" => format!("ContractError::{args}"),
+                _ => format!("ContractError::from({name}::{args})"),
+            });
+        }
+    }
+    entries
+}
+
+fn brackets(line: &str) -> i32 {
+    line.chars()
+        .map(|c| match c {
+            '(' | '[' => 1,
+            ')' | ']' => -1,
+            _ => 0,
+        })
+        .sum()
+}
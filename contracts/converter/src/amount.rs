@@ -0,0 +1,122 @@
+use crate::error::AmountError::InvalidAmountParsing;
+use crate::error::ContractError;
+use crate::error::ConvertError::UnknownUnit;
+use crate::state::Config;
+use cosmwasm_std::{Coin, Uint256};
+
+// Parse a human-style amount such as "1.5mfx" or "1.5 MFX" into base units. The unit is either
+// a configured denom, the amount then being in base units, or the symbol of one, case
+// insensitive, the amount then being in display units scaled by the denom's exponent
+pub fn parse_amount(config: &Config, input: &str) -> Result<Coin, ContractError> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(input.len());
+    let (number, unit) = (&input[..split], input[split..].trim());
+
+    let denoms = [
+        (
+            config.source_denom.as_str(),
+            config.source_symbol(),
+            config.source_exponent,
+        ),
+        (
+            config.target_denom.as_str(),
+            config.target_symbol(),
+            config.target_exponent,
+        ),
+    ];
+    let (denom, exponent) = denoms
+        .iter()
+        .find(|(denom, _, _)| unit == *denom)
+        .map(|(denom, _, _)| (*denom, 0))
+        .or_else(|| {
+            denoms
+                .iter()
+                .find(|(_, symbol, _)| unit.eq_ignore_ascii_case(symbol))
+                .map(|(denom, _, exponent)| (*denom, exponent.unwrap_or_default()))
+        })
+        .ok_or_else(|| ContractError::ConvertError(UnknownUnit(unit.to_string())))?;
+
+    let amount =
+        to_base_units(number, exponent).ok_or(ContractError::AmountError(InvalidAmountParsing))?;
+    Ok(Coin::new(amount, denom))
+}
+
+// Scale a decimal number by 10^exponent, failing when it has more decimals than the exponent
+fn to_base_units(number: &str, exponent: u32) -> Option<Uint256> {
+    let (int, frac) = number.split_once('.').unwrap_or((number, ""));
+    if int.is_empty() && frac.is_empty() || number.ends_with('.') || frac.len() > exponent as usize
+    {
+        return None;
+    }
+    let digits = format!("{int}{frac:0<width$}", width = exponent as usize);
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_amount;
+    use crate::error::{AmountError, ContractError, ConvertError};
+    use crate::rate::Rate;
+    use crate::state::Config;
+    use cosmwasm_std::coin;
+
+    fn config() -> Config {
+        let mut config = Config::try_with_defaults(Rate::parse("0.5").unwrap()).unwrap();
+        config.source_exponent = Some(6);
+        config.target_exponent = Some(18);
+        config.source_symbol = Some("MFX".to_string());
+        config
+    }
+
+    #[test]
+    fn test_parse_amount() {
+        let config = config();
+        let target = config.target_denom.to_string();
+        for (input, expected) in [
+            ("1.5mfx", coin(1_500_000, "umfx")),
+            ("1.5 MFX", coin(1_500_000, "umfx")),
+            (" 2 Mfx ", coin(2_000_000, "umfx")),
+            (".25mfx", coin(250_000, "umfx")),
+            ("0.000001mfx", coin(1, "umfx")),
+            ("1500umfx", coin(1_500, "umfx")),
+            ("0umfx", coin(0, "umfx")),
+            ("1 MFX", coin(1_000_000, "umfx")),
+        ] {
+            assert_eq!(parse_amount(&config, input).unwrap(), expected, "{input}");
+        }
+        // The target symbol defaults to its denom, which is then in base units
+        assert_eq!(
+            parse_amount(&config, &format!("7{target}")).unwrap(),
+            coin(7, target)
+        );
+    }
+
+    #[test]
+    fn test_parse_amount_invalid() {
+        let config = config();
+        for input in [
+            "1.5",
+            "mfx",
+            "1.5pwr",
+            "1..5mfx",
+            "1.mfx",
+            ".mfx",
+            "1.0000001mfx",
+            "1.5umfx",
+            "-1mfx",
+            "1e6umfx",
+        ] {
+            let err = parse_amount(&config, input).unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    ContractError::AmountError(AmountError::InvalidAmountParsing)
+                        | ContractError::ConvertError(ConvertError::UnknownUnit(_))
+                ),
+                "{input}: {err}"
+            );
+        }
+    }
+}
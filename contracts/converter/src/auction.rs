@@ -0,0 +1,110 @@
+use crate::error::AuctionError::InvalidAuction;
+use crate::error::ContractError;
+use crate::rate::Rate;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal256, Timestamp, Uint256};
+
+// Batch mode parameters. Conversions are committed during rounds of `round_length` seconds
+// and all settled at a uniform clearing rate. The configured rate applies up to
+// `target_demand` committed source tokens, beyond which the rate decreases in proportion to
+// the excess demand, down to `min_fraction` of the configured rate
+#[cw_serde]
+pub struct AuctionConfig {
+    pub round_length: u64,
+    pub target_demand: Uint256,
+    pub min_fraction: Decimal256,
+}
+
+// The round accepting commitments
+#[cw_serde]
+pub struct AuctionRound {
+    pub id: u64,
+    pub start: Timestamp,
+    pub denom: String,
+    pub committed: Uint256,
+}
+
+// Outcome of a settled round, claimed by each participant at the clearing rate
+#[cw_serde]
+pub struct Settlement {
+    pub round: u64,
+    pub denom: String,
+    pub committed: Uint256,
+    pub fraction: Decimal256,
+    pub rate: Rate,
+    pub time: Timestamp,
+}
+
+impl AuctionConfig {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.round_length == 0
+            || self.target_demand.is_zero()
+            || self.min_fraction.is_zero()
+            || self.min_fraction > Decimal256::one()
+        {
+            return Err(ContractError::AuctionError(InvalidAuction));
+        }
+        Ok(())
+    }
+
+    // Fraction of the configured rate paid for the given total demand
+    pub fn clearing_fraction(&self, demand: Uint256) -> Decimal256 {
+        if demand <= self.target_demand {
+            return Decimal256::one();
+        }
+        Decimal256::from_ratio(self.target_demand, demand).max(self.min_fraction)
+    }
+}
+
+impl AuctionRound {
+    #[inline]
+    pub fn is_over(&self, now: Timestamp, round_length: u64) -> bool {
+        now.seconds() >= self.start.seconds().saturating_add(round_length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AuctionConfig;
+    use crate::error::{AuctionError, ContractError};
+    use cosmwasm_std::{Decimal256, Uint256};
+
+    fn auction(min_fraction: &str) -> AuctionConfig {
+        AuctionConfig {
+            round_length: 3_600,
+            target_demand: Uint256::from(1_000u128),
+            min_fraction: min_fraction.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_auction_validate() {
+        assert!(auction("0.5").validate().is_ok());
+        assert!(auction("1").validate().is_ok());
+        for invalid in ["0", "1.1"] {
+            assert!(matches!(
+                auction(invalid).validate().unwrap_err(),
+                ContractError::AuctionError(AuctionError::InvalidAuction)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_auction_clearing_fraction() {
+        let a = auction("0.25");
+        assert_eq!(a.clearing_fraction(Uint256::zero()), Decimal256::one());
+        assert_eq!(
+            a.clearing_fraction(Uint256::from(1_000u128)),
+            Decimal256::one()
+        );
+        assert_eq!(
+            a.clearing_fraction(Uint256::from(2_000u128)),
+            Decimal256::percent(50)
+        );
+        // The fraction never goes below the floor
+        assert_eq!(
+            a.clearing_fraction(Uint256::from(10_000u128)),
+            Decimal256::percent(25)
+        );
+    }
+}
@@ -1,20 +1,135 @@
 use crate::denom::Denom;
 use const_format::formatcp;
+use manifest_access::PAUSER;
 
 pub const CONTRACT_NAME: &str = "manifest/converter";
 
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// Semver of the message API, independent of the crate version. Bump the major version when
+// a message or field is removed or changes meaning, the minor version when one is added
+pub const API_VERSION: &str = "1.37.0";
+
+// Reply id of the conversion submessage, the conversion id is passed as payload
+pub const REPLY_EXEC_ID: u64 = 1;
+
+// Reply id of the conversion submessage of a convert packet, the remote conversion is
+// passed as payload
+#[cfg(feature = "ibc")]
+pub const REPLY_REMOTE_CONVERT_ID: u64 = 2;
+
+// Reply id of the alerts sent to the notify target, only replied to on error
+pub const REPLY_NOTIFY_ID: u64 = 3;
+
+// Gas the notify target can use per alert, so it cannot revert the operation it is alerted of
+pub const NOTIFY_GAS_LIMIT: u64 = 500_000;
+
+// Version of the converter IBC application, negotiated on channel handshakes
+#[cfg(feature = "ibc")]
+pub const IBC_VERSION: &str = "manifest-converter-1";
+
+// Relative timeout of outgoing packets when none is given
+#[cfg(feature = "ibc")]
+pub const DEFAULT_IBC_TIMEOUT_SECONDS: u64 = 600;
+
+// Most recipients the minted tokens of a conversion can be split among
+pub const MAX_RECIPIENTS: usize = 10;
+
+// Most admin operations a batch can apply
+pub const MAX_BATCH_OPS: usize = 16;
+
+// Time a commit-reveal conversion can be revealed for, after which it can only be cancelled
+pub const COMMITMENT_TTL_SECONDS: u64 = 3_600;
+
+// Pagination limits for list queries
+pub const DEFAULT_PAGE_LIMIT: u32 = 10;
+pub const MAX_PAGE_LIMIT: u32 = 30;
+
+// Number of past epochs kept in storage
+pub const EPOCH_RETENTION: usize = 24;
+
+// Largest display exponent accepted for a denom
+pub const MAX_DENOM_EXPONENT: u32 = 18;
+
+// Longest display symbol accepted for a denom
+pub const MAX_SYMBOL_LEN: usize = 16;
+
+// Longest label, description and external url of the contract metadata
+pub const MAX_LABEL_LEN: usize = 64;
+pub const MAX_DESCRIPTION_LEN: usize = 512;
+pub const MAX_URL_LEN: usize = 256;
+
+// Length of the day of daily quotas and of the window of delegated rate changes, days
+// starting at midnight UTC
+pub const DAY_SECONDS: u64 = 86_400;
+
+// Longest message explaining why the contract is paused
+pub const MAX_PAUSE_MESSAGE_LEN: usize = 256;
+
+// Longest memo attached to a conversion
+pub const MAX_MEMO_LEN: usize = 256;
+
+// Rough gas used by the execute messages of users, for wallets to set a gas limit without
+// simulating. Conversions are dominated by the authz MsgExec running the send, the burn and
+// the mint. Estimates with a margin for the default config, checked by tests/gas_hints.rs
+// against the storage accesses and dispatched messages of each handler
+pub const GAS_HINTS: &[(&str, u64)] = &[
+    ("convert", 300_000),
+    ("convert_with_signature", 350_000),
+    ("deposit", 150_000),
+    ("withdraw", 150_000),
+    ("claim_refund", 150_000),
+    ("start_streaming_convert", 200_000),
+    ("claim_stream", 300_000),
+    ("cancel_stream", 150_000),
+    ("commit_convert", 150_000),
+    ("reveal_convert", 300_000),
+    ("cancel_commitment", 150_000),
+    ("auction_commit", 150_000),
+    ("claim_auction", 300_000),
+    ("cancel_auction_commitment", 150_000),
+];
+
+// Gas added to a conversion by each recipient beyond the first, each one getting its own mint
+pub const GAS_PER_RECIPIENT: u64 = 50_000;
+
+// Attributes carrying user addresses, omitted from responses when `redact_senders` is set
+pub const REDACTED_ATTRIBUTES: &[&str] = &["sender", "depositor", "receiver"];
+
+// Roles the admin can grant
+pub const KNOWN_ROLES: &[&str] = &[PAUSER];
+
+// Network the defaults below are compiled for, selected with the `mainnet`, `testnet` and
+// `local` features. Without any of them the mainnet defaults apply, and when several are
+// enabled `local` takes precedence over `testnet`
+#[cfg(feature = "local")]
+pub const PROFILE: &str = "local";
+#[cfg(all(feature = "testnet", not(feature = "local")))]
+pub const PROFILE: &str = "testnet";
+#[cfg(not(any(feature = "testnet", feature = "local")))]
+pub const PROFILE: &str = "mainnet";
+
 pub const BECH32_PREFIX: &str = "manifest";
 
-// The default POA admin address of the Manifest Network
+// The default POA admin address of the network. Testnet builds take it from the
+// CONVERTER_POA_ADMIN environment variable, so they never silently fall back to mainnet's
+#[cfg(not(any(feature = "testnet", feature = "local")))]
 pub const DEFAULT_POA_ADMIN: &str =
     formatcp!("{BECH32_PREFIX}1afk9zr2hn2jsac63h4hm60vl9z3e5u69gndzf7c99cqge3vzwjzsfmy9qj");
+#[cfg(all(feature = "testnet", not(feature = "local")))]
+pub const DEFAULT_POA_ADMIN: &str = env!(
+    "CONVERTER_POA_ADMIN",
+    "testnet builds require the CONVERTER_POA_ADMIN environment variable"
+);
+// First account of the local test node
+#[cfg(feature = "local")]
+pub const DEFAULT_POA_ADMIN: &str =
+    formatcp!("{BECH32_PREFIX}1hj5fveer5cjtn4wd6wstzugjfdxzl0xp8ws9ct");
 
-// The default base denom of the Manifest Network
+// The default base denom of the network
 pub const DEFAULT_SOURCE_DENOM: &str = "umfx";
 
-// The default target denom of the Manifest Network
+// The default target denom of the network
 pub const DEFAULT_TARGET_DENOM: &str = formatcp!("factory/{DEFAULT_POA_ADMIN}/upwr");
 
 pub fn default_source_denom() -> Denom {
@@ -1,166 +1,3753 @@
-use crate::consts::{CONTRACT_NAME, CONTRACT_VERSION};
+#[cfg(feature = "ibc")]
+use crate::consts::REPLY_REMOTE_CONVERT_ID;
+use crate::consts::{
+    CONTRACT_NAME, CONTRACT_VERSION, KNOWN_ROLES, NOTIFY_GAS_LIMIT, REDACTED_ATTRIBUTES,
+    REPLY_EXEC_ID, REPLY_NOTIFY_ID,
+};
+use crate::denom::{canonicalize, same_denom, Denom};
+use crate::epoch::Epoch;
 use crate::error::AmountError::NonPayable;
-use crate::error::ConfigError::SameDenom;
+use crate::error::ConfigError::{
+    GranteePrefixMismatch, GroupThresholdTooLow, NotGroupPolicy, PoaAdminIsGrantee,
+    RateBoundsImmutable, UnsupportedDecisionPolicy, VerifierNotContract,
+};
 use crate::error::ContractError;
+use crate::error::ConvertError::{
+    AboveMaxAmount, BelowMinAmount, EpochCapExceeded, FeeExceedsAmount, UnknownReply,
+};
+use crate::error::DenomError::UnknownDenom;
+use crate::error::FundsError::{FundingMismatch, InvalidFunding};
 use crate::error::MigrateError::InvalidContractName;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::state::{Config, ADMIN, CONFIG};
+use crate::error::RateError::{ApplyOverflowError, ApplyZeroError};
+use crate::float::{pay_from_float, pays_from_float, unpay_from_float};
+use crate::group;
+use crate::lifecycle::{operation, Lifecycle, Transition};
+use crate::messages::{
+    build_burn, build_exec, build_fund_community_pool, build_mint, build_send, encode_exec,
+    payload_hash, to_any_msg,
+};
+use crate::msg::{
+    Alert, ExecuteMsg, ExpectedGrant, InstantiateConfig, InstantiateMsg, MigrateMsg, NotifyMsg,
+    QueryMsg, ReceiptPayload, ReverseSimulateResponse, SimulateExecutionResponse, SudoMsg,
+    UpdateConfig,
+};
+use crate::querier::ManifestQuerier;
+#[cfg(feature = "quotas")]
+use crate::quota;
+use crate::rate::Rate;
+use crate::resolver::{check_resolver, resolve};
+use crate::state::{
+    load_config, load_epoch, migrate_config, record_admin, record_upgrade, save_epoch, Config,
+    Conversion, Mint, PendingCallback, RateBaseline, ADMIN, AUTHZ_ENABLED, CONFIG, CURRENT_EPOCH,
+    DELEGATIONS, DENOM_INFO, ESCROWED, FROZEN, METADATA, NOTIFY_TARGET, PAIR_TOTALS, PAUSE,
+    PENDING_ADMIN, PENDING_CALLBACKS, RATE_BASELINE, ROLES, ROUNDING_RESERVE, TOTALS, UNSOLICITED,
+    UNSOLICITED_TOTAL,
+};
+use crate::strategy::{check_oracle, resolve_rate, RateStrategy};
 use cosmwasm_std::{
-    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, MigrateInfo, Response, StdResult,
+    from_json, to_json_binary, to_json_vec, Addr, Api, Attribute, Binary, Coin, Decimal256, Deps,
+    DepsMut, Env, Event, MessageInfo, MigrateInfo, Order, Reply, Response, StdError, StdResult,
+    Storage, SubMsg, Timestamp, Uint256, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version};
 use cw_utils::nonpayable;
+use manifest_access::{validate_role, AccessMsg, AccessQueryMsg, AccessSudoMsg, PAUSER};
+use manifest_coins::{normalize_coins, same_coins, sort_coins, validate_coins};
+use manifest_std::google::protobuf::Any;
+use manifest_std::liftedinit::manifest::v1::MsgBurnHeldBalance;
+use manifest_std::osmosis::tokenfactory::v1beta1::MsgMint;
+use sha2::{Digest, Sha256};
 
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
-    let admin = deps.api.addr_validate(msg.admin.as_str())?;
+    let funding = check_funding(&info, msg.funding.as_deref())?;
+
+    let metadata = msg.metadata.clone();
+    let (admin, config) = msg.into_parts();
+    let config = build_config(deps.api, config)?;
+    let admin = validate_addr(deps.api, &config, &admin)?;
+    check_poa_admin(deps.as_ref(), &config)?;
+    check_grantee(&config, &env.contract.address)?;
+    check_verifier(deps.as_ref(), &config)?;
+    check_oracle(deps.as_ref(), &config)?;
+    check_resolver(deps.as_ref(), &config)?;
+    let warnings = check_denoms(deps.as_ref(), &config)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    CONFIG.save(deps.storage, &config)?;
+    if let Some(metadata) = metadata {
+        metadata.validate()?;
+        METADATA.save(deps.storage, &metadata)?;
+    }
+    cache_denom_info(deps.storage, &ManifestQuerier::new(deps.querier), &config)?;
+    CURRENT_EPOCH.save(deps.storage, &Epoch::new(0, env.block.time))?;
+
+    // Bootstrap funds in the target denom fund the float, so conversions can be paid out of
+    // them right away. Others are recorded as unsolicited funds of the instantiator, so they
+    // are accounted for and can be returned like any other
+    for coin in &funding {
+        if config.target_denom.matches(&coin.denom) {
+            crate::float::fund_float(deps.storage, coin)?;
+            continue;
+        }
+        UNSOLICITED.update(
+            deps.storage,
+            (&info.sender, &coin.denom),
+            |current| -> StdResult<_> { Ok(current.unwrap_or_default() + coin.amount) },
+        )?;
+        UNSOLICITED_TOTAL.update(deps.storage, &coin.denom, |total| -> StdResult<_> {
+            Ok(total.unwrap_or_default() + coin.amount)
+        })?;
+    }
 
+    record_admin(deps.storage, &admin, &env.block)?;
+    ADMIN.set(deps, Some(admin))?;
+
+    let res = Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attributes(warnings);
+    if funding.is_empty() {
+        return Ok(res);
+    }
+    let funding = funding
+        .iter()
+        .map(Coin::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    Ok(res.add_attribute("funding", funding))
+}
+
+// Check the instantiate funds against the declared funding. Without funding the message is
+// non-payable, with it the funds must match exactly, in any order
+fn check_funding(info: &MessageInfo, funding: Option<&[Coin]>) -> Result<Vec<Coin>, ContractError> {
+    let Some(funding) = funding else {
+        nonpayable(info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        return Ok(vec![]);
+    };
+
+    validate_coins(funding).map_err(|_| ContractError::FundsError(InvalidFunding))?;
+    if !same_coins(&info.funds, funding) {
+        return Err(ContractError::FundsError(FundingMismatch));
+    }
+    let mut expected = funding.to_vec();
+    sort_coins(&mut expected);
+    Ok(expected)
+}
+
+// Build a complete config from its declarative form, unset fields falling back to their
+// defaults, and validate it
+pub(crate) fn build_config(api: &dyn Api, msg: InstantiateConfig) -> Result<Config, ContractError> {
     // Rate is validated in its constructor
     // Denoms are validated in their constructors
+    // Fields left unset fall back to the defaults of a standard mainnet deployment
 
+    let defaults = Config::try_with_defaults(Rate::parse(&msg.rate)?)?;
     let config = Config {
-        poa_admin: deps.api.addr_validate(msg.poa_admin.as_str())?,
-        rate: crate::rate::Rate::parse(&msg.rate)?,
-        source_denom: crate::denom::Denom::new(msg.source_denom)?,
-        target_denom: crate::denom::Denom::new(msg.target_denom)?,
-        paused: msg.paused,
+        poa_admin: api.addr_validate(
+            msg.poa_admin
+                .as_deref()
+                .unwrap_or(defaults.poa_admin.as_str()),
+        )?,
+        rate: defaults.rate,
+        source_denom: match msg.source_denom {
+            Some(denom) => Denom::new(denom)?,
+            None => defaults.source_denom,
+        },
+        target_denom: match msg.target_denom {
+            Some(denom) => Denom::new(denom)?,
+            None => defaults.target_denom,
+        },
+        paused: msg.paused.unwrap_or(defaults.paused),
+        strict_denoms: msg.strict_denoms,
+        source_exponent: msg.source_exponent,
+        target_exponent: msg.target_exponent,
+        source_symbol: msg.source_symbol,
+        target_symbol: msg.target_symbol,
+        allowed_senders: msg.allowed_senders,
+        epoch_length: msg.epoch_length,
+        require_group_policy: msg.require_group_policy,
+        min_group_threshold: msg.min_group_threshold,
+        bech32_prefix: msg.bech32_prefix,
+        flat_fee: msg.flat_fee.map(canonical_fee),
+        rate_floor: msg.rate_floor.as_deref().map(Rate::parse).transpose()?,
+        rate_ceiling: msg.rate_ceiling.as_deref().map(Rate::parse).transpose()?,
+        verifier: msg
+            .verifier
+            .map(|verifier| api.addr_validate(&verifier))
+            .transpose()?,
+        redact_senders: msg.redact_senders,
+        rate_strategy: msg.rate_strategy,
+        float_payouts: msg.float_payouts,
+        resolver: msg
+            .resolver
+            .map(|resolver| api.addr_validate(&resolver))
+            .transpose()?,
+        hash_authz_payloads: msg.hash_authz_payloads,
+        fee_model: msg.fee_model,
+        min_convert_amount: msg.min_convert_amount,
+        max_convert_amount: msg.max_convert_amount,
+        daily_quota: msg.daily_quota,
+        epoch_cap: msg.epoch_cap,
+        fees_to_community_pool: msg.fees_to_community_pool,
+    };
+
+    config.validate()?;
+    Ok(config)
+}
+
+// Ensure both configured denoms exist on chain. A typo'd target denom would otherwise only
+// surface once a conversion mints a new junk denom. Unknown denoms fail the message when the
+// config is strict and are reported as attributes otherwise
+fn check_denoms(deps: Deps, config: &Config) -> Result<Vec<Attribute>, ContractError> {
+    let mut warnings = vec![];
+    for denom in [&config.source_denom, &config.target_denom] {
+        if denom.exists(&ManifestQuerier::new(deps.querier))? {
+            continue;
+        }
+        if config.strict_denoms.unwrap_or_default() {
+            return Err(ContractError::DenomError(UnknownDenom));
+        }
+        warnings.push(Attribute::new("unknown_denom", denom.as_str()));
+    }
+    Ok(warnings)
+}
+
+// Cache the bank metadata of the configured denoms, dropping the one of denoms no longer
+// configured
+fn cache_denom_info(
+    storage: &mut dyn Storage,
+    querier: &ManifestQuerier,
+    config: &Config,
+) -> StdResult<()> {
+    let cached = DENOM_INFO
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for denom in cached {
+        DENOM_INFO.remove(storage, &denom);
+    }
+    for denom in [&config.source_denom, &config.target_denom] {
+        DENOM_INFO.save(storage, denom.as_str(), &denom.info(querier))?;
+    }
+    Ok(())
+}
+
+// Ensure the POA admin is an x/group policy account when required, and that its decision
+// policy threshold meets the configured minimum. A wrong POA admin would otherwise only
+// surface once conversions send funds to it
+fn check_poa_admin(deps: Deps, config: &Config) -> Result<(), ContractError> {
+    if !config.require_group_policy.unwrap_or_default() && config.min_group_threshold.is_none() {
+        return Ok(());
+    }
+    let info = ManifestQuerier::new(deps.querier)
+        .group_policy_info(&config.poa_admin)
+        .ok_or(ContractError::ConfigError(NotGroupPolicy))?;
+    if let Some(min) = config.min_group_threshold {
+        let threshold =
+            group::threshold(&info).ok_or(ContractError::ConfigError(UnsupportedDecisionPolicy))?;
+        if threshold < Decimal256::from_ratio(min, 1u8) {
+            return Err(ContractError::ConfigError(GroupThresholdTooLow));
+        }
+    }
+    Ok(())
+}
+
+// Ensure the contract can hold the authz grants of the POA admin. Grants made to an address
+// of another chain, or by the contract to itself, would only fail once users convert
+fn check_grantee(config: &Config, contract: &Addr) -> Result<(), ContractError> {
+    if config.poa_admin == contract {
+        return Err(ContractError::ConfigError(PoaAdminIsGrantee));
+    }
+    config
+        .check_prefix(contract)
+        .map_err(|_| ContractError::ConfigError(GranteePrefixMismatch))
+}
+
+// Authz grants the POA admin must give the contract for conversions. The contract signs the
+// transfer to the POA admin itself, which needs no grant
+pub(crate) fn expected_grants(config: &Config, contract: &Addr) -> Vec<ExpectedGrant> {
+    [MsgBurnHeldBalance::TYPE_URL, MsgMint::TYPE_URL]
+        .into_iter()
+        .map(|type_url| ExpectedGrant {
+            granter: config.poa_admin.to_string(),
+            grantee: contract.to_string(),
+            type_url: type_url.to_string(),
+        })
+        .collect()
+}
+
+// Flat fee in the case the chain reports IBC hashes in, as sent funds are
+fn canonical_fee(fee: Coin) -> Coin {
+    Coin::new(fee.amount, canonicalize(&fee.denom))
+}
+
+// Assertions of the health check, by name, with the error of the failed ones. Only the state
+// and balances of the contract are read, so they are cheap enough to run periodically
+fn health_checks(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+) -> Vec<(&'static str, Result<(), String>)> {
+    let config_valid = config
+        .validate()
+        .and_then(|_| check_grantee(config, &env.contract.address))
+        .map_err(|err| err.to_string());
+    let denoms_canonical = [config.source_denom.as_str(), config.target_denom.as_str()]
+        .into_iter()
+        .chain(config.flat_fee.as_ref().map(|fee| fee.denom.as_str()))
+        .find(|denom| canonicalize(denom) != *denom)
+        .map_or(Ok(()), |denom| Err(format!("{denom} is not canonical")));
+    let authz_enabled = match AUTHZ_ENABLED.may_load(deps.storage) {
+        Ok(Some(false)) => Err("authz is disabled".to_string()),
+        Ok(_) => Ok(()),
+        Err(err) => Err(err.to_string()),
+    };
+    vec![
+        ("config", config_valid),
+        ("denoms", denoms_canonical),
+        ("authz", authz_enabled),
+        ("escrow", check_escrow(deps, &env.contract.address)),
+    ]
+}
+
+// The escrowed funds are held by the contract, a lower balance means some were lost
+fn check_escrow(deps: Deps, contract: &Addr) -> Result<(), String> {
+    for item in ESCROWED.range(deps.storage, None, None, Order::Ascending) {
+        let (denom, escrowed) = item.map_err(|err| err.to_string())?;
+        let balance = deps
+            .querier
+            .query_balance(contract, &denom)
+            .map_err(|err| err.to_string())?
+            .amount;
+        if balance < escrowed {
+            return Err(format!(
+                "{denom} balance {balance} is below escrowed {escrowed}"
+            ));
+        }
+    }
+    Ok(())
+}
+
+// Ensure the verifier is an existing contract. An account would fail every conversion
+fn check_verifier(deps: Deps, config: &Config) -> Result<(), ContractError> {
+    if let Some(verifier) = &config.verifier {
+        deps.querier
+            .query_wasm_contract_info(verifier)
+            .map_err(|_| ContractError::ConfigError(VerifierNotContract))?;
+    }
+    Ok(())
+}
+
+// Apply an update on top of the current config and run the whole config validation, as done
+// by `update_config`. Returns the resulting config and the unknown denom warnings
+pub(crate) fn apply_config_update(
+    deps: Deps,
+    contract: &Addr,
+    mut config: Config,
+    update: UpdateConfig,
+) -> Result<(Config, Vec<Attribute>), ContractError> {
+    if update.rate_floor.is_some() || update.rate_ceiling.is_some() {
+        return Err(ContractError::ConfigError(RateBoundsImmutable));
+    }
+
+    if let Some(poa_admin) = update.poa_admin {
+        let poa_admin_addr = deps.api.addr_validate(&poa_admin)?;
+        config.poa_admin = poa_admin_addr;
+    }
+
+    if let Some(rate) = update.rate {
+        config.rate = Rate::parse(&rate)?;
+    }
+
+    if let Some(source_denom) = update.source_denom {
+        config.source_denom = Denom::new(source_denom)?;
+    }
+
+    if let Some(target_denom) = update.target_denom {
+        config.target_denom = Denom::new(target_denom)?;
+    }
+
+    if let Some(paused) = update.paused {
+        let transition = if paused {
+            Transition::Pause
+        } else {
+            Transition::Resume
+        };
+        config.paused = Lifecycle::load(deps.storage, &config)?
+            .transition(transition)?
+            .is_paused();
+    }
+
+    if let Some(strict_denoms) = update.strict_denoms {
+        config.strict_denoms = Some(strict_denoms);
+    }
+
+    if let Some(source_exponent) = update.source_exponent {
+        config.source_exponent = Some(source_exponent);
+    }
+
+    if let Some(target_exponent) = update.target_exponent {
+        config.target_exponent = Some(target_exponent);
+    }
+
+    if let Some(source_symbol) = update.source_symbol {
+        config.source_symbol = Some(source_symbol);
+    }
+
+    if let Some(target_symbol) = update.target_symbol {
+        config.target_symbol = Some(target_symbol);
+    }
+
+    if let Some(allowed_senders) = update.allowed_senders {
+        config.allowed_senders = Some(allowed_senders);
+    }
+
+    if let Some(epoch_length) = update.epoch_length {
+        config.epoch_length = Some(epoch_length);
+    }
+
+    if let Some(require_group_policy) = update.require_group_policy {
+        config.require_group_policy = Some(require_group_policy);
+    }
+
+    if let Some(min_group_threshold) = update.min_group_threshold {
+        config.min_group_threshold = Some(min_group_threshold);
+    }
+
+    if let Some(flat_fee) = update.flat_fee {
+        config.flat_fee = Some(canonical_fee(flat_fee));
+    }
+
+    if let Some(verifier) = update.verifier {
+        config.verifier = Some(deps.api.addr_validate(&verifier)?);
+    }
+
+    if let Some(redact_senders) = update.redact_senders {
+        config.redact_senders = Some(redact_senders);
+    }
+
+    if let Some(rate_strategy) = update.rate_strategy {
+        config.rate_strategy = Some(rate_strategy);
+    }
+
+    if let Some(float_payouts) = update.float_payouts {
+        config.float_payouts = Some(float_payouts);
+    }
+
+    if let Some(resolver) = update.resolver {
+        config.resolver = Some(deps.api.addr_validate(&resolver)?);
+    }
+
+    if let Some(hash_authz_payloads) = update.hash_authz_payloads {
+        config.hash_authz_payloads = Some(hash_authz_payloads);
+    }
+
+    if let Some(fee_model) = update.fee_model {
+        config.fee_model = Some(fee_model);
+    }
+
+    if let Some(min_convert_amount) = update.min_convert_amount {
+        config.min_convert_amount = Some(min_convert_amount);
+    }
+
+    if let Some(max_convert_amount) = update.max_convert_amount {
+        config.max_convert_amount = Some(max_convert_amount);
+    }
+
+    if let Some(daily_quota) = update.daily_quota {
+        config.daily_quota = Some(daily_quota);
+    }
+
+    if let Some(epoch_cap) = update.epoch_cap {
+        config.epoch_cap = Some(epoch_cap);
+    }
+
+    if let Some(fees_to_community_pool) = update.fees_to_community_pool {
+        config.fees_to_community_pool = Some(fees_to_community_pool);
+    }
+
+    // Ensure source and target denoms are not the same and exponents are consistent
+    config.validate()?;
+    check_poa_admin(deps, &config)?;
+    check_grantee(&config, contract)?;
+    check_verifier(deps, &config)?;
+    check_oracle(deps, &config)?;
+    check_resolver(deps, &config)?;
+
+    let warnings = check_denoms(deps, &config)?;
+    Ok((config, warnings))
+}
+
+// Validate an address kept in contract state. Besides passing the chain's own validation, it
+// must use the configured bech32 prefix
+pub(crate) fn validate_addr(
+    api: &dyn Api,
+    config: &Config,
+    addr: &str,
+) -> Result<Addr, ContractError> {
+    let addr = api.addr_validate(addr)?;
+    config.check_prefix(&addr)?;
+    Ok(addr)
+}
+
+// Build the authz MsgExec sending the source tokens to the POA admin, burning them from its
+// held balance and minting the target tokens, with one mint per recipient. The contract signs
+// the send itself, so it needs no grant, and sending it within the MsgExec keeps the funds in
+// the contract if the burn or a mint fails
+#[cfg(any(feature = "ibc", test))]
+pub(crate) fn conversion_msg(
+    contract: &Addr,
+    config: &Config,
+    burned: &Coin,
+    mints: &[Mint],
+    from_float: bool,
+) -> cosmwasm_std::CosmosMsg {
+    build_exec(
+        contract.as_str(),
+        authz_msgs(contract, config, burned, mints, from_float),
+    )
+}
+
+// Messages wrapped in the authz MsgExec of a conversion. Paid out of the float, the minted
+// tokens are sent by the contract itself, which needs no grant
+fn authz_msgs(
+    contract: &Addr,
+    config: &Config,
+    burned: &Coin,
+    mints: &[Mint],
+    from_float: bool,
+) -> Vec<Any> {
+    let poa_admin = config.poa_admin.as_str();
+    let mut msgs = vec![
+        build_send(contract.as_str(), poa_admin, burned),
+        build_burn(poa_admin, burned),
+    ];
+    for mint in mints {
+        msgs.push(match from_float {
+            true => build_send(contract.as_str(), mint.recipient.as_str(), &mint.amount),
+            false => build_mint(poa_admin, mint.recipient.as_str(), &mint.amount),
+        });
+    }
+    msgs
+}
+
+// Withhold the fee of the fee model from the converted coin, in the source denom. A fee model
+// cannot be combined with a flat fee, so a conversion has at most one fee
+pub(crate) fn withhold_fee(
+    config: &Config,
+    coin: Coin,
+    fee: Option<Coin>,
+) -> Result<(Coin, Option<Coin>), ContractError> {
+    let Some(model) = &config.fee_model else {
+        return Ok((coin, fee));
+    };
+    let withheld = model.compute_fee(coin.amount)?;
+    if withheld.is_zero() {
+        return Ok((coin, fee));
+    }
+    if withheld >= coin.amount {
+        return Err(ContractError::ConvertError(FeeExceedsAmount));
+    }
+    Ok((
+        Coin::new(coin.amount - withheld, coin.denom.as_str()),
+        Some(Coin::new(withheld, coin.denom)),
+    ))
+}
+
+// Collect a fee held by the contract, sent to the POA admin or funding the community pool
+pub(crate) fn fee_msg(contract: &Addr, config: &Config, fee: &Coin) -> cosmwasm_std::CosmosMsg {
+    match config.fees_to_community_pool.unwrap_or_default() {
+        true => cosmwasm_std::CosmosMsg::Any(to_any_msg(build_fund_community_pool(
+            contract.as_str(),
+            fee,
+        ))),
+        false => cosmwasm_std::BankMsg::Send {
+            to_address: config.poa_admin.to_string(),
+            amount: vec![fee.clone()],
+        }
+        .into(),
+    }
+}
+
+// Simulate a conversion, returning the minted coin and the messages the contract would emit,
+// protobuf encoded. Bank sends are encoded as the MsgSend the chain executes
+pub(crate) fn simulate_execution(
+    deps: Deps,
+    env: &Env,
+    sender: &str,
+    amount: Uint256,
+) -> Result<SimulateExecutionResponse, ContractError> {
+    let config = load_config(deps.storage)?;
+    let sender = validate_addr(deps.api, &config, sender)?;
+    let (burned, fee) = withhold_fee(
+        &config,
+        Coin::new(amount, config.source_denom.as_str()),
+        config.flat_fee.clone(),
+    )?;
+    let rate = resolve_rate(deps, &env.block, &config, burned.amount, None)?;
+    let minted = Coin::new(
+        config.mint_amount(&rate, burned.amount)?,
+        config.target_denom.as_str(),
+    );
+    let mints = [Mint {
+        recipient: sender,
+        amount: minted.clone(),
+    }];
+
+    let contract = &env.contract.address;
+    let from_float = pays_from_float(deps.storage, &config, &minted)?;
+    let authz_msgs = authz_msgs(contract, &config, &burned, &mints, from_float);
+    let mut msgs = vec![encode_exec(contract.as_str(), authz_msgs.clone())];
+    if let Some(fee) = &fee {
+        msgs.push(to_any_msg(
+            match config.fees_to_community_pool.unwrap_or_default() {
+                true => build_fund_community_pool(contract.as_str(), fee),
+                false => build_send(contract.as_str(), config.poa_admin.as_str(), fee),
+            },
+        ));
+    }
+    Ok(SimulateExecutionResponse {
+        minted,
+        msgs,
+        authz_msgs: authz_msgs.into_iter().map(to_any_msg).collect(),
+    })
+}
+
+// Smallest amount of source tokens a conversion must send to mint at least `desired` target
+// tokens at the current block, found by searching the amounts a conversion mints. Fees and
+// rates depending on the amount can make the minted amount decrease at a tier boundary, in
+// which case the amount found mints enough but may not be the smallest
+pub(crate) fn reverse_simulate(
+    deps: Deps,
+    env: &Env,
+    desired: Uint256,
+) -> Result<ReverseSimulateResponse, ContractError> {
+    let config = load_config(deps.storage)?;
+    // Fee withheld and amount minted by a conversion of `amount`, None when too small to mint
+    let outcome = |amount: Uint256| -> Result<Option<(Option<Coin>, Uint256)>, ContractError> {
+        let coin = Coin::new(amount, config.source_denom.as_str());
+        let (burned, fee) = match withhold_fee(&config, coin, None) {
+            Ok(withheld) => withheld,
+            Err(ContractError::ConvertError(FeeExceedsAmount)) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let rate = resolve_rate(deps, &env.block, &config, burned.amount, None)?;
+        match config.mint_amount(&rate, burned.amount) {
+            Ok(minted) if minted >= desired => Ok(Some((fee, minted))),
+            Ok(_) | Err(ContractError::RateError(ApplyZeroError)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    };
+
+    // Start from the amount needed at the rate of the smallest conversions, without fees
+    let rate = resolve_rate(deps, &env.block, &config, Uint256::one(), None)?;
+    let mut high = config.source_amount(&rate, desired)?;
+    while outcome(high)?.is_none() {
+        high = high
+            .checked_mul(Uint256::from(2u8))
+            .map_err(|_| ContractError::RateError(ApplyOverflowError))?;
+    }
+    let mut low = Uint256::zero();
+    while high - low > Uint256::one() {
+        let mid = low + (high - low) / Uint256::from(2u8);
+        match outcome(mid)? {
+            Some(_) => high = mid,
+            None => low = mid,
+        }
+    }
+
+    let (fee, minted) = outcome(high)?.ok_or(ContractError::RateError(ApplyZeroError))?;
+    Ok(ReverseSimulateResponse {
+        amount: Coin::new(high, config.source_denom.as_str()),
+        minted: Coin::new(minted, config.target_denom.as_str()),
+        fee,
+        flat_fee: config.flat_fee,
+    })
+}
+
+// Account a successful conversion in the current epoch and the lifetime totals, and the value
+// lost to flooring its mint amount in the rounding reserve
+pub(crate) fn record_conversion(
+    storage: &mut dyn Storage,
+    now: Timestamp,
+    burned: &Coin,
+    minted: &Coin,
+    rate: Option<&Rate>,
+) -> Result<(), ContractError> {
+    let config = load_config(storage)?;
+    let mut epoch = load_epoch(storage, now, config.epoch_length)?;
+    epoch
+        .record(burned.amount, minted.amount)
+        .map_err(StdError::from)?;
+    save_epoch(storage, &epoch)?;
+    let mut totals = TOTALS.may_load(storage)?.unwrap_or_default();
+    totals
+        .record(burned.amount, minted.amount)
+        .map_err(StdError::from)?;
+    TOTALS.save(storage, &totals)?;
+    let pair = (burned.denom.as_str(), minted.denom.as_str());
+    let mut pair_totals = PAIR_TOTALS.may_load(storage, pair)?.unwrap_or_default();
+    pair_totals
+        .record(burned.amount, minted.amount)
+        .map_err(StdError::from)?;
+    PAIR_TOTALS.save(storage, pair, &pair_totals)?;
+    if let Some(rate) = rate {
+        let loss = config.rounding_loss(rate, burned.amount)?;
+        ROUNDING_RESERVE.update(storage, &minted.denom, |reserve| {
+            reserve
+                .unwrap_or_default()
+                .checked_add(loss)
+                .map_err(StdError::from)
+        })?;
+    }
+    Ok(())
+}
+
+// Reject conversions outside of the configured amount limits, fee included. Every conversion
+// path checks them, whether the coin is sent, drawn from an escrow or received over IBC
+pub(crate) fn check_convert_amount(config: &Config, amount: Uint256) -> Result<(), ContractError> {
+    if config.min_convert_amount.is_some_and(|min| amount < min) {
+        return Err(ContractError::ConvertError(BelowMinAmount));
+    }
+    if config.max_convert_amount.is_some_and(|max| amount > max) {
+        return Err(ContractError::ConvertError(AboveMaxAmount));
+    }
+    Ok(())
+}
+
+// Ensure minting the amount keeps the current epoch within the epoch cap. Conversions are
+// recorded in the epoch once they succeed, in the reply right after their dispatch
+pub(crate) fn check_epoch_cap(
+    storage: &dyn Storage,
+    now: Timestamp,
+    config: &Config,
+    minted: Uint256,
+) -> Result<(), ContractError> {
+    let Some(cap) = config.epoch_cap else {
+        return Ok(());
+    };
+    let epoch = load_epoch(storage, now, config.epoch_length)?;
+    if epoch.minted.saturating_add(minted) > cap {
+        return Err(ContractError::ConvertError(EpochCapExceeded));
+    }
+    Ok(())
+}
+
+// Alert the notify target, when one is set. The alert runs with a gas limit and its errors
+// are caught in reply, so a failing target cannot revert the operation
+pub(crate) fn notify(storage: &dyn Storage, alert: Alert) -> StdResult<Option<SubMsg>> {
+    let Some(target) = NOTIFY_TARGET.may_load(storage)? else {
+        return Ok(None);
+    };
+    let msg = WasmMsg::Execute {
+        contract_addr: target.to_string(),
+        msg: to_json_binary(&NotifyMsg::ConverterAlert { alert })?,
+        funds: vec![],
+    };
+    Ok(Some(
+        SubMsg::reply_on_error(msg, REPLY_NOTIFY_ID).with_gas_limit(NOTIFY_GAS_LIMIT),
+    ))
+}
+
+// Hash of the canonical receipt payload of a conversion, None when its rate was not recorded
+pub(crate) fn receipt_hash(
+    chain_id: &str,
+    contract: &Addr,
+    conversion: &Conversion,
+) -> StdResult<Option<Binary>> {
+    let Some(rate) = conversion.rate.clone() else {
+        return Ok(None);
+    };
+    let payload = ReceiptPayload {
+        chain_id: chain_id.to_string(),
+        contract: contract.to_string(),
+        id: conversion.id,
+        sender: conversion.sender.to_string(),
+        burned: conversion.burned.clone(),
+        minted: conversion.minted.clone(),
+        rate,
+        height: conversion.height,
+    };
+    Ok(Some(Binary::from(
+        Sha256::digest(to_json_vec(&payload)?).to_vec(),
+    )))
+}
+
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    use QueryMsg::*;
+
+    match msg {
+        ApiVersion {} => query::api_version(),
+        ContractInfo {} => query::contract_info(deps),
+        Status {} => query::status(deps),
+        Config {} => query::config(deps),
+        Admin {} => query::admin(deps),
+        Access(AccessQueryMsg::PendingAdmin {}) => query::pending_admin(deps),
+        Access(AccessQueryMsg::RoleMembers { role }) => query::role_members(deps, role),
+        Access(AccessQueryMsg::HasRole { role, address }) => query::has_role(deps, role, address),
+        Frozen {} => query::frozen(deps),
+        NotifyTarget {} => query::notify_target(deps),
+        Successor {} => query::successor(deps),
+        Delegations { pagination } => query::delegations(deps, pagination.unwrap_or_default()),
+        Grants {} => query::grants(deps, env),
+        ExpectedGrants {} => query::expected_grants(deps, env),
+        DisplayRate {} => query::display_rate(deps),
+        CurrentRate { amount } => query::current_rate(deps, env, amount),
+        ParseAmount { input } => query::parse_amount(deps, input),
+        SimulateExecution { sender, amount } => {
+            query::simulate_execution(deps, env, sender, amount)
+        }
+        ReverseSimulate { desired } => query::reverse_simulate(deps, env, desired),
+        #[cfg(feature = "quotas")]
+        RemainingQuota { address } => query::remaining_quota(deps, env, address),
+        CurrentEpoch {} => query::current_epoch(deps, env),
+        EpochUsage {} => query::epoch_usage(deps, env),
+        Metrics {} => query::metrics(deps, env),
+        PairTotals { pagination } => query::pair_totals(deps, pagination.unwrap_or_default()),
+        Epochs { start_after, limit } => query::epochs(deps, start_after, limit),
+        Quorum {} => query::quorum(deps),
+        PendingAction { id } => query::pending_action(deps, id),
+        PendingActions { start_after, limit } => query::pending_actions(deps, start_after, limit),
+        UnsolicitedBalance { denom } => query::unsolicited_balance(deps, env, denom),
+        UnsolicitedFunds { depositor } => query::unsolicited_funds(deps, depositor),
+        Refunds { address } => query::refunds(deps, address),
+        #[cfg(feature = "ibc")]
+        Channels { pagination } => query::channels(deps, pagination.unwrap_or_default()),
+        Defaults {} => query::defaults(),
+        ValidateConfigUpdate { config } => query::validate_config_update(deps, env, *config),
+        ConfigDiff { proposed } => query::config_diff(deps, env, *proposed),
+        DenomInfo { denom } => query::denom_info(deps, denom),
+        Conversion { id } => query::conversion(deps, id),
+        Report {
+            from_height,
+            to_height,
+            pagination,
+        } => query::report(deps, from_height, to_height, pagination.unwrap_or_default()),
+        VerifyReceipt { id } => query::verify_receipt(deps, env, id),
+        #[cfg(feature = "history")]
+        AdminHistory { pagination } => query::admin_history(deps, pagination.unwrap_or_default()),
+        #[cfg(feature = "history")]
+        UpgradeHistory { pagination } => {
+            query::upgrade_history(deps, pagination.unwrap_or_default())
+        }
+        Deposit { address } => query::deposit(deps, address),
+        Deposits { pagination } => query::deposits(deps, pagination.unwrap_or_default()),
+        Stream { id } => query::stream(deps, env, id),
+        Streams { pagination } => query::streams(deps, env, pagination.unwrap_or_default()),
+        Commitment { address } => query::commitment(deps, address),
+        Auction {} => query::auction(deps),
+        Float {} => query::float(deps),
+        AuctionCommitment { round, address } => query::auction_commitment(deps, round, address),
+        AuctionSettlement { round } => query::auction_settlement(deps, round),
+        StorageLayout {} => query::storage_layout(),
+        GasHints {} => query::gas_hints(),
+        ErrorCatalog {} => query::error_catalog(),
+    }
+}
+
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    use ExecuteMsg::*;
+
+    let config = load_config(deps.storage)?;
+    Lifecycle::load(deps.storage, &config)?.ensure_allows(deps.storage, operation(&msg))?;
+    let redact = config.redact_senders.unwrap_or_default();
+
+    let res = match msg {
+        UpdateAdmin { admin } => exec::update_admin(deps, env, info, admin),
+        Access(AccessMsg::ProposeAdmin { admin }) => exec::propose_admin(deps, info, admin),
+        Access(AccessMsg::AcceptAdmin {}) => exec::accept_admin(deps, env, info),
+        Access(AccessMsg::CancelAdminProposal {}) => exec::cancel_admin_proposal(deps, info),
+        Access(AccessMsg::GrantRole { role, address }) => {
+            exec::grant_role(deps, info, role, address)
+        }
+        Access(AccessMsg::RevokeRole { role, address }) => {
+            exec::revoke_role(deps, info, role, address)
+        }
+        Access(AccessMsg::RenounceRole { role }) => exec::renounce_role(deps, info, role),
+        UpdateConfig { config, strict } => {
+            exec::update_config(deps, env, info, *config, strict.unwrap_or_default())
+        }
+        ReplaceConfig { config } => exec::replace_config(deps, env, info, *config),
+        RefreshDenomInfo {} => exec::refresh_denom_info(deps, info),
+        SetAuthzEnabled { enabled } => exec::set_authz_enabled(deps, info, enabled),
+        EmitExpectedGrants {} => exec::emit_expected_grants(deps, env, info),
+        HealthCheck {} => exec::health_check(deps, env, info),
+        SetNotifyTarget { target } => exec::set_notify_target(deps, info, target),
+        UpdateMetadata { metadata } => exec::update_metadata(deps, info, metadata),
+        Batch { ops } => exec::batch(deps, env, info, ops),
+        SetDelegation {
+            delegate,
+            delegation,
+        } => exec::set_delegation(deps, info, delegate, delegation),
+        Pause { reason, message } => exec::pause(deps, env, info, reason, message),
+        Convert {
+            callback,
+            recipient,
+            recipients,
+            from_deposit,
+            memo,
+            min_receive,
+        } => exec::convert(
+            deps,
+            env,
+            info,
+            callback,
+            recipient,
+            recipients,
+            from_deposit,
+            memo,
+            min_receive,
+        ),
+        SetQuorum { members, threshold } => exec::set_quorum(deps, info, members, threshold),
+        ProposeAction { action } => exec::propose_action(deps, info, action),
+        ApproveAction { id } => exec::approve_action(deps, info, id),
+        ExecuteAction { id } => exec::execute_action(deps, env, info, id),
+        RecordUnsolicited { depositor, amount } => {
+            exec::record_unsolicited(deps, env, info, depositor, amount)
+        }
+        ReturnUnsolicited { depositor, denom } => {
+            exec::return_unsolicited(deps, info, depositor, denom)
+        }
+        BurnResidual {} => exec::burn_residual(deps, env, info),
+        MintRoundingReserve { recipient } => {
+            exec::mint_rounding_reserve(deps, env, info, recipient)
+        }
+        ClaimRefund {} => exec::claim_refund(deps, info),
+        Deposit { pubkey } => exec::deposit(deps, info, pubkey),
+        Withdraw { amount } => exec::withdraw(deps, info, amount),
+        FundFloat {} => exec::fund_float(deps, info),
+        WithdrawFloat { amount } => exec::withdraw_float(deps, info, amount),
+        ConvertWithSignature {
+            payload,
+            signature,
+            pubkey,
+        } => exec::convert_with_signature(deps, env, info, payload, signature, pubkey),
+        StartStreamingConvert { total, duration } => {
+            exec::start_streaming_convert(deps, env, info, total, duration)
+        }
+        ClaimStream { id } => exec::claim_stream(deps, env, info, id),
+        CancelStream { id } => exec::cancel_stream(deps, info, id),
+        CommitConvert { hash } => exec::commit_convert(deps, env, info, hash),
+        RevealConvert { amount, salt } => exec::reveal_convert(deps, env, info, amount, salt),
+        CancelCommitment {} => exec::cancel_commitment(deps, info),
+        SetAuction { auction } => exec::set_auction(deps, env, info, auction),
+        AuctionCommit {} => exec::auction_commit(deps, env, info),
+        SettleAuction {} => exec::settle_auction(deps, env, info),
+        ClaimAuction { round } => exec::claim_auction(deps, env, info, round),
+        CancelAuctionCommitment { round } => exec::cancel_auction_commitment(deps, info, round),
+        SetSuccessor { addr } => exec::set_successor(deps, info, addr),
+        MigrateMyPosition { streams } => exec::migrate_my_position(deps, info, streams),
+        #[cfg(feature = "ibc")]
+        TransferRemote {
+            channel_id,
+            receiver,
+            timeout_seconds,
+        } => exec::transfer_remote(deps, env, info, channel_id, receiver, timeout_seconds),
+    }?;
+    Ok(redact_senders(res, redact))
+}
+
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let redact = load_config(deps.storage)?
+        .redact_senders
+        .unwrap_or_default();
+
+    let res = match msg.id {
+        REPLY_EXEC_ID => reply::exec(deps, env, from_json(&msg.payload)?, msg.result),
+        #[cfg(feature = "ibc")]
+        REPLY_REMOTE_CONVERT_ID => {
+            crate::ibc::reply_convert(deps, env, from_json(&msg.payload)?, msg.result)
+        }
+        REPLY_NOTIFY_ID => reply::notify_failed(msg.result),
+        _ => Err(ContractError::ConvertError(UnknownReply)),
+    }?;
+    Ok(redact_senders(res, redact))
+}
+
+// Privacy deployments strip the user addresses from the attributes of the contract and of
+// the events it emits
+fn redact_senders(mut res: Response, redact: bool) -> Response {
+    if !redact {
+        return res;
+    }
+    let keep = |attr: &Attribute| !REDACTED_ATTRIBUTES.contains(&attr.key.as_str());
+    res.attributes.retain(keep);
+    for event in &mut res.events {
+        event.attributes.retain(keep);
+    }
+    res
+}
+
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    use SudoMsg::*;
+    match msg {
+        Freeze {} => sudo::set_frozen(deps, true),
+        Unfreeze {} => sudo::set_frozen(deps, false),
+        Access(AccessSudoMsg::ForceAdmin { admin }) => sudo::force_admin(deps, env, admin),
+        CancelScheduled {} => sudo::cancel_scheduled(deps, env),
+    }
+}
+
+pub fn migrate(
+    deps: DepsMut,
+    env: Env,
+    _msg: MigrateMsg,
+    info: MigrateInfo,
+) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::MigrateError(InvalidContractName));
+    }
+
+    // Storage layout moves are idempotent and run regardless of the stored version
+    let config_migrated = migrate_config(deps.storage)?;
+    // Deployments predating the admin history start it with their current admin
+    #[cfg(feature = "history")]
+    if !crate::state::ADMIN_CHANGE_COUNT.exists(deps.storage) {
+        if let Some(admin) = ADMIN.get(deps.as_ref())? {
+            record_admin(deps.storage, &admin, &env.block)?;
+        }
+    }
+
+    // Every migration is recorded, even without a version change, so the deployed lineage
+    // can be verified without archive event queries
+    record_upgrade(deps.storage, &stored.version, &env.block, &info.sender)?;
+    let migrated = Event::new("migrated")
+        .add_attribute("contract", CONTRACT_NAME)
+        .add_attribute("from_version", stored.version.as_str())
+        .add_attribute("to_version", CONTRACT_VERSION)
+        .add_attribute("height", env.block.height.to_string())
+        .add_attribute("initiator", info.sender);
+
+    if stored.version == CONTRACT_VERSION {
+        return Ok(Response::new()
+            .add_event(migrated)
+            .add_attribute("action", "migrate")
+            .add_attribute("note", "already at latest version")
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("config_migrated", config_migrated.to_string()));
+    }
+
+    // TODO: Add migration steps when needed
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_event(migrated)
+        .add_attribute("action", "migrate")
+        .add_attribute("contract", CONTRACT_NAME)
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", CONTRACT_VERSION)
+        .add_attribute("config_migrated", config_migrated.to_string()))
+}
+
+mod query {
+    use super::*;
+    use crate::consts::{
+        API_VERSION, BECH32_PREFIX, DEFAULT_PAGE_LIMIT, DEFAULT_POA_ADMIN, DEFAULT_SOURCE_DENOM,
+        DEFAULT_TARGET_DENOM, GAS_HINTS, GAS_PER_RECIPIENT, MAX_PAGE_LIMIT, PROFILE,
+    };
+    use crate::error::ConvertError::InvalidReportRange;
+    use crate::msg::{
+        ApiVersionResponse, AuctionResponse, ConfigDiffResponse, ContractInfoResponse,
+        CurrentRateResponse, DefaultsResponse, DelegationResponse, DepositResponse,
+        DisplayRateResponse, EpochUsageResponse, EpochsResponse, ErrorCatalogEntry,
+        ErrorCatalogResponse, ExpectedGrantsResponse, FloatResponse, GasHint, GasHintsResponse,
+        GrantsResponse, MetricsResponse, PairTotalsResponse, ParseAmountResponse,
+        PendingActionsResponse, RefundsResponse, ReportResponse, StatusResponse, StorageEntry,
+        StorageLayoutResponse, StreamResponse, SuccessorResponse, UnsolicitedBalanceResponse,
+        UnsolicitedFundsResponse, VerifyReceiptResponse,
     };
+    use crate::state::{
+        ConversionStatus, AUCTION, AUCTION_COMMITMENTS, AUCTION_ROUND, AUCTION_SETTLEMENTS,
+        COMMITMENTS, CONVERSIONS, CONVERSION_COUNT, DEPOSITS, EPOCHS, ESCROWED, FLOATS,
+        MIGRATED_POSITIONS, PROPOSALS, QUORUM, REFUNDS, STORAGE_LAYOUT, STREAMS, SUCCESSOR, TOTALS,
+        UNSOLICITED, UNSOLICITED_TOTAL,
+    };
+    use crate::strategy::RateStrategy;
+    use cosmwasm_std::{Coin, Decimal256, StdError, Uint256};
+    use cw_storage_plus::{Bound, KeyDeserialize};
+    use manifest_common::pagination::{paginate, PageRequest};
+    use manifest_std::liftedinit::manifest::v1::MsgBurnHeldBalance;
+    use manifest_std::osmosis::tokenfactory::v1beta1::MsgMint;
+    use std::collections::{BTreeMap, BTreeSet};
+
+    pub fn config(deps: Deps) -> StdResult<Binary> {
+        to_json_binary(&load_config(deps.storage)?)
+    }
+
+    pub fn admin(deps: Deps) -> StdResult<Binary> {
+        to_json_binary(&ADMIN.query_admin(deps)?)
+    }
+
+    pub fn pending_admin(deps: Deps) -> StdResult<Binary> {
+        to_json_binary(&PENDING_ADMIN.query(deps.storage)?)
+    }
+
+    pub fn role_members(deps: Deps, role: String) -> StdResult<Binary> {
+        to_json_binary(&ROLES.query_members(deps.storage, &role)?)
+    }
+
+    pub fn has_role(deps: Deps, role: String, address: String) -> StdResult<Binary> {
+        let address = deps.api.addr_validate(&address)?;
+        to_json_binary(&ROLES.query_has_role(deps.storage, &role, &address))
+    }
+
+    // Simulate an `update_config` without executing it, returning the resulting config or the
+    // error the update would fail with
+    pub fn validate_config_update(deps: Deps, env: Env, update: UpdateConfig) -> StdResult<Binary> {
+        let config = load_config(deps.storage)?;
+        let (config, _) = apply_config_update(deps, &env.contract.address, config, update)
+            .map_err(|e| StdError::msg(e.to_string()))?;
+        to_json_binary(&config)
+    }
+
+    pub fn config_diff(deps: Deps, env: Env, proposed: UpdateConfig) -> StdResult<Binary> {
+        let config = load_config(deps.storage)?;
+        let changes = proposed.changes(&config);
+        let error = apply_config_update(deps, &env.contract.address, config, proposed)
+            .err()
+            .map(|e| e.to_string());
+        to_json_binary(&ConfigDiffResponse { changes, error })
+    }
+
+    pub fn simulate_execution(
+        deps: Deps,
+        env: Env,
+        sender: String,
+        amount: Uint256,
+    ) -> StdResult<Binary> {
+        let res = super::simulate_execution(deps, &env, &sender, amount)
+            .map_err(|e| StdError::msg(e.to_string()))?;
+        to_json_binary(&res)
+    }
+
+    #[cfg(feature = "quotas")]
+    pub fn remaining_quota(deps: Deps, env: Env, address: String) -> StdResult<Binary> {
+        use crate::consts::DAY_SECONDS;
+        use crate::msg::RemainingQuotaResponse;
+
+        let config = load_config(deps.storage)?;
+        // Remote senders are keyed by channel, see quota::remote_sender
+        let address = match address.contains('/') {
+            true => Addr::unchecked(address),
+            false => deps.api.addr_validate(&address)?,
+        };
+        let now = env.block.time;
+        to_json_binary(&RemainingQuotaResponse {
+            quota: config.daily_quota,
+            used: quota::used(deps.storage, &address, now)?,
+            remaining: quota::remaining(deps.storage, &config, &address, now)?,
+            resets_at: Timestamp::from_seconds((quota::day(now) + 1) * DAY_SECONDS),
+        })
+    }
+
+    pub fn reverse_simulate(deps: Deps, env: Env, desired: Uint256) -> StdResult<Binary> {
+        let res = super::reverse_simulate(deps, &env, desired)
+            .map_err(|e| StdError::msg(e.to_string()))?;
+        to_json_binary(&res)
+    }
+
+    pub fn api_version() -> StdResult<Binary> {
+        to_json_binary(&ApiVersionResponse {
+            version: API_VERSION.to_string(),
+        })
+    }
+
+    pub fn contract_info(deps: Deps) -> StdResult<Binary> {
+        to_json_binary(&ContractInfoResponse {
+            contract_name: CONTRACT_NAME.to_string(),
+            contract_version: CONTRACT_VERSION.to_string(),
+            api_version: API_VERSION.to_string(),
+            metadata: METADATA.may_load(deps.storage)?,
+        })
+    }
+
+    pub fn status(deps: Deps) -> StdResult<Binary> {
+        let config = load_config(deps.storage)?;
+        to_json_binary(&StatusResponse {
+            paused: config.paused,
+            frozen: FROZEN.may_load(deps.storage)?.unwrap_or_default(),
+            authz_enabled: AUTHZ_ENABLED.may_load(deps.storage)?.unwrap_or(true),
+            pause: PAUSE.may_load(deps.storage)?,
+        })
+    }
+
+    pub fn defaults() -> StdResult<Binary> {
+        to_json_binary(&DefaultsResponse {
+            contract_name: CONTRACT_NAME.to_string(),
+            contract_version: CONTRACT_VERSION.to_string(),
+            profile: PROFILE.to_string(),
+            poa_admin: DEFAULT_POA_ADMIN.to_string(),
+            source_denom: DEFAULT_SOURCE_DENOM.to_string(),
+            target_denom: DEFAULT_TARGET_DENOM.to_string(),
+            bech32_prefix: BECH32_PREFIX.to_string(),
+        })
+    }
+
+    pub fn gas_hints() -> StdResult<Binary> {
+        let hints = GAS_HINTS
+            .iter()
+            .map(|(message, gas)| GasHint {
+                message: message.to_string(),
+                gas: *gas,
+            })
+            .collect();
+        to_json_binary(&GasHintsResponse {
+            hints,
+            per_recipient: GAS_PER_RECIPIENT,
+        })
+    }
+
+    pub fn error_catalog() -> StdResult<Binary> {
+        let mut errors: Vec<_> = ContractError::catalog()
+            .into_iter()
+            .filter_map(|err| {
+                Some(ErrorCatalogEntry {
+                    code: err.code()?,
+                    message: err.to_string(),
+                })
+            })
+            .collect();
+        errors.sort_by_key(|entry| entry.code);
+        to_json_binary(&ErrorCatalogResponse { errors })
+    }
+
+    pub fn storage_layout() -> StdResult<Binary> {
+        let entries = STORAGE_LAYOUT
+            .iter()
+            .map(|decl| StorageEntry {
+                name: decl.name.to_string(),
+                namespace: decl.namespace.to_string(),
+                kind: if decl.key.is_empty() { "item" } else { "map" }.to_string(),
+                key: decl.key.iter().map(|key| key.to_string()).collect(),
+                value: decl.value.to_string(),
+            })
+            .collect();
+        to_json_binary(&StorageLayoutResponse { entries })
+    }
+
+    pub fn frozen(deps: Deps) -> StdResult<Binary> {
+        to_json_binary(&FROZEN.may_load(deps.storage)?.unwrap_or_default())
+    }
+
+    pub fn notify_target(deps: Deps) -> StdResult<Binary> {
+        to_json_binary(&NOTIFY_TARGET.may_load(deps.storage)?)
+    }
+
+    pub fn successor(deps: Deps) -> StdResult<Binary> {
+        to_json_binary(&SuccessorResponse {
+            successor: SUCCESSOR.may_load(deps.storage)?,
+            migrated_positions: MIGRATED_POSITIONS
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        })
+    }
+
+    pub fn current_rate(deps: Deps, env: Env, amount: Option<Uint256>) -> StdResult<Binary> {
+        let config = load_config(deps.storage)?;
+        let rate = resolve_rate(deps, &env.block, &config, amount.unwrap_or_default(), None)
+            .map_err(|e| StdError::msg(e.to_string()))?;
+        to_json_binary(&CurrentRateResponse {
+            kind: config
+                .rate_strategy
+                .as_ref()
+                .map_or("fixed", RateStrategy::kind)
+                .to_string(),
+            rate: rate.into_inner(),
+        })
+    }
+
+    // Without configured exponents the rate applies to base units directly, which is then
+    // also its display value
+    pub fn display_rate(deps: Deps) -> StdResult<Binary> {
+        let config = load_config(deps.storage)?;
+        let source_exponent = config.source_exponent.unwrap_or_default();
+        let target_exponent = config.target_exponent.unwrap_or_default();
+        let raw_rate = config
+            .rate
+            .to_base_units(source_exponent, target_exponent)
+            .map_err(|e| StdError::msg(e.to_string()))?;
+        let display = format!(
+            "1 {} = {} {}",
+            config.source_symbol(),
+            config.rate,
+            config.target_symbol()
+        );
+        to_json_binary(&DisplayRateResponse {
+            display_rate: config.rate.clone().into_inner(),
+            raw_rate,
+            source_symbol: config.source_symbol().to_string(),
+            target_symbol: config.target_symbol().to_string(),
+            source_exponent,
+            target_exponent,
+            display,
+        })
+    }
+
+    pub fn parse_amount(deps: Deps, input: String) -> StdResult<Binary> {
+        let config = load_config(deps.storage)?;
+        let amount = crate::amount::parse_amount(&config, &input)
+            .map_err(|e| StdError::msg(e.to_string()))?;
+        to_json_binary(&ParseAmountResponse { amount })
+    }
+
+    pub fn current_epoch(deps: Deps, env: Env) -> StdResult<Binary> {
+        let config = load_config(deps.storage)?;
+        to_json_binary(&load_epoch(
+            deps.storage,
+            env.block.time,
+            config.epoch_length,
+        )?)
+    }
+
+    pub fn epoch_usage(deps: Deps, env: Env) -> StdResult<Binary> {
+        let config = load_config(deps.storage)?;
+        let epoch = load_epoch(deps.storage, env.block.time, config.epoch_length)?;
+        to_json_binary(&EpochUsageResponse {
+            epoch: epoch.id,
+            cap: config.epoch_cap,
+            minted: epoch.minted,
+            remaining: config.epoch_cap.map(|cap| cap.saturating_sub(epoch.minted)),
+            ends_at: config
+                .epoch_length
+                .map(|length| epoch.start.plus_seconds(length)),
+        })
+    }
+
+    // Everything ops dashboards need in a single query. Flags are reported as 0 or 1
+    pub fn metrics(deps: Deps, env: Env) -> StdResult<Binary> {
+        let config = load_config(deps.storage)?;
+        let totals = TOTALS.may_load(deps.storage)?.unwrap_or_default();
+        let epoch = load_epoch(deps.storage, env.block.time, config.epoch_length)?;
+        let frozen = FROZEN.may_load(deps.storage)?.unwrap_or_default();
+        let authz_enabled = AUTHZ_ENABLED.may_load(deps.storage)?.unwrap_or(true);
+        // Fractions of target base units, of the current target denom
+        let rounding_reserve = ROUNDING_RESERVE
+            .may_load(deps.storage, config.target_denom.as_str())?
+            .unwrap_or_default();
+
+        let metrics = BTreeMap::from([
+            ("burned_total", gauge(totals.burned)),
+            ("minted_total", gauge(totals.minted)),
+            ("conversions_total", gauge(totals.conversions)),
+            ("epoch_id", gauge(epoch.id)),
+            ("epoch_burned", gauge(epoch.burned)),
+            ("epoch_minted", gauge(epoch.minted)),
+            ("epoch_conversions", gauge(epoch.conversions)),
+            ("rate", config.rate.into_inner()),
+            ("paused", gauge(config.paused as u8)),
+            ("frozen", gauge(frozen as u8)),
+            ("authz_enabled", gauge(authz_enabled as u8)),
+            ("rounding_reserve", rounding_reserve),
+        ]);
+        to_json_binary(&MetricsResponse {
+            metrics: metrics
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        })
+    }
+
+    // Values too large for a decimal are reported as its maximum
+    fn gauge(value: impl Into<Uint256>) -> Decimal256 {
+        Decimal256::from_atomics(value.into(), 0).unwrap_or(Decimal256::MAX)
+    }
+
+    // Past epochs, only the last EPOCH_RETENTION epochs with conversions are kept
+    pub fn epochs(deps: Deps, start_after: Option<u64>, limit: Option<u32>) -> StdResult<Binary> {
+        let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+        let epochs = EPOCHS
+            .range(
+                deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|item| item.map(|(_, e)| e))
+            .collect::<StdResult<Vec<_>>>()?;
+        to_json_binary(&EpochsResponse { epochs })
+    }
+
+    // The POA admin must grant the contract the burn and mint messages used by conversions
+    pub fn grants(deps: Deps, env: Env) -> StdResult<Binary> {
+        let config = load_config(deps.storage)?;
+        to_json_binary(&GrantsResponse {
+            granter: config.poa_admin.to_string(),
+            grantee: env.contract.address.to_string(),
+            type_urls: vec![
+                MsgBurnHeldBalance::TYPE_URL.to_string(),
+                MsgMint::TYPE_URL.to_string(),
+            ],
+            enabled: AUTHZ_ENABLED.may_load(deps.storage)?.unwrap_or(true),
+        })
+    }
+
+    pub fn expected_grants(deps: Deps, env: Env) -> StdResult<Binary> {
+        let config = load_config(deps.storage)?;
+        to_json_binary(&ExpectedGrantsResponse {
+            grants: super::expected_grants(&config, &env.contract.address),
+        })
+    }
+
+    pub fn quorum(deps: Deps) -> StdResult<Binary> {
+        to_json_binary(&QUORUM.may_load(deps.storage)?)
+    }
+
+    pub fn pending_action(deps: Deps, id: u64) -> StdResult<Binary> {
+        to_json_binary(&PROPOSALS.load(deps.storage, id)?)
+    }
+
+    pub fn pending_actions(
+        deps: Deps,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Binary> {
+        let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+        let actions = PROPOSALS
+            .range(
+                deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|item| item.map(|(_, p)| p))
+            .collect::<StdResult<Vec<_>>>()?;
+        to_json_binary(&PendingActionsResponse { actions })
+    }
+
+    // The contract only holds funds between transactions as escrowed refunds, channel escrows,
+    // deposits, streams and commitments, the rest of its balance is unsolicited
+    pub fn unsolicited_balance(deps: Deps, env: Env, denom: String) -> StdResult<Binary> {
+        let balance = deps
+            .querier
+            .query_balance(&env.contract.address, &denom)?
+            .amount;
+        let recorded = UNSOLICITED_TOTAL
+            .may_load(deps.storage, &denom)?
+            .unwrap_or_default();
+        let escrowed = ESCROWED.may_load(deps.storage, &denom)?.unwrap_or_default();
+        to_json_binary(&UnsolicitedBalanceResponse {
+            balance,
+            recorded,
+            escrowed,
+            unrecorded: balance.saturating_sub(recorded).saturating_sub(escrowed),
+        })
+    }
+
+    pub fn unsolicited_funds(deps: Deps, depositor: String) -> StdResult<Binary> {
+        let depositor = deps.api.addr_validate(&depositor)?;
+        let funds = UNSOLICITED
+            .prefix(&depositor)
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(denom, amount)| Coin::new(amount, denom)))
+            .collect::<StdResult<Vec<_>>>()?;
+        to_json_binary(&UnsolicitedFundsResponse { funds })
+    }
+
+    pub fn refunds(deps: Deps, address: String) -> StdResult<Binary> {
+        let address = deps.api.addr_validate(&address)?;
+        let refunds = REFUNDS
+            .may_load(deps.storage, &address)?
+            .unwrap_or_default();
+        to_json_binary(&RefundsResponse { refunds })
+    }
+
+    pub fn conversion(deps: Deps, id: u64) -> StdResult<Binary> {
+        to_json_binary(&CONVERSIONS.load(deps.storage, id)?)
+    }
+
+    pub fn report(
+        deps: Deps,
+        from_height: u64,
+        to_height: u64,
+        pagination: PageRequest,
+    ) -> StdResult<Binary> {
+        if from_height > to_height {
+            return Err(StdError::msg(
+                ContractError::ConvertError(InvalidReportRange).to_string(),
+            ));
+        }
+        let mut start = first_conversion_at(deps.storage, from_height)?;
+        if let Some(key) = &pagination.start_after {
+            start = start.max(u64::from_vec(key.to_vec())? + 1);
+        }
+
+        // Read one more record than requested to know whether another page follows
+        let limit = pagination.limit();
+        let mut records = CONVERSIONS
+            .range(
+                deps.storage,
+                Some(Bound::inclusive(start)),
+                None,
+                Order::Ascending,
+            )
+            .take_while(|record| {
+                record
+                    .as_ref()
+                    .map_or(true, |(_, conversion)| conversion.height <= to_height)
+            })
+            .take(limit + 1)
+            .collect::<StdResult<Vec<_>>>()?;
+        let next_key = if records.len() > limit {
+            records.truncate(limit);
+            records
+                .last()
+                .map(|(id, _)| Binary::new(id.to_be_bytes().to_vec()))
+        } else {
+            None
+        };
+
+        let (mut conversions, mut failed) = (0, 0);
+        let (mut burned, mut minted, mut fees) = (vec![], vec![], vec![]);
+        let mut senders = BTreeSet::new();
+        for (_, conversion) in records {
+            match conversion.status {
+                ConversionStatus::Succeeded => {
+                    conversions += 1;
+                    burned.push(conversion.burned);
+                    minted.push(conversion.minted);
+                    fees.extend(conversion.fee);
+                    senders.insert(conversion.sender);
+                }
+                ConversionStatus::Failed { .. } => failed += 1,
+                ConversionStatus::Pending => {}
+            }
+        }
+        let normalize =
+            |coins: Vec<Coin>| normalize_coins(coins).map_err(|e| StdError::msg(e.to_string()));
+        to_json_binary(&ReportResponse {
+            from_height,
+            to_height,
+            conversions,
+            burned: normalize(burned)?,
+            minted: normalize(minted)?,
+            fees: normalize(fees)?,
+            failed,
+            senders: senders.into_iter().collect(),
+            next_key,
+        })
+    }
+
+    // Id of the first conversion recorded at or after the height. Ids are assigned in
+    // recording order, so their heights never decrease
+    fn first_conversion_at(storage: &dyn Storage, height: u64) -> StdResult<u64> {
+        let (mut low, mut high) = (
+            1,
+            CONVERSION_COUNT.may_load(storage)?.unwrap_or_default() + 1,
+        );
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if CONVERSIONS.load(storage, mid)?.height < height {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(low)
+    }
+
+    pub fn verify_receipt(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
+        let conversion = CONVERSIONS.load(deps.storage, id)?;
+        let computed = receipt_hash(&env.block.chain_id, &env.contract.address, &conversion)?;
+        let valid = computed.is_some() && computed == conversion.hash;
+        to_json_binary(&VerifyReceiptResponse {
+            id,
+            hash: conversion.hash,
+            computed,
+            valid,
+        })
+    }
+
+    pub fn deposit(deps: Deps, address: String) -> StdResult<Binary> {
+        let address = deps.api.addr_validate(&address)?;
+        to_json_binary(&DEPOSITS.may_load(deps.storage, &address)?)
+    }
+
+    pub fn deposits(deps: Deps, pagination: PageRequest) -> StdResult<Binary> {
+        let page = paginate(deps.storage, &DEPOSITS, &pagination, |address, deposit| {
+            Ok(DepositResponse {
+                address: address.to_string(),
+                amount: deposit.amount,
+                pubkey: deposit.pubkey,
+                nonce: deposit.nonce,
+            })
+        })?;
+        to_json_binary(&page)
+    }
+
+    pub fn stream(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
+        let stream = STREAMS.load(deps.storage, id)?;
+        to_json_binary(&StreamResponse {
+            claimable: stream.claimable(env.block.time),
+            stream,
+        })
+    }
+
+    pub fn streams(deps: Deps, env: Env, pagination: PageRequest) -> StdResult<Binary> {
+        let page = paginate(deps.storage, &STREAMS, &pagination, |_, stream| {
+            Ok(StreamResponse {
+                claimable: stream.claimable(env.block.time),
+                stream,
+            })
+        })?;
+        to_json_binary(&page)
+    }
+
+    pub fn commitment(deps: Deps, address: String) -> StdResult<Binary> {
+        let address = deps.api.addr_validate(&address)?;
+        to_json_binary(&COMMITMENTS.may_load(deps.storage, &address)?)
+    }
+
+    pub fn float(deps: Deps) -> StdResult<Binary> {
+        let config = load_config(deps.storage)?;
+        let denom = config.target_denom.to_string();
+        to_json_binary(&FloatResponse {
+            float: FLOATS.may_load(deps.storage, &denom)?.unwrap_or_default(),
+            enabled: config.float_payouts.unwrap_or_default(),
+            denom,
+        })
+    }
+
+    pub fn auction(deps: Deps) -> StdResult<Binary> {
+        to_json_binary(&AuctionResponse {
+            auction: AUCTION.may_load(deps.storage)?,
+            round: AUCTION_ROUND.may_load(deps.storage)?,
+        })
+    }
+
+    pub fn auction_commitment(deps: Deps, round: u64, address: String) -> StdResult<Binary> {
+        let address = deps.api.addr_validate(&address)?;
+        to_json_binary(&AUCTION_COMMITMENTS.may_load(deps.storage, (round, &address))?)
+    }
+
+    pub fn auction_settlement(deps: Deps, round: u64) -> StdResult<Binary> {
+        to_json_binary(&AUCTION_SETTLEMENTS.load(deps.storage, round)?)
+    }
+
+    pub fn denom_info(deps: Deps, denom: String) -> StdResult<Binary> {
+        to_json_binary(&DENOM_INFO.load(deps.storage, &denom)?)
+    }
+
+    #[cfg(feature = "history")]
+    pub fn admin_history(deps: Deps, pagination: PageRequest) -> StdResult<Binary> {
+        use crate::state::ADMIN_HISTORY;
+
+        let page = paginate(deps.storage, &ADMIN_HISTORY, &pagination, |_, change| {
+            Ok(change)
+        })?;
+        to_json_binary(&page)
+    }
+
+    #[cfg(feature = "history")]
+    pub fn upgrade_history(deps: Deps, pagination: PageRequest) -> StdResult<Binary> {
+        use crate::state::UPGRADE_HISTORY;
+
+        let page = paginate(deps.storage, &UPGRADE_HISTORY, &pagination, |_, upgrade| {
+            Ok(upgrade)
+        })?;
+        to_json_binary(&page)
+    }
+
+    pub fn pair_totals(deps: Deps, pagination: PageRequest) -> StdResult<Binary> {
+        let page = paginate(
+            deps.storage,
+            &PAIR_TOTALS,
+            &pagination,
+            |(source_denom, target_denom), totals| {
+                Ok(PairTotalsResponse {
+                    source_denom,
+                    target_denom,
+                    burned: totals.burned,
+                    minted: totals.minted,
+                    conversions: totals.conversions,
+                })
+            },
+        )?;
+        to_json_binary(&page)
+    }
+
+    pub fn delegations(deps: Deps, pagination: PageRequest) -> StdResult<Binary> {
+        let page = paginate(
+            deps.storage,
+            &DELEGATIONS,
+            &pagination,
+            |delegate, delegation| {
+                Ok(DelegationResponse {
+                    delegate,
+                    delegation,
+                })
+            },
+        )?;
+        to_json_binary(&page)
+    }
+
+    #[cfg(feature = "ibc")]
+    pub fn channels(deps: Deps, pagination: PageRequest) -> StdResult<Binary> {
+        use crate::msg::ChannelResponse;
+        use crate::state::{CHANNEL_ESCROW, IBC_CHANNELS};
+
+        let page = paginate(
+            deps.storage,
+            &IBC_CHANNELS,
+            &pagination,
+            |channel_id, channel| {
+                let escrow = CHANNEL_ESCROW
+                    .prefix(&channel_id)
+                    .range(deps.storage, None, None, Order::Ascending)
+                    .map(|item| item.map(|(denom, amount)| Coin::new(amount, denom)))
+                    .collect::<StdResult<Vec<_>>>()?;
+                Ok(ChannelResponse {
+                    channel_id,
+                    connection_id: channel.connection_id,
+                    counterparty_port_id: channel.counterparty_endpoint.port_id,
+                    counterparty_channel_id: channel.counterparty_endpoint.channel_id,
+                    escrow,
+                })
+            },
+        )?;
+        to_json_binary(&page)
+    }
+}
+
+mod reply {
+    use super::*;
+    use crate::state::{ConversionStatus, CONVERSIONS, ESCROWED, REFUNDS};
+    use cosmwasm_std::SubMsgResult;
+
+    // The send, burn and mint either all executed or were all reverted. In the latter case
+    // the source tokens are still held by the contract and escrowed for the sender to claim
+    pub fn exec(
+        deps: DepsMut,
+        env: Env,
+        id: u64,
+        result: SubMsgResult,
+    ) -> Result<Response, ContractError> {
+        let mut pending = CONVERSIONS.load(deps.storage, id)?;
+        let callback = PENDING_CALLBACKS.may_load(deps.storage, id)?;
+        PENDING_CALLBACKS.remove(deps.storage, id);
+
+        if let SubMsgResult::Err(err) = result {
+            pending.status = ConversionStatus::Failed { error: err.clone() };
+            CONVERSIONS.save(deps.storage, id, &pending)?;
+
+            let mut refunds = REFUNDS
+                .may_load(deps.storage, &pending.sender)?
+                .unwrap_or_default();
+            match refunds.iter_mut().find(|c| c.denom == pending.burned.denom) {
+                Some(c) => {
+                    c.amount = c
+                        .amount
+                        .checked_add(pending.burned.amount)
+                        .map_err(StdError::from)?
+                }
+                None => refunds.push(pending.burned.clone()),
+            }
+            REFUNDS.save(deps.storage, &pending.sender, &refunds)?;
+            ESCROWED.update(deps.storage, &pending.burned.denom, |total| {
+                total
+                    .unwrap_or_default()
+                    .checked_add(pending.burned.amount)
+                    .map_err(StdError::from)
+            })?;
+            if pending.from_float.unwrap_or_default() {
+                unpay_from_float(deps.storage, &pending.minted)?;
+            }
+
+            let alert = Alert::ConversionFailed {
+                conversion_id: id,
+                sender: pending.sender.to_string(),
+                error: err.clone(),
+            };
+            return Ok(Response::new()
+                .add_submessages(notify(deps.storage, alert)?)
+                .add_attribute("action", "fail_conversion")
+                .add_attribute("contract", CONTRACT_NAME)
+                .add_attribute("version", CONTRACT_VERSION)
+                .add_attribute("conversion_id", id.to_string())
+                .add_attribute("sender", pending.sender)
+                .add_attribute("refundable", pending.burned.to_string())
+                .add_attribute("error", err));
+        }
+
+        pending.status = ConversionStatus::Succeeded;
+        CONVERSIONS.save(deps.storage, id, &pending)?;
+        record_conversion(
+            deps.storage,
+            env.block.time,
+            &pending.burned,
+            &pending.minted,
+            pending.rate.as_ref(),
+        )?;
+
+        let res = Response::new()
+            .add_attribute("action", "finalize_conversion")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("conversion_id", id.to_string())
+            .add_attribute("sender", pending.sender)
+            .add_attribute("burned", pending.burned.to_string())
+            .add_attribute("minted", pending.minted.to_string());
+
+        // Forward the freshly minted tokens to the callback contract
+        let Some(PendingCallback { contract, msg }) = callback else {
+            return Ok(res);
+        };
+        Ok(res.add_message(WasmMsg::Execute {
+            contract_addr: contract.to_string(),
+            msg,
+            funds: vec![pending.minted],
+        }))
+    }
+
+    // A failed alert is only reported, the alerted operation stands
+    pub fn notify_failed(result: SubMsgResult) -> Result<Response, ContractError> {
+        let SubMsgResult::Err(err) = result else {
+            return Err(ContractError::ConvertError(UnknownReply));
+        };
+
+        Ok(Response::new()
+            .add_attribute("action", "notify_failed")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("error", err))
+    }
+}
+
+mod sudo {
+    use super::*;
+    use crate::state::PROPOSALS;
+
+    // Kill switch for exploit response, only reachable through chain governance so it
+    // holds even if the admin key is compromised
+    pub fn set_frozen(deps: DepsMut, frozen: bool) -> Result<Response, ContractError> {
+        let config = load_config(deps.storage)?;
+        let transition = if frozen {
+            Transition::Freeze
+        } else {
+            Transition::Unfreeze
+        };
+        let state = Lifecycle::load(deps.storage, &config)?.transition(transition)?;
+        FROZEN.save(deps.storage, &matches!(state, Lifecycle::Frozen { .. }))?;
+
+        let alert = if frozen {
+            Alert::Frozen {}
+        } else {
+            Alert::Unfrozen {}
+        };
+        Ok(Response::new()
+            .add_submessages(notify(deps.storage, alert)?)
+            .add_attribute("action", if frozen { "freeze" } else { "unfreeze" })
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("frozen", frozen.to_string()))
+    }
+
+    // Recovery of a lost or compromised admin key. It bypasses the quorum and the frozen
+    // state, governance being the last resort
+    pub fn force_admin(deps: DepsMut, env: Env, admin: String) -> Result<Response, ContractError> {
+        let new = validate_addr(deps.api, &load_config(deps.storage)?, &admin)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "force_admin")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attributes(exec::set_admin(deps, &env, new)?))
+    }
+
+    // Drop every change waiting to take effect, whatever the quorum or the admin are up to.
+    // Each cancelled change is reported in a `cancelled` event
+    pub fn cancel_scheduled(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+        let mut cancelled = vec![];
+
+        let proposals = PROPOSALS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, proposal)| proposal))
+            .collect::<StdResult<Vec<_>>>()?;
+        for proposal in proposals {
+            PROPOSALS.remove(deps.storage, proposal.id);
+            cancelled.push(
+                Event::new("cancelled")
+                    .add_attribute("kind", "quorum_action")
+                    .add_attribute("id", proposal.id.to_string())
+                    .add_attribute("quorum_action", proposal.action.name()),
+            );
+        }
+
+        if let Some(candidate) = PENDING_ADMIN.get(deps.storage)? {
+            PENDING_ADMIN.clear(deps.storage);
+            cancelled.push(
+                Event::new("cancelled")
+                    .add_attribute("kind", "admin_transfer")
+                    .add_attribute("candidate", candidate),
+            );
+        }
+
+        // Steps already started stay, the last of them keeps applying. Without any, the
+        // configured rate applies again
+        let mut config = load_config(deps.storage)?;
+        if let Some(RateStrategy::Scheduled { steps }) = &config.rate_strategy {
+            let (started, upcoming): (Vec<_>, Vec<_>) = steps
+                .iter()
+                .cloned()
+                .partition(|step| step.start <= env.block.time);
+            if !upcoming.is_empty() {
+                cancelled.extend(upcoming.iter().map(|step| {
+                    Event::new("cancelled")
+                        .add_attribute("kind", "rate_step")
+                        .add_attribute("start", step.start.seconds().to_string())
+                        .add_attribute("rate", step.rate.to_string())
+                }));
+                config.rate_strategy =
+                    (!started.is_empty()).then_some(RateStrategy::Scheduled { steps: started });
+                CONFIG.save(deps.storage, &config)?;
+            }
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "cancel_scheduled")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("cancelled", cancelled.len().to_string())
+            .add_events(cancelled))
+    }
+}
+
+mod exec {
+    use super::*;
+    use crate::auction::{AuctionConfig, AuctionRound, Settlement};
+    use crate::consts::COMMITMENT_TTL_SECONDS;
+    use crate::consts::{MAX_BATCH_OPS, MAX_MEMO_LEN, MAX_PAUSE_MESSAGE_LEN, MAX_RECIPIENTS};
+    use crate::error::AdminError::{CannotRenounce, NotAdmin};
+    use crate::error::AmountError::AmountIsZero;
+    use crate::error::AuctionError::{
+        BatchMode, NoCommitment, NotConfigured as AuctionNotConfigured, NotSettled,
+        PendingCommitments, RoundEnded, RoundOpen,
+    };
+    use crate::error::CommitError::{
+        AlreadyCommitted, ExceedsEscrow, Expired as CommitmentExpired, HashMismatch, InvalidHash,
+        NotFound as CommitmentNotFound, SameBlock,
+    };
+    use crate::error::ConfigError::{
+        Bech32PrefixImmutable, InvalidBatch, InvalidPauseMessage, NoSuccessor, NoopUpdate,
+        NotifyTargetNotContract, RateBoundsImmutable, SuccessorNotContract,
+    };
+    use crate::error::ConvertError::{
+        AuthzDisabled, CallbackNotContract, InvalidFee, InvalidFunds, InvalidMemo,
+        InvalidRecipients, InvalidSourceDenom, NoRefund, RecipientsWithCallback, SenderNotAllowed,
+        SenderNotVerified, ShareIsZero, SlippageExceeded, ZeroAmount,
+    };
+    use crate::error::DepositError::{DenomMismatch, InsufficientDeposit, NoDeposit};
+    use crate::error::FloatError::InvalidFunds as InvalidFloatFunds;
+    use crate::error::FundsError::{
+        ExceedsUnrecorded, NoPosition, NothingToBurn, NothingToMint, NothingToReturn,
+    };
+    use crate::error::QuorumError::{
+        ActionNotFound, AdminOnlyApproval, AlreadyApproved, AlreadyConfigured, ApprovalRequired,
+        NotConfigured, NotEnoughApprovals, NotMember,
+    };
+    use crate::error::RateError::ApplyOverflowError;
+    use crate::error::SignatureError::{
+        Expired, InvalidNonce, InvalidPubkey, InvalidSignature, PubkeyMismatch,
+    };
+    use crate::error::StreamError::{
+        InvalidDuration, NotFound as StreamNotFound, NotOwner, NothingToClaim,
+    };
+    use crate::msg::{
+        AdminOp, Callback, ConversionSignDoc, ConvertResponse, IsVerifiedResponse, Position,
+        QuorumAction, Recipient, RevealPreimage, SignedConversion, SuccessorMsg, VerifierQueryMsg,
+    };
+    use crate::quorum::{Proposal, Quorum};
+    use crate::state::{
+        Commitment, ConversionStatus, Delegation, Deposit, Metadata, PauseInfo, PauseReason,
+        SenderClass, AUCTION, AUCTION_COMMITMENTS, AUCTION_ROUND, AUCTION_SETTLEMENTS, COMMITMENTS,
+        CONVERSIONS, CONVERSION_COUNT, DEPOSITS, ESCROWED, MIGRATED_POSITIONS, PROPOSALS,
+        PROPOSAL_COUNT, QUORUM, REFUNDS, STREAMS, STREAM_COUNT, SUCCESSOR, UNSOLICITED,
+        UNSOLICITED_TOTAL,
+    };
+    use crate::stream::Stream;
+    use cosmwasm_std::BankMsg;
+    use cw_utils::one_coin;
+
+    pub fn update_admin(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        admin: Option<String>,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        ADMIN
+            .assert_admin(deps.as_ref(), &info.sender)
+            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+
+        // Once a quorum is configured, admin transfers must go through a proposal
+        if QUORUM.exists(deps.storage) {
+            return Err(ContractError::QuorumError(ApprovalRequired));
+        }
+
+        let admin = admin.ok_or(ContractError::AdminError(CannotRenounce))?;
+        let new = validate_addr(deps.api, &load_config(deps.storage)?, &admin)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "update_admin")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attributes(set_admin(deps, &env, new)?))
+    }
+
+    // Set the admin and record it in the admin history, leaving the state untouched when it
+    // does not change. The attributes carry the full lineage for indexers, noops included
+    // A pending transfer proposed by the previous admin is dropped
+    pub(super) fn set_admin(
+        deps: DepsMut,
+        env: &Env,
+        new: Addr,
+    ) -> Result<Vec<Attribute>, ContractError> {
+        let old = ADMIN.get(deps.as_ref())?;
+        let changed = old.as_ref() != Some(&new);
+        if changed {
+            record_admin(deps.storage, &new, &env.block)?;
+            PENDING_ADMIN.clear(deps.storage);
+            ADMIN.set(deps, Some(new.clone()))?;
+        }
+        Ok(vec![
+            Attribute::new("old_admin", old.map(String::from).unwrap_or_default()),
+            Attribute::new("new_admin", new),
+            Attribute::new("changed", changed.to_string()),
+        ])
+    }
+
+    // First step of an admin transfer, the candidate must accept it
+    pub fn propose_admin(
+        deps: DepsMut,
+        info: MessageInfo,
+        admin: String,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        ADMIN
+            .assert_admin(deps.as_ref(), &info.sender)
+            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+
+        // Once a quorum is configured, admin transfers must go through a proposal
+        if QUORUM.exists(deps.storage) {
+            return Err(ContractError::QuorumError(ApprovalRequired));
+        }
+
+        let candidate = validate_addr(deps.api, &load_config(deps.storage)?, &admin)?;
+        PENDING_ADMIN.propose(deps, &ADMIN, &info.sender, &candidate)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "propose_admin")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("pending_admin", candidate))
+    }
+
+    pub fn accept_admin(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        // A quorum configured after the proposal takes precedence over it
+        if QUORUM.exists(deps.storage) {
+            return Err(ContractError::QuorumError(ApprovalRequired));
+        }
+
+        let new = PENDING_ADMIN.accept(deps.storage, &info.sender)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "accept_admin")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attributes(set_admin(deps, &env, new)?))
+    }
+
+    pub fn cancel_admin_proposal(
+        deps: DepsMut,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        let candidate = PENDING_ADMIN.cancel(deps, &ADMIN, &info.sender)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "cancel_admin_proposal")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("pending_admin", candidate))
+    }
+
+    pub fn grant_role(
+        deps: DepsMut,
+        info: MessageInfo,
+        role: String,
+        address: String,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        ADMIN
+            .assert_admin(deps.as_ref(), &info.sender)
+            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+
+        validate_role(&role, KNOWN_ROLES)?;
+        let address = validate_addr(deps.api, &load_config(deps.storage)?, &address)?;
+        ROLES.grant(deps.storage, &role, &address)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "grant_role")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("role", role)
+            .add_attribute("address", address))
+    }
+
+    pub fn revoke_role(
+        deps: DepsMut,
+        info: MessageInfo,
+        role: String,
+        address: String,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        ADMIN
+            .assert_admin(deps.as_ref(), &info.sender)
+            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+
+        validate_role(&role, KNOWN_ROLES)?;
+        let address = validate_addr(deps.api, &load_config(deps.storage)?, &address)?;
+        ROLES.assert_role(deps.storage, &role, &address)?;
+        ROLES.revoke(deps.storage, &role, &address);
+
+        Ok(Response::new()
+            .add_attribute("action", "revoke_role")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("role", role)
+            .add_attribute("address", address))
+    }
+
+    pub fn renounce_role(
+        deps: DepsMut,
+        info: MessageInfo,
+        role: String,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        ROLES.assert_role(deps.storage, &role, &info.sender)?;
+        ROLES.revoke(deps.storage, &role, &info.sender);
+
+        Ok(Response::new()
+            .add_attribute("action", "renounce_role")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("role", role)
+            .add_attribute("address", info.sender))
+    }
+
+    // Stop (or resume) using the authz grants received from the POA admin. Grants can only be
+    // revoked by their granter, this lets the admin cut their usage from the contract side
+    pub fn set_authz_enabled(
+        deps: DepsMut,
+        info: MessageInfo,
+        enabled: bool,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        ADMIN
+            .assert_admin(deps.as_ref(), &info.sender)
+            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+
+        AUTHZ_ENABLED.save(deps.storage, &enabled)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_authz_enabled")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("enabled", enabled.to_string()))
+    }
+
+    // Emit one event per expected authz grant, so operators can check the grants they set up
+    // against the transaction logs. Anyone may call it, nothing is changed
+    pub fn emit_expected_grants(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        let config = load_config(deps.storage)?;
+        let events = expected_grants(&config, &env.contract.address)
+            .into_iter()
+            .map(|grant| {
+                Event::new("expected_grant")
+                    .add_attribute("granter", grant.granter)
+                    .add_attribute("grantee", grant.grantee)
+                    .add_attribute("type_url", grant.type_url)
+            });
+
+        Ok(Response::new()
+            .add_attribute("action", "emit_expected_grants")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_events(events))
+    }
+
+    // Report the health checks in a `health` event, one attribute per check set to `ok` or
+    // its error. Anyone may call it, nothing is changed
+    pub fn health_check(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        let config = load_config(deps.storage)?;
+        let checks = health_checks(deps.as_ref(), &env, &config);
+        let healthy = checks.iter().all(|(_, result)| result.is_ok());
+        let event =
+            Event::new("health")
+                .add_attribute("healthy", healthy.to_string())
+                .add_attributes(checks.into_iter().map(|(name, result)| {
+                    (name, result.err().unwrap_or_else(|| "ok".to_string()))
+                }));
+
+        Ok(Response::new()
+            .add_attribute("action", "health_check")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("healthy", healthy.to_string())
+            .add_event(event))
+    }
+
+    // Pause conversions, recording why so users hitting the paused error know whether it is
+    // maintenance or an incident
+    pub fn pause(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        reason: PauseReason,
+        message: Option<String>,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        ROLES.assert_admin_or_role(deps.as_ref(), &ADMIN, PAUSER, &info.sender)?;
+
+        if message
+            .as_ref()
+            .is_some_and(|message| message.len() > MAX_PAUSE_MESSAGE_LEN)
+        {
+            return Err(ContractError::ConfigError(InvalidPauseMessage));
+        }
+
+        let mut config = load_config(deps.storage)?;
+        // The pause keeps its start time when only its reason is updated
+        let since = match PAUSE.may_load(deps.storage)? {
+            Some(pause) if config.paused => pause.since,
+            _ => env.block.time,
+        };
+        config.paused = Lifecycle::load(deps.storage, &config)?
+            .transition(Transition::Pause)?
+            .is_paused();
+        CONFIG.save(deps.storage, &config)?;
+        PAUSE.save(
+            deps.storage,
+            &PauseInfo {
+                reason: reason.clone(),
+                message: message.clone(),
+                since,
+            },
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "pause")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("reason", reason.to_string())
+            .add_attribute("message", message.unwrap_or_default()))
+    }
+
+    pub fn update_metadata(
+        deps: DepsMut,
+        info: MessageInfo,
+        metadata: Option<Metadata>,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        ADMIN
+            .assert_admin(deps.as_ref(), &info.sender)
+            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+
+        let res = Response::new()
+            .add_attribute("action", "update_metadata")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION);
+
+        let Some(metadata) = metadata else {
+            METADATA.remove(deps.storage);
+            return Ok(res.add_attribute("label", "none"));
+        };
+        metadata.validate()?;
+        METADATA.save(deps.storage, &metadata)?;
+
+        Ok(res.add_attribute("label", metadata.label))
+    }
+
+    // Apply the operations in order. The first failing one fails the whole batch, reverting
+    // the ones applied before it
+    pub fn batch(
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        ops: Vec<AdminOp>,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        if ops.is_empty() || ops.len() > MAX_BATCH_OPS {
+            return Err(ContractError::ConfigError(InvalidBatch));
+        }
+
+        let mut res = Response::new()
+            .add_attribute("action", "batch")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("ops", ops.len().to_string());
+        for (index, op) in ops.into_iter().enumerate() {
+            let name = op.name();
+            let (deps, env, info) = (deps.branch(), env.clone(), info.clone());
+            let op_res = match op {
+                AdminOp::UpdateConfig { config, strict } => {
+                    update_config(deps, env, info, *config, strict.unwrap_or_default())
+                }
+                AdminOp::Pause { reason, message } => pause(deps, env, info, reason, message),
+                AdminOp::SetAuthzEnabled { enabled } => set_authz_enabled(deps, info, enabled),
+                AdminOp::SetNotifyTarget { target } => set_notify_target(deps, info, target),
+                AdminOp::SetDelegation {
+                    delegate,
+                    delegation,
+                } => set_delegation(deps, info, delegate, delegation),
+                AdminOp::UpdateMetadata { metadata } => update_metadata(deps, info, metadata),
+            }?;
+            res = res
+                .add_submessages(op_res.messages)
+                .add_events(op_res.events)
+                .add_event(
+                    Event::new("batch_op")
+                        .add_attribute("index", index.to_string())
+                        .add_attribute("op", name)
+                        .add_attributes(op_res.attributes),
+                );
+        }
+        Ok(res)
+    }
+
+    pub fn set_delegation(
+        deps: DepsMut,
+        info: MessageInfo,
+        delegate: String,
+        delegation: Option<Delegation>,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        ADMIN
+            .assert_admin(deps.as_ref(), &info.sender)
+            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+
+        let config = load_config(deps.storage)?;
+        let delegate = validate_addr(deps.api, &config, &delegate)?;
+        let res = Response::new()
+            .add_attribute("action", "set_delegation")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("delegate", delegate.as_str());
+
+        let Some(delegation) = delegation else {
+            DELEGATIONS.remove(deps.storage, &delegate);
+            return Ok(res.add_attribute("fields", "none"));
+        };
+        delegation.validate()?;
+        DELEGATIONS.save(deps.storage, &delegate, &delegation)?;
+
+        Ok(res.add_attribute("fields", delegation.fields.join(",")))
+    }
+
+    // Set the contract alerted of critical events. It must be an existing contract, an alert
+    // to an account would fail every time
+    pub fn set_notify_target(
+        deps: DepsMut,
+        info: MessageInfo,
+        target: Option<String>,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        ADMIN
+            .assert_admin(deps.as_ref(), &info.sender)
+            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+
+        let res = Response::new()
+            .add_attribute("action", "set_notify_target")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION);
+
+        let Some(target) = target else {
+            NOTIFY_TARGET.remove(deps.storage);
+            return Ok(res.add_attribute("target", "none"));
+        };
+        let config = load_config(deps.storage)?;
+        let resolved = resolve(deps.as_ref(), &config, &target)?;
+        let target = validate_addr(deps.api, &config, &resolved.address)?;
+        deps.querier
+            .query_wasm_contract_info(&target)
+            .map_err(|_| ContractError::ConfigError(NotifyTargetNotContract))?;
+        NOTIFY_TARGET.save(deps.storage, &target)?;
+
+        Ok(res
+            .add_attribute("target", target)
+            .add_attributes(resolved.attribute()))
+    }
+
+    // Set the contract user positions can be moved to. It is only checked to be a contract,
+    // the admin vouches for it accepting `receive_position`
+    pub fn set_successor(
+        deps: DepsMut,
+        info: MessageInfo,
+        addr: Option<String>,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        ADMIN
+            .assert_admin(deps.as_ref(), &info.sender)
+            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+
+        let res = Response::new()
+            .add_attribute("action", "set_successor")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION);
+
+        let Some(addr) = addr else {
+            SUCCESSOR.remove(deps.storage);
+            return Ok(res.add_attribute("successor", "none"));
+        };
+        let config = load_config(deps.storage)?;
+        let successor = validate_addr(deps.api, &config, &addr)?;
+        deps.querier
+            .query_wasm_contract_info(&successor)
+            .map_err(|_| ContractError::ConfigError(SuccessorNotContract))?;
+        SUCCESSOR.save(deps.storage, &successor)?;
+
+        Ok(res.add_attribute("successor", successor))
+    }
+
+    // Move the positions of the sender to the successor in a single `receive_position`
+    // message carrying their funds. Streams are not indexed by owner, so the sender lists the
+    // ones to move. Auction commitments settle in their round and are not moved
+    pub fn migrate_my_position(
+        deps: DepsMut,
+        info: MessageInfo,
+        streams: Option<Vec<u64>>,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        let successor = SUCCESSOR
+            .may_load(deps.storage)?
+            .ok_or(ContractError::ConfigError(NoSuccessor))?;
+
+        let refunds = REFUNDS
+            .may_load(deps.storage, &info.sender)?
+            .unwrap_or_default();
+        REFUNDS.remove(deps.storage, &info.sender);
+        // Emptied deposits are kept so their nonce survives, see DEPOSITS
+        let deposit = match DEPOSITS.may_load(deps.storage, &info.sender)? {
+            Some(deposit) if !deposit.amount.amount.is_zero() => {
+                debit_deposit(deps.storage, &info.sender, &deposit.amount)?;
+                Some(deposit.amount)
+            }
+            _ => None,
+        };
+        let commitment = COMMITMENTS
+            .may_load(deps.storage, &info.sender)?
+            .map(|commitment| commitment.funds);
+        COMMITMENTS.remove(deps.storage, &info.sender);
+        let mut moved = vec![];
+        for id in streams.unwrap_or_default() {
+            let stream = load_stream(deps.as_ref(), &info.sender, id)?;
+            STREAMS.remove(deps.storage, id);
+            moved.push(stream);
+        }
+        let remaining = moved
+            .iter()
+            .map(|stream| Coin::new(stream.remaining(), stream.total.denom.as_str()));
+        // The deposit was debited from the escrow already
+        for coin in refunds
+            .iter()
+            .chain(&commitment)
+            .cloned()
+            .chain(remaining.clone())
+        {
+            ESCROWED.update(deps.storage, &coin.denom, |escrowed| -> StdResult<_> {
+                Ok(escrowed.unwrap_or_default().saturating_sub(coin.amount))
+            })?;
+        }
+        let funds = normalize_coins(
+            refunds
+                .iter()
+                .chain(&deposit)
+                .chain(&commitment)
+                .cloned()
+                .chain(remaining),
+        )
+        .map_err(|e| StdError::msg(e.to_string()))?;
+        if funds.is_empty() {
+            return Err(ContractError::FundsError(NoPosition));
+        }
+        let migrated = MIGRATED_POSITIONS
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        MIGRATED_POSITIONS.save(deps.storage, &(migrated + 1))?;
+
+        let position = Position {
+            refunds,
+            deposit,
+            commitment,
+            streams: moved,
+        };
+        let migrated = funds
+            .iter()
+            .map(Coin::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        Ok(Response::new()
+            .add_message(WasmMsg::Execute {
+                contract_addr: successor.to_string(),
+                msg: to_json_binary(&SuccessorMsg::ReceivePosition {
+                    owner: info.sender.to_string(),
+                    position,
+                })?,
+                funds,
+            })
+            .add_attribute("action", "migrate_my_position")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("sender", info.sender)
+            .add_attribute("successor", successor)
+            .add_attribute("migrated", migrated))
+    }
+
+    // Configure the N-of-M quorum guarding destructive actions. This can only be done once
+    // by the admin, subsequent changes require the quorum's approval
+    pub fn set_quorum(
+        deps: DepsMut,
+        info: MessageInfo,
+        members: Vec<String>,
+        threshold: u32,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        ADMIN
+            .assert_admin(deps.as_ref(), &info.sender)
+            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+
+        if QUORUM.exists(deps.storage) {
+            return Err(ContractError::QuorumError(AlreadyConfigured));
+        }
+
+        let config = load_config(deps.storage)?;
+        let quorum = new_quorum(deps.api, &config, &members, threshold)?;
+        QUORUM.save(deps.storage, &quorum)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_quorum")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("members", quorum.members.len().to_string())
+            .add_attribute("threshold", quorum.threshold.to_string()))
+    }
+
+    // Propose a destructive action, the proposer's approval is recorded right away
+    pub fn propose_action(
+        deps: DepsMut,
+        info: MessageInfo,
+        action: QuorumAction,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        load_quorum_member(deps.as_ref(), &info.sender)?;
+
+        // Reject actions that could never be executed
+        validate_action(deps.api, &load_config(deps.storage)?, &action)?;
+
+        let id = PROPOSAL_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+        PROPOSAL_COUNT.save(deps.storage, &id)?;
+        PROPOSALS.save(
+            deps.storage,
+            id,
+            &Proposal {
+                id,
+                action,
+                proposer: info.sender.clone(),
+                approvals: vec![info.sender.clone()],
+            },
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "propose_action")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("id", id.to_string())
+            .add_attribute("proposer", info.sender))
+    }
+
+    pub fn approve_action(
+        deps: DepsMut,
+        info: MessageInfo,
+        id: u64,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        load_quorum_member(deps.as_ref(), &info.sender)?;
+
+        let mut proposal = PROPOSALS
+            .may_load(deps.storage, id)?
+            .ok_or(ContractError::QuorumError(ActionNotFound))?;
+        if proposal.approvals.contains(&info.sender) {
+            return Err(ContractError::QuorumError(AlreadyApproved));
+        }
+        proposal.approvals.push(info.sender.clone());
+        PROPOSALS.save(deps.storage, id, &proposal)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "approve_action")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("id", id.to_string())
+            .add_attribute("approver", info.sender)
+            .add_attribute("approvals", proposal.approvals.len().to_string()))
+    }
+
+    // Execute a proposal that gathered enough approvals from the current members
+    pub fn execute_action(
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        id: u64,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        let quorum = load_quorum_member(deps.as_ref(), &info.sender)?;
+
+        let proposal = PROPOSALS
+            .may_load(deps.storage, id)?
+            .ok_or(ContractError::QuorumError(ActionNotFound))?;
+        if !quorum.is_met(&proposal.approvals) {
+            return Err(ContractError::QuorumError(NotEnoughApprovals));
+        }
+        // The admin picks the members of the quorum, a quorum of the admin alone must not move
+        // the bounds routine updates are checked against
+        if matches!(proposal.action, QuorumAction::UpdateRateBounds { .. }) {
+            let admin = ADMIN.get(deps.as_ref())?;
+            let others = proposal
+                .approvals
+                .iter()
+                .filter(|approver| quorum.is_member(approver) && Some(*approver) != admin.as_ref());
+            if others.count() == 0 {
+                return Err(ContractError::QuorumError(AdminOnlyApproval));
+            }
+        }
+        PROPOSALS.remove(deps.storage, id);
+
+        let res = Response::new()
+            .add_attribute("action", "execute_action")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("id", id.to_string());
+
+        let config = load_config(deps.storage)?;
+        match proposal.action {
+            QuorumAction::UpdateAdmin { admin } => {
+                let new = validate_addr(deps.api, &config, &admin)?;
+                Ok(res
+                    .add_attribute("executed", "update_admin")
+                    .add_attributes(set_admin(deps.branch(), &env, new)?))
+            }
+            QuorumAction::UpdateQuorum { members, threshold } => {
+                let quorum = new_quorum(deps.api, &config, &members, threshold)?;
+                QUORUM.save(deps.storage, &quorum)?;
+                Ok(res
+                    .add_attribute("executed", "update_quorum")
+                    .add_attribute("members", quorum.members.len().to_string())
+                    .add_attribute("threshold", quorum.threshold.to_string()))
+            }
+            QuorumAction::UpdateRateBounds {
+                rate_floor,
+                rate_ceiling,
+            } => {
+                let config = with_rate_bounds(config, rate_floor, rate_ceiling)?;
+                CONFIG.save(deps.storage, &config)?;
+                let bound = |rate: &Option<Rate>| rate.as_ref().map(Rate::to_string);
+                Ok(res
+                    .add_attribute("executed", "update_rate_bounds")
+                    .add_attribute("rate_floor", bound(&config.rate_floor).unwrap_or_default())
+                    .add_attribute(
+                        "rate_ceiling",
+                        bound(&config.rate_ceiling).unwrap_or_default(),
+                    ))
+            }
+        }
+    }
+
+    // Replace the rate bounds of the config, the current rate must be within the new ones
+    fn with_rate_bounds(
+        config: Config,
+        rate_floor: Option<String>,
+        rate_ceiling: Option<String>,
+    ) -> Result<Config, ContractError> {
+        let config = Config {
+            rate_floor: rate_floor.as_deref().map(Rate::parse).transpose()?,
+            rate_ceiling: rate_ceiling.as_deref().map(Rate::parse).transpose()?,
+            ..config
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn new_quorum(
+        api: &dyn Api,
+        config: &Config,
+        members: &[String],
+        threshold: u32,
+    ) -> Result<Quorum, ContractError> {
+        let members = members
+            .iter()
+            .map(|m| validate_addr(api, config, m))
+            .collect::<Result<Vec<_>, _>>()?;
+        Quorum::new(members, threshold)
+    }
+
+    fn validate_action(
+        api: &dyn Api,
+        config: &Config,
+        action: &QuorumAction,
+    ) -> Result<(), ContractError> {
+        match action {
+            QuorumAction::UpdateAdmin { admin } => {
+                validate_addr(api, config, admin)?;
+            }
+            QuorumAction::UpdateQuorum { members, threshold } => {
+                new_quorum(api, config, members, *threshold)?;
+            }
+            QuorumAction::UpdateRateBounds {
+                rate_floor,
+                rate_ceiling,
+            } => {
+                with_rate_bounds(config.clone(), rate_floor.clone(), rate_ceiling.clone())?;
+            }
+        }
+        Ok(())
+    }
+
+    // Load the quorum and ensure the sender is one of its members
+    fn load_quorum_member(deps: Deps, sender: &Addr) -> Result<Quorum, ContractError> {
+        let quorum = QUORUM
+            .may_load(deps.storage)?
+            .ok_or(ContractError::QuorumError(NotConfigured))?;
+        if !quorum.is_member(sender) {
+            return Err(ContractError::QuorumError(NotMember));
+        }
+        Ok(quorum)
+    }
+
+    // Attribute part of the contract's unrecorded balance to the account that sent it outside
+    // of a conversion, so it can be returned later
+    pub fn record_unsolicited(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        depositor: String,
+        amount: Coin,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        ADMIN
+            .assert_admin(deps.as_ref(), &info.sender)
+            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+
+        let depositor = deps.api.addr_validate(&depositor)?;
+        if amount.amount.is_zero() {
+            return Err(ContractError::AmountError(AmountIsZero));
+        }
+
+        // Only funds that are not already attributed to a depositor can be recorded
+        let balance = deps
+            .querier
+            .query_balance(&env.contract.address, &amount.denom)?
+            .amount;
+        let recorded = UNSOLICITED_TOTAL
+            .may_load(deps.storage, &amount.denom)?
+            .unwrap_or_default();
+        let escrowed = ESCROWED
+            .may_load(deps.storage, &amount.denom)?
+            .unwrap_or_default();
+        if amount.amount > balance.saturating_sub(recorded).saturating_sub(escrowed) {
+            return Err(ContractError::FundsError(ExceedsUnrecorded));
+        }
+
+        UNSOLICITED.update(
+            deps.storage,
+            (&depositor, &amount.denom),
+            |current| -> StdResult<_> { Ok(current.unwrap_or_default() + amount.amount) },
+        )?;
+        UNSOLICITED_TOTAL.save(deps.storage, &amount.denom, &(recorded + amount.amount))?;
+
+        Ok(Response::new()
+            .add_attribute("action", "record_unsolicited")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("depositor", depositor)
+            .add_attribute("amount", amount.to_string()))
+    }
+
+    // Send the recorded unsolicited funds of a given denom back to their depositor
+    pub fn return_unsolicited(
+        deps: DepsMut,
+        info: MessageInfo,
+        depositor: String,
+        denom: String,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        ADMIN
+            .assert_admin(deps.as_ref(), &info.sender)
+            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+
+        let depositor = deps.api.addr_validate(&depositor)?;
+        let amount = UNSOLICITED
+            .may_load(deps.storage, (&depositor, &denom))?
+            .ok_or(ContractError::FundsError(NothingToReturn))?;
+        UNSOLICITED.remove(deps.storage, (&depositor, &denom));
+        UNSOLICITED_TOTAL.update(deps.storage, &denom, |total| -> StdResult<_> {
+            Ok(total.unwrap_or_default().saturating_sub(amount))
+        })?;
+
+        let returned = Coin::new(amount, denom);
+        Ok(Response::new()
+            .add_message(BankMsg::Send {
+                to_address: depositor.to_string(),
+                amount: vec![returned.clone()],
+            })
+            .add_attribute("action", "return_unsolicited")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("depositor", depositor)
+            .add_attribute("amount", returned.to_string()))
+    }
+
+    // Burn the target tokens accumulated at the contract address, e.g. from rounding,
+    // callbacks or misdirected mints, through the send and burn of a conversion. Escrowed
+    // tokens and recorded unsolicited funds are left alone
+    pub fn burn_residual(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        ADMIN
+            .assert_admin(deps.as_ref(), &info.sender)
+            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+        if !AUTHZ_ENABLED.may_load(deps.storage)?.unwrap_or(true) {
+            return Err(ContractError::ConvertError(AuthzDisabled));
+        }
+
+        let config = load_config(deps.storage)?;
+        let denom = config.target_denom.as_str();
+        let contract = &env.contract.address;
+        let balance = deps.querier.query_balance(contract, denom)?.amount;
+        let escrowed = ESCROWED.may_load(deps.storage, denom)?.unwrap_or_default();
+        let recorded = UNSOLICITED_TOTAL
+            .may_load(deps.storage, denom)?
+            .unwrap_or_default();
+        let residual = balance.saturating_sub(escrowed).saturating_sub(recorded);
+        if residual.is_zero() {
+            return Err(ContractError::FundsError(NothingToBurn));
+        }
+
+        let burned = Coin::new(residual, denom);
+        let poa_admin = config.poa_admin.as_str();
+        let msg = build_exec(
+            contract.as_str(),
+            vec![
+                build_send(contract.as_str(), poa_admin, &burned),
+                build_burn(poa_admin, &burned),
+            ],
+        );
+
+        Ok(Response::new()
+            .add_message(msg)
+            .add_attribute("action", "burn_residual")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("poa_admin", poa_admin)
+            .add_attribute("balance", balance.to_string())
+            .add_attribute("escrowed", escrowed.to_string())
+            .add_attribute("recorded", recorded.to_string())
+            .add_attribute("burned", burned.to_string()))
+    }
+
+    // Mint the value lost to flooring mint amounts once it adds up to whole base units, so it
+    // is not silently destroyed. The fraction left stays in the reserve
+    pub fn mint_rounding_reserve(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: Option<String>,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        ADMIN
+            .assert_admin(deps.as_ref(), &info.sender)
+            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+        if !AUTHZ_ENABLED.may_load(deps.storage)?.unwrap_or(true) {
+            return Err(ContractError::ConvertError(AuthzDisabled));
+        }
+
+        let config = load_config(deps.storage)?;
+        let resolved = recipient
+            .map(|recipient| resolve(deps.as_ref(), &config, &recipient))
+            .transpose()?;
+        let recipient = match &resolved {
+            Some(resolved) => validate_addr(deps.api, &config, &resolved.address)?,
+            None => config.poa_admin.clone(),
+        };
+        let denom = config.target_denom.as_str();
+        let reserve = ROUNDING_RESERVE
+            .may_load(deps.storage, denom)?
+            .unwrap_or_default();
+        let amount = reserve.to_uint_floor();
+        if amount.is_zero() {
+            return Err(ContractError::FundsError(NothingToMint));
+        }
+        let remaining = reserve - reserve.floor();
+        ROUNDING_RESERVE.save(deps.storage, denom, &remaining)?;
+
+        let minted = Coin::new(amount, denom);
+        let poa_admin = config.poa_admin.as_str();
+        let msg = build_exec(
+            env.contract.address.as_str(),
+            vec![build_mint(poa_admin, recipient.as_str(), &minted)],
+        );
+
+        Ok(Response::new()
+            .add_message(msg)
+            .add_attribute("action", "mint_rounding_reserve")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("recipient", recipient)
+            .add_attribute("minted", minted.to_string())
+            .add_attribute("remaining", remaining.to_string())
+            .add_attributes(resolved.and_then(|resolved| resolved.attribute())))
+    }
+
+    // Send the source tokens of the sender's failed conversions back to them
+    pub fn claim_refund(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+
+        let refunds = REFUNDS
+            .may_load(deps.storage, &info.sender)?
+            .ok_or(ContractError::ConvertError(NoRefund))?;
+        REFUNDS.remove(deps.storage, &info.sender);
+        for refund in &refunds {
+            ESCROWED.update(deps.storage, &refund.denom, |total| -> StdResult<_> {
+                Ok(total.unwrap_or_default().saturating_sub(refund.amount))
+            })?;
+        }
+
+        let refunded = refunds
+            .iter()
+            .map(Coin::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        Ok(Response::new()
+            .add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: refunds,
+            })
+            .add_attribute("action", "claim_refund")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("sender", info.sender)
+            .add_attribute("refunded", refunded))
+    }
+
+    // Credit source tokens to the sender's deposit, for their later conversions or the ones
+    // they sign off-chain with the given key
+    pub fn deposit(
+        deps: DepsMut,
+        info: MessageInfo,
+        pubkey: Option<Binary>,
+    ) -> Result<Response, ContractError> {
+        let config = load_config(deps.storage)?;
+        let coin = one_coin(&info).map_err(|_| ContractError::ConvertError(InvalidFunds))?;
+        if !config.source_denom.matches(&coin.denom) {
+            return Err(ContractError::ConvertError(InvalidSourceDenom));
+        }
+        if let Some(pubkey) = &pubkey {
+            let valid_key = match pubkey.as_slice() {
+                [0x02 | 0x03, rest @ ..] => rest.len() == 32,
+                [0x04, rest @ ..] => rest.len() == 64,
+                _ => false,
+            };
+            if !valid_key {
+                return Err(ContractError::SignatureError(InvalidPubkey));
+            }
+        }
+
+        // A deposit left in a previous source denom must be withdrawn first
+        let mut deposit = DEPOSITS
+            .may_load(deps.storage, &info.sender)?
+            .unwrap_or(Deposit {
+                amount: Coin::new(0u128, coin.denom.as_str()),
+                pubkey: None,
+                nonce: 0,
+            });
+        if deposit.amount.amount.is_zero() {
+            deposit.amount.denom = coin.denom.clone();
+        }
+        if deposit.amount.denom != coin.denom {
+            return Err(ContractError::DepositError(DenomMismatch));
+        }
+        deposit.amount.amount = deposit
+            .amount
+            .amount
+            .checked_add(coin.amount)
+            .map_err(StdError::from)?;
+        if pubkey.is_some() {
+            deposit.pubkey = pubkey;
+        }
+        DEPOSITS.save(deps.storage, &info.sender, &deposit)?;
+        ESCROWED.update(deps.storage, &coin.denom, |total| {
+            total
+                .unwrap_or_default()
+                .checked_add(coin.amount)
+                .map_err(StdError::from)
+        })?;
+
+        Ok(Response::new()
+            .add_attribute("action", "deposit")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("sender", info.sender)
+            .add_attribute("deposited", coin.to_string())
+            .add_attribute("balance", deposit.amount.to_string()))
+    }
+
+    // Send part of the sender's deposit back to them
+    pub fn withdraw(
+        deps: DepsMut,
+        info: MessageInfo,
+        amount: Uint256,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+
+        let denom = DEPOSITS
+            .may_load(deps.storage, &info.sender)?
+            .ok_or(ContractError::DepositError(NoDeposit))?
+            .amount
+            .denom;
+        let withdrawn = Coin::new(amount, denom);
+        let deposit = debit_deposit(deps.storage, &info.sender, &withdrawn)?;
+
+        Ok(Response::new()
+            .add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: vec![withdrawn.clone()],
+            })
+            .add_attribute("action", "withdraw")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("sender", info.sender)
+            .add_attribute("withdrawn", withdrawn.to_string())
+            .add_attribute("balance", deposit.amount.to_string()))
+    }
+
+    pub fn fund_float(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+        ADMIN
+            .assert_admin(deps.as_ref(), &info.sender)
+            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+        let config = load_config(deps.storage)?;
+        let coin = one_coin(&info).map_err(|_| ContractError::FloatError(InvalidFloatFunds))?;
+        if !config.target_denom.matches(&coin.denom) {
+            return Err(ContractError::FloatError(InvalidFloatFunds));
+        }
+        let float = crate::float::fund_float(deps.storage, &coin)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "fund_float")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("funded", coin.to_string())
+            .add_attribute("balance", float.balance.to_string())
+            .add_attribute("to_replenish", float.to_replenish.to_string()))
+    }
+
+    pub fn withdraw_float(
+        deps: DepsMut,
+        info: MessageInfo,
+        amount: Coin,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        ADMIN
+            .assert_admin(deps.as_ref(), &info.sender)
+            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+        if amount.amount.is_zero() {
+            return Err(ContractError::AmountError(AmountIsZero));
+        }
+        let float = crate::float::withdraw_float(deps.storage, &amount)?;
+
+        Ok(Response::new()
+            .add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: vec![amount.clone()],
+            })
+            .add_attribute("action", "withdraw_float")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("withdrawn", amount.to_string())
+            .add_attribute("balance", float.balance.to_string()))
+    }
+
+    // Escrow source tokens to convert them pro-rata over a duration, each claim converting the
+    // tokens vested since the previous one. The flat fee is collected once, when starting
+    pub fn start_streaming_convert(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        total: Uint256,
+        duration: u64,
+    ) -> Result<Response, ContractError> {
+        let config = load_config(deps.storage)?;
+        ensure_convertible(deps.storage)?;
+
+        if info.funds.iter().any(|c| c.amount.is_zero()) {
+            return Err(ContractError::ConvertError(ZeroAmount));
+        }
+        let (coin, fee) = split_funds(&info, &config)?;
+        if !config.source_denom.matches(&coin.denom) {
+            return Err(ContractError::ConvertError(InvalidSourceDenom));
+        }
+        if coin.amount != total {
+            return Err(ContractError::ConvertError(InvalidFunds));
+        }
+        if duration == 0 {
+            return Err(ContractError::StreamError(InvalidDuration));
+        }
+
+        let id = STREAM_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+        STREAM_COUNT.save(deps.storage, &id)?;
+        STREAMS.save(
+            deps.storage,
+            id,
+            &Stream {
+                id,
+                owner: info.sender.clone(),
+                total: coin.clone(),
+                claimed: Uint256::zero(),
+                start: env.block.time,
+                duration,
+            },
+        )?;
+        ESCROWED.update(deps.storage, &coin.denom, |escrowed| {
+            escrowed
+                .unwrap_or_default()
+                .checked_add(coin.amount)
+                .map_err(StdError::from)
+        })?;
+
+        let res = Response::new()
+            .add_attribute("action", "start_stream")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("sender", info.sender)
+            .add_attribute("stream_id", id.to_string())
+            .add_attribute("total", coin.to_string())
+            .add_attribute("duration", duration.to_string());
+        Ok(match fee {
+            Some(fee) => res
+                .add_message(fee_msg(&env.contract.address, &config, &fee))
+                .add_attribute("fee", fee.to_string()),
+            None => res,
+        })
+    }
+
+    // Convert the tokens of a stream vested since its last claim, at the current rate
+    pub fn claim_stream(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        id: u64,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        let config = load_config(deps.storage)?;
+        ensure_convertible(deps.storage)?;
+
+        let mut stream = load_stream(deps.as_ref(), &info.sender, id)?;
+        // A claim converts at most the maximum conversion amount, the rest is left vested for
+        // the next claims rather than failing the stream
+        let claimable = stream.claimable(env.block.time);
+        let claimable = config
+            .max_convert_amount
+            .map_or(claimable, |max| claimable.min(max));
+        if claimable.is_zero() {
+            return Err(ContractError::StreamError(NothingToClaim));
+        }
+        stream.claimed += claimable;
+        if stream.remaining().is_zero() {
+            STREAMS.remove(deps.storage, id);
+        } else {
+            STREAMS.save(deps.storage, id, &stream)?;
+        }
+        let coin = Coin::new(claimable, stream.total.denom.as_str());
+        ESCROWED.update(deps.storage, &coin.denom, |escrowed| -> StdResult<_> {
+            Ok(escrowed.unwrap_or_default().saturating_sub(coin.amount))
+        })?;
+
+        let res = convert_coin(
+            deps,
+            &env,
+            config,
+            stream.owner,
+            (coin, None),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )?;
+        Ok(res
+            .add_attribute("stream_id", id.to_string())
+            .add_attribute("stream_remaining", stream.total.amount - stream.claimed))
+    }
+
+    // Stop a stream and send the tokens not converted yet back to its owner
+    pub fn cancel_stream(
+        deps: DepsMut,
+        info: MessageInfo,
+        id: u64,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+
+        let stream = load_stream(deps.as_ref(), &info.sender, id)?;
+        STREAMS.remove(deps.storage, id);
+        let refund = Coin::new(stream.remaining(), stream.total.denom.as_str());
+        ESCROWED.update(deps.storage, &refund.denom, |escrowed| -> StdResult<_> {
+            Ok(escrowed.unwrap_or_default().saturating_sub(refund.amount))
+        })?;
+
+        Ok(Response::new()
+            .add_message(BankMsg::Send {
+                to_address: stream.owner.to_string(),
+                amount: vec![refund.clone()],
+            })
+            .add_attribute("action", "cancel_stream")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("sender", info.sender)
+            .add_attribute("stream_id", id.to_string())
+            .add_attribute("refunded", refund.to_string()))
+    }
+
+    fn load_stream(deps: Deps, sender: &Addr, id: u64) -> Result<Stream, ContractError> {
+        let stream = STREAMS
+            .may_load(deps.storage, id)?
+            .ok_or(ContractError::StreamError(StreamNotFound))?;
+        if stream.owner != *sender {
+            return Err(ContractError::StreamError(NotOwner));
+        }
+        Ok(stream)
+    }
+
+    // First phase of a commit-reveal conversion. Only the hash of the conversion is public
+    // until it is revealed, the committed funds may exceed the amount to convert
+    pub fn commit_convert(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        hash: Binary,
+    ) -> Result<Response, ContractError> {
+        let config = load_config(deps.storage)?;
+        ensure_convertible(deps.storage)?;
+        ensure_not_batched(deps.storage)?;
+
+        if hash.len() != 32 {
+            return Err(ContractError::CommitError(InvalidHash));
+        }
+        if COMMITMENTS.has(deps.storage, &info.sender) {
+            return Err(ContractError::CommitError(AlreadyCommitted));
+        }
+        if info.funds.iter().any(|c| c.amount.is_zero()) {
+            return Err(ContractError::ConvertError(ZeroAmount));
+        }
+        let (coin, fee) = split_funds(&info, &config)?;
+        if !config.source_denom.matches(&coin.denom) {
+            return Err(ContractError::ConvertError(InvalidSourceDenom));
+        }
+
+        let expires = env.block.time.plus_seconds(COMMITMENT_TTL_SECONDS);
+        COMMITMENTS.save(
+            deps.storage,
+            &info.sender,
+            &Commitment {
+                hash,
+                funds: coin.clone(),
+                height: env.block.height,
+                expires,
+            },
+        )?;
+        ESCROWED.update(deps.storage, &coin.denom, |escrowed| {
+            escrowed
+                .unwrap_or_default()
+                .checked_add(coin.amount)
+                .map_err(StdError::from)
+        })?;
+
+        let res = Response::new()
+            .add_attribute("action", "commit_convert")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("sender", info.sender)
+            .add_attribute("committed", coin.to_string())
+            .add_attribute("expires", expires.seconds().to_string());
+        Ok(match fee {
+            Some(fee) => res
+                .add_message(fee_msg(&env.contract.address, &config, &fee))
+                .add_attribute("fee", fee.to_string()),
+            None => res,
+        })
+    }
+
+    // Second phase of a commit-reveal conversion, converting the revealed amount at the
+    // current rate and returning the rest of the committed funds
+    pub fn reveal_convert(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        amount: Uint256,
+        salt: Binary,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        let config = load_config(deps.storage)?;
+        ensure_convertible(deps.storage)?;
+        ensure_not_batched(deps.storage)?;
+
+        let commitment = COMMITMENTS
+            .may_load(deps.storage, &info.sender)?
+            .ok_or(ContractError::CommitError(CommitmentNotFound))?;
+        if env.block.height <= commitment.height {
+            return Err(ContractError::CommitError(SameBlock));
+        }
+        if env.block.time >= commitment.expires {
+            return Err(ContractError::CommitError(CommitmentExpired));
+        }
+        let preimage = RevealPreimage {
+            sender: info.sender.to_string(),
+            amount,
+            salt,
+        };
+        if Sha256::digest(to_json_vec(&preimage)?).as_slice() != commitment.hash.as_slice() {
+            return Err(ContractError::CommitError(HashMismatch));
+        }
+        if amount.is_zero() {
+            return Err(ContractError::AmountError(AmountIsZero));
+        }
+        if amount > commitment.funds.amount {
+            return Err(ContractError::CommitError(ExceedsEscrow));
+        }
+
+        COMMITMENTS.remove(deps.storage, &info.sender);
+        let funds = commitment.funds;
+        ESCROWED.update(deps.storage, &funds.denom, |escrowed| -> StdResult<_> {
+            Ok(escrowed.unwrap_or_default().saturating_sub(funds.amount))
+        })?;
+
+        let coin = Coin::new(amount, funds.denom.as_str());
+        let excess = Coin::new(funds.amount - amount, funds.denom.as_str());
+        let res = convert_coin(
+            deps,
+            &env,
+            config,
+            info.sender.clone(),
+            (coin, None),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )?;
+        if excess.amount.is_zero() {
+            return Ok(res);
+        }
+        Ok(res
+            .add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: vec![excess.clone()],
+            })
+            .add_attribute("returned", excess.to_string()))
+    }
+
+    // Send the funds of the sender's pending commitment back to them, expired or not
+    pub fn cancel_commitment(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+
+        let commitment = COMMITMENTS
+            .may_load(deps.storage, &info.sender)?
+            .ok_or(ContractError::CommitError(CommitmentNotFound))?;
+        COMMITMENTS.remove(deps.storage, &info.sender);
+        let funds = commitment.funds;
+        ESCROWED.update(deps.storage, &funds.denom, |escrowed| -> StdResult<_> {
+            Ok(escrowed.unwrap_or_default().saturating_sub(funds.amount))
+        })?;
+
+        Ok(Response::new()
+            .add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: vec![funds.clone()],
+            })
+            .add_attribute("action", "cancel_commitment")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("sender", info.sender)
+            .add_attribute("refunded", funds.to_string()))
+    }
 
-    config.validate()?;
+    // Enable, reconfigure or disable batch mode. Enabling it opens a round right away, and it
+    // can only be disabled while the current round has no commitments
+    pub fn set_auction(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        auction: Option<AuctionConfig>,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        if !ADMIN.is_admin(deps.as_ref(), &info.sender)? {
+            return Err(ContractError::AdminError(NotAdmin));
+        }
 
-    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+        let round = AUCTION_ROUND.may_load(deps.storage)?;
+        let res = Response::new()
+            .add_attribute("action", "set_auction")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("sender", info.sender);
+        let Some(auction) = auction else {
+            if round.is_some_and(|r| !r.committed.is_zero()) {
+                return Err(ContractError::AuctionError(PendingCommitments));
+            }
+            AUCTION.remove(deps.storage);
+            return Ok(res.add_attribute("enabled", "false"));
+        };
+        auction.validate()?;
 
-    CONFIG.save(deps.storage, &config)?;
-    ADMIN.set(deps, Some(admin))?;
+        // A round left from a previous activation restarts, its id is kept
+        if !AUCTION.exists(deps.storage) {
+            let config = load_config(deps.storage)?;
+            let round = AuctionRound {
+                id: round.map_or(1, |r| r.id),
+                start: env.block.time,
+                denom: config.source_denom.to_string(),
+                committed: Uint256::zero(),
+            };
+            AUCTION_ROUND.save(deps.storage, &round)?;
+        }
+        AUCTION.save(deps.storage, &auction)?;
 
-    Ok(Response::new().add_attribute("action", "instantiate"))
-}
+        Ok(res
+            .add_attribute("enabled", "true")
+            .add_attribute("round_length", auction.round_length.to_string())
+            .add_attribute("target_demand", auction.target_demand)
+            .add_attribute("min_fraction", auction.min_fraction.to_string()))
+    }
 
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    use QueryMsg::*;
+    // Commit source tokens to the current round, to be converted at its clearing rate. The
+    // flat fee is collected with the commitment
+    pub fn auction_commit(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let config = load_config(deps.storage)?;
+        ensure_convertible(deps.storage)?;
+        let auction = AUCTION
+            .may_load(deps.storage)?
+            .ok_or(ContractError::AuctionError(AuctionNotConfigured))?;
 
-    match msg {
-        Config {} => query::config(deps),
-        Admin {} => query::admin(deps),
-    }
-}
+        if info.funds.iter().any(|c| c.amount.is_zero()) {
+            return Err(ContractError::ConvertError(ZeroAmount));
+        }
+        let (coin, fee) = split_funds(&info, &config)?;
+        let mut round = AUCTION_ROUND.load(deps.storage)?;
+        if !config.source_denom.matches(&coin.denom) || coin.denom != round.denom {
+            return Err(ContractError::ConvertError(InvalidSourceDenom));
+        }
+        if round.is_over(env.block.time, auction.round_length) {
+            return Err(ContractError::AuctionError(RoundEnded));
+        }
 
-pub fn execute(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    msg: ExecuteMsg,
-) -> Result<Response, ContractError> {
-    use ExecuteMsg::*;
-    match msg {
-        UpdateAdmin { admin } => exec::update_admin(deps, info, admin),
-        UpdateConfig { config } => exec::update_config(deps, info, config),
-        Convert {} => exec::convert(deps.as_ref(), env, info),
+        round.committed = round
+            .committed
+            .checked_add(coin.amount)
+            .map_err(StdError::from)?;
+        AUCTION_ROUND.save(deps.storage, &round)?;
+        let committed =
+            AUCTION_COMMITMENTS.update(deps.storage, (round.id, &info.sender), |committed| {
+                committed
+                    .unwrap_or_default()
+                    .checked_add(coin.amount)
+                    .map_err(StdError::from)
+            })?;
+        // The whole commitment is converted by the claim, hold it to the amount limits now
+        // rather than only once the round settled
+        check_convert_amount(&config, committed)?;
+        ESCROWED.update(deps.storage, &coin.denom, |escrowed| {
+            escrowed
+                .unwrap_or_default()
+                .checked_add(coin.amount)
+                .map_err(StdError::from)
+        })?;
+
+        let res = Response::new()
+            .add_attribute("action", "auction_commit")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("sender", info.sender)
+            .add_attribute("round", round.id.to_string())
+            .add_attribute("committed", coin.to_string())
+            .add_attribute("sender_committed", committed)
+            .add_attribute("round_committed", round.committed);
+        Ok(match fee {
+            Some(fee) => res
+                .add_message(fee_msg(&env.contract.address, &config, &fee))
+                .add_attribute("fee", fee.to_string()),
+            None => res,
+        })
     }
-}
 
-pub fn migrate(
-    deps: DepsMut,
-    _env: Env,
-    _msg: MigrateMsg,
-    _info: MigrateInfo,
-) -> Result<Response, ContractError> {
-    let stored = get_contract_version(deps.storage)?;
+    // Fix the clearing rate of the current round from its total demand, then open the next
+    // round. The configured rate at settlement is the base of the clearing rate
+    pub fn settle_auction(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        let config = load_config(deps.storage)?;
+        let auction = AUCTION
+            .may_load(deps.storage)?
+            .ok_or(ContractError::AuctionError(AuctionNotConfigured))?;
+        let round = AUCTION_ROUND.load(deps.storage)?;
+        if !round.is_over(env.block.time, auction.round_length) {
+            return Err(ContractError::AuctionError(RoundOpen));
+        }
 
-    if stored.contract != CONTRACT_NAME {
-        return Err(ContractError::MigrateError(InvalidContractName));
-    }
+        let fraction = auction.clearing_fraction(round.committed);
+        let rate = Rate::new(
+            config
+                .rate
+                .as_ref()
+                .checked_mul(fraction)
+                .map_err(|_| ContractError::RateError(ApplyOverflowError))?,
+        )?;
+        AUCTION_SETTLEMENTS.save(
+            deps.storage,
+            round.id,
+            &Settlement {
+                round: round.id,
+                denom: round.denom.clone(),
+                committed: round.committed,
+                fraction,
+                rate: rate.clone(),
+                time: env.block.time,
+            },
+        )?;
+        AUCTION_ROUND.save(
+            deps.storage,
+            &AuctionRound {
+                id: round.id + 1,
+                start: env.block.time,
+                denom: config.source_denom.to_string(),
+                committed: Uint256::zero(),
+            },
+        )?;
 
-    if stored.version == CONTRACT_VERSION {
-        return Ok(Response::new()
-            .add_attribute("action", "migrate")
-            .add_attribute("note", "already at latest version")
-            .add_attribute("version", CONTRACT_VERSION));
+        Ok(Response::new()
+            .add_attribute("action", "settle_auction")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("sender", info.sender)
+            .add_attribute("round", round.id.to_string())
+            .add_attribute("round_committed", round.committed)
+            .add_attribute("clearing_fraction", fraction.to_string())
+            .add_attribute("clearing_rate", rate.to_string()))
     }
 
-    // TODO: Add migration steps when needed
+    // Convert the sender's commitment to a settled round at its clearing rate
+    pub fn claim_auction(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        round: u64,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        let config = load_config(deps.storage)?;
+        ensure_convertible(deps.storage)?;
 
-    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+        let settlement = AUCTION_SETTLEMENTS
+            .may_load(deps.storage, round)?
+            .ok_or(ContractError::AuctionError(NotSettled))?;
+        let committed = AUCTION_COMMITMENTS
+            .may_load(deps.storage, (round, &info.sender))?
+            .ok_or(ContractError::AuctionError(NoCommitment))?;
+        AUCTION_COMMITMENTS.remove(deps.storage, (round, &info.sender));
+        ESCROWED.update(
+            deps.storage,
+            &settlement.denom,
+            |escrowed| -> StdResult<_> {
+                Ok(escrowed.unwrap_or_default().saturating_sub(committed))
+            },
+        )?;
 
-    Ok(Response::new()
-        .add_attribute("action", "migrate")
-        .add_attribute("contract", CONTRACT_NAME)
-        .add_attribute("from_version", stored.version)
-        .add_attribute("to_version", CONTRACT_VERSION))
-}
+        // The clearing rate replaces the one of the rate strategy, it is still held to the
+        // rate bounds. Commitments that cannot be converted are cancelled instead
+        let coin = Coin::new(committed, settlement.denom);
+        let res = convert_coin(
+            deps,
+            &env,
+            config,
+            info.sender,
+            (coin, None),
+            None,
+            None,
+            None,
+            None,
+            Some(&settlement.rate),
+        )?;
+        Ok(res
+            .add_attribute("round", round.to_string())
+            .add_attribute("clearing_rate", settlement.rate.to_string()))
+    }
 
-mod query {
-    use super::*;
+    // Send the sender's commitment to a settled round back rather than converting it, for
+    // claims that fail, e.g. with a clearing rate outside of the rate bounds
+    pub fn cancel_auction_commitment(
+        deps: DepsMut,
+        info: MessageInfo,
+        round: u64,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
 
-    pub fn config(deps: Deps) -> StdResult<Binary> {
-        to_json_binary(&CONFIG.load(deps.storage)?)
-    }
+        let settlement = AUCTION_SETTLEMENTS
+            .may_load(deps.storage, round)?
+            .ok_or(ContractError::AuctionError(NotSettled))?;
+        let committed = AUCTION_COMMITMENTS
+            .may_load(deps.storage, (round, &info.sender))?
+            .ok_or(ContractError::AuctionError(NoCommitment))?;
+        AUCTION_COMMITMENTS.remove(deps.storage, (round, &info.sender));
+        ESCROWED.update(
+            deps.storage,
+            &settlement.denom,
+            |escrowed| -> StdResult<_> {
+                Ok(escrowed.unwrap_or_default().saturating_sub(committed))
+            },
+        )?;
 
-    pub fn admin(deps: Deps) -> StdResult<Binary> {
-        to_json_binary(&ADMIN.query_admin(deps)?)
+        let refund = Coin::new(committed, settlement.denom);
+        Ok(Response::new()
+            .add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: vec![refund.clone()],
+            })
+            .add_attribute("action", "cancel_auction_commitment")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("sender", info.sender)
+            .add_attribute("round", round.to_string())
+            .add_attribute("refunded", refund.to_string()))
     }
-}
-
-mod exec {
-    use super::*;
-    use crate::denom::Denom;
-    use crate::error::AdminError::{CannotRenounce, NotAdmin};
-    use crate::error::ConvertError::{InvalidFunds, InvalidSourceDenom};
-    use crate::msg::UpdateConfig;
-    use crate::rate::Rate;
-    use cosmwasm_std::{AnyMsg, BankMsg, CosmosMsg};
-    use cw_utils::one_coin;
-    use manifest_std::cosmos::authz::v1beta1::MsgExec;
-    use manifest_std::google::protobuf::Any;
-    use manifest_std::liftedinit::manifest::v1::MsgBurnHeldBalance;
-    use manifest_std::osmosis::tokenfactory::v1beta1::MsgMint;
-    use prost::Message;
 
-    pub fn update_admin(
+    // Send source or target tokens to the counterparty chain of a converter channel. The
+    // tokens are escrowed until they come back, or refunded if the transfer fails
+    #[cfg(feature = "ibc")]
+    pub fn transfer_remote(
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
-        admin: Option<String>,
+        channel_id: String,
+        receiver: String,
+        timeout_seconds: Option<u64>,
     ) -> Result<Response, ContractError> {
-        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
-        ADMIN
-            .assert_admin(deps.as_ref(), &info.sender)
-            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+        use crate::consts::DEFAULT_IBC_TIMEOUT_SECONDS;
+        use crate::error::IbcError::{InvalidDenom, UnknownChannel};
+        use crate::msg::ConverterPacket;
+        use crate::state::IBC_CHANNELS;
+        use cosmwasm_std::IbcMsg;
+
+        let coin = one_coin(&info).map_err(|_| ContractError::ConvertError(InvalidFunds))?;
+        let config = load_config(deps.storage)?;
+        if !config.source_denom.matches(&coin.denom) && !config.target_denom.matches(&coin.denom) {
+            return Err(ContractError::IbcError(InvalidDenom));
+        }
+        if !IBC_CHANNELS.has(deps.storage, &channel_id) {
+            return Err(ContractError::IbcError(UnknownChannel));
+        }
+        crate::ibc::escrow(deps.storage, &channel_id, &coin.denom, coin.amount)?;
 
-        let admin_str = admin.ok_or(ContractError::AdminError(CannotRenounce))?;
-        let new = deps.api.addr_validate(&admin_str)?;
+        let packet = ConverterPacket::Transfer {
+            denom: coin.denom.clone(),
+            amount: coin.amount,
+            sender: info.sender.to_string(),
+            receiver: receiver.clone(),
+        };
+        let timeout = env
+            .block
+            .time
+            .plus_seconds(timeout_seconds.unwrap_or(DEFAULT_IBC_TIMEOUT_SECONDS));
 
-        let res = ADMIN
-            .execute_update_admin(deps, info, Some(new))
-            .map_err(|_| ContractError::AdminError(NotAdmin))?;
-        Ok(res
-            .add_attribute("action", "update_admin")
+        Ok(Response::new()
+            .add_message(IbcMsg::SendPacket {
+                channel_id: channel_id.clone(),
+                data: to_json_binary(&packet)?,
+                timeout: timeout.into(),
+            })
+            .add_attribute("action", "transfer_remote")
             .add_attribute("contract", CONTRACT_NAME)
             .add_attribute("version", CONTRACT_VERSION)
-            .add_attribute("new_admin", admin_str))
+            .add_attribute("channel_id", channel_id)
+            .add_attribute("sender", info.sender)
+            .add_attribute("receiver", receiver)
+            .add_attribute("amount", coin.to_string()))
     }
 
     // Update the contract configuration with new values
     pub fn update_config(
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
         config: UpdateConfig,
+        strict: bool,
     ) -> Result<Response, ContractError> {
         nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
-        ADMIN
-            .assert_admin(deps.as_ref(), &info.sender)
-            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+        let baseline =
+            RateBaseline::load(deps.storage, &load_config(deps.storage)?, env.block.time)?;
+        // Delegates may update the fields delegated to them, the admin any field
+        let delegation = if ADMIN.is_admin(deps.as_ref(), &info.sender)? {
+            None
+        } else {
+            let delegation = DELEGATIONS
+                .may_load(deps.storage, &info.sender)?
+                .ok_or(ContractError::AdminError(NotAdmin))?;
+            delegation.check(&load_config(deps.storage)?, &config, &baseline.rate)?;
+            Some(info.sender.clone())
+        };
+
+        // Automation relies on strict updates to detect changes that did not apply
+        if strict && (config.is_empty() || config.is_noop(&load_config(deps.storage)?)) {
+            return Err(ContractError::ConfigError(NoopUpdate));
+        }
 
         if config.is_empty() {
             return Ok(Response::new()
                 .add_attribute("action", "update_config")
                 .add_attribute("note", "empty config, no changes made"));
         }
-        let mut current_config = CONFIG.load(deps.storage)?;
+        let current_config = load_config(deps.storage)?;
 
         if config.is_noop(&current_config) {
             return Ok(Response::new()
@@ -168,33 +3755,27 @@ mod exec {
                 .add_attribute("note", "identical config, no changes made"));
         }
 
-        if let Some(poa_admin) = config.poa_admin {
-            let poa_admin_addr = deps.api.addr_validate(&poa_admin)?;
-            current_config.poa_admin = poa_admin_addr;
-        }
-
-        if let Some(rate) = config.rate {
-            current_config.rate = Rate::parse(&rate)?;
-        }
-
-        if let Some(source_denom) = config.source_denom {
-            current_config.source_denom = Denom::new(source_denom)?;
-        }
-
-        if let Some(target_denom) = config.target_denom {
-            current_config.target_denom = Denom::new(target_denom)?;
-        }
+        let previous_rate = current_config.rate.clone();
+        let (current_config, warnings) =
+            apply_config_update(deps.as_ref(), &env.contract.address, current_config, config)?;
 
-        if let Some(paused) = config.paused {
-            current_config.paused = paused;
+        CONFIG.save(deps.storage, &current_config)?;
+        // Later delegated changes of the day stay within the limit of the rate before the
+        // first one, while a change by the admin starts over from the new rate
+        if current_config.rate != previous_rate {
+            match delegation {
+                Some(_) => RATE_BASELINE.save(deps.storage, &baseline)?,
+                None => RATE_BASELINE.remove(deps.storage),
+            }
         }
-
-        // Ensure source and target denoms are not the same
-        if current_config.source_denom == current_config.target_denom {
-            return Err(ContractError::ConfigError(SameDenom));
+        if !current_config.paused {
+            PAUSE.remove(deps.storage);
         }
-
-        CONFIG.save(deps.storage, &current_config)?;
+        cache_denom_info(
+            deps.storage,
+            &ManifestQuerier::new(deps.querier),
+            &current_config,
+        )?;
 
         Ok(Response::new()
             .add_attribute("action", "update_config")
@@ -204,7 +3785,141 @@ mod exec {
             .add_attribute("rate", current_config.rate.to_string())
             .add_attribute("source_denom", current_config.source_denom.to_string())
             .add_attribute("target_denom", current_config.target_denom.to_string())
-            .add_attribute("paused", current_config.paused.to_string()))
+            .add_attribute("paused", current_config.paused.to_string())
+            .add_attributes(delegation.map(|delegate| ("delegate", delegate)))
+            .add_attributes(warnings))
+    }
+
+    pub fn replace_config(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        config: InstantiateConfig,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        ADMIN
+            .assert_admin(deps.as_ref(), &info.sender)
+            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+
+        let current_config = load_config(deps.storage)?;
+        let config = build_config(deps.api, config)?;
+        // Addresses already kept by the contract were validated against the current prefix
+        if config.bech32_prefix != current_config.bech32_prefix {
+            return Err(ContractError::ConfigError(Bech32PrefixImmutable));
+        }
+        if config.rate_floor != current_config.rate_floor
+            || config.rate_ceiling != current_config.rate_ceiling
+        {
+            return Err(ContractError::ConfigError(RateBoundsImmutable));
+        }
+        check_poa_admin(deps.as_ref(), &config)?;
+        check_grantee(&config, &env.contract.address)?;
+        check_verifier(deps.as_ref(), &config)?;
+        check_oracle(deps.as_ref(), &config)?;
+        check_resolver(deps.as_ref(), &config)?;
+        let warnings = check_denoms(deps.as_ref(), &config)?;
+
+        CONFIG.save(deps.storage, &config)?;
+        RATE_BASELINE.remove(deps.storage);
+        if !config.paused {
+            PAUSE.remove(deps.storage);
+        }
+        cache_denom_info(deps.storage, &ManifestQuerier::new(deps.querier), &config)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "replace_config")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("poa_admin", config.poa_admin)
+            .add_attribute("rate", config.rate.to_string())
+            .add_attribute("source_denom", config.source_denom.to_string())
+            .add_attribute("target_denom", config.target_denom.to_string())
+            .add_attribute("paused", config.paused.to_string())
+            .add_attributes(warnings))
+    }
+
+    // Refresh the cached bank metadata of the configured denoms, e.g. once it was set or
+    // updated on chain
+    pub fn refresh_denom_info(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        ADMIN
+            .assert_admin(deps.as_ref(), &info.sender)
+            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+
+        let config = load_config(deps.storage)?;
+        cache_denom_info(deps.storage, &ManifestQuerier::new(deps.querier), &config)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "refresh_denom_info")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION))
+    }
+
+    // Split the minted tokens among recipients in proportion to their weights. Rounding
+    // leftovers go to the last recipient so the shares add up to the minted amount. Recipients
+    // given as names are resolved, and the resolutions returned as attributes
+    fn split_mint(
+        deps: Deps,
+        config: &Config,
+        minted: &Coin,
+        recipients: &[Recipient],
+    ) -> Result<(Vec<Mint>, Vec<Attribute>), ContractError> {
+        if recipients.is_empty()
+            || recipients.len() > MAX_RECIPIENTS
+            || recipients.iter().any(|r| r.weight == 0)
+        {
+            return Err(ContractError::ConvertError(InvalidRecipients));
+        }
+        let total_weight: u128 = recipients.iter().map(|r| u128::from(r.weight)).sum();
+        let mut mints: Vec<Mint> = Vec::with_capacity(recipients.len());
+        let mut resolutions = vec![];
+        let mut remaining = minted.amount;
+        for (i, r) in recipients.iter().enumerate() {
+            let resolved = resolve(deps, config, &r.address)?;
+            let recipient = validate_addr(deps.api, config, &resolved.address)?;
+            resolutions.extend(resolved.attribute());
+            if mints.iter().any(|m| m.recipient == recipient) {
+                return Err(ContractError::ConvertError(InvalidRecipients));
+            }
+            let share = if i == recipients.len() - 1 {
+                remaining
+            } else {
+                minted.amount.multiply_ratio(r.weight, total_weight)
+            };
+            if share.is_zero() {
+                return Err(ContractError::ConvertError(ShareIsZero));
+            }
+            remaining -= share;
+            mints.push(Mint {
+                recipient,
+                amount: Coin::new(share, minted.denom.as_str()),
+            });
+        }
+        Ok((mints, resolutions))
+    }
+
+    // Split the funds of a conversion into the converted coin and the flat fee. With a flat
+    // fee configured, exactly two coins are expected: the fee itself, with its exact amount,
+    // and the coin to convert
+    fn split_funds(
+        info: &MessageInfo,
+        config: &Config,
+    ) -> Result<(Coin, Option<Coin>), ContractError> {
+        let Some(fee) = &config.flat_fee else {
+            let coin = one_coin(info).map_err(|_| ContractError::ConvertError(InvalidFunds))?;
+            return Ok((coin, None));
+        };
+        let (fees, coins): (Vec<_>, Vec<_>) = info
+            .funds
+            .iter()
+            .partition(|c| same_denom(&c.denom, &fee.denom));
+        if !matches!(fees.as_slice(), [paid] if paid.amount == fee.amount) {
+            return Err(ContractError::ConvertError(InvalidFee));
+        }
+        match coins.as_slice() {
+            [coin] => Ok(((*coin).clone(), Some(fee.clone()))),
+            _ => Err(ContractError::ConvertError(InvalidFunds)),
+        }
     }
 
     // Convert source tokens to target tokens
@@ -212,86 +3927,454 @@ mod exec {
     // 1. Validate that the sent funds are of the correct source_denom
     // 2. Send the source tokens to the POA admin address to be burned
     // 3. Calculate the amount of target tokens to mint based on the contract's rate
-    // 4. Burn and mint tokens via AuthZ messages
-    pub fn convert(deps: Deps, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
-        let config = CONFIG.load(deps.storage)?;
+    // 4. Send, burn and mint tokens via AuthZ messages
+    // 5. Track the conversion until the reply finalizes it or escrows a refund
+    // 6. Optionally forward the minted tokens to a callback contract
+    #[allow(clippy::too_many_arguments)]
+    pub fn convert(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        callback: Option<Callback>,
+        recipient: Option<String>,
+        recipients: Option<Vec<Recipient>>,
+        from_deposit: Option<Uint256>,
+        memo: Option<String>,
+        min_receive: Option<Uint256>,
+    ) -> Result<Response, ContractError> {
+        let config = load_config(deps.storage)?;
+        ensure_convertible(deps.storage)?;
+        ensure_not_batched(deps.storage)?;
 
-        // Ensure contract is not paused
-        if config.paused {
-            return Err(ContractError::Paused);
+        if memo.as_ref().is_some_and(|memo| memo.len() > MAX_MEMO_LEN) {
+            return Err(ContractError::ConvertError(InvalidMemo));
         }
 
+        // A single recipient is a split with one share, validated and resolved alike
+        let recipients = match (recipient, recipients) {
+            (Some(_), Some(_)) => return Err(ContractError::ConvertError(InvalidRecipients)),
+            (Some(address), None) => Some(vec![Recipient { address, weight: 1 }]),
+            (None, recipients) => recipients,
+        };
+
         // Funds (info.funds) are processed by the Bank module before reaching the contract
-        // Ensure exactly one coin is sent
-        let coin = one_coin(&info).map_err(|_| ContractError::ConvertError(InvalidFunds))?;
+        // Reject zero amounts explicitly, they would otherwise only fail once the send
+        // message is executed
+        if info.funds.iter().any(|c| c.amount.is_zero()) {
+            return Err(ContractError::ConvertError(ZeroAmount));
+        }
+
+        // Draw the coin from the sender's deposit, the funds then only carry the flat fee
+        let funds = match from_deposit {
+            Some(amount) => {
+                let fee = fee_funds(&info, &config)?;
+                let coin = Coin::new(amount, config.source_denom.as_str());
+                debit_deposit(deps.storage, &info.sender, &coin)?;
+                (coin, fee)
+            }
+            // Ensure exactly one coin is sent, alongside the flat fee when one is configured
+            None => split_funds(&info, &config)?,
+        };
+
+        convert_coin(
+            deps,
+            &env,
+            config,
+            info.sender,
+            funds,
+            callback,
+            recipients,
+            memo,
+            min_receive,
+            None,
+        )
+    }
+
+    // Funds of a conversion debited from a deposit, which must be the flat fee when one is
+    // configured and nothing otherwise
+    fn fee_funds(info: &MessageInfo, config: &Config) -> Result<Option<Coin>, ContractError> {
+        match (&config.flat_fee, info.funds.as_slice()) {
+            (None, []) => Ok(None),
+            (None, _) => Err(ContractError::AmountError(NonPayable)),
+            (Some(fee), [coin])
+                if same_denom(&coin.denom, &fee.denom) && coin.amount == fee.amount =>
+            {
+                Ok(Some(fee.clone()))
+            }
+            (Some(_), _) => Err(ContractError::ConvertError(InvalidFee)),
+        }
+    }
+
+    // Take a coin out of a deposit and its escrow, leaving the deposit in place even when
+    // emptied so its nonce is kept
+    fn debit_deposit(
+        storage: &mut dyn Storage,
+        owner: &Addr,
+        coin: &Coin,
+    ) -> Result<Deposit, ContractError> {
+        if coin.amount.is_zero() {
+            return Err(ContractError::AmountError(AmountIsZero));
+        }
+        let mut deposit = DEPOSITS
+            .may_load(storage, owner)?
+            .ok_or(ContractError::DepositError(NoDeposit))?;
+        if coin.denom != deposit.amount.denom || coin.amount > deposit.amount.amount {
+            return Err(ContractError::DepositError(InsufficientDeposit));
+        }
+        deposit.amount.amount -= coin.amount;
+        DEPOSITS.save(storage, owner, &deposit)?;
+        ESCROWED.update(storage, &coin.denom, |total| -> StdResult<_> {
+            Ok(total.unwrap_or_default().saturating_sub(coin.amount))
+        })?;
+        Ok(deposit)
+    }
 
+    fn ensure_convertible(storage: &dyn Storage) -> Result<(), ContractError> {
+        // Conversions burn and mint through authz
+        if !AUTHZ_ENABLED.may_load(storage)?.unwrap_or(true) {
+            return Err(ContractError::ConvertError(AuthzDisabled));
+        }
+        Ok(())
+    }
+
+    // Conversions are only settled in rounds while batch mode is enabled
+    fn ensure_not_batched(storage: &dyn Storage) -> Result<(), ContractError> {
+        if AUCTION.exists(storage) {
+            return Err(ContractError::AuctionError(BatchMode));
+        }
+        Ok(())
+    }
+
+    // Convert from a deposit on behalf of its owner, who signed the payload off-chain. The
+    // relayer only pays the gas, and the flat fee when one is configured
+    pub fn convert_with_signature(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        payload: SignedConversion,
+        signature: Binary,
+        pubkey: Binary,
+    ) -> Result<Response, ContractError> {
+        let config = load_config(deps.storage)?;
+        ensure_convertible(deps.storage)?;
+        ensure_not_batched(deps.storage)?;
+
+        let fee = fee_funds(&info, &config)?;
+
+        let owner = deps.api.addr_validate(&payload.owner)?;
+        let deposit = DEPOSITS
+            .may_load(deps.storage, &owner)?
+            .ok_or(ContractError::DepositError(NoDeposit))?;
+        if deposit.pubkey.as_ref() != Some(&pubkey) {
+            return Err(ContractError::SignatureError(PubkeyMismatch));
+        }
+        if env.block.time >= payload.deadline {
+            return Err(ContractError::SignatureError(Expired));
+        }
+        if payload.nonce != deposit.nonce {
+            return Err(ContractError::SignatureError(InvalidNonce));
+        }
+
+        let sign_doc = ConversionSignDoc {
+            chain_id: env.block.chain_id.clone(),
+            contract: env.contract.address.to_string(),
+            payload: payload.clone(),
+        };
+        let hash = Sha256::digest(to_json_vec(&sign_doc)?);
+        let verified = deps
+            .api
+            .secp256k1_verify(&hash, &signature, &pubkey)
+            .map_err(|_| ContractError::SignatureError(InvalidSignature))?;
+        if !verified {
+            return Err(ContractError::SignatureError(InvalidSignature));
+        }
+
+        let coin = payload.amount;
+        let mut deposit = debit_deposit(deps.storage, &owner, &coin)?;
+        deposit.nonce += 1;
+        DEPOSITS.save(deps.storage, &owner, &deposit)?;
+
+        let res = convert_coin(
+            deps,
+            &env,
+            config,
+            owner,
+            (coin, fee),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )?;
+        Ok(res
+            .add_attribute("relayer", info.sender)
+            .add_attribute("nonce", payload.nonce.to_string()))
+    }
+
+    // Convert a coin on behalf of the sender, whether sent as funds or debited from a deposit
+    #[allow(clippy::too_many_arguments)]
+    fn convert_coin(
+        deps: DepsMut,
+        env: &Env,
+        config: Config,
+        sender: Addr,
+        (coin, fee): (Coin, Option<Coin>),
+        callback: Option<Callback>,
+        recipients: Option<Vec<Recipient>>,
+        memo: Option<String>,
+        min_receive: Option<Uint256>,
+        settled_rate: Option<&Rate>,
+    ) -> Result<Response, ContractError> {
         // The coin should be of the source_denom type
-        if coin.denom != config.source_denom.to_string() {
+        if !config.source_denom.matches(&coin.denom) {
             return Err(ContractError::ConvertError(InvalidSourceDenom));
         }
+        check_convert_amount(&config, coin.amount)?;
+        #[cfg(feature = "quotas")]
+        quota::consume(deps.storage, &config, &sender, env.block.time, coin.amount)?;
+        let (coin, fee) = withhold_fee(&config, coin, fee)?;
 
-        // Calculate amount to mint based on rate
-        let amt_to_mint = config.rate.apply_to(coin.amount)?;
+        // Some programs are restricted to either accounts or contracts
+        if let Some(allowed) = &config.allowed_senders {
+            let is_contract = deps.querier.query_wasm_contract_info(&sender).is_ok();
+            let permitted = match allowed {
+                SenderClass::Any => true,
+                SenderClass::AccountsOnly => !is_contract,
+                SenderClass::ContractsOnly => is_contract,
+            };
+            if !permitted {
+                return Err(ContractError::ConvertError(SenderNotAllowed));
+            }
+        }
 
-        // Send tokens to burn to the POA address
-        let send = CosmosMsg::Bank(BankMsg::Send {
-            to_address: config.poa_admin.to_string(),
-            amount: vec![coin.clone()],
-        });
+        // Regulated deployments only let senders attested by the verifier convert
+        if let Some(verifier) = &config.verifier {
+            let res: IsVerifiedResponse = deps.querier.query_wasm_smart(
+                verifier,
+                &VerifierQueryMsg::IsVerified {
+                    address: sender.to_string(),
+                },
+            )?;
+            if !res.verified {
+                return Err(ContractError::ConvertError(SenderNotVerified));
+            }
+        }
 
-        // Prepare to burn the tokens from the POA's held balance
-        let burn = MsgBurnHeldBalance {
-            authority: config.poa_admin.to_string(),
-            burn_coins: vec![manifest_std::cosmos::base::v1beta1::Coin {
-                denom: config.source_denom.to_string(),
-                amount: coin.amount.to_string(),
-            }],
-        };
-        let any_burn = Any {
-            type_url: MsgBurnHeldBalance::TYPE_URL.to_string(),
-            value: burn.encode_to_vec(),
-        };
+        // Calculate amount to mint based on the rate resolved by the rate strategy
+        let rate = resolve_rate(
+            deps.as_ref(),
+            &env.block,
+            &config,
+            coin.amount,
+            settled_rate,
+        )?;
+        let amt_to_mint = config.mint_amount(&rate, coin.amount)?;
+        check_epoch_cap(deps.storage, env.block.time, &config, amt_to_mint)?;
 
-        // Prepare to mint new tokens to the sender's address
-        let mint = MsgMint {
-            sender: config.poa_admin.to_string(),
-            amount: Some(manifest_std::cosmos::base::v1beta1::Coin {
-                denom: config.target_denom.to_string(),
-                amount: amt_to_mint.to_string(),
-            }),
-            mint_to_address: info.sender.to_string(),
+        // Fail rather than mint less than the sender expected, e.g. after a rate update landed
+        // before the conversion
+        if min_receive.is_some_and(|min| amt_to_mint < min) {
+            return Err(ContractError::ConvertError(SlippageExceeded));
+        }
+
+        // A callback receives all the minted tokens, they cannot be split as well
+        if callback.is_some() && recipients.is_some() {
+            return Err(ContractError::ConvertError(RecipientsWithCallback));
+        }
+
+        // The callback target must be an existing contract. When a callback is set, the
+        // tokens are minted to the converter itself and forwarded along with the callback
+        let mut resolutions = vec![];
+        let callback = callback
+            .map(|cb| -> Result<_, ContractError> {
+                let resolved = resolve(deps.as_ref(), &config, &cb.contract)?;
+                resolutions.extend(resolved.attribute());
+                let contract = deps.api.addr_validate(&resolved.address)?;
+                deps.querier
+                    .query_wasm_contract_info(&contract)
+                    .map_err(|_| ContractError::ConvertError(CallbackNotContract))?;
+                Ok((contract, cb.msg))
+            })
+            .transpose()?;
+
+        let minted = Coin::new(amt_to_mint, config.target_denom.as_str());
+        let mints = match (&callback, recipients) {
+            (Some(_), _) => vec![Mint {
+                recipient: env.contract.address.clone(),
+                amount: minted.clone(),
+            }],
+            (None, Some(recipients)) => {
+                let (mints, resolved) = split_mint(deps.as_ref(), &config, &minted, &recipients)?;
+                resolutions.extend(resolved);
+                mints
+            }
+            (None, None) => vec![Mint {
+                recipient: sender.clone(),
+                amount: minted.clone(),
+            }],
         };
-        let any_mint = Any {
-            type_url: MsgMint::TYPE_URL.to_string(),
-            value: mint.encode_to_vec(),
+        // Paid out of the float when it covers the conversion, returned to it on failure
+        let from_float = pays_from_float(deps.storage, &config, &minted)?;
+        if from_float {
+            pay_from_float(deps.storage, &minted)?;
+        }
+        let authz = authz_msgs(&env.contract.address, &config, &coin, &mints, from_float);
+        let authz_attrs = match config.hash_authz_payloads.unwrap_or_default() {
+            true => vec![(
+                "authz_payload_hashes",
+                authz.iter().map(payload_hash).collect::<Vec<_>>().join(","),
+            )],
+            false => vec![
+                ("authz_msg_count", authz.len().to_string()),
+                ("burn_type", MsgBurnHeldBalance::TYPE_URL.to_string()),
+                ("mint_type", MsgMint::TYPE_URL.to_string()),
+            ],
         };
+        let msg = build_exec(env.contract.address.as_str(), authz);
 
-        // Execute both burn and mint via AuthZ
-        let exec = MsgExec {
-            grantee: env.contract.address.to_string(),
-            msgs: vec![any_burn, any_mint],
+        // The conversion stays pending until the reply confirms it, or escrows the funds
+        // for a refund when it failed. Its receipt is kept for lookups by id
+        let id = CONVERSION_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+        CONVERSION_COUNT.save(deps.storage, &id)?;
+        let mut conversion = Conversion {
+            id,
+            sender: sender.clone(),
+            burned: coin.clone(),
+            minted: minted.clone(),
+            mints: mints.clone(),
+            fee: fee.clone(),
+            status: ConversionStatus::Pending,
+            height: env.block.height,
+            time: env.block.time,
+            rate: Some(rate.clone()),
+            hash: None,
+            memo: memo.clone(),
+            from_float: from_float.then_some(true),
         };
+        conversion.hash = receipt_hash(&env.block.chain_id, &env.contract.address, &conversion)?;
+        CONVERSIONS.save(deps.storage, id, &conversion)?;
 
-        let msg = CosmosMsg::Any(AnyMsg {
-            type_url: MsgExec::TYPE_URL.to_string(),
-            value: exec.encode_to_vec().into(),
-        });
-
-        Ok(Response::new()
-            .add_message(send)
-            .add_message(msg)
+        let fee_msg = fee
+            .as_ref()
+            .map(|fee| fee_msg(&env.contract.address, &config, fee));
+        let res = Response::new()
+            .add_submessage(
+                SubMsg::reply_always(msg, REPLY_EXEC_ID).with_payload(to_json_binary(&id)?),
+            )
+            .set_data(to_json_binary(&ConvertResponse { conversion_id: id })?)
             .add_attribute("action", "convert")
             .add_attribute("contract", CONTRACT_NAME)
             .add_attribute("version", CONTRACT_VERSION)
-            .add_attribute("sender", info.sender)
+            .add_attribute("sender", sender.as_str())
             .add_attribute("poa_admin", config.poa_admin)
             .add_attribute("burned", coin.amount.to_string())
             .add_attribute("minted", amt_to_mint.to_string())
             .add_attribute("burned_denom", config.source_denom)
             .add_attribute("minted_denom", config.target_denom)
-            .add_attribute("authz_grantee", env.contract.address)
-            .add_attribute("authz_msg_count", "2")
-            .add_attribute("burn_type", MsgBurnHeldBalance::TYPE_URL)
-            .add_attribute("mint_type", MsgMint::TYPE_URL))
+            .add_attribute("authz_grantee", env.contract.address.as_str())
+            .add_attributes(authz_attrs)
+            .add_attribute("conversion_id", id.to_string());
+        let res = match from_float {
+            true => res.add_attribute("float_payout", minted.to_string()),
+            false => res,
+        };
+        let res = match mints.as_slice() {
+            [_, _, ..] => res.add_attribute("recipients", mints.len().to_string()),
+            [mint] if callback.is_none() && mint.recipient != sender => {
+                res.add_attribute("recipient", mint.recipient.as_str())
+            }
+            _ => res,
+        };
+        let res = res.add_attributes(resolutions);
+        let res = match memo {
+            Some(memo) => res.add_attribute("memo", memo),
+            None => res,
+        };
+
+        // The flat fee is collected whatever the outcome of the conversion
+        let res = match (fee, fee_msg) {
+            (Some(fee), Some(fee_msg)) => res
+                .add_message(fee_msg)
+                .add_attribute("fee", fee.to_string()),
+            _ => res,
+        };
+
+        // The reply forwards the minted tokens to the callback contract, only once they were
+        // minted: a failed conversion must not pay the callback out of the contract balance
+        let Some((contract, msg)) = callback else {
+            return Ok(res);
+        };
+        let res = res.add_attribute("callback_contract", contract.as_str());
+        PENDING_CALLBACKS.save(deps.storage, id, &PendingCallback { contract, msg })?;
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::conversion_msg;
+    use crate::messages::{build_burn, build_mint, build_send};
+    use crate::rate::Rate;
+    use crate::state::{Config, Mint};
+    use cosmwasm_std::{coin, Addr, CosmosMsg};
+    use manifest_std::cosmos::authz::v1beta1::MsgExec;
+    use prost::Message;
+
+    const CONTRACT: &str = "contract";
+    const POA_ADMIN: &str = "poa_admin";
+
+    #[test]
+    fn test_conversion_msg() {
+        let mut config = Config::try_with_defaults(Rate::parse("0.5").unwrap()).unwrap();
+        config.poa_admin = Addr::unchecked(POA_ADMIN);
+        let mints = [
+            Mint {
+                recipient: Addr::unchecked("alice"),
+                amount: coin(300, "upwr"),
+            },
+            Mint {
+                recipient: Addr::unchecked("bob"),
+                amount: coin(200, "upwr"),
+            },
+        ];
+        let msg = conversion_msg(
+            &Addr::unchecked(CONTRACT),
+            &config,
+            &coin(1_000, "umfx"),
+            &mints,
+            false,
+        );
+
+        let CosmosMsg::Any(any) = msg else {
+            panic!("expected an Any message, got {msg:?}");
+        };
+        assert_eq!(any.type_url, "/cosmos.authz.v1beta1.MsgExec");
+        let exec = MsgExec::decode(any.value.as_slice()).unwrap();
+        assert_eq!(exec.grantee, CONTRACT);
+        assert_eq!(
+            exec.msgs,
+            vec![
+                build_send(CONTRACT, POA_ADMIN, &coin(1_000, "umfx")),
+                build_burn(POA_ADMIN, &coin(1_000, "umfx")),
+                build_mint(POA_ADMIN, "alice", &coin(300, "upwr")),
+                build_mint(POA_ADMIN, "bob", &coin(200, "upwr")),
+            ]
+        );
+
+        // Paid out of the float, the contract sends the minted tokens itself
+        let msgs = crate::contract::authz_msgs(
+            &Addr::unchecked(CONTRACT),
+            &config,
+            &coin(1_000, "umfx"),
+            &mints,
+            true,
+        );
+        assert_eq!(
+            msgs[2..],
+            [
+                build_send(CONTRACT, "alice", &coin(300, "upwr")),
+                build_send(CONTRACT, "bob", &coin(200, "upwr")),
+            ]
+        );
     }
 }
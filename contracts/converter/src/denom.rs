@@ -1,5 +1,8 @@
+use crate::consts::MAX_SYMBOL_LEN;
 use crate::error::{ContractError, DenomError};
+use crate::querier::ManifestQuerier;
 use cosmwasm_schema::cw_serde;
+use cosmwasm_std::StdResult;
 
 type DenomInner = String;
 
@@ -33,9 +36,11 @@ impl Denom {
         Ok(())
     }
 
+    // Denoms are canonicalized before validation, so configuring a lowercase IBC hash or a
+    // denom padded with whitespace stores the form the chain reports
     #[inline]
     pub fn new(denom: impl Into<DenomInner>) -> Result<Self, ContractError> {
-        let d = Denom(denom.into());
+        let d = Denom(canonicalize(&denom.into()));
         d.validate()?;
         Ok(d)
     }
@@ -54,6 +59,67 @@ impl Denom {
     pub fn into_inner(self) -> DenomInner {
         self.0
     }
+
+    // Whether the denom is the same as the given one once canonicalized
+    #[inline]
+    pub fn matches(&self, denom: &str) -> bool {
+        same_denom(self.as_str(), denom)
+    }
+
+    // A denom is considered to exist on chain when it has a supply or bank metadata.
+    // Chains return an error when querying the metadata of an unknown denom
+    pub fn exists(&self, querier: &ManifestQuerier) -> StdResult<bool> {
+        if !querier.supply(self.as_str())?.is_zero() {
+            return Ok(true);
+        }
+        Ok(querier.denom_metadata(self.as_str()).is_some())
+    }
+
+    // Display symbol and exponent from the bank metadata of the denom, both unset when it has
+    // none. The exponent is the one of the display unit
+    pub fn info(&self, querier: &ManifestQuerier) -> DenomInfo {
+        let Some(metadata) = querier.denom_metadata(self.as_str()) else {
+            return DenomInfo {
+                denom: self.to_string(),
+                symbol: None,
+                exponent: None,
+            };
+        };
+        DenomInfo {
+            denom: self.to_string(),
+            symbol: Some(metadata.symbol).filter(|symbol| !symbol.is_empty()),
+            exponent: metadata
+                .denom_units
+                .iter()
+                .find(|unit| unit.denom == metadata.display)
+                .map(|unit| unit.exponent),
+        }
+    }
+}
+
+// Bank metadata of a configured denom, cached at config time
+#[cw_serde]
+pub struct DenomInfo {
+    pub denom: String,
+    pub symbol: Option<String>,
+    pub exponent: Option<u32>,
+}
+
+// Trim surrounding whitespace and upper-case the hash of an IBC denom, the case the chain
+// reports it in. Native and factory denoms are case sensitive and kept as they are
+pub fn canonicalize(denom: &str) -> String {
+    let denom = denom.trim();
+    match denom.get(..4) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("ibc/") => {
+            format!("ibc/{}", denom[4..].to_ascii_uppercase())
+        }
+        _ => denom.to_string(),
+    }
+}
+
+// Compare two denoms by their canonical forms
+pub fn same_denom(a: &str, b: &str) -> bool {
+    canonicalize(a) == canonicalize(b)
 }
 
 #[inline]
@@ -105,6 +171,17 @@ fn is_factory(s: &str) -> bool {
         .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | ':' | '_' | '-'))
 }
 
+// Display symbols are short ASCII alphanumeric tickers, e.g. "MFX"
+pub fn validate_symbol(symbol: &str) -> Result<(), ContractError> {
+    if symbol.is_empty()
+        || symbol.len() > MAX_SYMBOL_LEN
+        || !symbol.chars().all(|c| c.is_ascii_alphanumeric())
+    {
+        return Err(ContractError::DenomError(DenomError::InvalidSymbol));
+    }
+    Ok(())
+}
+
 impl From<Denom> for DenomInner {
     fn from(value: Denom) -> Self {
         value.0
@@ -126,7 +203,7 @@ impl std::fmt::Display for Denom {
 
 #[cfg(test)]
 mod tests {
-    use super::Denom;
+    use super::{canonicalize, same_denom, validate_symbol, Denom};
     use crate::error::{ContractError, DenomError};
 
     #[test]
@@ -160,6 +237,24 @@ mod tests {
         assert_eq!(d.into_inner(), ibc);
     }
 
+    #[test]
+    fn test_denom_canonical() {
+        let ibc = "ibc/E91A88D2F4A515E48A183869B10B7C20A73F6DEE1BBE864FD15924EADB8A078F";
+        for input in [
+            " ibc/e91a88d2f4a515e48a183869b10b7c20a73f6dee1bbe864fd15924eadb8a078f",
+            "IBC/E91A88D2F4A515E48A183869B10B7C20A73F6DEE1BBE864FD15924EADB8A078F\n",
+        ] {
+            assert_eq!(canonicalize(input), ibc);
+            assert_eq!(Denom::new(input).unwrap().into_inner(), ibc);
+            assert!(Denom::new(ibc).unwrap().matches(input));
+        }
+        assert_eq!(canonicalize(" uatom "), "uatom");
+        // Only IBC hashes are case insensitive
+        assert_eq!(canonicalize("uAtom"), "uAtom");
+        assert!(!same_denom("uatom", "UATOM"));
+        assert!(same_denom("uatom", "uatom\t"));
+    }
+
     #[test]
     fn test_denom_ibc_invalid() {
         let err = Denom::new("ibc/invalidhash").unwrap_err();
@@ -194,4 +289,16 @@ mod tests {
             ContractError::DenomError(DenomError::InvalidFactoryDenomFormat)
         ));
     }
+
+    #[test]
+    fn test_validate_symbol() {
+        assert!(validate_symbol("MFX").is_ok());
+        assert!(validate_symbol("PWR2").is_ok());
+        for invalid in ["", "M FX", "MFX!", "😀", &"A".repeat(17)] {
+            assert!(matches!(
+                validate_symbol(invalid).unwrap_err(),
+                ContractError::DenomError(DenomError::InvalidSymbol)
+            ));
+        }
+    }
 }
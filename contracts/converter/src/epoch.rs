@@ -0,0 +1,116 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{OverflowError, Timestamp, Uint256};
+
+// Conversion statistics accumulated over an epoch. Epochs last `epoch_length` seconds and
+// never roll over when no length is configured
+#[cw_serde]
+pub struct Epoch {
+    pub id: u64,
+    pub start: Timestamp,
+    pub burned: Uint256,
+    pub minted: Uint256,
+    pub conversions: u64,
+}
+
+// Lifetime conversion totals
+#[cw_serde]
+#[derive(Default)]
+pub struct Totals {
+    pub burned: Uint256,
+    pub minted: Uint256,
+    pub conversions: u64,
+}
+
+impl Totals {
+    pub fn record(&mut self, burned: Uint256, minted: Uint256) -> Result<(), OverflowError> {
+        self.burned = self.burned.checked_add(burned)?;
+        self.minted = self.minted.checked_add(minted)?;
+        self.conversions = self.conversions.saturating_add(1);
+        Ok(())
+    }
+}
+
+impl Epoch {
+    #[inline]
+    pub fn new(id: u64, start: Timestamp) -> Self {
+        Epoch {
+            id,
+            start,
+            burned: Uint256::zero(),
+            minted: Uint256::zero(),
+            conversions: 0,
+        }
+    }
+
+    pub fn is_over(&self, now: Timestamp, length: Option<u64>) -> bool {
+        length.is_some_and(|l| now.seconds() >= self.start.seconds().saturating_add(l))
+    }
+
+    // The epoch in effect at the given time. Epochs without any conversion are skipped, their
+    // ids are still accounted for so ids always map to the same time window
+    pub fn at(&self, now: Timestamp, length: Option<u64>) -> Self {
+        match length {
+            Some(l) if self.is_over(now, length) => {
+                let elapsed = (now.seconds() - self.start.seconds()) / l;
+                Epoch::new(self.id + elapsed, self.start.plus_seconds(elapsed * l))
+            }
+            _ => self.clone(),
+        }
+    }
+
+    pub fn record(&mut self, burned: Uint256, minted: Uint256) -> Result<(), OverflowError> {
+        self.burned = self.burned.checked_add(burned)?;
+        self.minted = self.minted.checked_add(minted)?;
+        self.conversions = self.conversions.saturating_add(1);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Epoch;
+    use cosmwasm_std::{Timestamp, Uint256};
+
+    const DAY: u64 = 86_400;
+
+    #[test]
+    fn test_epoch_without_length_never_ends() {
+        let epoch = Epoch::new(0, Timestamp::from_seconds(100));
+        let now = Timestamp::from_nanos(u64::MAX);
+        assert!(!epoch.is_over(now, None));
+        assert_eq!(epoch.at(now, None), epoch);
+    }
+
+    #[test]
+    fn test_epoch_roll_over() {
+        let epoch = Epoch::new(3, Timestamp::from_seconds(100));
+        assert!(!epoch.is_over(Timestamp::from_seconds(100 + DAY - 1), Some(DAY)));
+        assert!(epoch.is_over(Timestamp::from_seconds(100 + DAY), Some(DAY)));
+
+        let next = epoch.at(Timestamp::from_seconds(100 + DAY), Some(DAY));
+        assert_eq!(next, Epoch::new(4, Timestamp::from_seconds(100 + DAY)));
+    }
+
+    #[test]
+    fn test_epoch_skips_idle_epochs() {
+        let epoch = Epoch::new(0, Timestamp::from_seconds(0));
+        let next = epoch.at(Timestamp::from_seconds(3 * DAY + 5), Some(DAY));
+        assert_eq!(next, Epoch::new(3, Timestamp::from_seconds(3 * DAY)));
+    }
+
+    #[test]
+    fn test_epoch_record() {
+        let mut epoch = Epoch::new(0, Timestamp::from_seconds(0));
+        epoch
+            .record(Uint256::from(10u8), Uint256::from(5u8))
+            .unwrap();
+        epoch
+            .record(Uint256::from(10u8), Uint256::from(5u8))
+            .unwrap();
+        assert_eq!(epoch.burned, Uint256::from(20u8));
+        assert_eq!(epoch.minted, Uint256::from(10u8));
+        assert_eq!(epoch.conversions, 2);
+
+        assert!(epoch.record(Uint256::MAX, Uint256::zero()).is_err());
+    }
+}
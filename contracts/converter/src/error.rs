@@ -1,29 +1,126 @@
+use crate::state::PauseInfo;
 use cosmwasm_std::StdError;
+use manifest_access::AccessError;
+pub use manifest_errors::{AdminError, AmountError, DenomError, ErrorCode, MigrateError};
 use thiserror::Error;
 
+// With the `terse-errors` feature, the errors wrapping a category only name the category, so
+// the messages of the categories are left out of the build
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum ContractError {
     #[error("{0}")]
     StdError(#[from] StdError),
-    #[error("unauthorized: {0}")]
+    #[cfg_attr(not(feature = "terse-errors"), error("unauthorized: {0}"))]
+    #[cfg_attr(feature = "terse-errors", error("unauthorized"))]
     AdminError(#[from] AdminError),
-    #[error("invalid rate: {0}")]
+    #[cfg_attr(not(feature = "terse-errors"), error("invalid rate: {0}"))]
+    #[cfg_attr(feature = "terse-errors", error("invalid rate"))]
     RateError(#[from] RateError),
-    #[error("invalid denom: {0}")]
+    #[cfg_attr(not(feature = "terse-errors"), error("invalid denom: {0}"))]
+    #[cfg_attr(feature = "terse-errors", error("invalid denom"))]
     DenomError(#[from] DenomError),
-    #[error("invalid amount: {0}")]
+    #[cfg_attr(not(feature = "terse-errors"), error("invalid amount: {0}"))]
+    #[cfg_attr(feature = "terse-errors", error("invalid amount"))]
     AmountError(#[from] AmountError),
-    #[error("conversion error: {0}")]
+    #[cfg_attr(not(feature = "terse-errors"), error("conversion error: {0}"))]
+    #[cfg_attr(feature = "terse-errors", error("conversion error"))]
     ConvertError(#[from] ConvertError),
-    #[error("configuration error: {0}")]
+    #[cfg_attr(not(feature = "terse-errors"), error("configuration error: {0}"))]
+    #[cfg_attr(feature = "terse-errors", error("configuration error"))]
     ConfigError(#[from] ConfigError),
-    #[error("migration error: {0}")]
+    #[cfg_attr(not(feature = "terse-errors"), error("migration error: {0}"))]
+    #[cfg_attr(feature = "terse-errors", error("migration error"))]
     MigrateError(#[from] MigrateError),
-    #[error("contract is paused")]
-    Paused,
+    #[cfg_attr(not(feature = "terse-errors"), error("quorum error: {0}"))]
+    #[cfg_attr(feature = "terse-errors", error("quorum error"))]
+    QuorumError(#[from] QuorumError),
+    #[cfg_attr(not(feature = "terse-errors"), error("unsolicited funds error: {0}"))]
+    #[cfg_attr(feature = "terse-errors", error("unsolicited funds error"))]
+    FundsError(#[from] FundsError),
+    #[cfg_attr(not(feature = "terse-errors"), error("ibc error: {0}"))]
+    #[cfg_attr(feature = "terse-errors", error("ibc error"))]
+    IbcError(#[from] IbcError),
+    #[cfg_attr(not(feature = "terse-errors"), error("deposit error: {0}"))]
+    #[cfg_attr(feature = "terse-errors", error("deposit error"))]
+    DepositError(#[from] DepositError),
+    #[cfg_attr(not(feature = "terse-errors"), error("float error: {0}"))]
+    #[cfg_attr(feature = "terse-errors", error("float error"))]
+    FloatError(#[from] FloatError),
+    #[cfg_attr(not(feature = "terse-errors"), error("auction error: {0}"))]
+    #[cfg_attr(feature = "terse-errors", error("auction error"))]
+    AuctionError(#[from] AuctionError),
+    #[cfg_attr(not(feature = "terse-errors"), error("commitment error: {0}"))]
+    #[cfg_attr(feature = "terse-errors", error("commitment error"))]
+    CommitError(#[from] CommitError),
+    #[cfg_attr(not(feature = "terse-errors"), error("stream error: {0}"))]
+    #[cfg_attr(feature = "terse-errors", error("stream error"))]
+    StreamError(#[from] StreamError),
+    #[cfg_attr(not(feature = "terse-errors"), error("signature error: {0}"))]
+    #[cfg_attr(feature = "terse-errors", error("signature error"))]
+    SignatureError(#[from] SignatureError),
+    #[cfg_attr(not(feature = "terse-errors"), error("metadata error: {0}"))]
+    #[cfg_attr(feature = "terse-errors", error("metadata error"))]
+    MetadataError(#[from] MetadataError),
+    #[cfg_attr(
+        not(feature = "terse-errors"),
+        error(
+            "contract is paused{}",
+            .0.as_ref().map(|pause| format!(" ({pause})")).unwrap_or_default()
+        )
+    )]
+    #[cfg_attr(feature = "terse-errors", error("contract is paused"))]
+    Paused(Option<PauseInfo>),
+    #[error("contract is frozen")]
+    Frozen,
+}
+
+impl From<AccessError> for ContractError {
+    fn from(err: AccessError) -> Self {
+        match err {
+            AccessError::Std(err) => ContractError::StdError(err),
+            AccessError::Admin(err) => ContractError::AdminError(err),
+        }
+    }
+}
+
+impl ContractError {
+    // Stable code of the error, None for the errors of cosmwasm-std. Codes of the categories
+    // shared with the other Manifest contracts are defined in manifest-errors
+    pub fn code(&self) -> Option<u32> {
+        match self {
+            ContractError::StdError(_) => None,
+            ContractError::AdminError(err) => Some(err.code()),
+            ContractError::RateError(err) => Some(err.code()),
+            ContractError::DenomError(err) => Some(err.code()),
+            ContractError::AmountError(err) => Some(err.code()),
+            ContractError::ConvertError(err) => Some(err.code()),
+            ContractError::ConfigError(err) => Some(err.code()),
+            ContractError::MigrateError(err) => Some(err.code()),
+            ContractError::QuorumError(err) => Some(err.code()),
+            ContractError::FundsError(err) => Some(err.code()),
+            ContractError::IbcError(err) => Some(err.code()),
+            ContractError::DepositError(err) => Some(err.code()),
+            ContractError::FloatError(err) => Some(err.code()),
+            ContractError::AuctionError(err) => Some(err.code()),
+            ContractError::CommitError(err) => Some(err.code()),
+            ContractError::StreamError(err) => Some(err.code()),
+            ContractError::SignatureError(err) => Some(err.code()),
+            ContractError::MetadataError(err) => Some(err.code()),
+            ContractError::Paused(_) => Some(18001),
+            ContractError::Frozen => Some(18002),
+        }
+    }
+
+    // One error of every kind the contract can fail with, generated from the definitions
+    // above by the build script
+    pub(crate) fn catalog() -> Vec<ContractError> {
+        include!(concat!(env!("OUT_DIR"), "/error_catalog.rs"))
+    }
 }
 
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum RateError {
     #[error("rate is zero")]
     InvalidRateZero,
@@ -33,56 +130,527 @@ pub enum RateError {
     ApplyOverflowError,
     #[error("resulting amount is zero")]
     ApplyZeroError,
+    #[error("tiers must be non-empty, with increasing non-zero minimum amounts")]
+    InvalidTiers,
+    #[error("schedule must be non-empty, with increasing start times")]
+    InvalidSchedule,
+    #[error("curve volume must be greater than zero")]
+    InvalidCurve,
 }
 
-#[derive(Error, Debug)]
-pub enum AmountError {
-    #[error("amount is zero")]
-    AmountIsZero,
-    #[error("amount exceeds maximum")]
-    AmountExceedsMax,
-    #[error("failed to parse amount")]
-    InvalidAmountParsing,
-    #[error("non-payable function called with funds")]
-    NonPayable,
+impl ErrorCode for RateError {
+    fn code(&self) -> u32 {
+        match self {
+            RateError::InvalidRateZero => 5001,
+            RateError::InvalidRateParsing => 5002,
+            RateError::ApplyOverflowError => 5003,
+            RateError::ApplyZeroError => 5004,
+            RateError::InvalidTiers => 5005,
+            RateError::InvalidSchedule => 5006,
+            RateError::InvalidCurve => 5007,
+        }
+    }
 }
 
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum ConvertError {
     #[error("invalid funds sent")]
     InvalidFunds,
     #[error("invalid source denom")]
     InvalidSourceDenom,
+    #[error("callback target is not a contract")]
+    CallbackNotContract,
+    #[error("authz usage is disabled")]
+    AuthzDisabled,
+    #[error("sender is not allowed to convert")]
+    SenderNotAllowed,
+    #[error("sender is not verified by the attestation contract")]
+    SenderNotVerified,
+    #[error("unknown reply id")]
+    UnknownReply,
+    #[error("no refund pending for sender")]
+    NoRefund,
+    #[error("funds contain a zero amount coin")]
+    ZeroAmount,
+    #[error("fee coin does not match the flat fee")]
+    InvalidFee,
+    #[error(
+        "recipients must be unique, with non-zero weights, and at most {}",
+        crate::consts::MAX_RECIPIENTS
+    )]
+    InvalidRecipients,
+    #[error("recipients cannot be combined with a callback")]
+    RecipientsWithCallback,
+    #[error("minted amount is too small to split among recipients")]
+    ShareIsZero,
+    #[error("memo must be at most {} bytes", crate::consts::MAX_MEMO_LEN)]
+    InvalidMemo,
+    #[error("unknown amount unit \"{0}\", expected a configured denom or symbol")]
+    UnknownUnit(String),
+    #[error("report range must have from_height at most to_height")]
+    InvalidReportRange,
+    #[error("fee exceeds the converted amount")]
+    FeeExceedsAmount,
+    #[error("minted amount is below min_receive")]
+    SlippageExceeded,
+    #[error("amount is below the minimum conversion amount")]
+    BelowMinAmount,
+    #[error("amount is above the maximum conversion amount")]
+    AboveMaxAmount,
+    #[error("conversion exceeds the remaining daily quota")]
+    QuotaExceeded,
+    #[error("conversion exceeds the remaining epoch cap")]
+    EpochCapExceeded,
+}
+
+impl ErrorCode for ConvertError {
+    fn code(&self) -> u32 {
+        match self {
+            ConvertError::InvalidFunds => 6001,
+            ConvertError::InvalidSourceDenom => 6002,
+            ConvertError::CallbackNotContract => 6003,
+            ConvertError::AuthzDisabled => 6004,
+            ConvertError::SenderNotAllowed => 6005,
+            ConvertError::SenderNotVerified => 6006,
+            ConvertError::UnknownReply => 6007,
+            ConvertError::NoRefund => 6008,
+            ConvertError::ZeroAmount => 6009,
+            ConvertError::InvalidFee => 6010,
+            ConvertError::InvalidRecipients => 6011,
+            ConvertError::RecipientsWithCallback => 6012,
+            ConvertError::ShareIsZero => 6013,
+            ConvertError::InvalidMemo => 6014,
+            ConvertError::UnknownUnit(_) => 6015,
+            ConvertError::InvalidReportRange => 6016,
+            ConvertError::FeeExceedsAmount => 6017,
+            ConvertError::SlippageExceeded => 6018,
+            ConvertError::BelowMinAmount => 6019,
+            ConvertError::AboveMaxAmount => 6020,
+            ConvertError::QuotaExceeded => 6021,
+            ConvertError::EpochCapExceeded => 6022,
+        }
+    }
 }
 
 #[derive(Error, Debug)]
-pub enum DenomError {
-    #[error("denom is empty")]
-    EmptyDenom,
-    #[error("invalid ibc denom format")]
-    InvalidIbcDenomFormat,
-    #[error("invalid factory denom format")]
-    InvalidFactoryDenomFormat,
-    #[error("invalid denom format")]
-    InvalidDenomFormat,
+#[non_exhaustive]
+pub enum ConfigError {
+    #[error("source and target denom cannot be the same")]
+    SameDenom,
+    #[error("denom exponent exceeds maximum")]
+    InvalidExponent,
+    #[error("source and target exponents must be set together")]
+    IncompleteExponents,
+    #[error("epoch length must be greater than zero")]
+    InvalidEpochLength,
+    #[error("poa admin is not a group policy")]
+    NotGroupPolicy,
+    #[error("group decision policy is not a threshold policy")]
+    UnsupportedDecisionPolicy,
+    #[error("group decision policy threshold is below minimum")]
+    GroupThresholdTooLow,
+    #[error("invalid bech32 prefix")]
+    InvalidBech32Prefix,
+    #[error("address does not use the configured bech32 prefix")]
+    AddressPrefixMismatch,
+    #[error("flat fee must be non-zero and not in the source denom")]
+    InvalidFlatFee,
+    #[error("rate floor cannot exceed rate ceiling")]
+    InvalidRateBounds,
+    #[error("rate is outside the configured bounds")]
+    RateOutOfBounds,
+    #[error("rate bounds can only be changed through a quorum action")]
+    RateBoundsImmutable,
+    #[error("bech32 prefix cannot be changed")]
+    Bech32PrefixImmutable,
+    #[error("update does not change the config")]
+    NoopUpdate,
+    #[error("notify target is not a contract")]
+    NotifyTargetNotContract,
+    #[error("verifier is not a contract")]
+    VerifierNotContract,
+    #[error("rate oracle is not a contract")]
+    OracleNotContract,
+    #[error("name resolver is not a contract")]
+    ResolverNotContract,
+    #[error("name is not registered with the resolver")]
+    UnresolvedName,
+    #[error("batch must hold between 1 and 16 operations")]
+    InvalidBatch,
+    #[error("poa admin cannot be the contract itself, it must grant authz to the contract")]
+    PoaAdminIsGrantee,
+    #[error(
+        "contract address does not use the configured bech32 prefix, it cannot be an authz grantee"
+    )]
+    GranteePrefixMismatch,
+    #[error("delegation must list known config fields, without duplicates")]
+    InvalidDelegation,
+    #[error("config field {0} is not delegated to sender")]
+    FieldNotDelegated(String),
+    #[error("rate change exceeds the delegated limit")]
+    RateChangeExceedsLimit,
+    #[error(
+        "pause message must be at most {} bytes",
+        crate::consts::MAX_PAUSE_MESSAGE_LEN
+    )]
+    InvalidPauseMessage,
+    #[error("fee percentages must be at most 100, flat fees non-zero and tiers increasing")]
+    InvalidFeeModel,
+    #[error("flat fee cannot be combined with a fee model")]
+    ConflictingFees,
+    #[error("successor is not a contract")]
+    SuccessorNotContract,
+    #[error("no successor contract is set")]
+    NoSuccessor,
+    #[error("max conversion amount must be non-zero and at least the min conversion amount")]
+    InvalidConvertLimits,
+    #[error("daily quota must be non-zero")]
+    InvalidDailyQuota,
+    #[error("epoch cap must be non-zero")]
+    InvalidEpochCap,
+    #[error("daily quotas are not supported by this build")]
+    QuotasDisabled,
+}
+
+impl ErrorCode for ConfigError {
+    fn code(&self) -> u32 {
+        match self {
+            ConfigError::SameDenom => 7001,
+            ConfigError::InvalidExponent => 7002,
+            ConfigError::IncompleteExponents => 7003,
+            ConfigError::InvalidEpochLength => 7004,
+            ConfigError::NotGroupPolicy => 7005,
+            ConfigError::UnsupportedDecisionPolicy => 7006,
+            ConfigError::GroupThresholdTooLow => 7007,
+            ConfigError::InvalidBech32Prefix => 7008,
+            ConfigError::AddressPrefixMismatch => 7009,
+            ConfigError::InvalidFlatFee => 7010,
+            ConfigError::InvalidRateBounds => 7011,
+            ConfigError::RateOutOfBounds => 7012,
+            ConfigError::RateBoundsImmutable => 7013,
+            ConfigError::Bech32PrefixImmutable => 7014,
+            ConfigError::NoopUpdate => 7015,
+            ConfigError::NotifyTargetNotContract => 7016,
+            ConfigError::VerifierNotContract => 7017,
+            ConfigError::OracleNotContract => 7018,
+            ConfigError::ResolverNotContract => 7019,
+            ConfigError::UnresolvedName => 7020,
+            ConfigError::InvalidBatch => 7021,
+            ConfigError::PoaAdminIsGrantee => 7022,
+            ConfigError::GranteePrefixMismatch => 7023,
+            ConfigError::InvalidDelegation => 7024,
+            ConfigError::FieldNotDelegated(_) => 7025,
+            ConfigError::RateChangeExceedsLimit => 7026,
+            ConfigError::InvalidPauseMessage => 7027,
+            ConfigError::InvalidFeeModel => 7028,
+            ConfigError::ConflictingFees => 7029,
+            ConfigError::SuccessorNotContract => 7030,
+            ConfigError::NoSuccessor => 7031,
+            ConfigError::InvalidConvertLimits => 7032,
+            ConfigError::InvalidDailyQuota => 7033,
+            ConfigError::InvalidEpochCap => 7034,
+            ConfigError::QuotasDisabled => 7035,
+        }
+    }
 }
 
 #[derive(Error, Debug)]
-pub enum AdminError {
-    #[error("only admin can perform this action")]
-    NotAdmin,
-    #[error("cannot renounce admin role")]
-    CannotRenounce,
+#[non_exhaustive]
+pub enum QuorumError {
+    #[error("quorum must have at least one member")]
+    NoMembers,
+    #[error("quorum members must be unique")]
+    DuplicateMember,
+    #[error("threshold must be between 1 and the number of members")]
+    InvalidThreshold,
+    #[error("quorum is already configured")]
+    AlreadyConfigured,
+    #[error("quorum is not configured")]
+    NotConfigured,
+    #[error("action requires quorum approval")]
+    ApprovalRequired,
+    #[error("only quorum members can perform this action")]
+    NotMember,
+    #[error("action already approved by sender")]
+    AlreadyApproved,
+    #[error("pending action not found")]
+    ActionNotFound,
+    #[error("not enough approvals")]
+    NotEnoughApprovals,
+    #[error("rate bound changes need the approval of a member other than the admin")]
+    AdminOnlyApproval,
+}
+
+impl ErrorCode for QuorumError {
+    fn code(&self) -> u32 {
+        match self {
+            QuorumError::NoMembers => 8001,
+            QuorumError::DuplicateMember => 8002,
+            QuorumError::InvalidThreshold => 8003,
+            QuorumError::AlreadyConfigured => 8004,
+            QuorumError::NotConfigured => 8005,
+            QuorumError::ApprovalRequired => 8006,
+            QuorumError::NotMember => 8007,
+            QuorumError::AlreadyApproved => 8008,
+            QuorumError::ActionNotFound => 8009,
+            QuorumError::NotEnoughApprovals => 8010,
+            QuorumError::AdminOnlyApproval => 8011,
+        }
+    }
 }
 
 #[derive(Error, Debug)]
-pub enum ConfigError {
-    #[error("source and target denom cannot be the same")]
-    SameDenom,
+#[non_exhaustive]
+pub enum FundsError {
+    #[error("amount exceeds unrecorded balance")]
+    ExceedsUnrecorded,
+    #[error("no unsolicited funds recorded for depositor")]
+    NothingToReturn,
+    #[error("no residual target tokens to burn")]
+    NothingToBurn,
+    #[error("rounding reserve is below one base unit")]
+    NothingToMint,
+    #[error("funding must be non-zero coins with unique denoms")]
+    InvalidFunding,
+    #[error("funds do not match the declared funding")]
+    FundingMismatch,
+    #[error("sender has no position to migrate")]
+    NoPosition,
+}
+
+impl ErrorCode for FundsError {
+    fn code(&self) -> u32 {
+        match self {
+            FundsError::ExceedsUnrecorded => 9001,
+            FundsError::NothingToReturn => 9002,
+            FundsError::NothingToBurn => 9003,
+            FundsError::NothingToMint => 9004,
+            FundsError::InvalidFunding => 9005,
+            FundsError::FundingMismatch => 9006,
+            FundsError::NoPosition => 9007,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum IbcError {
+    #[error("invalid channel version")]
+    InvalidVersion,
+    #[error("channel must be unordered")]
+    InvalidOrder,
+    #[error("unknown channel")]
+    UnknownChannel,
+    #[error("channel cannot be closed")]
+    CloseNotAllowed,
+    #[error("insufficient channel escrow")]
+    InsufficientEscrow,
+    #[error("denom cannot be transferred")]
+    InvalidDenom,
+    #[error("invalid packet data")]
+    InvalidPacket,
+}
+
+impl ErrorCode for IbcError {
+    fn code(&self) -> u32 {
+        match self {
+            IbcError::InvalidVersion => 10001,
+            IbcError::InvalidOrder => 10002,
+            IbcError::UnknownChannel => 10003,
+            IbcError::CloseNotAllowed => 10004,
+            IbcError::InsufficientEscrow => 10005,
+            IbcError::InvalidDenom => 10006,
+            IbcError::InvalidPacket => 10007,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum SignatureError {
+    #[error("invalid secp256k1 public key")]
+    InvalidPubkey,
+    #[error("public key does not match the deposit")]
+    PubkeyMismatch,
+    #[error("invalid signature")]
+    InvalidSignature,
+    #[error("signature deadline has passed")]
+    Expired,
+    #[error("nonce does not match the deposit")]
+    InvalidNonce,
+}
+
+impl ErrorCode for SignatureError {
+    fn code(&self) -> u32 {
+        match self {
+            SignatureError::InvalidPubkey => 11001,
+            SignatureError::PubkeyMismatch => 11002,
+            SignatureError::InvalidSignature => 11003,
+            SignatureError::Expired => 11004,
+            SignatureError::InvalidNonce => 11005,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum MetadataError {
+    #[error(
+        "label must be non-empty and at most {} bytes",
+        crate::consts::MAX_LABEL_LEN
+    )]
+    Label,
+    #[error(
+        "description must be at most {} bytes",
+        crate::consts::MAX_DESCRIPTION_LEN
+    )]
+    Description,
+    #[error(
+        "url must be an http(s) url of at most {} bytes",
+        crate::consts::MAX_URL_LEN
+    )]
+    Url,
+}
+
+impl ErrorCode for MetadataError {
+    fn code(&self) -> u32 {
+        match self {
+            MetadataError::Label => 12001,
+            MetadataError::Description => 12002,
+            MetadataError::Url => 12003,
+        }
+    }
 }
 
 #[derive(Error, Debug)]
-pub enum MigrateError {
-    #[error("invalid contract name")]
-    InvalidContractName,
+#[non_exhaustive]
+pub enum DepositError {
+    #[error("no deposit for address")]
+    NoDeposit,
+    #[error("deposit is insufficient")]
+    InsufficientDeposit,
+    #[error("deposit is in another denom, withdraw it first")]
+    DenomMismatch,
+}
+
+impl ErrorCode for DepositError {
+    fn code(&self) -> u32 {
+        match self {
+            DepositError::NoDeposit => 13001,
+            DepositError::InsufficientDeposit => 13002,
+            DepositError::DenomMismatch => 13003,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum FloatError {
+    #[error("float must be funded with the target denom")]
+    InvalidFunds,
+    #[error("float is insufficient")]
+    InsufficientFloat,
+}
+
+impl ErrorCode for FloatError {
+    fn code(&self) -> u32 {
+        match self {
+            FloatError::InvalidFunds => 14001,
+            FloatError::InsufficientFloat => 14002,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum StreamError {
+    #[error("duration must be greater than zero")]
+    InvalidDuration,
+    #[error("stream not found")]
+    NotFound,
+    #[error("only the stream owner can perform this action")]
+    NotOwner,
+    #[error("nothing to claim yet")]
+    NothingToClaim,
+}
+
+impl ErrorCode for StreamError {
+    fn code(&self) -> u32 {
+        match self {
+            StreamError::InvalidDuration => 15001,
+            StreamError::NotFound => 15002,
+            StreamError::NotOwner => 15003,
+            StreamError::NothingToClaim => 15004,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum AuctionError {
+    #[error("invalid auction parameters")]
+    InvalidAuction,
+    #[error("auction mode is not enabled")]
+    NotConfigured,
+    #[error("conversions are batched in auction rounds")]
+    BatchMode,
+    #[error("round is still open")]
+    RoundOpen,
+    #[error("round has ended and must be settled")]
+    RoundEnded,
+    #[error("current round has commitments")]
+    PendingCommitments,
+    #[error("round is not settled")]
+    NotSettled,
+    #[error("no commitment for sender in round")]
+    NoCommitment,
+}
+
+impl ErrorCode for AuctionError {
+    fn code(&self) -> u32 {
+        match self {
+            AuctionError::InvalidAuction => 16001,
+            AuctionError::NotConfigured => 16002,
+            AuctionError::BatchMode => 16003,
+            AuctionError::RoundOpen => 16004,
+            AuctionError::RoundEnded => 16005,
+            AuctionError::PendingCommitments => 16006,
+            AuctionError::NotSettled => 16007,
+            AuctionError::NoCommitment => 16008,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum CommitError {
+    #[error("hash must be a 32 bytes SHA-256 digest")]
+    InvalidHash,
+    #[error("sender already has a pending commitment")]
+    AlreadyCommitted,
+    #[error("no pending commitment for sender")]
+    NotFound,
+    #[error("commitment can only be revealed in a later block")]
+    SameBlock,
+    #[error("commitment has expired")]
+    Expired,
+    #[error("revealed values do not match the commitment")]
+    HashMismatch,
+    #[error("amount exceeds the committed funds")]
+    ExceedsEscrow,
+}
+
+impl ErrorCode for CommitError {
+    fn code(&self) -> u32 {
+        match self {
+            CommitError::InvalidHash => 17001,
+            CommitError::AlreadyCommitted => 17002,
+            CommitError::NotFound => 17003,
+            CommitError::SameBlock => 17004,
+            CommitError::Expired => 17005,
+            CommitError::HashMismatch => 17006,
+            CommitError::ExceedsEscrow => 17007,
+        }
+    }
 }
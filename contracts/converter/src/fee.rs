@@ -0,0 +1,283 @@
+use crate::error::ConfigError::InvalidFeeModel;
+use crate::error::ContractError;
+use crate::error::RateError::ApplyOverflowError;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal256, Uint256};
+
+// Fee withheld from the converted source tokens and sent to the POA admin, or the community
+// pool with `fees_to_community_pool`, e.g.
+// `{"percent_plus_flat": {"percent": "0.5", "amount": "100"}}`. Percentages are in percent,
+// "0.5" taking 0.5% of the amount, and amounts in source base units
+#[cw_serde]
+#[non_exhaustive]
+pub enum FeeModel {
+    // No fee, as when no model is set
+    None {},
+    Percent {
+        percent: Decimal256,
+    },
+    Flat {
+        amount: Uint256,
+    },
+    PercentPlusFlat {
+        percent: Decimal256,
+        amount: Uint256,
+    },
+    // Percentage by conversion size. The tier with the highest minimum amount not above the
+    // converted amount applies, smaller conversions pay no fee
+    Tiered {
+        tiers: Vec<FeeTier>,
+    },
+}
+
+#[cw_serde]
+pub struct FeeTier {
+    pub min_amount: Uint256,
+    pub percent: Decimal256,
+}
+
+impl FeeModel {
+    // Percentages are at most 100, flat amounts non-zero, and tiers non-empty with increasing
+    // minimum amounts
+    pub fn validate(&self) -> Result<(), ContractError> {
+        let valid = match self {
+            FeeModel::None {} => true,
+            FeeModel::Percent { percent } => valid_percent(percent),
+            FeeModel::Flat { amount } => !amount.is_zero(),
+            FeeModel::PercentPlusFlat { percent, amount } => {
+                valid_percent(percent) && !amount.is_zero()
+            }
+            FeeModel::Tiered { tiers } => {
+                let increasing = tiers.windows(2).all(|w| w[0].min_amount < w[1].min_amount);
+                !tiers.is_empty()
+                    && increasing
+                    && tiers.iter().all(|tier| valid_percent(&tier.percent))
+            }
+        };
+        if !valid {
+            return Err(ContractError::ConfigError(InvalidFeeModel));
+        }
+        Ok(())
+    }
+
+    // Fee of a conversion of `amount` source base units, rounded down. Flat fees can exceed
+    // the amount, which conversions reject
+    pub fn compute_fee(&self, amount: Uint256) -> Result<Uint256, ContractError> {
+        match self {
+            FeeModel::None {} => Ok(Uint256::zero()),
+            FeeModel::Percent { percent } => percent_of(amount, percent),
+            FeeModel::Flat { amount: flat } => Ok(*flat),
+            FeeModel::PercentPlusFlat {
+                percent,
+                amount: flat,
+            } => percent_of(amount, percent)?
+                .checked_add(*flat)
+                .map_err(|_| ContractError::RateError(ApplyOverflowError)),
+            FeeModel::Tiered { tiers } => {
+                match tiers.iter().rev().find(|tier| tier.min_amount <= amount) {
+                    Some(tier) => percent_of(amount, &tier.percent),
+                    None => Ok(Uint256::zero()),
+                }
+            }
+        }
+    }
+}
+
+fn valid_percent(percent: &Decimal256) -> bool {
+    *percent <= Decimal256::percent(10_000)
+}
+
+fn percent_of(amount: Uint256, percent: &Decimal256) -> Result<Uint256, ContractError> {
+    let fraction = percent
+        .checked_mul(Decimal256::percent(1))
+        .map_err(|_| ContractError::RateError(ApplyOverflowError))?;
+    amount
+        .checked_mul_floor(fraction)
+        .map_err(|_| ContractError::RateError(ApplyOverflowError))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ConfigError;
+    use std::str::FromStr;
+
+    fn dec(value: &str) -> Decimal256 {
+        Decimal256::from_str(value).unwrap()
+    }
+
+    fn tiered() -> FeeModel {
+        FeeModel::Tiered {
+            tiers: vec![
+                FeeTier {
+                    min_amount: Uint256::from(1_000u32),
+                    percent: dec("2"),
+                },
+                FeeTier {
+                    min_amount: Uint256::from(10_000u32),
+                    percent: dec("1"),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn compute_fee() {
+        let cases = [
+            (FeeModel::None {}, 1_000u32, 0u32),
+            (FeeModel::None {}, 0, 0),
+            (FeeModel::Percent { percent: dec("1") }, 1_000, 10),
+            (
+                FeeModel::Percent {
+                    percent: dec("0.5"),
+                },
+                1_000,
+                5,
+            ),
+            // Rounded down
+            (
+                FeeModel::Percent {
+                    percent: dec("0.5"),
+                },
+                199,
+                0,
+            ),
+            (
+                FeeModel::Percent {
+                    percent: dec("33.3"),
+                },
+                1_000,
+                333,
+            ),
+            (
+                FeeModel::Percent {
+                    percent: dec("100"),
+                },
+                1_000,
+                1_000,
+            ),
+            (FeeModel::Percent { percent: dec("0") }, 1_000, 0),
+            (
+                FeeModel::Flat {
+                    amount: 25u32.into(),
+                },
+                1_000,
+                25,
+            ),
+            // Exceeding the amount, rejected by conversions
+            (
+                FeeModel::Flat {
+                    amount: 25u32.into(),
+                },
+                10,
+                25,
+            ),
+            (
+                FeeModel::PercentPlusFlat {
+                    percent: dec("1"),
+                    amount: 25u32.into(),
+                },
+                1_000,
+                35,
+            ),
+            (
+                FeeModel::PercentPlusFlat {
+                    percent: dec("1"),
+                    amount: 25u32.into(),
+                },
+                0,
+                25,
+            ),
+            // Below the first tier
+            (tiered(), 999, 0),
+            (tiered(), 1_000, 20),
+            (tiered(), 9_999, 199),
+            (tiered(), 10_000, 100),
+            (tiered(), 50_000, 500),
+        ];
+        for (model, amount, expected) in cases {
+            assert_eq!(
+                model.compute_fee(Uint256::from(amount)).unwrap(),
+                Uint256::from(expected),
+                "{model:?} on {amount}"
+            );
+        }
+    }
+
+    #[test]
+    fn compute_fee_overflow() {
+        let model = FeeModel::PercentPlusFlat {
+            percent: dec("100"),
+            amount: Uint256::MAX,
+        };
+        assert!(matches!(
+            model.compute_fee(Uint256::one()).unwrap_err(),
+            ContractError::RateError(ApplyOverflowError)
+        ));
+    }
+
+    #[test]
+    fn validate() {
+        let tier = |min_amount: u32, percent: &str| FeeTier {
+            min_amount: min_amount.into(),
+            percent: dec(percent),
+        };
+        let valid = [
+            FeeModel::None {},
+            FeeModel::Percent { percent: dec("0") },
+            FeeModel::Percent {
+                percent: dec("100"),
+            },
+            FeeModel::Flat {
+                amount: 1u32.into(),
+            },
+            FeeModel::PercentPlusFlat {
+                percent: dec("1"),
+                amount: 1u32.into(),
+            },
+            tiered(),
+            FeeModel::Tiered {
+                tiers: vec![tier(0, "1")],
+            },
+        ];
+        for model in valid {
+            assert!(model.validate().is_ok(), "{model:?}");
+        }
+
+        let invalid = [
+            FeeModel::Percent {
+                percent: dec("100.1"),
+            },
+            FeeModel::Flat {
+                amount: Uint256::zero(),
+            },
+            FeeModel::PercentPlusFlat {
+                percent: dec("101"),
+                amount: 1u32.into(),
+            },
+            FeeModel::PercentPlusFlat {
+                percent: dec("1"),
+                amount: Uint256::zero(),
+            },
+            FeeModel::Tiered { tiers: vec![] },
+            FeeModel::Tiered {
+                tiers: vec![tier(10, "1"), tier(10, "2")],
+            },
+            FeeModel::Tiered {
+                tiers: vec![tier(10, "1"), tier(5, "2")],
+            },
+            FeeModel::Tiered {
+                tiers: vec![tier(10, "101")],
+            },
+        ];
+        for model in invalid {
+            assert!(
+                matches!(
+                    model.validate().unwrap_err(),
+                    ContractError::ConfigError(ConfigError::InvalidFeeModel)
+                ),
+                "{model:?}"
+            );
+        }
+    }
+}
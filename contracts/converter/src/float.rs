@@ -0,0 +1,160 @@
+use crate::error::ContractError;
+use crate::error::FloatError::InsufficientFloat;
+use crate::state::{Config, ESCROWED, FLOATS};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Coin, StdError, StdResult, Storage, Uint256};
+
+// Target tokens held by the contract to pay conversions out instantly instead of minting them,
+// e.g. for the reverse direction when minting is slower. Funded and withdrawn by the admin
+#[cw_serde]
+#[derive(Default)]
+pub struct Float {
+    pub balance: Uint256,
+    // Paid out and not replenished by a later funding yet
+    pub to_replenish: Uint256,
+    // Lifetime totals
+    pub funded: Uint256,
+    pub paid_out: Uint256,
+    pub withdrawn: Uint256,
+}
+
+// Whether a conversion minting `minted` is paid out of the float, which must cover it whole.
+// Otherwise it is minted as usual
+pub(crate) fn pays_from_float(
+    storage: &dyn Storage,
+    config: &Config,
+    minted: &Coin,
+) -> StdResult<bool> {
+    if !config.float_payouts.unwrap_or_default() {
+        return Ok(false);
+    }
+    let float = FLOATS.may_load(storage, &minted.denom)?.unwrap_or_default();
+    Ok(float.balance >= minted.amount)
+}
+
+pub(crate) fn fund_float(storage: &mut dyn Storage, coin: &Coin) -> Result<Float, ContractError> {
+    update_float(storage, &coin.denom, |float| {
+        float.balance = float
+            .balance
+            .checked_add(coin.amount)
+            .map_err(StdError::from)?;
+        float.funded = float
+            .funded
+            .checked_add(coin.amount)
+            .map_err(StdError::from)?;
+        float.to_replenish = float.to_replenish.saturating_sub(coin.amount);
+        Ok(())
+    })
+}
+
+// Take a payout out of the float, before the conversion paying it executes
+pub(crate) fn pay_from_float(
+    storage: &mut dyn Storage,
+    coin: &Coin,
+) -> Result<Float, ContractError> {
+    update_float(storage, &coin.denom, |float| {
+        float.balance = float
+            .balance
+            .checked_sub(coin.amount)
+            .map_err(|_| ContractError::FloatError(InsufficientFloat))?;
+        float.paid_out = float
+            .paid_out
+            .checked_add(coin.amount)
+            .map_err(StdError::from)?;
+        float.to_replenish = float
+            .to_replenish
+            .checked_add(coin.amount)
+            .map_err(StdError::from)?;
+        Ok(())
+    })
+}
+
+// Return the payout of a failed conversion to the float, its tokens never left the contract
+pub(crate) fn unpay_from_float(
+    storage: &mut dyn Storage,
+    coin: &Coin,
+) -> Result<Float, ContractError> {
+    update_float(storage, &coin.denom, |float| {
+        float.balance = float
+            .balance
+            .checked_add(coin.amount)
+            .map_err(StdError::from)?;
+        float.paid_out = float.paid_out.saturating_sub(coin.amount);
+        float.to_replenish = float.to_replenish.saturating_sub(coin.amount);
+        Ok(())
+    })
+}
+
+pub(crate) fn withdraw_float(
+    storage: &mut dyn Storage,
+    coin: &Coin,
+) -> Result<Float, ContractError> {
+    update_float(storage, &coin.denom, |float| {
+        float.balance = float
+            .balance
+            .checked_sub(coin.amount)
+            .map_err(|_| ContractError::FloatError(InsufficientFloat))?;
+        float.withdrawn = float
+            .withdrawn
+            .checked_add(coin.amount)
+            .map_err(StdError::from)?;
+        Ok(())
+    })
+}
+
+// The float is held alongside the other escrows, so it is never swept as residual tokens
+fn update_float(
+    storage: &mut dyn Storage,
+    denom: &str,
+    update: impl FnOnce(&mut Float) -> Result<(), ContractError>,
+) -> Result<Float, ContractError> {
+    let mut float = FLOATS.may_load(storage, denom)?.unwrap_or_default();
+    let before = float.balance;
+    update(&mut float)?;
+    FLOATS.save(storage, denom, &float)?;
+    ESCROWED.update(storage, denom, |total| -> StdResult<_> {
+        let total = total.unwrap_or_default();
+        if float.balance >= before {
+            total
+                .checked_add(float.balance - before)
+                .map_err(StdError::from)
+        } else {
+            Ok(total.saturating_sub(before - float.balance))
+        }
+    })?;
+    Ok(float)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::coin;
+    use cosmwasm_std::testing::MockStorage;
+
+    #[test]
+    fn test_float_accounting() {
+        let mut storage = MockStorage::new();
+        fund_float(&mut storage, &coin(1_000, "umfx")).unwrap();
+        pay_from_float(&mut storage, &coin(300, "umfx")).unwrap();
+        let float = pay_from_float(&mut storage, &coin(200, "umfx")).unwrap();
+        assert_eq!(float.balance, Uint256::from(500u32));
+        assert_eq!(float.to_replenish, Uint256::from(500u32));
+
+        // A failed payout is returned, a withdrawal is not due for replenishing
+        unpay_from_float(&mut storage, &coin(200, "umfx")).unwrap();
+        let float = withdraw_float(&mut storage, &coin(100, "umfx")).unwrap();
+        assert_eq!(float.paid_out, Uint256::from(300u32));
+        assert_eq!(float.to_replenish, Uint256::from(300u32));
+
+        // Funding replenishes the payouts first
+        let float = fund_float(&mut storage, &coin(200, "umfx")).unwrap();
+        assert_eq!(float.to_replenish, Uint256::from(100u32));
+        assert_eq!(
+            ESCROWED.load(&storage, "umfx").unwrap(),
+            Uint256::from(800u32)
+        );
+
+        assert!(pay_from_float(&mut storage, &coin(801, "umfx")).is_err());
+        assert!(withdraw_float(&mut storage, &coin(1, "other")).is_err());
+    }
+}
@@ -0,0 +1,68 @@
+use cosmwasm_std::Decimal256;
+use manifest_std::cosmos::group::v1::{GroupPolicyInfo, ThresholdDecisionPolicy};
+use prost::Message;
+use std::str::FromStr;
+
+// Threshold of a threshold decision policy. Percentage policies have no absolute threshold
+pub fn threshold(info: &GroupPolicyInfo) -> Option<Decimal256> {
+    let policy = info.decision_policy.as_ref()?;
+    if policy.type_url != ThresholdDecisionPolicy::TYPE_URL {
+        return None;
+    }
+    let policy = ThresholdDecisionPolicy::decode(policy.value.as_slice()).ok()?;
+    Decimal256::from_str(&policy.threshold).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use manifest_std::cosmos::group::v1::PercentageDecisionPolicy;
+    use manifest_std::google::protobuf::Any;
+
+    fn info_with_policy(policy: Option<Any>) -> GroupPolicyInfo {
+        GroupPolicyInfo {
+            decision_policy: policy,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn threshold_policy() {
+        let policy = ThresholdDecisionPolicy {
+            threshold: "2".to_string(),
+            windows: None,
+        };
+        let info = info_with_policy(Some(Any {
+            type_url: ThresholdDecisionPolicy::TYPE_URL.to_string(),
+            value: policy.encode_to_vec(),
+        }));
+        assert_eq!(threshold(&info), Some(Decimal256::from_str("2").unwrap()));
+    }
+
+    #[test]
+    fn percentage_policy() {
+        let policy = PercentageDecisionPolicy {
+            percentage: "0.5".to_string(),
+            windows: None,
+        };
+        let info = info_with_policy(Some(Any {
+            type_url: PercentageDecisionPolicy::TYPE_URL.to_string(),
+            value: policy.encode_to_vec(),
+        }));
+        assert_eq!(threshold(&info), None);
+    }
+
+    #[test]
+    fn missing_policy() {
+        assert_eq!(threshold(&info_with_policy(None)), None);
+    }
+
+    #[test]
+    fn malformed_policy() {
+        let info = info_with_policy(Some(Any {
+            type_url: ThresholdDecisionPolicy::TYPE_URL.to_string(),
+            value: vec![0xff],
+        }));
+        assert_eq!(threshold(&info), None);
+    }
+}
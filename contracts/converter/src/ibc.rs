@@ -0,0 +1,355 @@
+use crate::consts::{CONTRACT_NAME, CONTRACT_VERSION, IBC_VERSION, REPLY_REMOTE_CONVERT_ID};
+use crate::contract::{
+    check_convert_amount, check_epoch_cap, conversion_msg, notify, record_conversion,
+};
+use crate::denom::canonicalize;
+use crate::error::ContractError;
+use crate::error::ConvertError::AuthzDisabled;
+use crate::error::IbcError::{
+    CloseNotAllowed, InsufficientEscrow, InvalidDenom, InvalidOrder, InvalidPacket, InvalidVersion,
+    UnknownChannel,
+};
+use crate::lifecycle::{Lifecycle, Operation};
+use crate::msg::{Alert, ConvertAck, ConverterPacket};
+#[cfg(feature = "quotas")]
+use crate::quota;
+use crate::state::{
+    load_config, Mint, RemoteConversion, AUTHZ_ENABLED, CHANNEL_ESCROW, ESCROWED, IBC_CHANNELS,
+};
+use crate::strategy::resolve_rate;
+use cosmwasm_std::{
+    from_json, to_json_binary, BankMsg, Coin, DepsMut, Env, Ibc3ChannelOpenResponse,
+    IbcBasicResponse, IbcChannelCloseMsg, IbcChannelConnectMsg, IbcChannelOpenMsg,
+    IbcChannelOpenResponse, IbcOrder, IbcPacket, IbcPacketAckMsg, IbcPacketReceiveMsg,
+    IbcPacketTimeoutMsg, IbcReceiveResponse, Never, Response, StdAck, StdError, Storage, SubMsg,
+    SubMsgResult, Uint256,
+};
+
+// Channels of the converter application are unordered and must agree on its version on
+// both ends
+pub fn channel_open(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelOpenMsg,
+) -> Result<IbcChannelOpenResponse, ContractError> {
+    let channel = msg.channel();
+    if channel.order != IbcOrder::Unordered {
+        return Err(ContractError::IbcError(InvalidOrder));
+    }
+    if channel.version != IBC_VERSION {
+        return Err(ContractError::IbcError(InvalidVersion));
+    }
+    if let Some(version) = msg.counterparty_version() {
+        if version != IBC_VERSION {
+            return Err(ContractError::IbcError(InvalidVersion));
+        }
+    }
+
+    Ok(Some(Ibc3ChannelOpenResponse {
+        version: IBC_VERSION.to_string(),
+    }))
+}
+
+pub fn channel_connect(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelConnectMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let channel = msg.channel();
+    IBC_CHANNELS.save(deps.storage, &channel.endpoint.channel_id, channel)?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "channel_connect")
+        .add_attribute("contract", CONTRACT_NAME)
+        .add_attribute("version", CONTRACT_VERSION)
+        .add_attribute("channel_id", channel.endpoint.channel_id.as_str()))
+}
+
+// Closing a channel would strand the tokens escrowed for it, so the contract never initiates
+// it. A close forced by the counterparty is recorded, the escrow stays held by the contract
+pub fn channel_close(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelCloseMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let channel = match msg {
+        IbcChannelCloseMsg::CloseInit { .. } => {
+            return Err(ContractError::IbcError(CloseNotAllowed))
+        }
+        IbcChannelCloseMsg::CloseConfirm { channel } => channel,
+    };
+    IBC_CHANNELS.remove(deps.storage, &channel.endpoint.channel_id);
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "channel_close")
+        .add_attribute("contract", CONTRACT_NAME)
+        .add_attribute("version", CONTRACT_VERSION)
+        .add_attribute("channel_id", channel.endpoint.channel_id))
+}
+
+// Failures are returned as error acknowledgements so the counterparty chain can refund the
+// sender, the state changes of a failed packet are reverted with it
+pub fn packet_receive(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketReceiveMsg,
+) -> Result<IbcReceiveResponse, Never> {
+    match receive(deps, env, msg.packet) {
+        Ok(res) => Ok(res),
+        Err(err) => Ok(IbcReceiveResponse::new(StdAck::error(err.to_string()))
+            .add_attribute("action", "receive_packet")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("error", err.to_string())),
+    }
+}
+
+fn receive(
+    deps: DepsMut,
+    env: Env,
+    packet: IbcPacket,
+) -> Result<IbcReceiveResponse, ContractError> {
+    let channel_id = packet.dest.channel_id;
+    if !IBC_CHANNELS.has(deps.storage, &channel_id) {
+        return Err(ContractError::IbcError(UnknownChannel));
+    }
+    let packet: ConverterPacket =
+        from_json(&packet.data).map_err(|_| ContractError::IbcError(InvalidPacket))?;
+    let config = load_config(deps.storage)?;
+
+    match packet {
+        // Tokens coming back from the counterparty chain are released from the escrow
+        ConverterPacket::Transfer {
+            denom,
+            amount,
+            sender,
+            receiver,
+        } => {
+            let denom = canonicalize(&denom);
+            if denom != config.source_denom.as_str() && denom != config.target_denom.as_str() {
+                return Err(ContractError::IbcError(InvalidDenom));
+            }
+            let receiver = deps.api.addr_validate(&receiver)?;
+            release(deps.storage, &channel_id, &denom, amount)?;
+
+            Ok(IbcReceiveResponse::new(StdAck::success(b"\x01"))
+                .add_message(BankMsg::Send {
+                    to_address: receiver.to_string(),
+                    amount: vec![Coin::new(amount, denom.as_str())],
+                })
+                .add_attribute("action", "receive_transfer")
+                .add_attribute("contract", CONTRACT_NAME)
+                .add_attribute("version", CONTRACT_VERSION)
+                .add_attribute("channel_id", channel_id)
+                .add_attribute("sender", sender)
+                .add_attribute("receiver", receiver)
+                .add_attribute("amount", Coin::new(amount, denom).to_string()))
+        }
+        // Source tokens escrowed for the counterparty chain are converted like a local
+        // conversion, the target tokens being minted to the contract and escrowed in turn
+        ConverterPacket::Convert {
+            amount,
+            sender,
+            receiver,
+        } => {
+            Lifecycle::load(deps.storage, &config)?
+                .ensure_allows(deps.storage, Operation::Convert)?;
+            if !AUTHZ_ENABLED.may_load(deps.storage)?.unwrap_or(true) {
+                return Err(ContractError::ConvertError(AuthzDisabled));
+            }
+
+            check_convert_amount(&config, amount)?;
+            #[cfg(feature = "quotas")]
+            quota::consume(
+                deps.storage,
+                &config,
+                &quota::remote_sender(&channel_id, &sender),
+                env.block.time,
+                amount,
+            )?;
+            let rate = resolve_rate(deps.as_ref(), &env.block, &config, amount, None)?;
+            let minted = config.mint_amount(&rate, amount)?;
+            check_epoch_cap(deps.storage, env.block.time, &config, minted)?;
+            let burned = Coin::new(amount, config.source_denom.as_str());
+            let minted = Coin::new(minted, config.target_denom.as_str());
+            release(deps.storage, &channel_id, &burned.denom, burned.amount)?;
+            escrow(deps.storage, &channel_id, &minted.denom, minted.amount)?;
+
+            let contract = env.contract.address;
+            let mints = [Mint {
+                recipient: contract.clone(),
+                amount: minted.clone(),
+            }];
+            let msg = conversion_msg(&contract, &config, &burned, &mints, false);
+            let conversion = RemoteConversion {
+                channel_id: channel_id.clone(),
+                burned: burned.clone(),
+                minted: minted.clone(),
+                rate,
+            };
+
+            let ack = to_json_binary(&ConvertAck {
+                minted: minted.amount,
+            })?;
+
+            Ok(IbcReceiveResponse::new(StdAck::success(ack))
+                .add_submessage(
+                    SubMsg::reply_always(msg, REPLY_REMOTE_CONVERT_ID)
+                        .with_payload(to_json_binary(&conversion)?),
+                )
+                .add_attribute("action", "receive_convert")
+                .add_attribute("contract", CONTRACT_NAME)
+                .add_attribute("version", CONTRACT_VERSION)
+                .add_attribute("channel_id", channel_id)
+                .add_attribute("sender", sender)
+                .add_attribute("receiver", receiver)
+                .add_attribute("burned", burned.to_string())
+                .add_attribute("minted", minted.to_string()))
+        }
+    }
+}
+
+// The send, burn and mint of a convert packet either all executed or were all reverted. In
+// the latter case the escrow changes are undone and the acknowledgement replaced by an error
+pub fn reply_convert(
+    deps: DepsMut,
+    env: Env,
+    conversion: RemoteConversion,
+    result: SubMsgResult,
+) -> Result<Response, ContractError> {
+    let RemoteConversion {
+        channel_id,
+        burned,
+        minted,
+        rate,
+    } = conversion;
+
+    if let SubMsgResult::Err(err) = result {
+        escrow(deps.storage, &channel_id, &burned.denom, burned.amount)?;
+        release(deps.storage, &channel_id, &minted.denom, minted.amount)?;
+
+        let alert = Alert::RemoteConversionFailed {
+            channel_id: channel_id.clone(),
+            error: err.clone(),
+        };
+        return Ok(Response::new()
+            .set_data(StdAck::error(err.clone()))
+            .add_submessages(notify(deps.storage, alert)?)
+            .add_attribute("action", "fail_remote_conversion")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("channel_id", channel_id)
+            .add_attribute("error", err));
+    }
+
+    record_conversion(deps.storage, env.block.time, &burned, &minted, Some(&rate))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "finalize_remote_conversion")
+        .add_attribute("contract", CONTRACT_NAME)
+        .add_attribute("version", CONTRACT_VERSION)
+        .add_attribute("channel_id", channel_id)
+        .add_attribute("burned", burned.to_string())
+        .add_attribute("minted", minted.to_string()))
+}
+
+// Transfers rejected by the counterparty chain are refunded to their sender
+pub fn packet_ack(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcPacketAckMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let ack: StdAck =
+        from_json(&msg.acknowledgement.data).map_err(|_| ContractError::IbcError(InvalidPacket))?;
+    match ack {
+        StdAck::Success(_) => Ok(IbcBasicResponse::new()
+            .add_attribute("action", "packet_ack")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("channel_id", msg.original_packet.src.channel_id)
+            .add_attribute("sequence", msg.original_packet.sequence.to_string())
+            .add_attribute("success", "true")),
+        StdAck::Error(err) => Ok(refund(deps.storage, &msg.original_packet)?
+            .add_attribute("action", "packet_ack")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("channel_id", msg.original_packet.src.channel_id)
+            .add_attribute("sequence", msg.original_packet.sequence.to_string())
+            .add_attribute("success", "false")
+            .add_attribute("error", err)),
+    }
+}
+
+pub fn packet_timeout(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcPacketTimeoutMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    Ok(refund(deps.storage, &msg.packet)?
+        .add_attribute("action", "packet_timeout")
+        .add_attribute("contract", CONTRACT_NAME)
+        .add_attribute("version", CONTRACT_VERSION)
+        .add_attribute("channel_id", msg.packet.src.channel_id)
+        .add_attribute("sequence", msg.packet.sequence.to_string()))
+}
+
+// Release the escrow of an outgoing transfer back to its sender
+fn refund(
+    storage: &mut dyn Storage,
+    packet: &IbcPacket,
+) -> Result<IbcBasicResponse, ContractError> {
+    let ConverterPacket::Transfer {
+        denom,
+        amount,
+        sender,
+        ..
+    } = from_json(&packet.data).map_err(|_| ContractError::IbcError(InvalidPacket))?
+    else {
+        return Err(ContractError::IbcError(InvalidPacket));
+    };
+    release(storage, &packet.src.channel_id, &denom, amount)?;
+
+    let refund = Coin::new(amount, denom);
+    Ok(IbcBasicResponse::new()
+        .add_message(BankMsg::Send {
+            to_address: sender.clone(),
+            amount: vec![refund.clone()],
+        })
+        .add_attribute("refunded", refund.to_string())
+        .add_attribute("sender", sender))
+}
+
+// Hold tokens for the counterparty chain of a channel
+pub(crate) fn escrow(
+    storage: &mut dyn Storage,
+    channel_id: &str,
+    denom: &str,
+    amount: Uint256,
+) -> Result<(), ContractError> {
+    for total in [CHANNEL_ESCROW.key((channel_id, denom)), ESCROWED.key(denom)] {
+        let current = total.may_load(storage)?.unwrap_or_default();
+        total.save(
+            storage,
+            &current.checked_add(amount).map_err(StdError::from)?,
+        )?;
+    }
+    Ok(())
+}
+
+// Give back tokens held for the counterparty chain of a channel, which can never exceed what
+// was escrowed for it
+pub(crate) fn release(
+    storage: &mut dyn Storage,
+    channel_id: &str,
+    denom: &str,
+    amount: Uint256,
+) -> Result<(), ContractError> {
+    for total in [CHANNEL_ESCROW.key((channel_id, denom)), ESCROWED.key(denom)] {
+        let current = total.may_load(storage)?.unwrap_or_default();
+        let remaining = current
+            .checked_sub(amount)
+            .map_err(|_| ContractError::IbcError(InsufficientEscrow))?;
+        total.save(storage, &remaining)?;
+    }
+    Ok(())
+}
@@ -1,16 +1,65 @@
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, SudoMsg};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    entry_point, Binary, Deps, DepsMut, Env, MessageInfo, MigrateInfo, Response, StdResult,
+    Binary, Deps, DepsMut, Env, MessageInfo, MigrateInfo, Reply, Response, StdResult,
+};
+#[cfg(feature = "ibc")]
+use cosmwasm_std::{
+    IbcBasicResponse, IbcChannelCloseMsg, IbcChannelConnectMsg, IbcChannelOpenMsg,
+    IbcChannelOpenResponse, IbcPacketAckMsg, IbcPacketReceiveMsg, IbcPacketTimeoutMsg,
+    IbcReceiveResponse, Never,
 };
 
-mod consts;
+mod amount;
+mod auction;
+pub mod consts;
 mod contract;
 mod denom;
+mod epoch;
 mod error;
-mod msg;
+mod fee;
+mod float;
+mod group;
+#[cfg(feature = "ibc")]
+mod ibc;
+mod lifecycle;
+mod messages;
+pub mod msg;
+pub mod querier;
+mod quorum;
+#[cfg(feature = "quotas")]
+mod quota;
 mod rate;
+mod resolver;
 mod state;
+mod strategy;
+mod stream;
+
+// Types downstream contracts build messages and read responses with, e.g.
+// `use converter::prelude::*`. The other modules are internal and may change between versions
+pub mod prelude {
+    pub use crate::auction::{AuctionConfig, AuctionRound, Settlement};
+    pub use crate::denom::{Denom, DenomInfo};
+    pub use crate::epoch::{Epoch, Totals};
+    pub use crate::error::{
+        AdminError, AmountError, AuctionError, CommitError, ConfigError, ContractError,
+        ConvertError, DenomError, DepositError, FloatError, FundsError, IbcError, MetadataError,
+        MigrateError, QuorumError, RateError, SignatureError, StreamError,
+    };
+    pub use crate::fee::{FeeModel, FeeTier};
+    pub use crate::float::Float;
+    pub use crate::msg::*;
+    pub use crate::quorum::Proposal;
+    pub use crate::rate::Rate;
+    pub use crate::state::{
+        Config, Conversion, ConversionStatus, Delegation, Metadata, Mint, PauseInfo, PauseReason,
+        SenderClass,
+    };
+    pub use crate::strategy::{RateStep, RateStrategy, RateTier};
+    pub use crate::stream::Stream;
+}
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -37,6 +86,11 @@ pub fn execute(
     contract::execute(deps, env, info, msg)
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    contract::reply(deps, env, msg)
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(
     deps: DepsMut,
@@ -46,3 +100,68 @@ pub fn migrate(
 ) -> Result<Response, ContractError> {
     contract::migrate(deps, env, msg, info)
 }
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    contract::sudo(deps, env, msg)
+}
+
+#[cfg(feature = "ibc")]
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_open(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcChannelOpenMsg,
+) -> Result<IbcChannelOpenResponse, ContractError> {
+    ibc::channel_open(deps, env, msg)
+}
+
+#[cfg(feature = "ibc")]
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_connect(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcChannelConnectMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    ibc::channel_connect(deps, env, msg)
+}
+
+#[cfg(feature = "ibc")]
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_close(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcChannelCloseMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    ibc::channel_close(deps, env, msg)
+}
+
+#[cfg(feature = "ibc")]
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_receive(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketReceiveMsg,
+) -> Result<IbcReceiveResponse, Never> {
+    ibc::packet_receive(deps, env, msg)
+}
+
+#[cfg(feature = "ibc")]
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_ack(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketAckMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    ibc::packet_ack(deps, env, msg)
+}
+
+#[cfg(feature = "ibc")]
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_timeout(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketTimeoutMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    ibc::packet_timeout(deps, env, msg)
+}
@@ -0,0 +1,212 @@
+use crate::error::ContractError;
+use crate::msg::ExecuteMsg;
+use crate::state::{Config, FROZEN, PAUSE};
+use cosmwasm_std::{StdResult, Storage};
+
+// State of the contract, derived from the paused flag of the config and the frozen flag set
+// through governance. Freezing keeps the paused flag, which is restored on unfreezing
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Lifecycle {
+    Active,
+    Paused,
+    Frozen { paused: bool },
+}
+
+// Changes of state, by the pauser role or a config update for pause and resume, and by
+// governance for freeze and unfreeze
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Transition {
+    Pause,
+    Resume,
+    Freeze,
+    Unfreeze,
+}
+
+// What an execute message does, which decides whether the current state allows it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operation {
+    // Administration of the contract: config, roles, quorum, delegations, metadata
+    Admin,
+    // Start or settle a conversion, burning source and minting target tokens
+    Convert,
+    // Move tokens in or out of the contract without converting them
+    Funds,
+}
+
+impl Lifecycle {
+    pub fn load(storage: &dyn Storage, config: &Config) -> StdResult<Self> {
+        let frozen = FROZEN.may_load(storage)?.unwrap_or_default();
+        Ok(match (frozen, config.paused) {
+            (true, paused) => Lifecycle::Frozen { paused },
+            (false, true) => Lifecycle::Paused,
+            (false, false) => Lifecycle::Active,
+        })
+    }
+
+    pub fn is_paused(self) -> bool {
+        matches!(self, Lifecycle::Paused | Lifecycle::Frozen { paused: true })
+    }
+
+    pub fn allows(self, op: Operation) -> bool {
+        match self {
+            Lifecycle::Active => true,
+            Lifecycle::Paused => op != Operation::Convert,
+            // A frozen contract rejects every execute message, admin ones included
+            Lifecycle::Frozen { .. } => false,
+        }
+    }
+
+    // Fail with the error of the current state when it does not allow the operation, with
+    // the reason of the pause when one was given
+    pub fn ensure_allows(self, storage: &dyn Storage, op: Operation) -> Result<(), ContractError> {
+        if self.allows(op) {
+            return Ok(());
+        }
+        match self {
+            Lifecycle::Frozen { .. } => Err(ContractError::Frozen),
+            _ => Err(ContractError::Paused(PAUSE.may_load(storage)?)),
+        }
+    }
+
+    // State after the transition. Pausing and resuming are execute messages, so a frozen
+    // contract rejects them, while governance can freeze and unfreeze in any state
+    pub fn transition(self, transition: Transition) -> Result<Self, ContractError> {
+        match (self, transition) {
+            (Lifecycle::Frozen { .. }, Transition::Pause | Transition::Resume) => {
+                Err(ContractError::Frozen)
+            }
+            (_, Transition::Pause) => Ok(Lifecycle::Paused),
+            (_, Transition::Resume) => Ok(Lifecycle::Active),
+            (state, Transition::Freeze) => Ok(Lifecycle::Frozen {
+                paused: state.is_paused(),
+            }),
+            (Lifecycle::Frozen { paused: true }, Transition::Unfreeze) => Ok(Lifecycle::Paused),
+            (Lifecycle::Frozen { paused: false }, Transition::Unfreeze) => Ok(Lifecycle::Active),
+            (state, Transition::Unfreeze) => Ok(state),
+        }
+    }
+}
+
+// The match is exhaustive on purpose, every new execute message must be given an operation
+pub fn operation(msg: &ExecuteMsg) -> Operation {
+    use ExecuteMsg::*;
+
+    match msg {
+        Convert { .. }
+        | ConvertWithSignature { .. }
+        | StartStreamingConvert { .. }
+        | ClaimStream { .. }
+        | CommitConvert { .. }
+        | RevealConvert { .. }
+        | AuctionCommit {}
+        | ClaimAuction { .. } => Operation::Convert,
+        RecordUnsolicited { .. }
+        | ReturnUnsolicited { .. }
+        | BurnResidual {}
+        | MintRoundingReserve { .. }
+        | ClaimRefund {}
+        | Deposit { .. }
+        | Withdraw { .. }
+        | FundFloat {}
+        | WithdrawFloat { .. }
+        | CancelStream { .. }
+        | CancelCommitment {}
+        | CancelAuctionCommitment { .. }
+        | MigrateMyPosition { .. } => Operation::Funds,
+        #[cfg(feature = "ibc")]
+        TransferRemote { .. } => Operation::Funds,
+        UpdateConfig { .. }
+        | ReplaceConfig { .. }
+        | RefreshDenomInfo {}
+        | UpdateAdmin { .. }
+        | Access(_)
+        | SetAuthzEnabled { .. }
+        | EmitExpectedGrants {}
+        | HealthCheck {}
+        | SetDelegation { .. }
+        | SetNotifyTarget { .. }
+        | Pause { .. }
+        | UpdateMetadata { .. }
+        | Batch { .. }
+        | SetQuorum { .. }
+        | ProposeAction { .. }
+        | ApproveAction { .. }
+        | ExecuteAction { .. }
+        | SetAuction { .. }
+        | SetSuccessor { .. } => Operation::Admin,
+        // Settling moves no tokens, it fixes the clearing rate of a round from the configured
+        // rate and opens the next one. Callable by anyone, it runs the auction on behalf of the
+        // admin who configured it
+        SettleAuction {} => Operation::Admin,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Lifecycle::*;
+    use Transition::*;
+
+    const STATES: [Lifecycle; 4] = [
+        Active,
+        Paused,
+        Frozen { paused: false },
+        Frozen { paused: true },
+    ];
+
+    // Every (state, transition) pair, the expected state being None when the transition is
+    // rejected
+    #[test]
+    fn transitions() {
+        let spec = [
+            (Active, Pause, Some(Paused)),
+            (Active, Resume, Some(Active)),
+            (Active, Freeze, Some(Frozen { paused: false })),
+            (Active, Unfreeze, Some(Active)),
+            (Paused, Pause, Some(Paused)),
+            (Paused, Resume, Some(Active)),
+            (Paused, Freeze, Some(Frozen { paused: true })),
+            (Paused, Unfreeze, Some(Paused)),
+            (Frozen { paused: false }, Pause, None),
+            (Frozen { paused: false }, Resume, None),
+            (
+                Frozen { paused: false },
+                Freeze,
+                Some(Frozen { paused: false }),
+            ),
+            (Frozen { paused: false }, Unfreeze, Some(Active)),
+            (Frozen { paused: true }, Pause, None),
+            (Frozen { paused: true }, Resume, None),
+            (
+                Frozen { paused: true },
+                Freeze,
+                Some(Frozen { paused: true }),
+            ),
+            (Frozen { paused: true }, Unfreeze, Some(Paused)),
+        ];
+        assert_eq!(spec.len(), STATES.len() * 4);
+        for (state, transition, expected) in spec {
+            assert_eq!(
+                state.transition(transition).ok(),
+                expected,
+                "{state:?} on {transition:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn allowed_operations() {
+        let spec = [
+            (Active, [true, true, true]),
+            (Paused, [true, false, true]),
+            (Frozen { paused: false }, [false, false, false]),
+            (Frozen { paused: true }, [false, false, false]),
+        ];
+        for (state, allowed) in spec {
+            let ops = [Operation::Admin, Operation::Convert, Operation::Funds];
+            for (op, allowed) in ops.into_iter().zip(allowed) {
+                assert_eq!(state.allows(op), allowed, "{state:?} on {op:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use cosmwasm_std::{AnyMsg, Coin, CosmosMsg};
+use manifest_std::cosmos::authz::v1beta1::MsgExec;
+use manifest_std::cosmos::bank::v1beta1::MsgSend;
+use manifest_std::cosmos::distribution::v1beta1::MsgFundCommunityPool;
+use manifest_std::google::protobuf::Any;
+use manifest_std::liftedinit::manifest::v1::MsgBurnHeldBalance;
+use manifest_std::osmosis::tokenfactory::v1beta1::MsgMint;
+use prost::Message;
+use sha2::{Digest, Sha256};
+
+// Builders of the protobuf messages of a conversion. They only encode their inputs, so they
+// can be tested without an App and shared by execution and simulation
+
+fn proto_coin(coin: &Coin) -> manifest_std::cosmos::base::v1beta1::Coin {
+    manifest_std::cosmos::base::v1beta1::Coin {
+        denom: coin.denom.clone(),
+        amount: coin.amount.to_string(),
+    }
+}
+
+pub fn build_send(from: &str, to: &str, coin: &Coin) -> Any {
+    let send = MsgSend {
+        from_address: from.to_string(),
+        to_address: to.to_string(),
+        amount: vec![proto_coin(coin)],
+    };
+    Any {
+        type_url: MsgSend::TYPE_URL.to_string(),
+        value: send.encode_to_vec(),
+    }
+}
+
+// Fund the community pool from the balance of the depositor, i.e. the contract
+pub fn build_fund_community_pool(depositor: &str, coin: &Coin) -> Any {
+    let fund = MsgFundCommunityPool {
+        amount: vec![proto_coin(coin)],
+        depositor: depositor.to_string(),
+    };
+    Any {
+        type_url: MsgFundCommunityPool::TYPE_URL.to_string(),
+        value: fund.encode_to_vec(),
+    }
+}
+
+// Burn from the held balance of the authority, i.e. the POA admin
+pub fn build_burn(authority: &str, coin: &Coin) -> Any {
+    let burn = MsgBurnHeldBalance {
+        authority: authority.to_string(),
+        burn_coins: vec![proto_coin(coin)],
+    };
+    Any {
+        type_url: MsgBurnHeldBalance::TYPE_URL.to_string(),
+        value: burn.encode_to_vec(),
+    }
+}
+
+// Mint tokenfactory tokens of the sender, i.e. the POA admin, to the recipient
+pub fn build_mint(sender: &str, recipient: &str, coin: &Coin) -> Any {
+    let mint = MsgMint {
+        sender: sender.to_string(),
+        amount: Some(proto_coin(coin)),
+        mint_to_address: recipient.to_string(),
+    };
+    Any {
+        type_url: MsgMint::TYPE_URL.to_string(),
+        value: mint.encode_to_vec(),
+    }
+}
+
+// Execute the messages through the grants given to the grantee
+pub fn build_exec(grantee: &str, msgs: Vec<Any>) -> CosmosMsg {
+    CosmosMsg::Any(encode_exec(grantee, msgs))
+}
+
+pub fn encode_exec(grantee: &str, msgs: Vec<Any>) -> AnyMsg {
+    let exec = MsgExec {
+        grantee: grantee.to_string(),
+        msgs,
+    };
+    to_any_msg(Any {
+        type_url: MsgExec::TYPE_URL.to_string(),
+        value: exec.encode_to_vec(),
+    })
+}
+
+// Hex SHA-256 of a message as encoded in the MsgExec, type URL included, so a dispatched
+// message can be matched with the one the chain executed
+pub fn payload_hash(any: &Any) -> String {
+    Sha256::digest(any.encode_to_vec())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+pub fn to_any_msg(any: Any) -> AnyMsg {
+    AnyMsg {
+        type_url: any.type_url,
+        value: any.value.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        build_burn, build_exec, build_fund_community_pool, build_mint, build_send, payload_hash,
+    };
+    use cosmwasm_std::{coin, CosmosMsg};
+    use manifest_std::cosmos::authz::v1beta1::MsgExec;
+    use manifest_std::cosmos::bank::v1beta1::MsgSend;
+    use manifest_std::cosmos::base::v1beta1::Coin;
+    use manifest_std::cosmos::distribution::v1beta1::MsgFundCommunityPool;
+    use manifest_std::liftedinit::manifest::v1::MsgBurnHeldBalance;
+    use manifest_std::osmosis::tokenfactory::v1beta1::MsgMint;
+    use prost::Message;
+
+    const CONTRACT: &str = "contract";
+    const POA_ADMIN: &str = "poa_admin";
+
+    fn proto_coin(amount: &str, denom: &str) -> Coin {
+        Coin {
+            denom: denom.to_string(),
+            amount: amount.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_build_send() {
+        let any = build_send(CONTRACT, POA_ADMIN, &coin(1_000, "umfx"));
+        assert_eq!(any.type_url, "/cosmos.bank.v1beta1.MsgSend");
+        assert_eq!(
+            MsgSend::decode(any.value.as_slice()).unwrap(),
+            MsgSend {
+                from_address: CONTRACT.to_string(),
+                to_address: POA_ADMIN.to_string(),
+                amount: vec![proto_coin("1000", "umfx")],
+            }
+        );
+    }
+
+    #[test]
+    fn test_build_fund_community_pool() {
+        let any = build_fund_community_pool(CONTRACT, &coin(10, "umfx"));
+        assert_eq!(
+            any.type_url,
+            "/cosmos.distribution.v1beta1.MsgFundCommunityPool"
+        );
+        assert_eq!(
+            MsgFundCommunityPool::decode(any.value.as_slice()).unwrap(),
+            MsgFundCommunityPool {
+                amount: vec![proto_coin("10", "umfx")],
+                depositor: CONTRACT.to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_build_burn() {
+        let any = build_burn(POA_ADMIN, &coin(1_000, "umfx"));
+        assert_eq!(any.type_url, "/liftedinit.manifest.v1.MsgBurnHeldBalance");
+        assert_eq!(
+            MsgBurnHeldBalance::decode(any.value.as_slice()).unwrap(),
+            MsgBurnHeldBalance {
+                authority: POA_ADMIN.to_string(),
+                burn_coins: vec![proto_coin("1000", "umfx")],
+            }
+        );
+    }
+
+    #[test]
+    fn test_build_mint() {
+        let any = build_mint(POA_ADMIN, "recipient", &coin(500, "upwr"));
+        assert_eq!(any.type_url, "/osmosis.tokenfactory.v1beta1.MsgMint");
+        assert_eq!(
+            MsgMint::decode(any.value.as_slice()).unwrap(),
+            MsgMint {
+                sender: POA_ADMIN.to_string(),
+                amount: Some(proto_coin("500", "upwr")),
+                mint_to_address: "recipient".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_build_exec() {
+        let msgs = vec![
+            build_burn(POA_ADMIN, &coin(1_000, "umfx")),
+            build_mint(POA_ADMIN, "recipient", &coin(500, "upwr")),
+        ];
+        let msg = build_exec(CONTRACT, msgs.clone());
+
+        let CosmosMsg::Any(any) = msg else {
+            panic!("expected an Any message, got {msg:?}");
+        };
+        assert_eq!(any.type_url, "/cosmos.authz.v1beta1.MsgExec");
+        assert_eq!(
+            MsgExec::decode(any.value.as_slice()).unwrap(),
+            MsgExec {
+                grantee: CONTRACT.to_string(),
+                msgs,
+            }
+        );
+    }
+
+    #[test]
+    fn test_payload_hash() {
+        let burn = build_burn(POA_ADMIN, &coin(1_000, "umfx"));
+        let hash = payload_hash(&burn);
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, payload_hash(&burn.clone()));
+        assert_ne!(
+            hash,
+            payload_hash(&build_burn(POA_ADMIN, &coin(1_001, "umfx")))
+        );
+    }
+}
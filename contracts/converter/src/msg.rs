@@ -1,31 +1,1024 @@
-use crate::state::Config;
+use crate::auction::{AuctionConfig, AuctionRound};
+use crate::epoch::Epoch;
+use crate::fee::FeeModel;
+use crate::float::Float;
+use crate::quorum::Proposal;
+use crate::rate::Rate;
+use crate::state::{Config, Delegation, Metadata, PauseInfo, PauseReason, SenderClass};
+use crate::strategy::RateStrategy;
+use crate::stream::Stream;
 use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_string, Addr, AnyMsg, Binary, Coin, Decimal256, Timestamp, Uint256};
+use manifest_access::{AccessMsg, AccessQueryMsg, AccessSudoMsg};
+use manifest_common::pagination::PageRequest;
+use serde::Serialize;
+use std::collections::BTreeMap;
 
 #[cw_serde]
 pub struct InstantiateMsg {
     pub admin: String,
-    pub poa_admin: String,
     pub rate: String,
-    pub source_denom: String,
-    pub target_denom: String,
-    pub paused: bool,
+    // Default to the POA admin and denoms of the Manifest Network, and to unpaused
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub poa_admin: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_denom: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_denom: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paused: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strict_denoms: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_exponent: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_exponent: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_symbol: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_symbol: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_senders: Option<SenderClass>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epoch_length: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub require_group_policy: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_group_threshold: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bech32_prefix: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flat_fee: Option<Coin>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_floor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_ceiling: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verifier: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redact_senders: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_strategy: Option<RateStrategy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub float_payouts: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolver: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash_authz_payloads: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_model: Option<FeeModel>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_convert_amount: Option<Uint256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_convert_amount: Option<Uint256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_quota: Option<Uint256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epoch_cap: Option<Uint256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fees_to_community_pool: Option<bool>,
+    // Label, description and external url of the contract, updatable by the admin
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+    // Funds the message must be sent with, exactly. Without it the message is non-payable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub funding: Option<Vec<Coin>>,
+}
+
+// Complete declarative config, the instantiate message without its admin. Unset fields take
+// the same defaults as on instantiate
+#[cw_serde]
+pub struct InstantiateConfig {
+    pub rate: String,
+    // Default to the POA admin and denoms of the Manifest Network, and to unpaused
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub poa_admin: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_denom: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_denom: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paused: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strict_denoms: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_exponent: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_exponent: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_symbol: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_symbol: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_senders: Option<SenderClass>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epoch_length: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub require_group_policy: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_group_threshold: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bech32_prefix: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flat_fee: Option<Coin>,
+    // Rate bounds are only changed through a quorum action, they must match the current ones
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_floor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_ceiling: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verifier: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redact_senders: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_strategy: Option<RateStrategy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub float_payouts: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolver: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash_authz_payloads: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_model: Option<FeeModel>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_convert_amount: Option<Uint256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_convert_amount: Option<Uint256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_quota: Option<Uint256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epoch_cap: Option<Uint256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fees_to_community_pool: Option<bool>,
+}
+
+impl InstantiateMsg {
+    pub fn into_parts(self) -> (String, InstantiateConfig) {
+        let config = InstantiateConfig {
+            rate: self.rate,
+            poa_admin: self.poa_admin,
+            source_denom: self.source_denom,
+            target_denom: self.target_denom,
+            paused: self.paused,
+            strict_denoms: self.strict_denoms,
+            source_exponent: self.source_exponent,
+            target_exponent: self.target_exponent,
+            source_symbol: self.source_symbol,
+            target_symbol: self.target_symbol,
+            allowed_senders: self.allowed_senders,
+            epoch_length: self.epoch_length,
+            require_group_policy: self.require_group_policy,
+            min_group_threshold: self.min_group_threshold,
+            bech32_prefix: self.bech32_prefix,
+            flat_fee: self.flat_fee,
+            rate_floor: self.rate_floor,
+            rate_ceiling: self.rate_ceiling,
+            verifier: self.verifier,
+            redact_senders: self.redact_senders,
+            rate_strategy: self.rate_strategy,
+            float_payouts: self.float_payouts,
+            resolver: self.resolver,
+            hash_authz_payloads: self.hash_authz_payloads,
+            fee_model: self.fee_model,
+            min_convert_amount: self.min_convert_amount,
+            max_convert_amount: self.max_convert_amount,
+            daily_quota: self.daily_quota,
+            epoch_cap: self.epoch_cap,
+            fees_to_community_pool: self.fees_to_community_pool,
+        };
+        (self.admin, config)
+    }
 }
 
 #[cw_serde]
+#[non_exhaustive]
 pub enum ExecuteMsg {
-    Convert {},
-    UpdateConfig { config: UpdateConfig },
-    UpdateAdmin { admin: Option<String> },
+    Convert {
+        callback: Option<Callback>,
+        // Mint to this address instead of the sender
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        recipient: Option<String>,
+        // Split the minted tokens among several recipients instead of the sender
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        recipients: Option<Vec<Recipient>>,
+        // Convert this amount from the sender's deposit instead of the sent funds
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        from_deposit: Option<Uint256>,
+        // Stored with the conversion record and emitted as an attribute
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        memo: Option<String>,
+        // Fail when fewer target base units would be minted
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_receive: Option<Uint256>,
+    },
+    UpdateConfig {
+        config: Box<UpdateConfig>,
+        // Fail instead of succeeding without changes when the update is empty or a noop
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        strict: Option<bool>,
+    },
+    ReplaceConfig {
+        config: Box<InstantiateConfig>,
+    },
+    RefreshDenomInfo {},
+    UpdateAdmin {
+        admin: Option<String>,
+    },
+    // Two-step admin transfer and roles, e.g. `{"access": {"accept_admin": {}}}`
+    Access(AccessMsg),
+    SetAuthzEnabled {
+        enabled: bool,
+    },
+    // Emit the expected authz grants as events, for runbooks setting up or auditing grants
+    EmitExpectedGrants {},
+    // Run cheap runtime assertions and report them in a `health` event, for monitoring bots.
+    // Anyone may call it, nothing is changed
+    HealthCheck {},
+    // Delegate part of the config authority to a sub-operator, or revoke it
+    SetDelegation {
+        delegate: String,
+        delegation: Option<Delegation>,
+    },
+    // Set the contract alerted of critical events, or stop alerting
+    SetNotifyTarget {
+        target: Option<String>,
+    },
+    // Pause conversions with a reason shown to users, or update the reason of the current
+    // pause. Unpausing through a config update clears it
+    Pause {
+        reason: PauseReason,
+        message: Option<String>,
+    },
+    // Replace the label, description and external url of the contract, or clear them
+    UpdateMetadata {
+        metadata: Option<Metadata>,
+    },
+    // Apply several admin operations at once, all of them or none. Each operation is authorized
+    // as if sent on its own, and reports its attributes in a `batch_op` event
+    Batch {
+        ops: Vec<AdminOp>,
+    },
+    SetQuorum {
+        members: Vec<String>,
+        threshold: u32,
+    },
+    ProposeAction {
+        action: QuorumAction,
+    },
+    ApproveAction {
+        id: u64,
+    },
+    ExecuteAction {
+        id: u64,
+    },
+    RecordUnsolicited {
+        depositor: String,
+        amount: Coin,
+    },
+    ReturnUnsolicited {
+        depositor: String,
+        denom: String,
+    },
+    // Burn the target tokens left at the contract address, outside of escrows and recorded
+    // unsolicited funds
+    BurnResidual {},
+    // Mint the whole target base units accumulated in the rounding reserve to the recipient,
+    // the POA admin when unset
+    MintRoundingReserve {
+        recipient: Option<String>,
+    },
+    ClaimRefund {},
+    // Hold source tokens for later conversions of the sender, including the ones authorized by
+    // signatures of the given secp256k1 key, compressed or uncompressed. A new key replaces the
+    // previous one
+    Deposit {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pubkey: Option<Binary>,
+    },
+    Withdraw {
+        amount: Uint256,
+    },
+    // Add the target tokens sent as funds to the float paying conversions out when
+    // `float_payouts` is set
+    FundFloat {},
+    // Send part of a float back to the admin, in the target denom or a previous one
+    WithdrawFloat {
+        amount: Coin,
+    },
+    // Convert from the deposit of the payload owner, on their signature of the sign doc of
+    // the payload. The sender relays the message and pays the flat fee when one is configured
+    ConvertWithSignature {
+        payload: SignedConversion,
+        signature: Binary,
+        pubkey: Binary,
+    },
+    // Escrow the source tokens sent as funds, `total` of them, to convert them gradually over
+    // `duration` seconds
+    StartStreamingConvert {
+        total: Uint256,
+        duration: u64,
+    },
+    ClaimStream {
+        id: u64,
+    },
+    CancelStream {
+        id: u64,
+    },
+    // Commit to a conversion with the SHA-256 hash of its reveal preimage, sending the source
+    // tokens as funds, at least the amount to convert
+    CommitConvert {
+        hash: Binary,
+    },
+    // Convert the committed amount, in a later block and before the commitment expires
+    RevealConvert {
+        amount: Uint256,
+        salt: Binary,
+    },
+    // Drop the pending commitment and get its funds back
+    CancelCommitment {},
+    // Enable batch mode with the given parameters, or disable it
+    SetAuction {
+        auction: Option<AuctionConfig>,
+    },
+    // Commit the source tokens sent as funds to the current auction round
+    AuctionCommit {},
+    // Settle the current round once over and open the next one, callable by anyone
+    SettleAuction {},
+    ClaimAuction {
+        round: u64,
+    },
+    // Get the commitment to a settled round back instead of claiming it
+    CancelAuctionCommitment {
+        round: u64,
+    },
+    // Set the contract user positions can be moved to at the end of life of this one, or
+    // clear it
+    SetSuccessor {
+        addr: Option<String>,
+    },
+    // Move the pending refunds, deposit, commitment and given streams of the sender to the
+    // successor, along with their funds
+    MigrateMyPosition {
+        streams: Option<Vec<u64>>,
+    },
+    #[cfg(feature = "ibc")]
+    TransferRemote {
+        channel_id: String,
+        receiver: String,
+        timeout_seconds: Option<u64>,
+    },
+}
+
+// Destructive actions requiring N-of-M approval once a quorum is configured
+#[cw_serde]
+#[non_exhaustive]
+pub enum QuorumAction {
+    UpdateAdmin {
+        admin: String,
+    },
+    UpdateQuorum {
+        members: Vec<String>,
+        threshold: u32,
+    },
+    // Change the bounds every rate update must respect, unset to remove a bound
+    UpdateRateBounds {
+        rate_floor: Option<String>,
+        rate_ceiling: Option<String>,
+    },
+}
+
+impl QuorumAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            QuorumAction::UpdateAdmin { .. } => "update_admin",
+            QuorumAction::UpdateQuorum { .. } => "update_quorum",
+            QuorumAction::UpdateRateBounds { .. } => "update_rate_bounds",
+        }
+    }
+}
+
+// Operation of a batch, applied like the execute message of the same name
+#[cw_serde]
+#[non_exhaustive]
+pub enum AdminOp {
+    UpdateConfig {
+        config: Box<UpdateConfig>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        strict: Option<bool>,
+    },
+    Pause {
+        reason: PauseReason,
+        message: Option<String>,
+    },
+    SetAuthzEnabled {
+        enabled: bool,
+    },
+    SetNotifyTarget {
+        target: Option<String>,
+    },
+    SetDelegation {
+        delegate: String,
+        delegation: Option<Delegation>,
+    },
+    UpdateMetadata {
+        metadata: Option<Metadata>,
+    },
+}
+
+impl AdminOp {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AdminOp::UpdateConfig { .. } => "update_config",
+            AdminOp::Pause { .. } => "pause",
+            AdminOp::SetAuthzEnabled { .. } => "set_authz_enabled",
+            AdminOp::SetNotifyTarget { .. } => "set_notify_target",
+            AdminOp::SetDelegation { .. } => "set_delegation",
+            AdminOp::UpdateMetadata { .. } => "update_metadata",
+        }
+    }
+}
+
+// Data of the convert response
+#[cw_serde]
+pub struct ConvertResponse {
+    pub conversion_id: u64,
+}
+
+// Recipient of a share of the minted tokens, proportional to its weight
+#[cw_serde]
+pub struct Recipient {
+    pub address: String,
+    pub weight: u64,
+}
+
+// Conversion authorized by the owner of a deposit. The nonce must match the one of the
+// deposit, which is incremented by every signed conversion
+#[cw_serde]
+pub struct SignedConversion {
+    pub owner: String,
+    pub amount: Coin,
+    pub nonce: u64,
+    pub deadline: Timestamp,
+}
+
+// Canonical payload of a conversion receipt. The SHA-256 hash of its JSON serialization is a
+// stable reference to the conversion, unique across chains and deployments
+#[cw_serde]
+pub struct ReceiptPayload {
+    pub chain_id: String,
+    pub contract: String,
+    pub id: u64,
+    pub sender: String,
+    pub burned: Coin,
+    pub minted: Coin,
+    pub rate: Rate,
+    pub height: u64,
+}
+
+#[cw_serde]
+pub struct ContractInfoResponse {
+    pub contract_name: String,
+    pub contract_version: String,
+    pub api_version: String,
+    pub metadata: Option<Metadata>,
+}
+
+#[cw_serde]
+pub struct StatusResponse {
+    pub paused: bool,
+    pub frozen: bool,
+    pub authz_enabled: bool,
+    // Reason of the pause, when one was given
+    pub pause: Option<PauseInfo>,
+}
+
+#[cw_serde]
+pub struct ApiVersionResponse {
+    pub version: String,
+}
+
+#[cw_serde]
+pub struct ReportResponse {
+    pub from_height: u64,
+    pub to_height: u64,
+    // Succeeded conversions of the page and their totals per denom
+    pub conversions: u64,
+    pub burned: Vec<Coin>,
+    pub minted: Vec<Coin>,
+    pub fees: Vec<Coin>,
+    // Conversions whose source tokens were escrowed for their sender to claim back
+    pub failed: u64,
+    // Distinct senders of the succeeded conversions of the page, in address order
+    pub senders: Vec<Addr>,
+    // Set when more records of the range follow, pass it as `start_after`
+    pub next_key: Option<Binary>,
+}
+
+#[cw_serde]
+pub struct VerifyReceiptResponse {
+    pub id: u64,
+    // Hash recorded with the conversion
+    pub hash: Option<Binary>,
+    // Hash recomputed from the recorded conversion
+    pub computed: Option<Binary>,
+    pub valid: bool,
+}
+
+// Document whose JSON serialization is hashed with SHA-256 and signed by the deposit key.
+// Binding the chain and the contract prevents replays on other deployments
+#[cw_serde]
+pub struct ConversionSignDoc {
+    pub chain_id: String,
+    pub contract: String,
+    pub payload: SignedConversion,
+}
+
+// Values revealed by a commit-reveal conversion. The commitment hash is the SHA-256 hash of
+// the JSON serialization of this preimage
+#[cw_serde]
+pub struct RevealPreimage {
+    pub sender: String,
+    pub amount: Uint256,
+    pub salt: Binary,
+}
+
+// Contract to invoke, with the minted coins attached, after a successful conversion
+#[cw_serde]
+pub struct Callback {
+    pub contract: String,
+    pub msg: Binary,
+}
+
+// Message executed on the notify target when a critical event happens. Alerts are best
+// effort, a failing target does not revert the operation
+#[cw_serde]
+#[non_exhaustive]
+pub enum NotifyMsg {
+    ConverterAlert { alert: Alert },
+}
+
+// Message executed on the successor contract by `MigrateMyPosition`, with the funds of the
+// position attached
+#[cw_serde]
+#[non_exhaustive]
+pub enum SuccessorMsg {
+    ReceivePosition { owner: String, position: Position },
+}
+
+// Positions of a user moved to the successor. Streams keep their schedule, the funds cover
+// the tokens they did not convert yet
+#[cw_serde]
+pub struct Position {
+    pub refunds: Vec<Coin>,
+    pub deposit: Option<Coin>,
+    pub commitment: Option<Coin>,
+    pub streams: Vec<Stream>,
+}
+
+// Query answered by the attestation contract gating conversions, see `Config::verifier`
+#[cw_serde]
+#[non_exhaustive]
+pub enum VerifierQueryMsg {
+    IsVerified { address: String },
+}
+
+#[cw_serde]
+pub struct IsVerifiedResponse {
+    pub verified: bool,
+}
+
+// Query answered by the name service resolving names to addresses, see `Config::resolver`
+#[cw_serde]
+#[non_exhaustive]
+pub enum ResolverQueryMsg {
+    Resolve { name: String },
+}
+
+#[cw_serde]
+pub struct ResolveResponse {
+    pub address: Option<String>,
+}
+
+// Query answered by the oracle of an oracle-backed rate strategy, see `RateStrategy`
+#[cw_serde]
+#[non_exhaustive]
+pub enum OracleQueryMsg {
+    Rate {
+        source_denom: String,
+        target_denom: String,
+    },
 }
 
 #[cw_serde]
+pub struct OracleRateResponse {
+    pub rate: Decimal256,
+}
+
+#[cw_serde]
+#[non_exhaustive]
+pub enum Alert {
+    // Chain governance froze the contract
+    Frozen {},
+    // Chain governance unfroze the contract
+    Unfrozen {},
+    // The authz execution of a conversion failed, its source tokens are refundable
+    ConversionFailed {
+        conversion_id: u64,
+        sender: String,
+        error: String,
+    },
+    // The authz execution of a conversion received over IBC failed
+    RemoteConversionFailed {
+        channel_id: String,
+        error: String,
+    },
+}
+
+// Messages only chain governance can send
+#[cw_serde]
+#[non_exhaustive]
+pub enum SudoMsg {
+    Freeze {},
+    Unfreeze {},
+    // Set the admin when its key is lost or compromised
+    Access(AccessSudoMsg),
+    // Emergency brake cancelling every change waiting to take effect: the actions proposed
+    // to the quorum, the pending admin transfer and the rate steps not started yet
+    CancelScheduled {},
+}
+
+#[cw_serde]
+#[non_exhaustive]
 pub enum QueryMsg {
+    // Semver of the message API, for composing contracts to check compatibility
+    ApiVersion {},
+    // Name, version and operator-provided metadata of the contract
+    ContractInfo {},
+    // Whether conversions are possible, and why not
+    Status {},
     Config {},
     Admin {},
+    // Pending admin transfer and role members
+    Access(AccessQueryMsg),
+    Frozen {},
+    NotifyTarget {},
+    // Successor contract and how many positions were moved to it
+    Successor {},
+    // Sub-operators holding part of the config authority
+    Delegations {
+        pagination: Option<PageRequest>,
+    },
+    Grants {},
+    // Every (granter, grantee, msg type URL) authz grant a conversion relies on
+    ExpectedGrants {},
+    DisplayRate {},
+    // Rate a conversion of `amount` source base units would get at the current block, and the
+    // strategy resolving it. Without an amount, the rate of the smallest conversions
+    CurrentRate {
+        amount: Option<Uint256>,
+    },
+    // Base units of a human-style amount such as "1.5mfx", see ParseAmountResponse
+    ParseAmount {
+        input: String,
+    },
+    // Messages a conversion of `amount` source base units by `sender` would emit, encoded
+    // exactly as the contract sends them
+    SimulateExecution {
+        sender: String,
+        amount: Uint256,
+    },
+    // Source tokens a conversion must send to mint at least `desired` target base units at
+    // the current block, the inverse of SimulateExecution
+    ReverseSimulate {
+        desired: Uint256,
+    },
+    // Source base units the address can still convert today under the daily quota
+    #[cfg(feature = "quotas")]
+    RemainingQuota {
+        address: String,
+    },
+    CurrentEpoch {},
+    // Target base units minted in the current epoch against the epoch cap
+    EpochUsage {},
+    Metrics {},
+    // Lifetime totals per pair of source and target denoms
+    PairTotals {
+        pagination: Option<PageRequest>,
+    },
+    Epochs {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    Quorum {},
+    PendingAction {
+        id: u64,
+    },
+    PendingActions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    UnsolicitedBalance {
+        denom: String,
+    },
+    UnsolicitedFunds {
+        depositor: String,
+    },
+    Refunds {
+        address: String,
+    },
+    #[cfg(feature = "ibc")]
+    Channels {
+        pagination: Option<PageRequest>,
+    },
+    Defaults {},
+    ValidateConfigUpdate {
+        config: Box<UpdateConfig>,
+    },
+    ConfigDiff {
+        proposed: Box<UpdateConfig>,
+    },
+    DenomInfo {
+        denom: String,
+    },
+    Conversion {
+        id: u64,
+    },
+    // Settlement totals of the conversions recorded between two heights, inclusive, one page
+    // of records at a time. Merge the pages for the whole range
+    Report {
+        from_height: u64,
+        to_height: u64,
+        pagination: Option<PageRequest>,
+    },
+    // Recompute the receipt hash of a conversion and check it against the recorded one
+    VerifyReceipt {
+        id: u64,
+    },
+    #[cfg(feature = "history")]
+    AdminHistory {
+        pagination: Option<PageRequest>,
+    },
+    #[cfg(feature = "history")]
+    UpgradeHistory {
+        pagination: Option<PageRequest>,
+    },
+    Deposit {
+        address: String,
+    },
+    Deposits {
+        pagination: Option<PageRequest>,
+    },
+    Stream {
+        id: u64,
+    },
+    Streams {
+        pagination: Option<PageRequest>,
+    },
+    Commitment {
+        address: String,
+    },
+    Auction {},
+    // Float of the target denom and its replenishment accounting
+    Float {},
+    AuctionCommitment {
+        round: u64,
+        address: String,
+    },
+    AuctionSettlement {
+        round: u64,
+    },
+    // Namespace, key components and value type of every storage item and map, for indexers
+    // syncing the raw state
+    StorageLayout {},
+    // Rough gas estimates of the execute messages of users, for wallets skipping simulation
+    GasHints {},
+    // Stable code and English message of every error, for frontends localizing errors
+    ErrorCatalog {},
+}
+
+// Defaults compiled into the contract, applied to the instantiate fields left unset
+#[cw_serde]
+pub struct DefaultsResponse {
+    pub contract_name: String,
+    pub contract_version: String,
+    pub profile: String,
+    pub poa_admin: String,
+    pub source_denom: String,
+    pub target_denom: String,
+    pub bech32_prefix: String,
+}
+
+#[cw_serde]
+pub struct CurrentRateResponse {
+    // Kind of the rate strategy, `fixed` when none is set
+    pub kind: String,
+    pub rate: Decimal256,
+}
+
+// The rate in display units (e.g. "1 MFX = 0.5 PWR") alongside the raw rate in base units
+#[cw_serde]
+pub struct DisplayRateResponse {
+    pub display_rate: Decimal256,
+    pub raw_rate: Decimal256,
+    pub source_symbol: String,
+    pub target_symbol: String,
+    pub source_exponent: u32,
+    pub target_exponent: u32,
+    pub display: String,
+}
+
+// An amount in base units, parsed from display units of a configured symbol, case insensitive
+// and scaled by the denom's exponent, or from base units of a configured denom
+#[cw_serde]
+pub struct ParseAmountResponse {
+    pub amount: Coin,
+}
+
+#[cw_serde]
+pub struct SimulateExecutionResponse {
+    pub minted: Coin,
+    // Messages emitted by the contract, the authz MsgExec first, then the flat fee transfer
+    pub msgs: Vec<AnyMsg>,
+    // Messages executed by the contract on behalf of the POA admin, as wrapped in the MsgExec
+    pub authz_msgs: Vec<AnyMsg>,
+}
+
+// Minting of the current epoch, `cap` and `remaining` being unset without an epoch cap and
+// `ends_at` without an epoch length
+#[cw_serde]
+pub struct EpochUsageResponse {
+    pub epoch: u64,
+    pub cap: Option<Uint256>,
+    pub minted: Uint256,
+    pub remaining: Option<Uint256>,
+    pub ends_at: Option<Timestamp>,
+}
+
+// Daily quota of an address, `quota` and `remaining` being unset without a quota. Days start
+// at midnight UTC
+#[cfg(feature = "quotas")]
+#[cw_serde]
+pub struct RemainingQuotaResponse {
+    pub quota: Option<Uint256>,
+    pub used: Uint256,
+    pub remaining: Option<Uint256>,
+    pub resets_at: Timestamp,
+}
+
+#[cw_serde]
+pub struct ReverseSimulateResponse {
+    // Coin to convert, including the fee withheld by the fee model
+    pub amount: Coin,
+    // Target tokens the amount mints, at least the desired amount
+    pub minted: Coin,
+    // Part of the amount withheld by the fee model
+    pub fee: Option<Coin>,
+    // Sent along with the amount when a flat fee is configured
+    pub flat_fee: Option<Coin>,
+}
+
+// Authz grants the contract expects to hold in order to convert
+#[cw_serde]
+pub struct GrantsResponse {
+    pub granter: String,
+    pub grantee: String,
+    pub type_urls: Vec<String>,
+    pub enabled: bool,
+}
+
+// An authz grant from `granter` to `grantee` for the messages of `type_url`
+#[cw_serde]
+pub struct ExpectedGrant {
+    pub granter: String,
+    pub grantee: String,
+    pub type_url: String,
+}
+
+#[cw_serde]
+pub struct ExpectedGrantsResponse {
+    pub grants: Vec<ExpectedGrant>,
+}
+
+// Flat numeric gauges and counters, meant to be scraped by off-chain exporters
+#[cw_serde]
+pub struct MetricsResponse {
+    pub metrics: BTreeMap<String, Decimal256>,
+}
+
+#[cw_serde]
+pub struct PairTotalsResponse {
+    pub source_denom: String,
+    pub target_denom: String,
+    pub burned: Uint256,
+    pub minted: Uint256,
+    pub conversions: u64,
+}
+
+#[cw_serde]
+pub struct EpochsResponse {
+    pub epochs: Vec<Epoch>,
+}
+
+#[cw_serde]
+pub struct PendingActionsResponse {
+    pub actions: Vec<Proposal>,
+}
+
+#[cw_serde]
+pub struct FloatResponse {
+    pub denom: String,
+    // Whether conversions are paid out of the float, when it covers them
+    pub enabled: bool,
+    pub float: Float,
 }
 
 #[cw_serde]
-pub enum MigrateMsg {}
+pub struct UnsolicitedBalanceResponse {
+    pub balance: Uint256,
+    pub recorded: Uint256,
+    pub escrowed: Uint256,
+    pub unrecorded: Uint256,
+}
+
+#[cw_serde]
+pub struct UnsolicitedFundsResponse {
+    pub funds: Vec<Coin>,
+}
+
+// Contract user positions can be moved to, and how many were moved so far
+#[cw_serde]
+pub struct SuccessorResponse {
+    pub successor: Option<Addr>,
+    pub migrated_positions: u64,
+}
+
+// Source tokens of failed conversions the address can claim back
+#[cw_serde]
+pub struct RefundsResponse {
+    pub refunds: Vec<Coin>,
+}
+
+// Packets of the converter IBC application. Transfers move source or target tokens across the
+// channel, the sending chain escrowing them. Converts ask the converter to convert source
+// tokens previously transferred over the channel, the target tokens staying escrowed for the
+// counterparty chain to credit the receiver with
+#[cw_serde]
+#[non_exhaustive]
+pub enum ConverterPacket {
+    Transfer {
+        denom: String,
+        amount: Uint256,
+        sender: String,
+        receiver: String,
+    },
+    Convert {
+        amount: Uint256,
+        sender: String,
+        receiver: String,
+    },
+}
+
+// Result of a successful convert packet
+#[cw_serde]
+pub struct ConvertAck {
+    pub minted: Uint256,
+}
+
+// The deposit of an address in the deposits list
+#[cw_serde]
+pub struct DepositResponse {
+    pub address: String,
+    pub amount: Coin,
+    pub pubkey: Option<Binary>,
+    pub nonce: u64,
+}
+
+// Batch mode parameters, unset when disabled, and the round accepting commitments
+#[cw_serde]
+pub struct AuctionResponse {
+    pub auction: Option<AuctionConfig>,
+    pub round: Option<AuctionRound>,
+}
+
+// A streaming conversion and the tokens its owner can claim now
+#[cw_serde]
+pub struct StreamResponse {
+    pub stream: Stream,
+    pub claimable: Uint256,
+}
+
+// A connected channel of the converter IBC application and the tokens escrowed for it
+#[cw_serde]
+pub struct ChannelResponse {
+    pub channel_id: String,
+    pub connection_id: String,
+    pub counterparty_port_id: String,
+    pub counterparty_channel_id: String,
+    pub escrow: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
 
 // TODO: Write a macro to generate this struct from the Config struct
 #[cw_serde]
@@ -35,22 +1028,127 @@ pub struct UpdateConfig {
     pub poa_admin: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rate: Option<String>,
+    // Rejected, the bounds are only changed through a quorum action. Kept so an update setting
+    // them fails instead of ignoring them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_floor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_ceiling: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub source_denom: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub target_denom: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub paused: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strict_denoms: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_exponent: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_exponent: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_symbol: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_symbol: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_senders: Option<SenderClass>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epoch_length: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub require_group_policy: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_group_threshold: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flat_fee: Option<Coin>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verifier: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redact_senders: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_strategy: Option<RateStrategy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub float_payouts: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolver: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash_authz_payloads: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_model: Option<FeeModel>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_convert_amount: Option<Uint256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_convert_amount: Option<Uint256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_quota: Option<Uint256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epoch_cap: Option<Uint256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fees_to_community_pool: Option<bool>,
 }
 
 impl UpdateConfig {
+    // Names of the fields of an update, as listed by `changes`
+    pub const FIELDS: &'static [&'static str] = &[
+        "poa_admin",
+        "rate",
+        "source_denom",
+        "target_denom",
+        "paused",
+        "strict_denoms",
+        "source_exponent",
+        "target_exponent",
+        "source_symbol",
+        "target_symbol",
+        "allowed_senders",
+        "epoch_length",
+        "require_group_policy",
+        "min_group_threshold",
+        "flat_fee",
+        "verifier",
+        "redact_senders",
+        "rate_strategy",
+        "float_payouts",
+        "resolver",
+        "hash_authz_payloads",
+        "fee_model",
+        "min_convert_amount",
+        "max_convert_amount",
+        "daily_quota",
+        "epoch_cap",
+        "fees_to_community_pool",
+    ];
+
     // Check if no fields are set in this update
     pub fn is_empty(&self) -> bool {
         self.poa_admin.is_none()
             && self.rate.is_none()
+            && self.rate_floor.is_none()
+            && self.rate_ceiling.is_none()
             && self.source_denom.is_none()
             && self.target_denom.is_none()
             && self.paused.is_none()
+            && self.strict_denoms.is_none()
+            && self.source_exponent.is_none()
+            && self.target_exponent.is_none()
+            && self.source_symbol.is_none()
+            && self.target_symbol.is_none()
+            && self.allowed_senders.is_none()
+            && self.epoch_length.is_none()
+            && self.require_group_policy.is_none()
+            && self.min_group_threshold.is_none()
+            && self.flat_fee.is_none()
+            && self.verifier.is_none()
+            && self.redact_senders.is_none()
+            && self.rate_strategy.is_none()
+            && self.float_payouts.is_none()
+            && self.resolver.is_none()
+            && self.hash_authz_payloads.is_none()
+            && self.fee_model.is_none()
+            && self.min_convert_amount.is_none()
+            && self.max_convert_amount.is_none()
+            && self.daily_quota.is_none()
+            && self.epoch_cap.is_none()
+            && self.fees_to_community_pool.is_none()
     }
 
     // Check if applying this update to the given config would result in no changes
@@ -67,18 +1165,263 @@ impl UpdateConfig {
                     .as_ref()
                     .map(|r| r == &other.rate.as_ref().to_string())
                     .unwrap_or(true))
+            // Updates of the bounds are rejected rather than skipped
+            && self.rate_floor.is_none()
+            && self.rate_ceiling.is_none()
             && (self.source_denom.is_none()
                 || self
                     .source_denom
                     .as_ref()
-                    .map(|d| d == other.source_denom.as_str())
+                    .map(|d| other.source_denom.matches(d))
                     .unwrap_or(true))
             && (self.target_denom.is_none()
                 || self
                     .target_denom
                     .as_ref()
-                    .map(|d| d == other.target_denom.as_str())
+                    .map(|d| other.target_denom.matches(d))
                     .unwrap_or(true))
             && (self.paused.is_none() || self.paused.map(|p| p == other.paused).unwrap_or(true))
+            && (self.strict_denoms.is_none() || self.strict_denoms == other.strict_denoms)
+            && (self.source_exponent.is_none() || self.source_exponent == other.source_exponent)
+            && (self.target_exponent.is_none() || self.target_exponent == other.target_exponent)
+            && (self.source_symbol.is_none() || self.source_symbol == other.source_symbol)
+            && (self.target_symbol.is_none() || self.target_symbol == other.target_symbol)
+            && (self.allowed_senders.is_none() || self.allowed_senders == other.allowed_senders)
+            && (self.epoch_length.is_none() || self.epoch_length == other.epoch_length)
+            && (self.require_group_policy.is_none()
+                || self.require_group_policy == other.require_group_policy)
+            && (self.min_group_threshold.is_none()
+                || self.min_group_threshold == other.min_group_threshold)
+            && (self.flat_fee.is_none() || self.flat_fee == other.flat_fee)
+            && (self.verifier.is_none()
+                || self.verifier.as_deref() == other.verifier.as_ref().map(Addr::as_str))
+            && (self.redact_senders.is_none() || self.redact_senders == other.redact_senders)
+            && (self.rate_strategy.is_none() || self.rate_strategy == other.rate_strategy)
+            && (self.float_payouts.is_none() || self.float_payouts == other.float_payouts)
+            && (self.resolver.is_none()
+                || self.resolver.as_deref() == other.resolver.as_ref().map(Addr::as_str))
+            && (self.hash_authz_payloads.is_none()
+                || self.hash_authz_payloads == other.hash_authz_payloads)
+            && (self.fee_model.is_none() || self.fee_model == other.fee_model)
+            && (self.min_convert_amount.is_none()
+                || self.min_convert_amount == other.min_convert_amount)
+            && (self.max_convert_amount.is_none()
+                || self.max_convert_amount == other.max_convert_amount)
+            && (self.daily_quota.is_none() || self.daily_quota == other.daily_quota)
+            && (self.epoch_cap.is_none() || self.epoch_cap == other.epoch_cap)
+            && (self.fees_to_community_pool.is_none()
+                || self.fees_to_community_pool == other.fees_to_community_pool)
+    }
+
+    // List the fields applying this update to the given config would change, compared the
+    // same way as in `is_noop`
+    pub fn changes(&self, other: &Config) -> Vec<ConfigChange> {
+        fn opt<T: ToString>(value: &Option<T>) -> Option<String> {
+            value.as_ref().map(T::to_string)
+        }
+
+        let mut changes = vec![];
+        let mut push = |field: &str, old: Option<String>, new: Option<String>| {
+            if let Some(new) = new.filter(|new| old.as_ref() != Some(new)) {
+                changes.push(ConfigChange {
+                    field: field.to_string(),
+                    old,
+                    new,
+                });
+            }
+        };
+        push(
+            "poa_admin",
+            Some(other.poa_admin.to_string()),
+            self.poa_admin.clone(),
+        );
+        push(
+            "rate",
+            Some(other.rate.as_ref().to_string()),
+            self.rate.clone(),
+        );
+        push(
+            "source_denom",
+            Some(other.source_denom.to_string()),
+            self.source_denom.clone(),
+        );
+        push(
+            "target_denom",
+            Some(other.target_denom.to_string()),
+            self.target_denom.clone(),
+        );
+        push("paused", Some(other.paused.to_string()), opt(&self.paused));
+        push(
+            "strict_denoms",
+            opt(&other.strict_denoms),
+            opt(&self.strict_denoms),
+        );
+        push(
+            "source_exponent",
+            opt(&other.source_exponent),
+            opt(&self.source_exponent),
+        );
+        push(
+            "target_exponent",
+            opt(&other.target_exponent),
+            opt(&self.target_exponent),
+        );
+        push(
+            "source_symbol",
+            other.source_symbol.clone(),
+            self.source_symbol.clone(),
+        );
+        push(
+            "target_symbol",
+            other.target_symbol.clone(),
+            self.target_symbol.clone(),
+        );
+        push(
+            "allowed_senders",
+            other
+                .allowed_senders
+                .as_ref()
+                .map(|s| s.as_str().to_string()),
+            self.allowed_senders
+                .as_ref()
+                .map(|s| s.as_str().to_string()),
+        );
+        push(
+            "epoch_length",
+            opt(&other.epoch_length),
+            opt(&self.epoch_length),
+        );
+        push(
+            "require_group_policy",
+            opt(&other.require_group_policy),
+            opt(&self.require_group_policy),
+        );
+        push(
+            "min_group_threshold",
+            opt(&other.min_group_threshold),
+            opt(&self.min_group_threshold),
+        );
+        push("flat_fee", opt(&other.flat_fee), opt(&self.flat_fee));
+        push("verifier", opt(&other.verifier), self.verifier.clone());
+        push(
+            "redact_senders",
+            opt(&other.redact_senders),
+            opt(&self.redact_senders),
+        );
+        push(
+            "rate_strategy",
+            other.rate_strategy.as_ref().map(strategy_json),
+            self.rate_strategy.as_ref().map(strategy_json),
+        );
+        push(
+            "float_payouts",
+            opt(&other.float_payouts),
+            opt(&self.float_payouts),
+        );
+        push("resolver", opt(&other.resolver), self.resolver.clone());
+        push(
+            "hash_authz_payloads",
+            opt(&other.hash_authz_payloads),
+            opt(&self.hash_authz_payloads),
+        );
+        push(
+            "fee_model",
+            other.fee_model.as_ref().map(strategy_json),
+            self.fee_model.as_ref().map(strategy_json),
+        );
+        push(
+            "min_convert_amount",
+            opt(&other.min_convert_amount),
+            opt(&self.min_convert_amount),
+        );
+        push(
+            "max_convert_amount",
+            opt(&other.max_convert_amount),
+            opt(&self.max_convert_amount),
+        );
+        push(
+            "daily_quota",
+            opt(&other.daily_quota),
+            opt(&self.daily_quota),
+        );
+        push("epoch_cap", opt(&other.epoch_cap), opt(&self.epoch_cap));
+        push(
+            "fees_to_community_pool",
+            opt(&other.fees_to_community_pool),
+            opt(&self.fees_to_community_pool),
+        );
+        changes
     }
 }
+
+// Strategies and fee models are compared and reported in their JSON form
+fn strategy_json(strategy: &impl Serialize) -> String {
+    to_json_string(strategy).unwrap_or_default()
+}
+
+// A config field an update would change, with its current and proposed values. Unset fields
+// have no current value
+#[cw_serde]
+pub struct ConfigChange {
+    pub field: String,
+    pub old: Option<String>,
+    pub new: String,
+}
+
+#[cw_serde]
+pub struct DelegationResponse {
+    pub delegate: Addr,
+    pub delegation: Delegation,
+}
+
+#[cw_serde]
+pub struct ConfigDiffResponse {
+    pub changes: Vec<ConfigChange>,
+    // The error `update_config` would fail with, if any
+    pub error: Option<String>,
+}
+
+// Values are stored as JSON. The key of an item is its namespace, the key of a map entry is
+// its namespace and components encoded by cw-storage-plus, see the README
+#[cw_serde]
+pub struct StorageLayoutResponse {
+    pub entries: Vec<StorageEntry>,
+}
+
+// Static estimates compiled into the contract, not measured against the current state. A
+// conversion split among recipients needs `per_recipient` more gas for each extra recipient
+#[cw_serde]
+pub struct GasHintsResponse {
+    pub hints: Vec<GasHint>,
+    pub per_recipient: u64,
+}
+
+#[cw_serde]
+pub struct GasHint {
+    pub message: String,
+    pub gas: u64,
+}
+
+// Errors sorted by code. Messages are the ones returned by failing messages, with `{0}` in
+// place of the value of a detail, and codes never change meaning once released
+#[cw_serde]
+pub struct ErrorCatalogResponse {
+    pub errors: Vec<ErrorCatalogEntry>,
+}
+
+#[cw_serde]
+pub struct ErrorCatalogEntry {
+    pub code: u32,
+    pub message: String,
+}
+
+#[cw_serde]
+pub struct StorageEntry {
+    pub name: String,
+    pub namespace: String,
+    // "item" or "map"
+    pub kind: String,
+    // Types of the key components of a map, in order: "u64", "string" or "addr"
+    pub key: Vec<String>,
+    pub value: String,
+}
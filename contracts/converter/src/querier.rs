@@ -0,0 +1,156 @@
+use cosmwasm_std::{Addr, DenomMetadata, QuerierWrapper, StdResult, Uint256};
+use manifest_std::cosmos::authz::v1beta1::{Grant, QueryGrantsRequest, QueryGrantsResponse};
+use manifest_std::cosmos::group::v1::{
+    GroupPolicyInfo, QueryGroupPolicyInfoRequest, QueryGroupPolicyInfoResponse,
+};
+use manifest_std::osmosis::tokenfactory::v1beta1::{
+    QueryDenomAuthorityMetadataRequest, QueryDenomAuthorityMetadataResponse,
+};
+use prost::Message;
+
+// Semver of the querier methods, independent of the message API. Bump the major version when
+// a method is removed or changes meaning, the minor version when one is added, e.g. for a
+// custom query of the Manifest chain
+pub const QUERIER_VERSION: &str = "1.0.0";
+
+// gRPC paths of the chain queries, only ever built here
+const AUTHZ_GRANTS_PATH: &str = "/cosmos.authz.v1beta1.Query/Grants";
+const GROUP_POLICY_INFO_PATH: &str = "/cosmos.group.v1.Query/GroupPolicyInfo";
+const DENOM_AUTHORITY_METADATA_PATH: &str =
+    "/osmosis.tokenfactory.v1beta1.Query/DenomAuthorityMetadata";
+
+// Typed access to the queries of the chain modules. Queries a chain may not serve, or answers
+// it for unknown entries with an error, return None instead of failing the message
+#[derive(Clone, Copy)]
+pub struct ManifestQuerier<'a> {
+    querier: QuerierWrapper<'a>,
+}
+
+impl<'a> ManifestQuerier<'a> {
+    pub fn new(querier: QuerierWrapper<'a>) -> Self {
+        Self { querier }
+    }
+
+    // Total supply of the denom, zero for an unknown denom
+    pub fn supply(&self, denom: &str) -> StdResult<Uint256> {
+        Ok(self.querier.query_supply(denom)?.amount)
+    }
+
+    // Bank metadata of the denom. Chains fail the query for a denom without metadata
+    pub fn denom_metadata(&self, denom: &str) -> Option<DenomMetadata> {
+        self.querier
+            .query_denom_metadata(denom)
+            .ok()
+            .filter(|metadata| !metadata.base.is_empty())
+    }
+
+    // The x/group policy account at the address. Accounts that are not group policies fail
+    // the query, reported as None like any malformed response
+    pub fn group_policy_info(&self, address: &Addr) -> Option<GroupPolicyInfo> {
+        let request = QueryGroupPolicyInfoRequest {
+            address: address.to_string(),
+        };
+        self.grpc::<QueryGroupPolicyInfoResponse>(GROUP_POLICY_INFO_PATH, request)?
+            .info
+            .filter(|info| info.address == address.as_str())
+    }
+
+    // Authz grants of the message type from the granter to the grantee, empty when none
+    pub fn authz_grants(
+        &self,
+        granter: &Addr,
+        grantee: &Addr,
+        type_url: &str,
+    ) -> Option<Vec<Grant>> {
+        let request = QueryGrantsRequest {
+            granter: granter.to_string(),
+            grantee: grantee.to_string(),
+            msg_type_url: type_url.to_string(),
+            pagination: None,
+        };
+        self.grpc::<QueryGrantsResponse>(AUTHZ_GRANTS_PATH, request)
+            .map(|response| response.grants)
+    }
+
+    // Admin of a tokenfactory denom, None for other denoms or when the admin was renounced
+    pub fn tokenfactory_admin(&self, denom: &str) -> Option<String> {
+        let request = QueryDenomAuthorityMetadataRequest {
+            denom: denom.to_string(),
+        };
+        self.grpc::<QueryDenomAuthorityMetadataResponse>(DENOM_AUTHORITY_METADATA_PATH, request)?
+            .authority_metadata
+            .map(|metadata| metadata.admin)
+            .filter(|admin| !admin.is_empty())
+    }
+
+    fn grpc<T: Message + Default>(&self, path: &str, request: impl Message) -> Option<T> {
+        let response = self
+            .querier
+            .query_grpc(path.to_string(), request.encode_to_vec().into())
+            .ok()?;
+        T::decode(response.as_slice()).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockQuerier;
+    use cosmwasm_std::{
+        from_json, Binary, ContractResult, Empty, GrpcQuery, Querier, QuerierResult, QueryRequest,
+        SystemError, SystemResult,
+    };
+    use manifest_std::osmosis::tokenfactory::v1beta1::DenomAuthorityMetadata;
+
+    // Answers the tokenfactory authority query of a single denom, failing every other query
+    struct TokenfactoryQuerier;
+
+    impl Querier for TokenfactoryQuerier {
+        fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+            let unsupported = |kind: &str| {
+                SystemResult::Err(SystemError::UnsupportedRequest {
+                    kind: kind.to_string(),
+                })
+            };
+            let Ok(QueryRequest::Grpc(GrpcQuery { path, data })) =
+                from_json::<QueryRequest<Empty>>(bin_request)
+            else {
+                return unsupported("non-grpc");
+            };
+            let request = QueryDenomAuthorityMetadataRequest::decode(data.as_slice()).unwrap();
+            if path != DENOM_AUTHORITY_METADATA_PATH || request.denom != "factory/admin/upwr" {
+                return SystemResult::Ok(ContractResult::Err("not found".to_string()));
+            }
+            let response = QueryDenomAuthorityMetadataResponse {
+                authority_metadata: Some(DenomAuthorityMetadata {
+                    admin: "admin".to_string(),
+                }),
+            };
+            SystemResult::Ok(ContractResult::Ok(Binary::from(response.encode_to_vec())))
+        }
+    }
+
+    #[test]
+    fn grpc_query() {
+        let querier = TokenfactoryQuerier;
+        let querier = ManifestQuerier::new(QuerierWrapper::new(&querier));
+        assert_eq!(
+            querier.tokenfactory_admin("factory/admin/upwr"),
+            Some("admin".to_string())
+        );
+        assert_eq!(querier.tokenfactory_admin("umfx"), None);
+    }
+
+    // Chains not serving a query make every gRPC method fall back to None
+    #[test]
+    fn grpc_query_unsupported() {
+        let querier = MockQuerier::<Empty>::new(&[]);
+        let querier = ManifestQuerier::new(QuerierWrapper::new(&querier));
+        let addr = Addr::unchecked("addr");
+        assert_eq!(querier.tokenfactory_admin("factory/admin/upwr"), None);
+        assert_eq!(querier.group_policy_info(&addr), None);
+        assert_eq!(querier.authz_grants(&addr, &addr, "/type.Url"), None);
+        assert_eq!(querier.denom_metadata("umfx"), None);
+        assert_eq!(querier.supply("umfx").unwrap(), Uint256::zero());
+    }
+}
@@ -0,0 +1,112 @@
+use crate::error::ContractError;
+use crate::error::QuorumError::{DuplicateMember, InvalidThreshold, NoMembers};
+use crate::msg::QuorumAction;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Addr;
+
+// N-of-M approval policy guarding destructive actions
+#[cw_serde]
+pub struct Quorum {
+    pub members: Vec<Addr>,
+    pub threshold: u32,
+}
+
+// A destructive action waiting for quorum approval
+#[cw_serde]
+pub struct Proposal {
+    pub id: u64,
+    pub action: QuorumAction,
+    pub proposer: Addr,
+    pub approvals: Vec<Addr>,
+}
+
+impl Quorum {
+    #[inline]
+    pub fn new(members: Vec<Addr>, threshold: u32) -> Result<Self, ContractError> {
+        let q = Quorum { members, threshold };
+        q.validate()?;
+        Ok(q)
+    }
+
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.members.is_empty() {
+            return Err(ContractError::QuorumError(NoMembers));
+        }
+        let mut unique = self.members.clone();
+        unique.sort();
+        unique.dedup();
+        if unique.len() != self.members.len() {
+            return Err(ContractError::QuorumError(DuplicateMember));
+        }
+        if self.threshold == 0 || self.threshold as usize > self.members.len() {
+            return Err(ContractError::QuorumError(InvalidThreshold));
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn is_member(&self, addr: &Addr) -> bool {
+        self.members.contains(addr)
+    }
+
+    // Only approvals from current members count, members may have been removed since
+    pub fn is_met(&self, approvals: &[Addr]) -> bool {
+        let count = approvals.iter().filter(|a| self.is_member(a)).count();
+        count >= self.threshold as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Quorum;
+    use crate::error::{ContractError, QuorumError};
+    use cosmwasm_std::Addr;
+
+    fn addrs(names: &[&str]) -> Vec<Addr> {
+        names.iter().map(|n| Addr::unchecked(*n)).collect()
+    }
+
+    #[test]
+    fn test_quorum() {
+        let q = Quorum::new(addrs(&["a", "b", "c"]), 2).unwrap();
+        assert!(q.is_member(&Addr::unchecked("a")));
+        assert!(!q.is_member(&Addr::unchecked("d")));
+    }
+
+    #[test]
+    fn test_quorum_no_members() {
+        assert!(matches!(
+            Quorum::new(vec![], 1).unwrap_err(),
+            ContractError::QuorumError(QuorumError::NoMembers)
+        ));
+    }
+
+    #[test]
+    fn test_quorum_duplicate_member() {
+        assert!(matches!(
+            Quorum::new(addrs(&["a", "b", "a"]), 1).unwrap_err(),
+            ContractError::QuorumError(QuorumError::DuplicateMember)
+        ));
+    }
+
+    #[test]
+    fn test_quorum_invalid_threshold() {
+        assert!(matches!(
+            Quorum::new(addrs(&["a", "b"]), 0).unwrap_err(),
+            ContractError::QuorumError(QuorumError::InvalidThreshold)
+        ));
+        assert!(matches!(
+            Quorum::new(addrs(&["a", "b"]), 3).unwrap_err(),
+            ContractError::QuorumError(QuorumError::InvalidThreshold)
+        ));
+    }
+
+    #[test]
+    fn test_quorum_is_met() {
+        let q = Quorum::new(addrs(&["a", "b", "c"]), 2).unwrap();
+        assert!(!q.is_met(&addrs(&["a"])));
+        assert!(q.is_met(&addrs(&["a", "b"])));
+        // Approvals from non-members are ignored
+        assert!(!q.is_met(&addrs(&["a", "d"])));
+    }
+}
@@ -0,0 +1,146 @@
+use crate::consts::DAY_SECONDS;
+use crate::error::ContractError;
+use crate::error::ConvertError::QuotaExceeded;
+use crate::state::{Config, QUOTA_USAGE};
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Timestamp, Uint256};
+
+// Key of a sender on the counterparty chain of a channel. Their address is not valid on this
+// chain, and cannot collide with local ones which never contain a slash
+#[cfg(feature = "ibc")]
+pub fn remote_sender(channel_id: &str, sender: &str) -> Addr {
+    Addr::unchecked(format!("{channel_id}/{sender}"))
+}
+
+// Day of the block time, days starting at midnight UTC
+pub fn day(time: Timestamp) -> u64 {
+    time.seconds() / DAY_SECONDS
+}
+
+// Source base units the sender converted on the day of the block time
+pub fn used(storage: &dyn Storage, sender: &Addr, time: Timestamp) -> StdResult<Uint256> {
+    Ok(QUOTA_USAGE
+        .may_load(storage, (sender, day(time)))?
+        .unwrap_or_default())
+}
+
+// What the sender can still convert today, None without a daily quota
+pub fn remaining(
+    storage: &dyn Storage,
+    config: &Config,
+    sender: &Addr,
+    time: Timestamp,
+) -> StdResult<Option<Uint256>> {
+    let Some(quota) = config.daily_quota else {
+        return Ok(None);
+    };
+    Ok(Some(quota.saturating_sub(used(storage, sender, time)?)))
+}
+
+// Count a conversion against the daily quota of the sender, failing when it exceeds what is
+// left of it. The usage of previous days is pruned, only today's is kept
+pub fn consume(
+    storage: &mut dyn Storage,
+    config: &Config,
+    sender: &Addr,
+    time: Timestamp,
+    amount: Uint256,
+) -> Result<(), ContractError> {
+    let Some(quota) = config.daily_quota else {
+        return Ok(());
+    };
+    let today = day(time);
+    let used = used(storage, sender, time)?;
+    let total = used.saturating_add(amount);
+    if total > quota {
+        return Err(ContractError::ConvertError(QuotaExceeded));
+    }
+
+    let past = QUOTA_USAGE
+        .prefix(sender)
+        .keys(storage, None, None, Order::Ascending)
+        .filter(|day| !matches!(day, Ok(day) if *day == today))
+        .collect::<StdResult<Vec<_>>>()?;
+    for day in past {
+        QUOTA_USAGE.remove(storage, (sender, day));
+    }
+    QUOTA_USAGE.save(storage, (sender, today), &total)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ConvertError;
+    use crate::rate::Rate;
+    use crate::state::Config;
+    use cosmwasm_std::testing::MockStorage;
+
+    fn config(quota: Option<u32>) -> Config {
+        let mut config = Config::try_with_defaults(Rate::parse("1").unwrap()).unwrap();
+        config.daily_quota = quota.map(Uint256::from);
+        config
+    }
+
+    #[test]
+    fn consume_within_day() {
+        let mut storage = MockStorage::new();
+        let config = config(Some(100));
+        let sender = Addr::unchecked("sender");
+        let now = Timestamp::from_seconds(3 * DAY_SECONDS + 10);
+        consume(&mut storage, &config, &sender, now, 60u32.into()).unwrap();
+        assert_eq!(
+            remaining(&storage, &config, &sender, now).unwrap(),
+            Some(40u32.into())
+        );
+        assert!(matches!(
+            consume(&mut storage, &config, &sender, now, 41u32.into()).unwrap_err(),
+            ContractError::ConvertError(ConvertError::QuotaExceeded)
+        ));
+        consume(&mut storage, &config, &sender, now, 40u32.into()).unwrap();
+        assert_eq!(
+            remaining(&storage, &config, &sender, now).unwrap(),
+            Some(Uint256::zero())
+        );
+
+        // Other senders have their own quota
+        let other = Addr::unchecked("other");
+        assert_eq!(
+            remaining(&storage, &config, &other, now).unwrap(),
+            Some(100u32.into())
+        );
+    }
+
+    #[test]
+    fn consume_resets_daily() {
+        let mut storage = MockStorage::new();
+        let config = config(Some(100));
+        let sender = Addr::unchecked("sender");
+        let now = Timestamp::from_seconds(3 * DAY_SECONDS - 1);
+        consume(&mut storage, &config, &sender, now, 100u32.into()).unwrap();
+
+        let tomorrow = now.plus_seconds(1);
+        assert_eq!(
+            remaining(&storage, &config, &sender, tomorrow).unwrap(),
+            Some(100u32.into())
+        );
+        consume(&mut storage, &config, &sender, tomorrow, 10u32.into()).unwrap();
+        // Only today's usage is kept
+        let days: Vec<_> = QUOTA_USAGE
+            .prefix(&sender)
+            .keys(&storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()
+            .unwrap();
+        assert_eq!(days, [day(tomorrow)]);
+    }
+
+    #[test]
+    fn consume_without_quota() {
+        let mut storage = MockStorage::new();
+        let config = config(None);
+        let sender = Addr::unchecked("sender");
+        let now = Timestamp::from_seconds(0);
+        consume(&mut storage, &config, &sender, now, Uint256::MAX).unwrap();
+        assert_eq!(remaining(&storage, &config, &sender, now).unwrap(), None);
+        assert!(QUOTA_USAGE.is_empty(&storage));
+    }
+}
@@ -36,11 +36,6 @@ impl Rate {
         self.0
     }
 
-    #[inline]
-    pub fn as_ref(&self) -> &RateInner {
-        &self.0
-    }
-
     fn _parse(s: &str) -> Result<Decimal256, ContractError> {
         s.parse::<Decimal256>()
             .map_err(|_| ContractError::RateError(InvalidRateParsing))
@@ -58,6 +53,33 @@ impl Rate {
 
     #[inline]
     pub fn apply_to(&self, amount: impl Into<Uint256>) -> Result<Uint256, ContractError> {
+        self.apply_scaled(amount, 0, 0)
+    }
+
+    // Apply the rate to an amount of source base units and scale the result to the target
+    // denom, so the rate can be expressed in display units when the exponents differ
+    pub fn apply_scaled(
+        &self,
+        amount: impl Into<Uint256>,
+        source_exponent: u32,
+        target_exponent: u32,
+    ) -> Result<Uint256, ContractError> {
+        let res = self.apply_exact(amount, source_exponent, target_exponent)?;
+        let floor = res.to_uint_floor();
+        if floor.is_zero() {
+            return Err(ContractError::RateError(ApplyZeroError));
+        }
+        Ok(floor)
+    }
+
+    // Target base units an amount converts to before flooring, the fraction being the value
+    // lost to rounding
+    pub fn apply_exact(
+        &self,
+        amount: impl Into<Uint256>,
+        source_exponent: u32,
+        target_exponent: u32,
+    ) -> Result<Decimal256, ContractError> {
         let amount = amount.into();
         if amount.is_zero() {
             return Err(ContractError::AmountError(AmountIsZero));
@@ -68,15 +90,76 @@ impl Rate {
             .0
             .checked_mul(amount_dec)
             .map_err(|_| ContractError::RateError(ApplyOverflowError))?;
+        rescale(res, source_exponent, target_exponent)
+    }
 
-        let floor = res.to_uint_floor();
-        if floor.is_zero() {
-            return Err(ContractError::RateError(ApplyZeroError));
+    // The rate expressed in target base units per source base unit, for a rate expressed in
+    // display units. Precision below 18 decimals is truncated
+    pub fn to_base_units(
+        &self,
+        source_exponent: u32,
+        target_exponent: u32,
+    ) -> Result<Decimal256, ContractError> {
+        rescale(self.0, source_exponent, target_exponent)
+    }
+
+    // Smallest amount of source base units `apply_scaled` converts to at least `desired` target
+    // base units, accounting for its flooring
+    pub fn reverse_scaled(
+        &self,
+        desired: Uint256,
+        source_exponent: u32,
+        target_exponent: u32,
+    ) -> Result<Uint256, ContractError> {
+        if desired.is_zero() {
+            return Err(ContractError::AmountError(AmountIsZero));
         }
-        Ok(floor)
+        let desired_dec = Decimal256::from_atomics(desired, 0)
+            .map_err(|_| ContractError::AmountError(AmountExceedsMax))?;
+        let converts =
+            |amount: Uint256| match self.apply_scaled(amount, source_exponent, target_exponent) {
+                Ok(minted) => Ok(minted >= desired),
+                Err(ContractError::RateError(ApplyZeroError)) => Ok(false),
+                Err(e) => Err(e),
+            };
+
+        // The rate in base units is truncated, so the estimate can be off by a unit either way
+        let mut amount = desired_dec
+            .checked_div(self.to_base_units(source_exponent, target_exponent)?)
+            .map_err(|_| ContractError::RateError(ApplyOverflowError))?
+            .to_uint_ceil()
+            .max(Uint256::one());
+        while !converts(amount)? {
+            amount = amount
+                .checked_add(Uint256::one())
+                .map_err(|_| ContractError::RateError(ApplyOverflowError))?;
+        }
+        while amount > Uint256::one() && converts(amount - Uint256::one())? {
+            amount -= Uint256::one();
+        }
+        Ok(amount)
     }
 }
 
+// Scale a value by 10^(target - source)
+fn rescale(
+    value: Decimal256,
+    source_exponent: u32,
+    target_exponent: u32,
+) -> Result<Decimal256, ContractError> {
+    let scale = Uint256::from(10u8)
+        .checked_pow(source_exponent.abs_diff(target_exponent))
+        .ok()
+        .and_then(|s| Decimal256::from_atomics(s, 0).ok())
+        .ok_or(ContractError::RateError(ApplyOverflowError))?;
+    let scaled = if target_exponent >= source_exponent {
+        value.checked_mul(scale).ok()
+    } else {
+        value.checked_div(scale).ok()
+    };
+    scaled.ok_or(ContractError::RateError(ApplyOverflowError))
+}
+
 impl From<Rate> for RateInner {
     fn from(value: Rate) -> Self {
         value.0
@@ -89,6 +172,13 @@ impl std::fmt::Display for Rate {
     }
 }
 
+impl AsRef<RateInner> for Rate {
+    #[inline]
+    fn as_ref(&self) -> &RateInner {
+        &self.0
+    }
+}
+
 impl TryFrom<RateInner> for Rate {
     type Error = ContractError;
     fn try_from(value: RateInner) -> Result<Self, Self::Error> {
@@ -108,7 +198,7 @@ mod tests {
     use super::{Rate, RateInner};
     use crate::error::RateError::ApplyZeroError;
     use crate::error::{AmountError, ContractError, RateError};
-    use cosmwasm_std::{Uint128, Uint256};
+    use cosmwasm_std::{Decimal256, Uint128, Uint256};
     use std::str::FromStr;
 
     #[test]
@@ -154,6 +244,16 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_rate_apply_exact() {
+        let rate = Rate::parse("0.3333").unwrap();
+        let exact = rate.apply_exact(1_000u128, 0, 0).unwrap();
+        assert_eq!(exact, Decimal256::from_str("333.3").unwrap());
+        assert_eq!(rate.apply_to(1_000u128).unwrap(), exact.to_uint_floor());
+        let exact = rate.apply_exact(1_000u128, 6, 3).unwrap();
+        assert_eq!(exact, Decimal256::from_str("0.3333").unwrap());
+    }
+
     #[test]
     fn test_rate_invalid() {
         assert!(matches!(
@@ -236,4 +336,94 @@ mod tests {
             ContractError::RateError(ApplyZeroError)
         ));
     }
+
+    #[test]
+    fn test_rate_apply_scaled() {
+        let r = Rate::parse("0.5").unwrap();
+        // 1 MFX (6 decimals) = 0.5 PWR (18 decimals)
+        assert_eq!(
+            r.apply_scaled(1_000_000u32, 6, 18).unwrap(),
+            Uint256::from(500_000_000_000_000_000u128)
+        );
+        // 1 PWR (18 decimals) = 0.5 MFX (6 decimals)
+        assert_eq!(
+            r.apply_scaled(1_000_000_000_000_000_000u128, 18, 6)
+                .unwrap(),
+            Uint256::from(500_000u32)
+        );
+        assert_eq!(
+            r.apply_scaled(100u8, 6, 6).unwrap(),
+            r.apply_to(100u8).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rate_apply_scaled_zero_result() {
+        let r = Rate::parse("0.5").unwrap();
+        assert!(matches!(
+            r.apply_scaled(1_000_000_000_000u64, 18, 6).unwrap_err(),
+            ContractError::RateError(ApplyZeroError)
+        ));
+    }
+
+    #[test]
+    fn test_rate_apply_scaled_overflow() {
+        let r = Rate::parse(&RateInner::MAX.to_string()).unwrap();
+        assert!(matches!(
+            r.apply_scaled(1u8, 0, 1).unwrap_err(),
+            ContractError::RateError(RateError::ApplyOverflowError)
+        ));
+    }
+
+    #[test]
+    fn test_rate_to_base_units() {
+        let r = Rate::parse("0.5").unwrap();
+        assert_eq!(
+            r.to_base_units(6, 18).unwrap(),
+            RateInner::from_str("500000000000").unwrap()
+        );
+        assert_eq!(
+            r.to_base_units(18, 6).unwrap(),
+            RateInner::from_str("0.0000000000005").unwrap()
+        );
+        assert_eq!(r.to_base_units(6, 6).unwrap(), r.into_inner());
+    }
+
+    #[test]
+    fn test_rate_reverse_scaled() {
+        let cases = [
+            ("0.5", 0, 0, 1u128, 2u128),
+            ("0.5", 0, 0, 3, 6),
+            ("3", 0, 0, 10, 4),
+            ("0.333333333333333333", 0, 0, 1, 4),
+            ("0.333333333333333333", 0, 0, 1_000, 3_001),
+            ("0.5", 6, 18, 500_000_000_000_000_000, 1_000_000),
+            ("0.5", 6, 18, 500_000_000_000_000_001, 1_000_001),
+            ("0.5", 18, 6, 1, 2_000_000_000_000),
+            ("1.5", 6, 6, 1_000, 667),
+        ];
+        for (rate, s, t, desired, expected) in cases {
+            let r = Rate::parse(rate).unwrap();
+            let amount = r.reverse_scaled(Uint256::from(desired), s, t).unwrap();
+            assert_eq!(amount, Uint256::from(expected), "{rate} for {desired}");
+            // The amount mints the desired amount, one unit less does not
+            assert!(r.apply_scaled(amount, s, t).unwrap() >= Uint256::from(desired));
+            assert!(!r
+                .apply_scaled(amount - Uint256::one(), s, t)
+                .is_ok_and(|minted| minted >= Uint256::from(desired)));
+        }
+    }
+
+    #[test]
+    fn test_rate_reverse_scaled_invalid() {
+        let r = Rate::parse("0.5").unwrap();
+        assert!(matches!(
+            r.reverse_scaled(Uint256::zero(), 0, 0).unwrap_err(),
+            ContractError::AmountError(AmountError::AmountIsZero)
+        ));
+        assert!(matches!(
+            r.reverse_scaled(Uint256::MAX, 0, 0).unwrap_err(),
+            ContractError::AmountError(AmountError::AmountExceedsMax)
+        ));
+    }
 }
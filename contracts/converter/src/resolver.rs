@@ -0,0 +1,59 @@
+use crate::error::ConfigError::{ResolverNotContract, UnresolvedName};
+use crate::error::ContractError;
+use crate::msg::{ResolveResponse, ResolverQueryMsg};
+use crate::state::Config;
+use cosmwasm_std::{Attribute, Deps};
+
+// Address given to the contract, resolved from `name` when it was given as one
+pub(crate) struct Resolved {
+    pub address: String,
+    pub name: Option<String>,
+}
+
+impl Resolved {
+    // Record of the resolution for the events, e.g. `treasury=manifest1...`
+    pub fn attribute(&self) -> Option<Attribute> {
+        self.name
+            .as_ref()
+            .map(|name| Attribute::new("resolved", format!("{name}={}", self.address)))
+    }
+}
+
+// Ensure the resolver is an existing contract. An account would fail every name lookup
+pub(crate) fn check_resolver(deps: Deps, config: &Config) -> Result<(), ContractError> {
+    if let Some(resolver) = &config.resolver {
+        deps.querier
+            .query_wasm_contract_info(resolver)
+            .map_err(|_| ContractError::ConfigError(ResolverNotContract))?;
+    }
+    Ok(())
+}
+
+// Resolve a name to an address through the resolver, at the time the message executes. Input
+// that is already a valid address is kept as is, as is any input when no resolver is set, and
+// is left to the caller to validate
+pub(crate) fn resolve(deps: Deps, config: &Config, input: &str) -> Result<Resolved, ContractError> {
+    let as_is = || Resolved {
+        address: input.to_string(),
+        name: None,
+    };
+    let Some(resolver) = &config.resolver else {
+        return Ok(as_is());
+    };
+    if deps.api.addr_validate(input).is_ok() {
+        return Ok(as_is());
+    }
+    let res: ResolveResponse = deps.querier.query_wasm_smart(
+        resolver,
+        &ResolverQueryMsg::Resolve {
+            name: input.to_string(),
+        },
+    )?;
+    let address = res
+        .address
+        .ok_or(ContractError::ConfigError(UnresolvedName))?;
+    Ok(Resolved {
+        address,
+        name: Some(input.to_string()),
+    })
+}
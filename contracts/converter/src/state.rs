@@ -1,12 +1,35 @@
-use crate::consts::{default_source_denom, default_target_denom, DEFAULT_POA_ADMIN};
-use crate::denom::Denom;
-use crate::error::ConfigError::SameDenom;
+use crate::auction::{AuctionConfig, AuctionRound, Settlement};
+use crate::consts::{
+    default_source_denom, default_target_denom, BECH32_PREFIX, DAY_SECONDS, DEFAULT_POA_ADMIN,
+    EPOCH_RETENTION, MAX_DENOM_EXPONENT, MAX_DESCRIPTION_LEN, MAX_LABEL_LEN, MAX_URL_LEN,
+};
+use crate::denom::{same_denom, validate_symbol, Denom, DenomInfo};
+use crate::epoch::{Epoch, Totals};
+use crate::error::ConfigError::ConflictingFees;
+use crate::error::ConfigError::{
+    AddressPrefixMismatch, FieldNotDelegated, IncompleteExponents, InvalidBech32Prefix,
+    InvalidConvertLimits, InvalidDailyQuota, InvalidDelegation, InvalidEpochCap,
+    InvalidEpochLength, InvalidExponent, InvalidFlatFee, InvalidRateBounds, QuotasDisabled,
+    RateChangeExceedsLimit, RateOutOfBounds, SameDenom,
+};
 use crate::error::ContractError;
+use crate::error::MetadataError;
+use crate::fee::FeeModel;
+use crate::float::Float;
+use crate::msg::UpdateConfig;
+use crate::quorum::{Proposal, Quorum};
 use crate::rate::Rate;
+use crate::strategy::RateStrategy;
+use crate::stream::Stream;
+use bech32::Hrp;
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{
+    Addr, Binary, BlockInfo, Coin, Decimal256, Order, StdError, StdResult, Storage, Timestamp,
+    Uint256,
+};
 use cw_controllers::Admin;
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
+use manifest_access::{PendingAdmin, Roles};
 
 // Never rename/remove fields from this struct, only add optional fields to avoid
 // breaking changes. If you need to rename/remove a field, you must version the config
@@ -17,6 +40,90 @@ pub struct Config {
     pub source_denom: Denom,
     pub target_denom: Denom,
     pub paused: bool,
+    // Fail instead of warning when a configured denom has no supply and no metadata
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strict_denoms: Option<bool>,
+    // Display exponents of the denoms. When set, the rate is expressed in display units and
+    // minted amounts are scaled by the exponent difference
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_exponent: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_exponent: Option<u32>,
+    // Display symbols of the denoms, e.g. "MFX"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_symbol: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_symbol: Option<String>,
+    // Which kind of sender may convert, anyone when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_senders: Option<SenderClass>,
+    // Length of the statistics epochs in seconds, a single never-ending epoch when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epoch_length: Option<u64>,
+    // Whether the POA admin must be an x/group policy account, checked on every config change
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub require_group_policy: Option<bool>,
+    // Minimum threshold of the POA admin's decision policy, implies `require_group_policy`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_group_threshold: Option<u64>,
+    // Bech32 prefix of the configured addresses, `manifest` when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bech32_prefix: Option<String>,
+    // Flat fee attached to every conversion as a second coin alongside the source tokens, and
+    // sent to the POA admin
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flat_fee: Option<Coin>,
+    // Bounds the rate must stay within, whoever updates it. Only a quorum action can change
+    // them once the contract is instantiated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_floor: Option<Rate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_ceiling: Option<Rate>,
+    // Attestation contract answering `IsVerified { address }`, only verified senders may
+    // convert when set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verifier: Option<Addr>,
+    // Omit the addresses of users from emitted attributes, for privacy-sensitive deployments.
+    // They are still kept in the state records
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redact_senders: Option<bool>,
+    // How the rate of a conversion is resolved from `rate`, which applies as is when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_strategy: Option<RateStrategy>,
+    // Pay conversions out of the float of the target denom instead of minting, when it covers
+    // them, see FLOATS
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub float_payouts: Option<bool>,
+    // Name service contract answering `Resolve { name }`. When set, recipients, callback and
+    // notify target contracts can be given as names, resolved when the message executes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolver: Option<Addr>,
+    // Emit a hash of every message dispatched through authz instead of their type URLs and
+    // count, for matching them with the MsgExec executed on chain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash_authz_payloads: Option<bool>,
+    // Fee withheld from the converted source tokens and sent to the POA admin. Cannot be
+    // combined with `flat_fee`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_model: Option<FeeModel>,
+    // Smallest amount of source base units `convert` accepts, before any fee is withheld.
+    // Dust amounts would otherwise only fail once the rate floors them to zero
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_convert_amount: Option<Uint256>,
+    // Largest amount of source base units a single `convert` accepts, bounding mint sizes
+    // without pausing the contract. Non-zero and at least `min_convert_amount`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_convert_amount: Option<Uint256>,
+    // Source base units a sender may `convert` per day, fee included. Non-zero
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_quota: Option<Uint256>,
+    // Target base units all conversions together may mint per epoch, see `epoch_length`.
+    // Non-zero
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epoch_cap: Option<Uint256>,
+    // Send fees to the community pool through MsgFundCommunityPool instead of to the POA admin
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fees_to_community_pool: Option<bool>,
     // Future fields should be optional, e.g.
     //
     //   #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -25,9 +132,594 @@ pub struct Config {
     // If non-optional fields are added, config must be versioned and the migration handler must be updated
 }
 
+#[cw_serde]
+#[non_exhaustive]
+pub enum SenderClass {
+    Any,
+    // Externally owned accounts only, i.e. not contracts
+    AccountsOnly,
+    ContractsOnly,
+}
+
+impl SenderClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SenderClass::Any => "any",
+            SenderClass::AccountsOnly => "accounts_only",
+            SenderClass::ContractsOnly => "contracts_only",
+        }
+    }
+}
+
+// Receipt of a conversion, pending until its submessage replied
+#[cw_serde]
+pub struct Conversion {
+    pub id: u64,
+    pub sender: Addr,
+    pub burned: Coin,
+    pub minted: Coin,
+    // How the minted tokens are distributed
+    pub mints: Vec<Mint>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee: Option<Coin>,
+    pub status: ConversionStatus,
+    pub height: u64,
+    pub time: Timestamp,
+    // Rate applied and hash of the canonical receipt, see ReceiptPayload. Both are unset on
+    // receipts recorded before they were introduced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate: Option<Rate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<Binary>,
+    // Free text set by the sender, e.g. to match the conversion with an entry of an
+    // external ledger. Not part of the receipt hash
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+    // Set when the minted tokens were paid out of the float instead of minted, not part of
+    // the receipt hash either
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_float: Option<bool>,
+}
+
+// Callback of a conversion, forwarding the minted tokens to the contract once they exist
+#[cw_serde]
+pub struct PendingCallback {
+    pub contract: Addr,
+    pub msg: Binary,
+}
+
+// Config authority delegated by the admin to a sub-operator, e.g. an ops key allowed to
+// toggle `paused` and nudge the rate but not to change the denoms or the POA admin
+#[cw_serde]
+pub struct Delegation {
+    // Fields of `update_config` the delegate may change
+    pub fields: Vec<String>,
+    // Largest change of the rate within a day relative to the rate before the first delegated
+    // change of the day, e.g. 0.05 for 5%. Unlimited when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_rate_change: Option<Decimal256>,
+}
+
+impl Delegation {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        let mut fields = self.fields.clone();
+        fields.sort();
+        fields.dedup();
+        if fields.is_empty()
+            || fields.len() != self.fields.len()
+            || fields
+                .iter()
+                .any(|field| !UpdateConfig::FIELDS.contains(&field.as_str()))
+        {
+            return Err(ContractError::ConfigError(InvalidDelegation));
+        }
+        Ok(())
+    }
+
+    // Ensure applying the update to the config only changes delegated fields, and the rate
+    // within the delegated limit of the baseline
+    pub fn check(
+        &self,
+        config: &Config,
+        update: &UpdateConfig,
+        baseline: &Rate,
+    ) -> Result<(), ContractError> {
+        for change in update.changes(config) {
+            if !self.fields.contains(&change.field) {
+                return Err(ContractError::ConfigError(FieldNotDelegated(change.field)));
+            }
+        }
+        let (Some(max), Some(rate)) = (self.max_rate_change, &update.rate) else {
+            return Ok(());
+        };
+        let baseline = *baseline.as_ref();
+        let new = *Rate::parse(rate)?.as_ref();
+        let limit = baseline.checked_mul(max).map_err(StdError::from)?;
+        if new.abs_diff(baseline) > limit {
+            return Err(ContractError::ConfigError(RateChangeExceedsLimit));
+        }
+        Ok(())
+    }
+}
+
+// Rate before the first delegated rate change of a day. Delegated changes are limited
+// relative to it rather than to the current rate, so that several updates of the same day
+// cannot add up beyond the limit
+#[cw_serde]
+pub struct RateBaseline {
+    pub day: u64,
+    pub rate: Rate,
+}
+
+impl RateBaseline {
+    // Baseline of the day of the block time, the current rate when no delegated change was
+    // made that day
+    pub fn load(storage: &dyn Storage, config: &Config, time: Timestamp) -> StdResult<Self> {
+        let day = time.seconds() / DAY_SECONDS;
+        Ok(RATE_BASELINE
+            .may_load(storage)?
+            .filter(|baseline| baseline.day == day)
+            .unwrap_or_else(|| RateBaseline {
+                day,
+                rate: config.rate.clone(),
+            }))
+    }
+}
+
+// Why the contract was paused
+#[cw_serde]
+#[non_exhaustive]
+pub enum PauseReason {
+    Maintenance,
+    Incident,
+    Upgrade,
+    Other,
+}
+
+impl std::fmt::Display for PauseReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let reason = match self {
+            PauseReason::Maintenance => "maintenance",
+            PauseReason::Incident => "incident",
+            PauseReason::Upgrade => "upgrade",
+            PauseReason::Other => "other",
+        };
+        f.write_str(reason)
+    }
+}
+
+// Reason given by the admin for the current pause, cleared when the contract is unpaused
+#[cw_serde]
+pub struct PauseInfo {
+    pub reason: PauseReason,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    pub since: Timestamp,
+}
+
+impl std::fmt::Display for PauseInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.message {
+            Some(message) => write!(f, "{}: {}", self.reason, message),
+            None => write!(f, "{}", self.reason),
+        }
+    }
+}
+
+// Operator-provided self-description of the contract, for explorers and wallets
+#[cw_serde]
+pub struct Metadata {
+    pub label: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl Metadata {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.label.trim().is_empty() || self.label.len() > MAX_LABEL_LEN {
+            return Err(ContractError::MetadataError(MetadataError::Label));
+        }
+        if let Some(description) = &self.description {
+            if description.len() > MAX_DESCRIPTION_LEN {
+                return Err(ContractError::MetadataError(MetadataError::Description));
+            }
+        }
+        if let Some(url) = &self.url {
+            if url.len() > MAX_URL_LEN
+                || !(url.starts_with("https://") || url.starts_with("http://"))
+                || url.chars().any(char::is_whitespace)
+            {
+                return Err(ContractError::MetadataError(MetadataError::Url));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cw_serde]
+pub struct Mint {
+    pub recipient: Addr,
+    pub amount: Coin,
+}
+
+#[cw_serde]
+#[non_exhaustive]
+pub enum ConversionStatus {
+    Pending,
+    Succeeded,
+    // The source tokens were escrowed for the sender to claim back
+    Failed { error: String },
+}
+
+// A conversion requested by a convert packet, passed along its submessage until the reply
+#[cfg(feature = "ibc")]
+#[cw_serde]
+pub struct RemoteConversion {
+    pub channel_id: String,
+    pub burned: Coin,
+    pub minted: Coin,
+    pub rate: Rate,
+}
+
+// Hidden conversion committed with its funds, revealed in a later block. The funds may exceed
+// the converted amount to hide it, the excess is returned on reveal
+#[cw_serde]
+pub struct Commitment {
+    pub hash: Binary,
+    pub funds: Coin,
+    pub height: u64,
+    pub expires: Timestamp,
+}
+
+// Source tokens held by the contract for later conversions of their owner
+#[cw_serde]
+pub struct Deposit {
+    pub amount: Coin,
+    // secp256k1 key of the owner, signing the conversions debited from the deposit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pubkey: Option<Binary>,
+    // Nonce expected by the next signed conversion
+    pub nonce: u64,
+}
+
+// An admin of the contract and the block it took over at
+#[cfg(feature = "history")]
+#[cw_serde]
+pub struct AdminChange {
+    pub admin: Addr,
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+#[cfg(feature = "history")]
+#[cw_serde]
+pub struct Upgrade {
+    pub from_version: String,
+    pub to_version: String,
+    pub height: u64,
+    pub initiator: Addr,
+}
+
 // Never rename the storage keys
-pub const CONFIG: Item<Config> = Item::new("config");
+//
+// The config lives in a versioned namespace. When its stored shape changes, add a new
+// namespace, keep the previous one as the legacy fallback and move the data on migrate
+pub const CONFIG: Item<Config> = Item::new("config_v2");
+pub const LEGACY_CONFIG: Item<Config> = Item::new("config");
 pub const ADMIN: Admin = Admin::new("admin");
+// Candidate of a two-step admin transfer
+pub const PENDING_ADMIN: PendingAdmin = PendingAdmin::new("pending_admin");
+// Addresses holding a role granted by the admin, e.g. the pauser
+pub const ROLES: Roles = Roles::new("roles");
+// Every admin in order, starting from the one at instantiate (or at the first migration for
+// older deployments)
+#[cfg(feature = "history")]
+pub const ADMIN_HISTORY: Map<u64, AdminChange> = Map::new("admin_history");
+#[cfg(feature = "history")]
+pub const ADMIN_CHANGE_COUNT: Item<u64> = Item::new("admin_change_count");
+// Every migration executed, in order, starting with the first one after it was introduced
+#[cfg(feature = "history")]
+pub const UPGRADE_HISTORY: Map<u64, Upgrade> = Map::new("upgrade_history");
+#[cfg(feature = "history")]
+pub const UPGRADE_COUNT: Item<u64> = Item::new("upgrade_count");
+// Set by chain governance (sudo) only, blocks every execute message when true
+pub const FROZEN: Item<bool> = Item::new("frozen");
+// Whether the contract uses the authz grants it received, enabled when unset
+pub const AUTHZ_ENABLED: Item<bool> = Item::new("authz_enabled");
+// Config authority delegated by the admin, per delegate
+pub const DELEGATIONS: Map<&Addr, Delegation> = Map::new("delegations");
+// Baseline of the delegated rate changes of the day, cleared when the admin sets the rate
+pub const RATE_BASELINE: Item<RateBaseline> = Item::new("rate_baseline");
+// Reason of the current pause, when the contract was paused with one
+pub const PAUSE: Item<PauseInfo> = Item::new("pause");
+// Label, description and external url of the contract, unset when none was given
+pub const METADATA: Item<Metadata> = Item::new("metadata");
+// Contract alerted of critical events, set by the admin
+pub const NOTIFY_TARGET: Item<Addr> = Item::new("notify_target");
+// Contract user positions are moved to at the end of life of this one, set by the admin, and
+// how many were moved
+pub const SUCCESSOR: Item<Addr> = Item::new("successor");
+pub const MIGRATED_POSITIONS: Item<u64> = Item::new("migrated_positions");
+pub const QUORUM: Item<Quorum> = Item::new("quorum");
+pub const PROPOSALS: Map<u64, Proposal> = Map::new("proposals");
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+// Funds sent to the contract outside of a conversion, keyed by (depositor, denom), and
+// their running total per denom
+pub const UNSOLICITED: Map<(&Addr, &str), Uint256> = Map::new("unsolicited");
+pub const UNSOLICITED_TOTAL: Map<&str, Uint256> = Map::new("unsolicited_total");
+// Statistics of the epoch in progress and of the last EPOCH_RETENTION epochs
+pub const CURRENT_EPOCH: Item<Epoch> = Item::new("current_epoch");
+pub const EPOCHS: Map<u64, Epoch> = Map::new("epochs");
+pub const CONVERSIONS: Map<u64, Conversion> = Map::new("conversions");
+pub const CONVERSION_COUNT: Item<u64> = Item::new("conversion_count");
+// Source tokens of failed conversions held for their sender to claim
+pub const REFUNDS: Map<&Addr, Vec<Coin>> = Map::new("refunds");
+// Callbacks of the conversions in flight, sent by the reply once their conversion succeeded
+pub const PENDING_CALLBACKS: Map<u64, PendingCallback> = Map::new("pending_callbacks");
+// Connected channels of the converter IBC application, and the tokens escrowed for the
+// counterparty chain per channel and denom
+#[cfg(feature = "ibc")]
+pub const IBC_CHANNELS: Map<&str, cosmwasm_std::IbcChannel> = Map::new("ibc_channels");
+#[cfg(feature = "ibc")]
+pub const CHANNEL_ESCROW: Map<(&str, &str), Uint256> = Map::new("channel_escrow");
+// Bank metadata of the configured denoms
+pub const DENOM_INFO: Map<&str, DenomInfo> = Map::new("denom_info");
+// Source tokens held for conversions of their owner, direct or signed. Emptied deposits are
+// kept with a zero amount so their nonce survives and old signatures cannot be replayed
+pub const DEPOSITS: Map<&Addr, Deposit> = Map::new("deposits");
+// Streaming conversions in progress, removed once fully claimed or cancelled
+pub const STREAMS: Map<u64, Stream> = Map::new("streams");
+pub const STREAM_COUNT: Item<u64> = Item::new("stream_count");
+// Pending commit-reveal conversions, one per sender
+pub const COMMITMENTS: Map<&Addr, Commitment> = Map::new("commitments");
+// Batch mode, conversions are then committed during rounds and settled at a clearing rate.
+// The round is kept while the mode is disabled so round ids keep increasing
+pub const AUCTION: Item<AuctionConfig> = Item::new("auction");
+pub const AUCTION_ROUND: Item<AuctionRound> = Item::new("auction_round");
+pub const AUCTION_COMMITMENTS: Map<(u64, &Addr), Uint256> = Map::new("auction_commitments");
+pub const AUCTION_SETTLEMENTS: Map<u64, Settlement> = Map::new("auction_settlements");
+// Fractions of target base units lost to flooring per target denom, until minted to the
+// treasury in whole base units
+pub const ROUNDING_RESERVE: Map<&str, Decimal256> = Map::new("rounding_reserve");
+// Float per denom paying conversions out when `float_payouts` is set
+pub const FLOATS: Map<&str, Float> = Map::new("floats");
+// Total per denom of the refunds, channel escrows, deposits, streams, commitments and floats
+pub const ESCROWED: Map<&str, Uint256> = Map::new("escrowed");
+// Lifetime totals, counted from the first conversion after they were introduced
+pub const TOTALS: Item<Totals> = Item::new("totals");
+// Lifetime totals per (source denom, target denom) pair, counted from the first conversion
+// after they were introduced
+pub const PAIR_TOTALS: Map<(&str, &str), Totals> = Map::new("pair_totals");
+
+// Source base units converted per sender and day, against the daily quota. Only the day of
+// the last conversion of a sender is kept
+#[cfg(feature = "quotas")]
+pub const QUOTA_USAGE: Map<(&Addr, u64), Uint256> = Map::new("quota_usage");
+
+// Raw layout of the storage above, for indexers decoding the contract state without its code.
+// Keep it in step with the declarations, the tests check every namespace against them.
+// Storage of the subsystems left out of the build is left out of the layout
+pub(crate) struct StorageDecl {
+    pub name: &'static str,
+    pub namespace: &'static str,
+    // Key components of a map, in order. Empty for an item
+    pub key: &'static [&'static str],
+    pub value: &'static str,
+}
+
+const fn item(name: &'static str, namespace: &'static str, value: &'static str) -> StorageDecl {
+    StorageDecl {
+        name,
+        namespace,
+        key: &[],
+        value,
+    }
+}
+
+const fn map(
+    name: &'static str,
+    namespace: &'static str,
+    key: &'static [&'static str],
+    value: &'static str,
+) -> StorageDecl {
+    StorageDecl {
+        name,
+        namespace,
+        key,
+        value,
+    }
+}
+
+pub(crate) const STORAGE_LAYOUT: &[StorageDecl] = &[
+    item("CONFIG", "config_v2", "Config"),
+    item("LEGACY_CONFIG", "config", "Config"),
+    item("ADMIN", "admin", "Option<Addr>"),
+    item("PENDING_ADMIN", "pending_admin", "Addr"),
+    map("ROLES", "roles", &["string", "addr"], "Empty"),
+    #[cfg(feature = "history")]
+    map("ADMIN_HISTORY", "admin_history", &["u64"], "AdminChange"),
+    #[cfg(feature = "history")]
+    item("ADMIN_CHANGE_COUNT", "admin_change_count", "u64"),
+    #[cfg(feature = "history")]
+    map("UPGRADE_HISTORY", "upgrade_history", &["u64"], "Upgrade"),
+    #[cfg(feature = "history")]
+    item("UPGRADE_COUNT", "upgrade_count", "u64"),
+    item("FROZEN", "frozen", "bool"),
+    item("AUTHZ_ENABLED", "authz_enabled", "bool"),
+    map("DELEGATIONS", "delegations", &["addr"], "Delegation"),
+    item("RATE_BASELINE", "rate_baseline", "RateBaseline"),
+    item("PAUSE", "pause", "PauseInfo"),
+    item("METADATA", "metadata", "Metadata"),
+    item("NOTIFY_TARGET", "notify_target", "Addr"),
+    item("SUCCESSOR", "successor", "Addr"),
+    item("MIGRATED_POSITIONS", "migrated_positions", "u64"),
+    item("QUORUM", "quorum", "Quorum"),
+    map("PROPOSALS", "proposals", &["u64"], "Proposal"),
+    item("PROPOSAL_COUNT", "proposal_count", "u64"),
+    map("UNSOLICITED", "unsolicited", &["addr", "string"], "Uint256"),
+    map(
+        "UNSOLICITED_TOTAL",
+        "unsolicited_total",
+        &["string"],
+        "Uint256",
+    ),
+    item("CURRENT_EPOCH", "current_epoch", "Epoch"),
+    map("EPOCHS", "epochs", &["u64"], "Epoch"),
+    map("CONVERSIONS", "conversions", &["u64"], "Conversion"),
+    item("CONVERSION_COUNT", "conversion_count", "u64"),
+    map("REFUNDS", "refunds", &["addr"], "Vec<Coin>"),
+    map(
+        "PENDING_CALLBACKS",
+        "pending_callbacks",
+        &["u64"],
+        "PendingCallback",
+    ),
+    #[cfg(feature = "ibc")]
+    map("IBC_CHANNELS", "ibc_channels", &["string"], "IbcChannel"),
+    #[cfg(feature = "ibc")]
+    map(
+        "CHANNEL_ESCROW",
+        "channel_escrow",
+        &["string", "string"],
+        "Uint256",
+    ),
+    map("DENOM_INFO", "denom_info", &["string"], "DenomInfo"),
+    map("DEPOSITS", "deposits", &["addr"], "Deposit"),
+    map("STREAMS", "streams", &["u64"], "Stream"),
+    item("STREAM_COUNT", "stream_count", "u64"),
+    map("COMMITMENTS", "commitments", &["addr"], "Commitment"),
+    item("AUCTION", "auction", "AuctionConfig"),
+    item("AUCTION_ROUND", "auction_round", "AuctionRound"),
+    map(
+        "AUCTION_COMMITMENTS",
+        "auction_commitments",
+        &["u64", "addr"],
+        "Uint256",
+    ),
+    map(
+        "AUCTION_SETTLEMENTS",
+        "auction_settlements",
+        &["u64"],
+        "Settlement",
+    ),
+    map(
+        "ROUNDING_RESERVE",
+        "rounding_reserve",
+        &["string"],
+        "Decimal256",
+    ),
+    map("FLOATS", "floats", &["string"], "Float"),
+    map("ESCROWED", "escrowed", &["string"], "Uint256"),
+    item("TOTALS", "totals", "Totals"),
+    map(
+        "PAIR_TOTALS",
+        "pair_totals",
+        &["string", "string"],
+        "Totals",
+    ),
+    #[cfg(feature = "quotas")]
+    map("QUOTA_USAGE", "quota_usage", &["addr", "u64"], "Uint256"),
+];
+
+// Load the config from the current namespace, falling back to the legacy one for
+// deployments that have not been migrated yet
+pub fn load_config(storage: &dyn Storage) -> StdResult<Config> {
+    match CONFIG.may_load(storage)? {
+        Some(config) => Ok(config),
+        None => LEGACY_CONFIG.load(storage),
+    }
+}
+
+// Move the config out of the legacy namespace. Returns whether legacy data was found.
+// Safe to run multiple times
+pub fn migrate_config(storage: &mut dyn Storage) -> StdResult<bool> {
+    let Some(legacy) = LEGACY_CONFIG.may_load(storage)? else {
+        return Ok(false);
+    };
+    // A config already saved in the current namespace is always the most recent one
+    if !CONFIG.exists(storage) {
+        CONFIG.save(storage, &legacy)?;
+    }
+    LEGACY_CONFIG.remove(storage);
+    Ok(true)
+}
+
+// Load the epoch in effect at the given time. Deployments instantiated before epochs were
+// tracked start their first epoch at their first conversion
+pub fn load_epoch(storage: &dyn Storage, now: Timestamp, length: Option<u64>) -> StdResult<Epoch> {
+    Ok(match CURRENT_EPOCH.may_load(storage)? {
+        Some(epoch) => epoch.at(now, length),
+        None => Epoch::new(0, now),
+    })
+}
+
+// Append an admin to the admin history
+#[cfg(feature = "history")]
+pub fn record_admin(storage: &mut dyn Storage, admin: &Addr, block: &BlockInfo) -> StdResult<()> {
+    let id = ADMIN_CHANGE_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    ADMIN_CHANGE_COUNT.save(storage, &id)?;
+    ADMIN_HISTORY.save(
+        storage,
+        id,
+        &AdminChange {
+            admin: admin.clone(),
+            height: block.height,
+            time: block.time,
+        },
+    )
+}
+
+// Builds without the `history` feature keep no history
+#[cfg(not(feature = "history"))]
+pub fn record_admin(_: &mut dyn Storage, _: &Addr, _: &BlockInfo) -> StdResult<()> {
+    Ok(())
+}
+
+// Append a migration to the upgrade history
+#[cfg(feature = "history")]
+pub fn record_upgrade(
+    storage: &mut dyn Storage,
+    from_version: &str,
+    block: &BlockInfo,
+    initiator: &Addr,
+) -> StdResult<()> {
+    let id = UPGRADE_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    UPGRADE_COUNT.save(storage, &id)?;
+    UPGRADE_HISTORY.save(
+        storage,
+        id,
+        &Upgrade {
+            from_version: from_version.to_string(),
+            to_version: crate::consts::CONTRACT_VERSION.to_string(),
+            height: block.height,
+            initiator: initiator.clone(),
+        },
+    )
+}
+
+#[cfg(not(feature = "history"))]
+pub fn record_upgrade(_: &mut dyn Storage, _: &str, _: &BlockInfo, _: &Addr) -> StdResult<()> {
+    Ok(())
+}
+
+// Save the epoch in effect, archiving the previous one when it rolled over
+pub fn save_epoch(storage: &mut dyn Storage, epoch: &Epoch) -> StdResult<()> {
+    if let Some(previous) = CURRENT_EPOCH.may_load(storage)? {
+        if previous.id != epoch.id {
+            EPOCHS.save(storage, previous.id, &previous)?;
+            // Keep a bounded history
+            let ids = EPOCHS
+                .keys(storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            for id in ids.iter().take(ids.len().saturating_sub(EPOCH_RETENTION)) {
+                EPOCHS.remove(storage, *id);
+            }
+        }
+    }
+    CURRENT_EPOCH.save(storage, epoch)
+}
 
 impl Config {
     pub fn try_with_defaults(rate: Rate) -> Result<Self, ContractError> {
@@ -42,6 +734,31 @@ impl Config {
             source_denom: s,
             target_denom: t,
             paused: false,
+            strict_denoms: None,
+            source_exponent: None,
+            target_exponent: None,
+            source_symbol: None,
+            target_symbol: None,
+            allowed_senders: None,
+            epoch_length: None,
+            require_group_policy: None,
+            min_group_threshold: None,
+            bech32_prefix: None,
+            flat_fee: None,
+            rate_floor: None,
+            rate_ceiling: None,
+            verifier: None,
+            redact_senders: None,
+            rate_strategy: None,
+            float_payouts: None,
+            resolver: None,
+            hash_authz_payloads: None,
+            fee_model: None,
+            min_convert_amount: None,
+            max_convert_amount: None,
+            daily_quota: None,
+            epoch_cap: None,
+            fees_to_community_pool: None,
         })
     }
 
@@ -49,6 +766,323 @@ impl Config {
         if self.source_denom == self.target_denom {
             return Err(ContractError::ConfigError(SameDenom));
         }
+        if self.epoch_length == Some(0) {
+            return Err(ContractError::ConfigError(InvalidEpochLength));
+        }
+        if let Some(prefix) = &self.bech32_prefix {
+            if !Hrp::parse(prefix).is_ok_and(|hrp| hrp.as_str() == prefix.to_lowercase()) {
+                return Err(ContractError::ConfigError(InvalidBech32Prefix));
+            }
+        }
+        self.check_prefix(&self.poa_admin)?;
+        if let Some(verifier) = &self.verifier {
+            self.check_prefix(verifier)?;
+        }
+        if let Some(resolver) = &self.resolver {
+            self.check_prefix(resolver)?;
+        }
+        // Funds are merged per denom, a fee in the source denom could not be told apart
+        if let Some(fee) = &self.flat_fee {
+            if fee.amount.is_zero() || same_denom(&fee.denom, self.source_denom.as_str()) {
+                return Err(ContractError::ConfigError(InvalidFlatFee));
+            }
+        }
+        if let (Some(floor), Some(ceiling)) = (&self.rate_floor, &self.rate_ceiling) {
+            if floor.as_ref() > ceiling.as_ref() {
+                return Err(ContractError::ConfigError(InvalidRateBounds));
+            }
+        }
+        self.check_rate_bounds(&self.rate)?;
+        if let Some(strategy) = &self.rate_strategy {
+            strategy.validate(self)?;
+        }
+        // The flat fee is a coin sent alongside the conversion, in a denom other than the source
+        // denom, so it cannot be expressed as a fee model which withholds source tokens. A
+        // config uses one or the other, never both
+        if let Some(model) = &self.fee_model {
+            model.validate()?;
+            if self.flat_fee.is_some() {
+                return Err(ContractError::ConfigError(ConflictingFees));
+            }
+        }
+        if self.daily_quota.is_some_and(|quota| quota.is_zero()) {
+            return Err(ContractError::ConfigError(InvalidDailyQuota));
+        }
+        // Builds without the `quotas` feature would not enforce it
+        if cfg!(not(feature = "quotas")) && self.daily_quota.is_some() {
+            return Err(ContractError::ConfigError(QuotasDisabled));
+        }
+        if self.epoch_cap.is_some_and(|cap| cap.is_zero()) {
+            return Err(ContractError::ConfigError(InvalidEpochCap));
+        }
+        if let Some(max) = self.max_convert_amount {
+            if max.is_zero() || self.min_convert_amount.is_some_and(|min| min > max) {
+                return Err(ContractError::ConfigError(InvalidConvertLimits));
+            }
+        }
+        for symbol in [&self.source_symbol, &self.target_symbol]
+            .into_iter()
+            .flatten()
+        {
+            validate_symbol(symbol)?;
+        }
+        match (self.source_exponent, self.target_exponent) {
+            (Some(s), Some(t)) if s.max(t) > MAX_DENOM_EXPONENT => {
+                Err(ContractError::ConfigError(InvalidExponent))
+            }
+            (Some(_), None) | (None, Some(_)) => {
+                Err(ContractError::ConfigError(IncompleteExponents))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn bech32_prefix(&self) -> &str {
+        self.bech32_prefix.as_deref().unwrap_or(BECH32_PREFIX)
+    }
+
+    // Ensure an address uses the configured bech32 prefix
+    pub fn check_prefix(&self, addr: &Addr) -> Result<(), ContractError> {
+        match bech32::decode(addr.as_str()) {
+            Ok((hrp, _)) if hrp.as_str() == self.bech32_prefix() => Ok(()),
+            _ => Err(ContractError::ConfigError(AddressPrefixMismatch)),
+        }
+    }
+
+    // Symbol shown to users for a denom, the denom itself when no symbol is configured
+    pub fn source_symbol(&self) -> &str {
+        self.source_symbol
+            .as_deref()
+            .unwrap_or(self.source_denom.as_str())
+    }
+
+    pub fn target_symbol(&self) -> &str {
+        self.target_symbol
+            .as_deref()
+            .unwrap_or(self.target_denom.as_str())
+    }
+
+    // Ensure a rate is within the rate bounds
+    pub fn check_rate_bounds(&self, rate: &Rate) -> Result<(), ContractError> {
+        let rate = rate.as_ref();
+        if self
+            .rate_floor
+            .as_ref()
+            .is_some_and(|floor| rate < floor.as_ref())
+            || self
+                .rate_ceiling
+                .as_ref()
+                .is_some_and(|ceiling| rate > ceiling.as_ref())
+        {
+            return Err(ContractError::ConfigError(RateOutOfBounds));
+        }
         Ok(())
     }
+
+    // Amount of target tokens to mint for the given amount of source tokens, at a rate
+    // resolved by the rate strategy
+    pub fn mint_amount(&self, rate: &Rate, amount: Uint256) -> Result<Uint256, ContractError> {
+        match (self.source_exponent, self.target_exponent) {
+            (Some(s), Some(t)) => rate.apply_scaled(amount, s, t),
+            _ => rate.apply_to(amount),
+        }
+    }
+
+    // Smallest amount of source tokens minting at least the given amount of target tokens at
+    // the rate, the inverse of `mint_amount`
+    pub fn source_amount(&self, rate: &Rate, minted: Uint256) -> Result<Uint256, ContractError> {
+        match (self.source_exponent, self.target_exponent) {
+            (Some(s), Some(t)) => rate.reverse_scaled(minted, s, t),
+            _ => rate.reverse_scaled(minted, 0, 0),
+        }
+    }
+
+    // Fraction of a target base unit lost by flooring the mint amount
+    pub fn rounding_loss(&self, rate: &Rate, amount: Uint256) -> Result<Decimal256, ContractError> {
+        let exact = rate.apply_exact(
+            amount,
+            self.source_exponent.unwrap_or_default(),
+            self.target_exponent.unwrap_or_default(),
+        )?;
+        Ok(exact - exact.floor())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load_config, migrate_config, Config, CONFIG, LEGACY_CONFIG};
+    use crate::error::{ConfigError, ContractError};
+    use crate::rate::Rate;
+    use cosmwasm_std::testing::MockStorage;
+    use cosmwasm_std::Uint256;
+
+    fn config(rate: &str) -> Config {
+        Config::try_with_defaults(Rate::parse(rate).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_load_config_current() {
+        let mut storage = MockStorage::new();
+        CONFIG.save(&mut storage, &config("1")).unwrap();
+        assert_eq!(load_config(&storage).unwrap(), config("1"));
+    }
+
+    #[test]
+    fn test_load_config_legacy_fallback() {
+        let mut storage = MockStorage::new();
+        LEGACY_CONFIG.save(&mut storage, &config("1")).unwrap();
+        assert_eq!(load_config(&storage).unwrap(), config("1"));
+    }
+
+    #[test]
+    fn test_load_config_missing() {
+        let storage = MockStorage::new();
+        assert!(load_config(&storage).is_err());
+    }
+
+    #[test]
+    fn test_migrate_config() {
+        let mut storage = MockStorage::new();
+        LEGACY_CONFIG.save(&mut storage, &config("1")).unwrap();
+
+        assert!(migrate_config(&mut storage).unwrap());
+        assert!(!LEGACY_CONFIG.exists(&storage));
+        assert_eq!(CONFIG.load(&storage).unwrap(), config("1"));
+
+        // Running again is a noop
+        assert!(!migrate_config(&mut storage).unwrap());
+        assert_eq!(CONFIG.load(&storage).unwrap(), config("1"));
+    }
+
+    #[test]
+    fn test_migrate_config_keeps_current() {
+        let mut storage = MockStorage::new();
+        LEGACY_CONFIG.save(&mut storage, &config("1")).unwrap();
+        CONFIG.save(&mut storage, &config("2")).unwrap();
+
+        assert!(migrate_config(&mut storage).unwrap());
+        assert!(!LEGACY_CONFIG.exists(&storage));
+        assert_eq!(CONFIG.load(&storage).unwrap(), config("2"));
+    }
+
+    #[test]
+    fn test_validate_exponents() {
+        let mut c = config("1");
+        c.source_exponent = Some(6);
+        assert!(matches!(
+            c.validate().unwrap_err(),
+            ContractError::ConfigError(ConfigError::IncompleteExponents)
+        ));
+        c.target_exponent = Some(19);
+        assert!(matches!(
+            c.validate().unwrap_err(),
+            ContractError::ConfigError(ConfigError::InvalidExponent)
+        ));
+        c.target_exponent = Some(18);
+        assert!(c.validate().is_ok());
+    }
+
+    #[test]
+    fn test_mint_amount() {
+        let mut c = config("0.5");
+        assert_eq!(
+            c.mint_amount(&c.rate, Uint256::from(100u8)).unwrap(),
+            Uint256::from(50u8)
+        );
+        c.source_exponent = Some(6);
+        c.target_exponent = Some(8);
+        assert_eq!(
+            c.mint_amount(&c.rate, Uint256::from(100u8)).unwrap(),
+            Uint256::from(5000u16)
+        );
+    }
+
+    #[test]
+    fn test_storage_layout() {
+        use super::*;
+        // The wrapped namespaces of ADMIN, PENDING_ADMIN and ROLES are not exposed
+        let declared: Vec<(&str, Vec<u8>)> = vec![
+            ("CONFIG", CONFIG.as_slice().to_vec()),
+            ("LEGACY_CONFIG", LEGACY_CONFIG.as_slice().to_vec()),
+            #[cfg(feature = "history")]
+            ("ADMIN_HISTORY", ADMIN_HISTORY.namespace_bytes().to_vec()),
+            #[cfg(feature = "history")]
+            ("ADMIN_CHANGE_COUNT", ADMIN_CHANGE_COUNT.as_slice().to_vec()),
+            #[cfg(feature = "history")]
+            (
+                "UPGRADE_HISTORY",
+                UPGRADE_HISTORY.namespace_bytes().to_vec(),
+            ),
+            #[cfg(feature = "history")]
+            ("UPGRADE_COUNT", UPGRADE_COUNT.as_slice().to_vec()),
+            ("FROZEN", FROZEN.as_slice().to_vec()),
+            ("AUTHZ_ENABLED", AUTHZ_ENABLED.as_slice().to_vec()),
+            ("DELEGATIONS", DELEGATIONS.namespace_bytes().to_vec()),
+            ("RATE_BASELINE", RATE_BASELINE.as_slice().to_vec()),
+            ("PAUSE", PAUSE.as_slice().to_vec()),
+            ("METADATA", METADATA.as_slice().to_vec()),
+            ("NOTIFY_TARGET", NOTIFY_TARGET.as_slice().to_vec()),
+            ("SUCCESSOR", SUCCESSOR.as_slice().to_vec()),
+            ("MIGRATED_POSITIONS", MIGRATED_POSITIONS.as_slice().to_vec()),
+            ("QUORUM", QUORUM.as_slice().to_vec()),
+            ("PROPOSALS", PROPOSALS.namespace_bytes().to_vec()),
+            ("PROPOSAL_COUNT", PROPOSAL_COUNT.as_slice().to_vec()),
+            ("UNSOLICITED", UNSOLICITED.namespace_bytes().to_vec()),
+            (
+                "UNSOLICITED_TOTAL",
+                UNSOLICITED_TOTAL.namespace_bytes().to_vec(),
+            ),
+            ("CURRENT_EPOCH", CURRENT_EPOCH.as_slice().to_vec()),
+            ("EPOCHS", EPOCHS.namespace_bytes().to_vec()),
+            ("CONVERSIONS", CONVERSIONS.namespace_bytes().to_vec()),
+            ("CONVERSION_COUNT", CONVERSION_COUNT.as_slice().to_vec()),
+            ("REFUNDS", REFUNDS.namespace_bytes().to_vec()),
+            (
+                "PENDING_CALLBACKS",
+                PENDING_CALLBACKS.namespace_bytes().to_vec(),
+            ),
+            #[cfg(feature = "ibc")]
+            ("IBC_CHANNELS", IBC_CHANNELS.namespace_bytes().to_vec()),
+            #[cfg(feature = "ibc")]
+            ("CHANNEL_ESCROW", CHANNEL_ESCROW.namespace_bytes().to_vec()),
+            ("DENOM_INFO", DENOM_INFO.namespace_bytes().to_vec()),
+            ("DEPOSITS", DEPOSITS.namespace_bytes().to_vec()),
+            ("STREAMS", STREAMS.namespace_bytes().to_vec()),
+            ("STREAM_COUNT", STREAM_COUNT.as_slice().to_vec()),
+            ("COMMITMENTS", COMMITMENTS.namespace_bytes().to_vec()),
+            ("AUCTION", AUCTION.as_slice().to_vec()),
+            ("AUCTION_ROUND", AUCTION_ROUND.as_slice().to_vec()),
+            (
+                "AUCTION_COMMITMENTS",
+                AUCTION_COMMITMENTS.namespace_bytes().to_vec(),
+            ),
+            (
+                "AUCTION_SETTLEMENTS",
+                AUCTION_SETTLEMENTS.namespace_bytes().to_vec(),
+            ),
+            (
+                "ROUNDING_RESERVE",
+                ROUNDING_RESERVE.namespace_bytes().to_vec(),
+            ),
+            ("FLOATS", FLOATS.namespace_bytes().to_vec()),
+            ("ESCROWED", ESCROWED.namespace_bytes().to_vec()),
+            ("TOTALS", TOTALS.as_slice().to_vec()),
+            ("PAIR_TOTALS", PAIR_TOTALS.namespace_bytes().to_vec()),
+            #[cfg(feature = "quotas")]
+            ("QUOTA_USAGE", QUOTA_USAGE.namespace_bytes().to_vec()),
+        ];
+        for (name, namespace) in &declared {
+            let decl = STORAGE_LAYOUT.iter().find(|decl| decl.name == *name);
+            assert_eq!(
+                decl.map(|decl| decl.namespace.as_bytes()),
+                Some(namespace.as_slice()),
+                "{name}"
+            );
+        }
+        assert_eq!(STORAGE_LAYOUT.len(), declared.len() + 3);
+        let mut namespaces: Vec<_> = STORAGE_LAYOUT.iter().map(|decl| decl.namespace).collect();
+        namespaces.sort();
+        namespaces.dedup();
+        assert_eq!(namespaces.len(), STORAGE_LAYOUT.len());
+    }
 }
@@ -0,0 +1,290 @@
+use crate::error::ConfigError::OracleNotContract;
+use crate::error::ContractError;
+use crate::error::RateError::{ApplyOverflowError, InvalidCurve, InvalidSchedule, InvalidTiers};
+use crate::msg::{OracleQueryMsg, OracleRateResponse};
+use crate::rate::Rate;
+use crate::state::{Config, PAIR_TOTALS};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, BlockInfo, Decimal256, Deps, Timestamp, Uint256};
+
+// How the rate of a conversion is resolved from the configured rate, e.g.
+// `{"tiered": {"tiers": [...]}}`. The name of the variant is its `kind`
+#[cw_serde]
+#[non_exhaustive]
+pub enum RateStrategy {
+    // The configured rate, as when no strategy is set
+    Fixed {},
+    // Rate by conversion size. The tier with the highest minimum amount not above the converted
+    // amount applies, smaller conversions get the configured rate
+    Tiered { tiers: Vec<RateTier> },
+    // Rate by block time. The last step started applies, the configured rate before the first
+    Scheduled { steps: Vec<RateStep> },
+    // Rate answered by an oracle contract to `{"rate": {"source_denom": .., "target_denom": ..}}`
+    OracleBacked { oracle: Addr },
+    // Rate moving linearly from the configured rate to `end_rate` as the amount of source
+    // tokens converted for the pair grows to `volume`, then staying at `end_rate`
+    Curve { end_rate: Rate, volume: Uint256 },
+}
+
+#[cw_serde]
+pub struct RateTier {
+    pub min_amount: Uint256,
+    pub rate: Rate,
+}
+
+#[cw_serde]
+pub struct RateStep {
+    pub start: Timestamp,
+    pub rate: Rate,
+}
+
+impl RateStrategy {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RateStrategy::Fixed {} => "fixed",
+            RateStrategy::Tiered { .. } => "tiered",
+            RateStrategy::Scheduled { .. } => "scheduled",
+            RateStrategy::OracleBacked { .. } => "oracle_backed",
+            RateStrategy::Curve { .. } => "curve",
+        }
+    }
+
+    // Check the parameters of the strategy and that every rate it can resolve to is within the
+    // rate bounds of the config. Oracle rates are only known when converting
+    pub fn validate(&self, config: &Config) -> Result<(), ContractError> {
+        match self {
+            RateStrategy::Fixed {} => Ok(()),
+            RateStrategy::Tiered { tiers } => {
+                let increasing = tiers.windows(2).all(|w| w[0].min_amount < w[1].min_amount);
+                if tiers.is_empty() || tiers[0].min_amount.is_zero() || !increasing {
+                    return Err(ContractError::RateError(InvalidTiers));
+                }
+                tiers
+                    .iter()
+                    .try_for_each(|tier| config.check_rate_bounds(&tier.rate))
+            }
+            RateStrategy::Scheduled { steps } => {
+                let increasing = steps.windows(2).all(|w| w[0].start < w[1].start);
+                if steps.is_empty() || !increasing {
+                    return Err(ContractError::RateError(InvalidSchedule));
+                }
+                steps
+                    .iter()
+                    .try_for_each(|step| config.check_rate_bounds(&step.rate))
+            }
+            RateStrategy::OracleBacked { oracle } => config.check_prefix(oracle),
+            RateStrategy::Curve { end_rate, volume } => {
+                if volume.is_zero() {
+                    return Err(ContractError::RateError(InvalidCurve));
+                }
+                config.check_rate_bounds(end_rate)
+            }
+        }
+    }
+}
+
+// Ensure the oracle of an oracle-backed strategy is an existing contract
+pub(crate) fn check_oracle(deps: Deps, config: &Config) -> Result<(), ContractError> {
+    if let Some(RateStrategy::OracleBacked { oracle }) = &config.rate_strategy {
+        deps.querier
+            .query_wasm_contract_info(oracle)
+            .map_err(|_| ContractError::ConfigError(OracleNotContract))?;
+    }
+    Ok(())
+}
+
+// Resolve the rate of a conversion of the given amount of source tokens at the given block.
+// A rate settled beforehand, the clearing rate of an auction round, is used instead of the
+// strategy. The resolved rate is checked against the rate bounds, whatever the strategy
+pub(crate) fn resolve_rate(
+    deps: Deps,
+    block: &BlockInfo,
+    config: &Config,
+    amount: Uint256,
+    settled: Option<&Rate>,
+) -> Result<Rate, ContractError> {
+    if let Some(rate) = settled {
+        config.check_rate_bounds(rate)?;
+        return Ok(rate.clone());
+    }
+    let rate = match &config.rate_strategy {
+        None | Some(RateStrategy::Fixed {}) => config.rate.clone(),
+        Some(RateStrategy::Tiered { tiers }) => tiers
+            .iter()
+            .rev()
+            .find(|tier| tier.min_amount <= amount)
+            .map_or(&config.rate, |tier| &tier.rate)
+            .clone(),
+        Some(RateStrategy::Scheduled { steps }) => steps
+            .iter()
+            .rev()
+            .find(|step| step.start <= block.time)
+            .map_or(&config.rate, |step| &step.rate)
+            .clone(),
+        Some(RateStrategy::OracleBacked { oracle }) => {
+            let res: OracleRateResponse = deps.querier.query_wasm_smart(
+                oracle,
+                &OracleQueryMsg::Rate {
+                    source_denom: config.source_denom.to_string(),
+                    target_denom: config.target_denom.to_string(),
+                },
+            )?;
+            Rate::new(res.rate)?
+        }
+        Some(RateStrategy::Curve { end_rate, volume }) => {
+            let pair = (config.source_denom.as_str(), config.target_denom.as_str());
+            let converted = PAIR_TOTALS
+                .may_load(deps.storage, pair)?
+                .unwrap_or_default()
+                .burned;
+            curve_rate(&config.rate, end_rate, converted.min(*volume), *volume)?
+        }
+    };
+    config.check_rate_bounds(&rate)?;
+    Ok(rate)
+}
+
+// Rate at `converted` out of `volume` along the line from `start` to `end`
+fn curve_rate(
+    start: &Rate,
+    end: &Rate,
+    converted: Uint256,
+    volume: Uint256,
+) -> Result<Rate, ContractError> {
+    let progress = Decimal256::from_ratio(converted, volume);
+    let (start, end) = (*start.as_ref(), *end.as_ref());
+    let rate = if end >= start {
+        (end - start)
+            .checked_mul(progress)
+            .ok()
+            .and_then(|delta| start.checked_add(delta).ok())
+    } else {
+        (start - end)
+            .checked_mul(progress)
+            .ok()
+            .map(|delta| start - delta)
+    };
+    Rate::new(rate.ok_or(ContractError::RateError(ApplyOverflowError))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Config;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+
+    fn rate(s: &str) -> Rate {
+        Rate::parse(s).unwrap()
+    }
+
+    fn config(strategy: RateStrategy) -> Config {
+        let mut config = Config::try_with_defaults(rate("0.5")).unwrap();
+        config.rate_strategy = Some(strategy);
+        config
+    }
+
+    fn tiered() -> RateStrategy {
+        RateStrategy::Tiered {
+            tiers: vec![
+                RateTier {
+                    min_amount: Uint256::from(1_000u32),
+                    rate: rate("0.6"),
+                },
+                RateTier {
+                    min_amount: Uint256::from(10_000u32),
+                    rate: rate("0.7"),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_resolve_tiered() {
+        let deps = mock_dependencies();
+        let env = mock_env();
+        let config = config(tiered());
+        for (amount, expected) in [
+            (999u32, "0.5"),
+            (1_000, "0.6"),
+            (9_999, "0.6"),
+            (10_000, "0.7"),
+        ] {
+            let resolved = resolve_rate(deps.as_ref(), &env.block, &config, amount.into(), None);
+            assert_eq!(resolved.unwrap(), rate(expected), "amount {amount}");
+        }
+    }
+
+    #[test]
+    fn test_resolve_scheduled() {
+        let deps = mock_dependencies();
+        let mut env = mock_env();
+        let start = env.block.time;
+        let config = config(RateStrategy::Scheduled {
+            steps: vec![RateStep {
+                start: start.plus_seconds(60),
+                rate: rate("0.4"),
+            }],
+        });
+        let resolved = resolve_rate(deps.as_ref(), &env.block, &config, Uint256::one(), None);
+        assert_eq!(resolved.unwrap(), rate("0.5"));
+        env.block.time = start.plus_seconds(60);
+        let resolved = resolve_rate(deps.as_ref(), &env.block, &config, Uint256::one(), None);
+        assert_eq!(resolved.unwrap(), rate("0.4"));
+    }
+
+    #[test]
+    fn test_curve_rate() {
+        let volume = Uint256::from(100u32);
+        for (start, end, converted, expected) in [
+            ("0.5", "1", 0u32, "0.5"),
+            ("0.5", "1", 50, "0.75"),
+            ("0.5", "1", 100, "1"),
+            ("1", "0.5", 25, "0.875"),
+        ] {
+            let resolved = curve_rate(&rate(start), &rate(end), converted.into(), volume);
+            assert_eq!(resolved.unwrap(), rate(expected));
+        }
+    }
+
+    #[test]
+    fn test_resolve_out_of_bounds() {
+        let deps = mock_dependencies();
+        let env = mock_env();
+        let mut config = config(tiered());
+        config.rate_ceiling = Some(rate("0.6"));
+        let resolved = resolve_rate(
+            deps.as_ref(),
+            &env.block,
+            &config,
+            Uint256::from(10_000u32),
+            None,
+        );
+        assert!(resolved.is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let tiers = |amounts: &[u32]| RateStrategy::Tiered {
+            tiers: amounts
+                .iter()
+                .map(|&min_amount| RateTier {
+                    min_amount: min_amount.into(),
+                    rate: rate("0.6"),
+                })
+                .collect(),
+        };
+        let base = config(RateStrategy::Fixed {});
+        assert!(tiered().validate(&base).is_ok());
+        assert!(tiers(&[]).validate(&base).is_err());
+        assert!(tiers(&[0]).validate(&base).is_err());
+        assert!(tiers(&[2, 1]).validate(&base).is_err());
+        assert!(RateStrategy::Scheduled { steps: vec![] }
+            .validate(&base)
+            .is_err());
+        let curve = RateStrategy::Curve {
+            end_rate: rate("1"),
+            volume: Uint256::zero(),
+        };
+        assert!(curve.validate(&base).is_err());
+    }
+}
@@ -0,0 +1,84 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, Timestamp, Uint256};
+
+// Source tokens escrowed to be converted gradually. The tokens vest linearly over `duration`
+// seconds from `start`, and each claim converts the vested tokens not claimed yet at the rate
+// in effect at the time of the claim
+#[cw_serde]
+pub struct Stream {
+    pub id: u64,
+    pub owner: Addr,
+    pub total: Coin,
+    pub claimed: Uint256,
+    pub start: Timestamp,
+    pub duration: u64,
+}
+
+impl Stream {
+    // Tokens vested at the given time, claimed or not
+    pub fn vested(&self, now: Timestamp) -> Uint256 {
+        let elapsed = now.seconds().saturating_sub(self.start.seconds());
+        if elapsed >= self.duration {
+            return self.total.amount;
+        }
+        self.total.amount.multiply_ratio(elapsed, self.duration)
+    }
+
+    #[inline]
+    pub fn claimable(&self, now: Timestamp) -> Uint256 {
+        self.vested(now).saturating_sub(self.claimed)
+    }
+
+    // Tokens not converted yet, refunded when the stream is cancelled
+    #[inline]
+    pub fn remaining(&self) -> Uint256 {
+        self.total.amount.saturating_sub(self.claimed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Stream;
+    use cosmwasm_std::{coin, Addr, Timestamp, Uint256};
+
+    fn stream(claimed: u128) -> Stream {
+        Stream {
+            id: 1,
+            owner: Addr::unchecked("owner"),
+            total: coin(1_000, "umfx"),
+            claimed: Uint256::from(claimed),
+            start: Timestamp::from_seconds(100),
+            duration: 1_000,
+        }
+    }
+
+    #[test]
+    fn test_stream_vested() {
+        let s = stream(0);
+        assert_eq!(s.vested(Timestamp::from_seconds(50)), Uint256::zero());
+        assert_eq!(s.vested(Timestamp::from_seconds(100)), Uint256::zero());
+        assert_eq!(
+            s.vested(Timestamp::from_seconds(350)),
+            Uint256::from(250u128)
+        );
+        assert_eq!(
+            s.vested(Timestamp::from_seconds(1_100)),
+            Uint256::from(1_000u128)
+        );
+        assert_eq!(
+            s.vested(Timestamp::from_seconds(5_000)),
+            Uint256::from(1_000u128)
+        );
+    }
+
+    #[test]
+    fn test_stream_claimable() {
+        let s = stream(250);
+        assert_eq!(s.claimable(Timestamp::from_seconds(350)), Uint256::zero());
+        assert_eq!(
+            s.claimable(Timestamp::from_seconds(600)),
+            Uint256::from(250u128)
+        );
+        assert_eq!(s.remaining(), Uint256::from(750u128));
+    }
+}
@@ -0,0 +1,238 @@
+use crate::common::*;
+use cosmwasm_std::Addr;
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+const PAUSER: &str = "pauser";
+
+fn create_msg_propose_admin(admin: &str) -> Value {
+    json!({"access": {"propose_admin": {"admin": admin}}})
+}
+
+fn create_msg_accept_admin() -> Value {
+    json!({"access": {"accept_admin": {}}})
+}
+
+fn create_msg_grant_role(role: &str, address: &str) -> Value {
+    json!({"access": {"grant_role": {"role": role, "address": address}}})
+}
+
+fn create_msg_revoke_role(role: &str, address: &str) -> Value {
+    json!({"access": {"revoke_role": {"role": role, "address": address}}})
+}
+
+fn create_msg_pause() -> Value {
+    json!({"pause": {"reason": "incident", "message": null}})
+}
+
+fn query_access(app: &AppAccepting, contract_addr: &Addr, msg: Value) -> Value {
+    app.wrap()
+        .query_wasm_smart(contract_addr, &json!({"access": msg}))
+        .unwrap()
+}
+
+fn query_admin(app: &AppAccepting, contract_addr: &Addr) -> Value {
+    app.wrap()
+        .query_wasm_smart(contract_addr, &json!({"admin": {}}))
+        .unwrap()
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::propose_admin(default_admin(), create_msg_propose_admin(DEFAULT_SENDER), Expect::Ok)]
+#[case::propose_unauthorized(DEFAULT_SENDER, create_msg_propose_admin(DEFAULT_SENDER), Expect::ErrContains(ONLY_ADMIN))]
+#[case::propose_invalid_admin(default_admin(), create_msg_propose_admin("invalid"), Expect::ErrContains(PARSE_FAILED))]
+#[case::accept_without_proposal(DEFAULT_SENDER, create_msg_accept_admin(), Expect::ErrContains(NO_PENDING_ADMIN))]
+#[case::cancel_without_proposal(default_admin(), json!({"access": {"cancel_admin_proposal": {}}}), Expect::ErrContains(NO_PENDING_ADMIN))]
+#[case::grant_role(default_admin(), create_msg_grant_role(PAUSER, DEFAULT_SENDER), Expect::Ok)]
+#[case::grant_unauthorized(DEFAULT_SENDER, create_msg_grant_role(PAUSER, DEFAULT_SENDER), Expect::ErrContains(ONLY_ADMIN))]
+#[case::grant_unknown_role(default_admin(), create_msg_grant_role("minter", DEFAULT_SENDER), Expect::ErrContains(UNKNOWN_ROLE))]
+#[case::revoke_not_granted(default_admin(), create_msg_revoke_role(PAUSER, DEFAULT_SENDER), Expect::ErrContains(MISSING_PAUSER_ROLE))]
+#[case::renounce_not_granted(DEFAULT_SENDER, json!({"access": {"renounce_role": {"role": PAUSER}}}), Expect::ErrContains(MISSING_PAUSER_ROLE))]
+fn execute_access(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] exec_sender: &str,
+    #[case] exec_msg: Value,
+    #[case] expect: Expect<'_>,
+) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        exec_sender,
+        &exec_msg,
+        &[],
+        expect,
+    );
+}
+
+#[rstest]
+fn two_step_admin_transfer(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let contract = contract_addr.as_str();
+    let candidate = make_addr("candidate").to_string();
+
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_propose_admin(&candidate),
+        &[],
+        Expect::Ok,
+    );
+    assert_eq!(
+        query_access(&app, &contract_addr, json!({"pending_admin": {}})),
+        json!({"pending_admin": candidate})
+    );
+
+    // The admin stays in charge until the candidate accepts
+    assert_eq!(
+        query_admin(&app, &contract_addr),
+        json!({"admin": default_admin()})
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_accept_admin(),
+        &[],
+        Expect::ErrContains(NOT_PENDING_ADMIN),
+    );
+    run_execute(
+        &mut app,
+        &candidate,
+        contract,
+        &create_msg_accept_admin(),
+        &[],
+        Expect::Ok,
+    );
+
+    assert_eq!(
+        query_admin(&app, &contract_addr),
+        json!({"admin": candidate})
+    );
+    assert_eq!(
+        query_access(&app, &contract_addr, json!({"pending_admin": {}})),
+        json!({"pending_admin": null})
+    );
+    #[cfg(feature = "history")]
+    {
+        let history: Value = app
+            .wrap()
+            .query_wasm_smart(contract, &json!({"admin_history": {}}))
+            .unwrap();
+        assert_eq!(history["items"][1]["admin"], json!(candidate));
+    }
+}
+
+#[rstest]
+fn update_admin_drops_proposal(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let contract = contract_addr.as_str();
+    let candidate = make_addr("candidate").to_string();
+
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_propose_admin(&candidate),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_update_admin(Some(DEFAULT_SENDER)),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        &candidate,
+        contract,
+        &create_msg_accept_admin(),
+        &[],
+        Expect::ErrContains(NO_PENDING_ADMIN),
+    );
+}
+
+#[rstest]
+fn pauser_role(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let contract = contract_addr.as_str();
+
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_grant_role(PAUSER, DEFAULT_SENDER),
+        &[],
+        Expect::Ok,
+    );
+    assert_eq!(
+        query_access(
+            &app,
+            &contract_addr,
+            json!({"role_members": {"role": PAUSER}})
+        ),
+        json!({"members": [DEFAULT_SENDER]})
+    );
+    assert_eq!(
+        query_access(
+            &app,
+            &contract_addr,
+            json!({"has_role": {"role": PAUSER, "address": DEFAULT_SENDER}})
+        ),
+        json!({"has_role": true})
+    );
+
+    // The pauser can pause, resuming is left to the admin
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_pause(),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_update_config(Field::Paused, false),
+        &[],
+        Expect::ErrContains(ONLY_ADMIN),
+    );
+
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_revoke_role(PAUSER, DEFAULT_SENDER),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_pause(),
+        &[],
+        Expect::ErrContains(MISSING_PAUSER_ROLE),
+    );
+    assert_eq!(
+        query_access(
+            &app,
+            &contract_addr,
+            json!({"role_members": {"role": PAUSER}})
+        ),
+        json!({"members": []})
+    );
+}
@@ -0,0 +1,57 @@
+use crate::common::*;
+use converter::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, NotifyMsg, QueryMsg, SudoMsg};
+use cosmwasm_schema::schema_for;
+use rstest::*;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+mod common;
+
+// Fingerprint of the message schemas API_VERSION was released with. When the messages change,
+// this test fails until the API version is bumped along with the fingerprint: the major
+// version when a message or field is removed or changes meaning, the minor version when one
+// is added. Lean builds leave the messages of their disabled subsystems out, so only the full
+// build is fingerprinted
+const API_FINGERPRINT: &str = "63b2ce6003745d066ebd0a9a17b6411e11424e25958133f72887cf6419b201f0";
+
+fn fingerprint() -> String {
+    let schemas = [
+        schema_for!(InstantiateMsg),
+        schema_for!(ExecuteMsg),
+        schema_for!(QueryMsg),
+        schema_for!(SudoMsg),
+        schema_for!(MigrateMsg),
+        schema_for!(NotifyMsg),
+    ];
+    format!(
+        "{:x}",
+        Sha256::digest(serde_json::to_vec(&schemas).unwrap())
+    )
+}
+
+#[rstest]
+fn query_api_version(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let res: Value = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &json!({"api_version": {}}))
+        .unwrap();
+    assert_eq!(res, json!({"version": API_VERSION}));
+}
+
+#[test]
+fn api_version_bumped_with_messages() {
+    if !cfg!(all(
+        feature = "history",
+        feature = "ibc",
+        feature = "quotas"
+    )) {
+        return;
+    }
+    assert_eq!(
+        fingerprint(),
+        API_FINGERPRINT,
+        "the message API changed, bump API_VERSION in src/consts.rs and update this test"
+    );
+}
@@ -0,0 +1,502 @@
+use crate::common::*;
+use cosmwasm_std::{coin, Addr, Event};
+use cw_multi_test::Executor;
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn auction_config(min_fraction: &str) -> Value {
+    json!({"round_length": 100, "target_demand": "1000", "min_fraction": min_fraction})
+}
+
+fn create_msg_set_auction(auction: Value) -> Value {
+    json!({"set_auction": {"auction": auction}})
+}
+
+fn create_msg_commit() -> Value {
+    json!({"auction_commit": {}})
+}
+
+fn create_msg_settle() -> Value {
+    json!({"settle_auction": {}})
+}
+
+fn create_msg_claim(round: u64) -> Value {
+    json!({"claim_auction": {"round": round}})
+}
+
+fn create_msg_cancel(round: u64) -> Value {
+    json!({"cancel_auction_commitment": {"round": round}})
+}
+
+// Instantiate the contract and enable batch mode
+fn setup_auction(setup_with_funds: (AppAccepting, u64)) -> (AppAccepting, Addr) {
+    let (app, contract_addr, _) = prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        default_admin(),
+        &create_msg_set_auction(auction_config("0.25")),
+        &[],
+        Expect::Ok,
+    );
+    (app, contract_addr)
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::ok(default_admin(), create_msg_set_auction(auction_config("0.25")), Expect::Ok)]
+#[case::disable(default_admin(), create_msg_set_auction(Value::Null), Expect::Ok)]
+#[case::unauthorized(DEFAULT_SENDER, create_msg_set_auction(auction_config("0.25")), Expect::ErrContains(ONLY_ADMIN))]
+#[case::zero_min_fraction(default_admin(), create_msg_set_auction(auction_config("0")), Expect::ErrContains(INVALID_AUCTION))]
+#[case::min_fraction_above_one(default_admin(), create_msg_set_auction(auction_config("1.5")), Expect::ErrContains(INVALID_AUCTION))]
+#[case::zero_round_length(default_admin(), create_msg_set_auction(json!({"round_length": 0, "target_demand": "1000", "min_fraction": "0.5"})), Expect::ErrContains(INVALID_AUCTION))]
+fn set_auction(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] exec_sender: &str,
+    #[case] exec_msg: Value,
+    #[case] expect: Expect<'_>,
+) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        exec_sender,
+        &exec_msg,
+        &[],
+        expect,
+    );
+}
+
+#[rstest]
+fn commit_disabled(setup_with_funds: (AppAccepting, u64)) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        default_sender(),
+        &create_msg_commit(),
+        &[default_convert_amount()],
+        Expect::ErrContains(AUCTION_NOT_CONFIGURED),
+    );
+}
+
+#[rstest]
+fn convert_in_batch_mode(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr) = setup_auction(setup_with_funds);
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &default_convert(),
+        &[default_convert_amount()],
+        Expect::ErrContains(BATCH_MODE),
+    );
+
+    // Conversions are direct again once batch mode is disabled
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract_addr.as_str(),
+        &create_msg_set_auction(Value::Null),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &default_convert(),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+}
+
+// The commitments of a sender add up to the amount their claim converts, which must stay
+// within the maximum conversion amount
+#[rstest]
+fn commit_max_amount(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr, _) = prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &modify_instantiate(Field::MaxConvertAmount, "1000"),
+        &[],
+        default_admin(),
+        &create_msg_set_auction(auction_config("0.25")),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &create_msg_commit(),
+        &[coin(600, DEFAULT_SOURCE_DENOM)],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &create_msg_commit(),
+        &[coin(401, DEFAULT_SOURCE_DENOM)],
+        Expect::ErrContains(ABOVE_MAX_AMOUNT),
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &create_msg_commit(),
+        &[coin(400, DEFAULT_SOURCE_DENOM)],
+        Expect::Ok,
+    );
+}
+
+// Claims count against the daily quota of the committer
+#[cfg(feature = "quotas")]
+#[rstest]
+fn claim_daily_quota(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr, _) = prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &modify_instantiate(Field::DailyQuota, "500"),
+        &[],
+        default_admin(),
+        &create_msg_set_auction(auction_config("0.25")),
+        &[],
+        Expect::Ok,
+    );
+    let contract = contract_addr.as_str();
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_commit(),
+        &[coin(600, DEFAULT_SOURCE_DENOM)],
+        Expect::Ok,
+    );
+    advance_seconds(&mut app, 100);
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_settle(),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_claim(1),
+        &[],
+        Expect::ErrContains(QUOTA_EXCEEDED),
+    );
+}
+
+#[rstest]
+fn auction_round(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr) = setup_auction(setup_with_funds);
+    let contract = contract_addr.as_str();
+    let other = make_addr("other");
+    app.send_tokens(
+        Addr::unchecked(DEFAULT_SENDER),
+        other.clone(),
+        &[default_convert_amount()],
+    )
+    .unwrap();
+
+    // Twice the target demand is committed, the clearing rate is half the configured one
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_commit(),
+        &[coin(600, DEFAULT_SOURCE_DENOM)],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_commit(),
+        &[coin(400, DEFAULT_SOURCE_DENOM)],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        other.as_str(),
+        contract,
+        &create_msg_commit(),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_settle(),
+        &[],
+        Expect::ErrContains(ROUND_OPEN),
+    );
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_set_auction(Value::Null),
+        &[],
+        Expect::ErrContains(PENDING_COMMITMENTS),
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_claim(1),
+        &[],
+        Expect::ErrContains(NOT_SETTLED),
+    );
+
+    advance_seconds(&mut app, 100);
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_commit(),
+        &[default_convert_amount()],
+        Expect::ErrContains(ROUND_ENDED),
+    );
+    let res = app
+        .execute_contract(
+            other.clone(),
+            contract_addr.clone(),
+            &create_msg_settle(),
+            &[],
+        )
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "settle_auction")
+            .add_attribute("round", "1")
+            .add_attribute("round_committed", "2000")
+            .add_attribute("clearing_fraction", "0.5")
+            .add_attribute("clearing_rate", "0.25")
+    ));
+    let settlement: Value = app
+        .wrap()
+        .query_wasm_smart(contract, &json!({"auction_settlement": {"round": 1}}))
+        .unwrap();
+    assert_eq!(settlement["rate"], json!("0.25"));
+    let auction: Value = app
+        .wrap()
+        .query_wasm_smart(contract, &json!({"auction": {}}))
+        .unwrap();
+    assert_eq!(auction["round"]["id"], json!(2));
+    assert_eq!(auction["round"]["committed"], json!("0"));
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(DEFAULT_SENDER),
+            contract_addr.clone(),
+            &create_msg_claim(1),
+            &[],
+        )
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "convert")
+            .add_attribute("burned", "1000")
+            .add_attribute("minted", "250")
+            .add_attribute("round", "1")
+    ));
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_claim(1),
+        &[],
+        Expect::ErrContains(NO_COMMITMENT),
+    );
+    run_execute(
+        &mut app,
+        other.as_str(),
+        contract,
+        &create_msg_claim(1),
+        &[],
+        Expect::Ok,
+    );
+    let unsolicited: Value = app
+        .wrap()
+        .query_wasm_smart(
+            contract,
+            &json!({"unsolicited_balance": {"denom": DEFAULT_SOURCE_DENOM}}),
+        )
+        .unwrap();
+    assert_eq!(unsolicited["escrowed"], json!("0"));
+}
+
+// Claims convert at the clearing rate whatever the rate strategy
+#[rstest]
+fn claim_with_rate_strategy(setup_with_funds: (AppAccepting, u64)) {
+    let strategy = json!({"tiered": {"tiers": [{"min_amount": "1000", "rate": "0.6"}]}});
+    let (mut app, contract_addr, _) = prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &modify_instantiate(Field::RateStrategy, strategy),
+        &[],
+        default_admin(),
+        &create_msg_set_auction(auction_config("0.25")),
+        &[],
+        Expect::Ok,
+    );
+    let contract = contract_addr.as_str();
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_commit(),
+        &[coin(2_000, DEFAULT_SOURCE_DENOM)],
+        Expect::Ok,
+    );
+    advance_seconds(&mut app, 100);
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_settle(),
+        &[],
+        Expect::Ok,
+    );
+    let res = app
+        .execute_contract(
+            Addr::unchecked(DEFAULT_SENDER),
+            contract_addr.clone(),
+            &create_msg_claim(1),
+            &[],
+        )
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("burned", "2000")
+            .add_attribute("minted", "500")
+            .add_attribute("clearing_rate", "0.25")
+    ));
+}
+
+// A clearing rate below the rate floor cannot be claimed, the commitment is cancelled instead
+#[rstest]
+fn cancel_commitment_out_of_bounds(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr, _) = prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &modify_instantiate(Field::RateFloor, "0.3"),
+        &[],
+        default_admin(),
+        &create_msg_set_auction(auction_config("0.25")),
+        &[],
+        Expect::Ok,
+    );
+    let contract = contract_addr.as_str();
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_commit(),
+        &[coin(2_000, DEFAULT_SOURCE_DENOM)],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_cancel(1),
+        &[],
+        Expect::ErrContains(NOT_SETTLED),
+    );
+    advance_seconds(&mut app, 100);
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_settle(),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_claim(1),
+        &[],
+        Expect::ErrContains(RATE_OUT_OF_BOUNDS),
+    );
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(DEFAULT_SENDER),
+            contract_addr.clone(),
+            &create_msg_cancel(1),
+            &[],
+        )
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "cancel_auction_commitment")
+            .add_attribute("refunded", coin(2_000, DEFAULT_SOURCE_DENOM).to_string())
+    ));
+    assert_eq!(
+        app.wrap()
+            .query_balance(DEFAULT_SENDER, DEFAULT_SOURCE_DENOM)
+            .unwrap(),
+        coin(1_000_000, DEFAULT_SOURCE_DENOM)
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_claim(1),
+        &[],
+        Expect::ErrContains(NO_COMMITMENT),
+    );
+}
+
+#[rstest]
+fn auction_round_below_target(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr) = setup_auction(setup_with_funds);
+    let contract = contract_addr.as_str();
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_commit(),
+        &[coin(500, DEFAULT_SOURCE_DENOM)],
+        Expect::Ok,
+    );
+    advance_seconds(&mut app, 100);
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_settle(),
+        &[],
+        Expect::Ok,
+    );
+
+    // The configured rate applies in full up to the target demand
+    let res = app
+        .execute_contract(
+            Addr::unchecked(DEFAULT_SENDER),
+            contract_addr.clone(),
+            &create_msg_claim(1),
+            &[],
+        )
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("burned", "500")
+            .add_attribute("minted", "250")
+    ));
+}
@@ -0,0 +1,355 @@
+use crate::common::*;
+use cosmwasm_std::{coin, Addr, Event, Uint256};
+use cw_multi_test::Executor;
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn create_msg_set_authz_enabled(enabled: bool) -> Value {
+    json!({"set_authz_enabled": {"enabled": enabled}})
+}
+
+fn query_grants(app: &AppAccepting, contract_addr: &Addr) -> Value {
+    app.wrap()
+        .query_wasm_smart(contract_addr, &json!({"grants": {}}))
+        .unwrap()
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::disable(default_admin(), create_msg_set_authz_enabled(false), Expect::Ok)]
+#[case::enable(default_admin(), create_msg_set_authz_enabled(true), Expect::Ok)]
+#[case::unauthorized(DEFAULT_SENDER, create_msg_set_authz_enabled(false), Expect::ErrContains(ONLY_ADMIN))]
+#[case::invalid(default_admin(), json!({"set_authz_enabled": {"enabled": "a"}}), Expect::ErrContains(INVALID_TYPE_STRING))]
+fn set_authz_enabled(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] exec_sender: &str,
+    #[case] exec_msg: Value,
+    #[case] expect: Expect<'_>,
+) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        exec_sender,
+        &exec_msg,
+        &[],
+        expect,
+    );
+}
+
+#[rstest]
+fn authz_disabled_blocks_convert(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr, _) = prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        default_admin(),
+        &create_msg_set_authz_enabled(false),
+        &[],
+        Expect::Ok,
+    );
+    let contract = contract_addr.as_str();
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &default_convert(),
+        &[default_convert_amount()],
+        Expect::ErrContains(AUTHZ_DISABLED),
+    );
+
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_set_authz_enabled(true),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &default_convert(),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+}
+
+#[rstest]
+fn query_grants_expected(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let expected = |enabled: bool| {
+        json!({
+            "granter": DEFAULT_POA_ADMIN,
+            "grantee": contract_addr,
+            "type_urls": [
+                "/liftedinit.manifest.v1.MsgBurnHeldBalance",
+                "/osmosis.tokenfactory.v1beta1.MsgMint",
+            ],
+            "enabled": enabled,
+        })
+    };
+    assert_eq!(query_grants(&app, &contract_addr), expected(true));
+
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract_addr.as_str(),
+        &create_msg_set_authz_enabled(false),
+        &[],
+        Expect::Ok,
+    );
+    assert_eq!(query_grants(&app, &contract_addr), expected(false));
+}
+
+#[rstest]
+fn expected_grants(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let grant = |type_url: &str| json!({"granter": DEFAULT_POA_ADMIN, "grantee": contract_addr, "type_url": type_url});
+
+    let grants: Value = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &json!({"expected_grants": {}}))
+        .unwrap();
+    assert_eq!(
+        grants,
+        json!({"grants": [
+            grant("/liftedinit.manifest.v1.MsgBurnHeldBalance"),
+            grant("/osmosis.tokenfactory.v1beta1.MsgMint"),
+        ]})
+    );
+
+    // Anyone can emit them, one event per grant
+    let res = app
+        .execute_contract(
+            Addr::unchecked(DEFAULT_SENDER),
+            contract_addr.clone(),
+            &json!({"emit_expected_grants": {}}),
+            &[],
+        )
+        .unwrap();
+    for type_url in [
+        "/liftedinit.manifest.v1.MsgBurnHeldBalance",
+        "/osmosis.tokenfactory.v1beta1.MsgMint",
+    ] {
+        assert!(res.has_event(
+            &Event::new("wasm-expected_grant")
+                .add_attribute("granter", DEFAULT_POA_ADMIN)
+                .add_attribute("grantee", contract_addr.as_str())
+                .add_attribute("type_url", type_url)
+        ));
+    }
+}
+
+#[rstest]
+fn poa_admin_is_grantee(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract_addr.as_str(),
+        &create_msg_update_config(Field::PoaAdmin, contract_addr.as_str()),
+        &[],
+        Expect::ErrContains(POA_ADMIN_IS_GRANTEE),
+    );
+}
+
+// A MsgExec rejected by x/authz fails the conversion without reverting it, the source tokens
+// are kept for the sender to claim back
+#[rustfmt::skip]
+#[rstest]
+#[case::missing_grant(AuthzFailure::MissingGrant, AUTHZ_NOT_FOUND)]
+#[case::expired_grant(AuthzFailure::ExpiredGrant, AUTHZ_EXPIRED)]
+#[case::unexpected_type_url(AuthzFailure::UnexpectedTypeUrl, UNRESOLVED_TYPE_URL)]
+fn authz_failure_refunds(#[case] failure: AuthzFailure, #[case] error: &str) {
+    let (mut app, code_id) = setup_authz(Some(failure));
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &default_instantiate(),
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(default_sender()),
+            contract_addr.clone(),
+            &default_convert(),
+            &[default_convert_amount()],
+        )
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "fail_conversion")
+            .add_attribute("refundable", default_convert_amount().to_string())
+    ));
+
+    let receipt: Value = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &json!({"conversion": {"id": 1}}))
+        .unwrap();
+    let receipt_error = receipt["status"]["failed"]["error"].as_str().unwrap();
+    assert!(receipt_error.contains(error), "unexpected error: {receipt_error}");
+
+    let before = app
+        .wrap()
+        .query_balance(default_sender(), DEFAULT_SOURCE_DENOM)
+        .unwrap();
+    app.execute_contract(
+        Addr::unchecked(default_sender()),
+        contract_addr,
+        &json!({"claim_refund": {}}),
+        &[],
+    )
+    .unwrap();
+    let after = app
+        .wrap()
+        .query_balance(default_sender(), DEFAULT_SOURCE_DENOM)
+        .unwrap();
+    assert_eq!(after.amount - before.amount, default_convert_amount().amount);
+}
+
+// The callback is only sent once the conversion succeeded. Target tokens the contract holds,
+// e.g. its float, must not pay a callback whose tokens were never minted
+#[rstest]
+fn authz_failure_skips_callback(
+    #[with(Some(AuthzFailure::MissingGrant))] setup_authz: (AppAuthz, u64),
+) {
+    let (mut app, code_id) = setup_authz;
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &default_instantiate(),
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+    let receiver = instantiate_receiver(&mut app);
+    app.send_tokens(
+        Addr::unchecked(default_sender()),
+        contract_addr.clone(),
+        &[coin(500, DEFAULT_TARGET_DENOM)],
+    )
+    .unwrap();
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(default_sender()),
+            contract_addr.clone(),
+            &create_msg_convert_with_callback(receiver.as_str(), json!({"hook": {}})),
+            &[default_convert_amount()],
+        )
+        .unwrap();
+    assert!(res.has_event(&Event::new("wasm").add_attribute("action", "fail_conversion")));
+
+    let balance = |addr: &Addr| {
+        app.wrap()
+            .query_balance(addr, DEFAULT_TARGET_DENOM)
+            .unwrap()
+            .amount
+    };
+    assert!(balance(&receiver).is_zero());
+    assert_eq!(balance(&contract_addr), Uint256::from(500u32));
+}
+
+#[rstest]
+fn authz_without_failure_succeeds(setup_authz: (AppAuthz, u64)) {
+    let (mut app, code_id) = setup_authz;
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &default_instantiate(),
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(default_sender()),
+            contract_addr,
+            &default_convert(),
+            &[default_convert_amount()],
+        )
+        .unwrap();
+    assert!(res.has_event(&Event::new("wasm").add_attribute("action", "finalize_conversion")));
+}
+
+#[rstest]
+fn authz_payload_hashes(setup_with_funds: (AppAccepting, u64)) {
+    use converter::msg::SimulateExecutionResponse;
+    use manifest_std::google::protobuf::Any;
+    use prost::Message;
+    use sha2::{Digest, Sha256};
+
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &modify_instantiate(Field::HashAuthzPayloads, true),
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+    let simulated: SimulateExecutionResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &contract_addr,
+            &json!({"simulate_execution": {"sender": DEFAULT_SENDER, "amount": "1000"}}),
+        )
+        .unwrap();
+
+    // One hash per message of the MsgExec, in order, over its encoding with the type URL
+    let hashes = simulated
+        .authz_msgs
+        .into_iter()
+        .map(|msg| {
+            let any = Any {
+                type_url: msg.type_url,
+                value: msg.value.to_vec(),
+            };
+            Sha256::digest(any.encode_to_vec())
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+    let res = app
+        .execute_contract(
+            Addr::unchecked(DEFAULT_SENDER),
+            contract_addr,
+            &default_convert(),
+            &[default_convert_amount()],
+        )
+        .unwrap();
+    assert!(
+        res.has_event(&Event::new("wasm").add_attribute("authz_payload_hashes", hashes.join(",")))
+    );
+    let convert = res
+        .events
+        .iter()
+        .find(|event| event.attributes.iter().any(|a| a.value == "convert"))
+        .unwrap();
+    assert!(convert
+        .attributes
+        .iter()
+        .all(|a| a.key != "authz_msg_count" && a.key != "mint_type"));
+}
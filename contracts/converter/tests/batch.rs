@@ -0,0 +1,110 @@
+use crate::common::*;
+use cosmwasm_std::{coin, Addr, Event};
+use cw_multi_test::Executor;
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn create_msg_batch(ops: Vec<Value>) -> Value {
+    json!({"batch": {"ops": ops}})
+}
+
+// Update of the single field, so that the ops of a batch do not override each other
+fn update_config_op(field: Field, value: impl serde::Serialize) -> Value {
+    json!({"update_config": {"config": {field.as_ref(): value}}})
+}
+
+fn pause_op() -> Value {
+    json!({"pause": {"reason": "maintenance", "message": null}})
+}
+
+fn query_config(app: &AppAccepting, contract_addr: &Addr) -> Value {
+    app.wrap()
+        .query_wasm_smart(contract_addr, &json!({"config": {}}))
+        .unwrap()
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::single_op(default_admin(), create_msg_batch(vec![pause_op()]), Expect::Ok)]
+#[case::empty(default_admin(), create_msg_batch(vec![]), Expect::ErrContains(INVALID_BATCH))]
+#[case::too_many(default_admin(), create_msg_batch(vec![pause_op(); 17]), Expect::ErrContains(INVALID_BATCH))]
+#[case::unauthorized(DEFAULT_SENDER, create_msg_batch(vec![update_config_op(Field::Rate, "0.6")]), Expect::ErrContains(ONLY_ADMIN))]
+fn execute_batch(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] exec_sender: &str,
+    #[case] exec_msg: Value,
+    #[case] expect: Expect<'_>,
+) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        exec_sender,
+        &exec_msg,
+        &[],
+        expect,
+    );
+}
+
+#[rstest]
+fn batch_applies_all_ops(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(default_admin()),
+            contract_addr.clone(),
+            &create_msg_batch(vec![
+                update_config_op(Field::Rate, "0.6"),
+                update_config_op(Field::FlatFee, coin(10, DUMMY_DENOM)),
+                pause_op(),
+            ]),
+            &[],
+        )
+        .unwrap();
+
+    // Each operation reports its own attributes
+    assert!(res.has_event(
+        &Event::new("wasm-batch_op")
+            .add_attribute("index", "0")
+            .add_attribute("op", "update_config")
+            .add_attribute("action", "update_config")
+    ));
+    assert!(res.has_event(
+        &Event::new("wasm-batch_op")
+            .add_attribute("index", "2")
+            .add_attribute("op", "pause")
+            .add_attribute("action", "pause")
+    ));
+    let config = query_config(&app, &contract_addr);
+    assert_eq!(config["rate"], json!("0.6"));
+    assert_eq!(config["flat_fee"], json!(coin(10, DUMMY_DENOM)));
+    assert_eq!(config["paused"], json!(true));
+}
+
+#[rstest]
+fn batch_is_atomic(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+
+    // The notify target is not a contract, the rate update before it is reverted
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract_addr.as_str(),
+        &create_msg_batch(vec![
+            update_config_op(Field::Rate, "0.6"),
+            json!({"set_notify_target": {"target": DEFAULT_SENDER}}),
+        ]),
+        &[],
+        Expect::ErrContains(NOTIFY_TARGET_NOT_CONTRACT),
+    );
+    assert_eq!(
+        query_config(&app, &contract_addr)["rate"],
+        json!(DEFAULT_RATE)
+    );
+}
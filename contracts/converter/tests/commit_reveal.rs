@@ -0,0 +1,241 @@
+use crate::common::*;
+use converter::msg::RevealPreimage;
+use cosmwasm_std::{coin, to_json_vec, Addr, Binary, Coin, Event, Uint256};
+use cw_multi_test::Executor;
+use rstest::*;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+mod common;
+
+fn salt() -> Binary {
+    Binary::from(b"salt")
+}
+
+fn commitment_hash(amount: u128, salt: &Binary) -> Binary {
+    let preimage = RevealPreimage {
+        sender: DEFAULT_SENDER.to_string(),
+        amount: Uint256::from(amount),
+        salt: salt.clone(),
+    };
+    Binary::from(Sha256::digest(to_json_vec(&preimage).unwrap()).as_slice())
+}
+
+fn create_msg_commit(hash: &Binary) -> Value {
+    json!({"commit_convert": {"hash": hash}})
+}
+
+fn create_msg_reveal(amount: u128, salt: &Binary) -> Value {
+    json!({"reveal_convert": {"amount": amount.to_string(), "salt": salt}})
+}
+
+// Instantiate the contract and commit to converting 600 of the default convert amount
+fn setup_commitment(setup_with_funds: (AppAccepting, u64)) -> (AppAccepting, Addr) {
+    let (app, contract_addr, _) = prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        default_sender(),
+        &create_msg_commit(&commitment_hash(600, &salt())),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+    (app, contract_addr)
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::ok(create_msg_commit(&commitment_hash(600, &salt())), Expect::Ok)]
+#[case::invalid_hash(create_msg_commit(&Binary::from(b"short")), Expect::ErrContains(INVALID_COMMITMENT_HASH))]
+fn commit_convert(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] exec_msg: Value,
+    #[case] expect: Expect<'_>,
+) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        default_sender(),
+        &exec_msg,
+        &[default_convert_amount()],
+        expect,
+    );
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::same_block(0, create_msg_reveal(600, &salt()), vec![], Expect::ErrContains(COMMITMENT_SAME_BLOCK))]
+#[case::expired(3_600, create_msg_reveal(600, &salt()), vec![], Expect::ErrContains(COMMITMENT_EXPIRED))]
+#[case::wrong_amount(5, create_msg_reveal(500, &salt()), vec![], Expect::ErrContains(COMMITMENT_HASH_MISMATCH))]
+#[case::wrong_salt(5, create_msg_reveal(600, &Binary::from(b"pepper")), vec![], Expect::ErrContains(COMMITMENT_HASH_MISMATCH))]
+#[case::already_committed(5, create_msg_commit(&commitment_hash(600, &salt())), vec![default_convert_amount()], Expect::ErrContains(ALREADY_COMMITTED))]
+fn reveal_convert_invalid(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] elapsed: u64,
+    #[case] exec_msg: Value,
+    #[case] funds: Vec<Coin>,
+    #[case] expect: Expect<'_>,
+) {
+    let (mut app, contract_addr) = setup_commitment(setup_with_funds);
+    if elapsed > 0 {
+        advance_seconds(&mut app, elapsed);
+    }
+    run_execute(&mut app, DEFAULT_SENDER, contract_addr.as_str(), &exec_msg, &funds, expect);
+}
+
+#[rstest]
+fn reveal_convert(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr) = setup_commitment(setup_with_funds);
+    advance_seconds(&mut app, 5);
+    let res = app
+        .execute_contract(
+            Addr::unchecked(DEFAULT_SENDER),
+            contract_addr.clone(),
+            &create_msg_reveal(600, &salt()),
+            &[],
+        )
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "convert")
+            .add_attribute("burned", "600")
+            .add_attribute("minted", "300")
+            .add_attribute("returned", coin(400, DEFAULT_SOURCE_DENOM).to_string())
+    ));
+    assert_eq!(
+        app.wrap()
+            .query_balance(DEFAULT_SENDER, DEFAULT_SOURCE_DENOM)
+            .unwrap(),
+        coin(999_400, DEFAULT_SOURCE_DENOM)
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &create_msg_reveal(600, &salt()),
+        &[],
+        Expect::ErrContains(NO_COMMITMENT_PENDING),
+    );
+}
+
+#[rstest]
+fn reveal_convert_exceeds_funds(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr, _) = prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        default_sender(),
+        &create_msg_commit(&commitment_hash(2_000, &salt())),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+    advance_seconds(&mut app, 5);
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &create_msg_reveal(2_000, &salt()),
+        &[],
+        Expect::ErrContains(COMMITMENT_EXCEEDS_FUNDS),
+    );
+}
+
+// A revealed amount above the maximum conversion amount fails, the commitment can still be
+// cancelled
+#[rstest]
+fn reveal_convert_max_amount(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr, _) = prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &modify_instantiate(Field::MaxConvertAmount, "500"),
+        &[],
+        default_sender(),
+        &create_msg_commit(&commitment_hash(600, &salt())),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+    advance_seconds(&mut app, 5);
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &create_msg_reveal(600, &salt()),
+        &[],
+        Expect::ErrContains(ABOVE_MAX_AMOUNT),
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &json!({"cancel_commitment": {}}),
+        &[],
+        Expect::Ok,
+    );
+}
+
+// Revealed conversions count against the daily quota of the sender
+#[cfg(feature = "quotas")]
+#[rstest]
+fn reveal_convert_daily_quota(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr, _) = prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &modify_instantiate(Field::DailyQuota, "500"),
+        &[],
+        default_sender(),
+        &create_msg_commit(&commitment_hash(600, &salt())),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+    advance_seconds(&mut app, 5);
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &create_msg_reveal(600, &salt()),
+        &[],
+        Expect::ErrContains(QUOTA_EXCEEDED),
+    );
+}
+
+#[rstest]
+fn cancel_commitment(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr) = setup_commitment(setup_with_funds);
+    let commitment: Value = app
+        .wrap()
+        .query_wasm_smart(
+            &contract_addr,
+            &json!({"commitment": {"address": DEFAULT_SENDER}}),
+        )
+        .unwrap();
+    assert_eq!(commitment["funds"], json!(default_convert_amount()));
+
+    // Expired commitments can only be cancelled
+    advance_seconds(&mut app, 3_600);
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &json!({"cancel_commitment": {}}),
+        &[],
+        Expect::Ok,
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance(DEFAULT_SENDER, DEFAULT_SOURCE_DENOM)
+            .unwrap(),
+        coin(1_000_000, DEFAULT_SOURCE_DENOM)
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &json!({"cancel_commitment": {}}),
+        &[],
+        Expect::ErrContains(NO_COMMITMENT_PENDING),
+    );
+}
@@ -1,16 +1,32 @@
 #![allow(dead_code)] // Allow dead code since not all helpers are used in every test file
 
 use const_format::str_splice_out;
-use converter::{execute, instantiate, migrate, query};
+use converter::{execute, instantiate, migrate, query, reply, sudo};
 use cosmwasm_std::testing::{MockApi, MockStorage};
-use cosmwasm_std::{coin, Addr, Coin, Empty};
+use cosmwasm_std::{
+    coin, to_json_binary, Addr, AnyMsg, Api, Binary, BlockInfo, Coin, CosmosMsg, CustomMsg,
+    CustomQuery, Deps, DepsMut, Empty, Env, Event, GrpcQuery, MessageInfo, Order, Querier, Record,
+    Response, StdError, StdResult, Storage,
+};
 use cw_multi_test::{
-    App, AppBuilder, BankKeeper, ContractWrapper, DistributionKeeper, Executor, FailingModule,
-    GovFailingModule, IbcFailingModule, StakeKeeper, StargateAccepting, WasmKeeper,
+    App, AppBuilder, AppResponse, BankKeeper, ContractWrapper, CosmosRouter, DistributionKeeper,
+    Executor, FailingModule, GovFailingModule, IbcFailingModule, StakeKeeper, Stargate,
+    StargateAccepting, StargateFailing, WasmKeeper,
+};
+use manifest_std::cosmos::authz::v1beta1::MsgExec;
+use manifest_std::cosmos::group::v1::{
+    GroupPolicyInfo, QueryGroupPolicyInfoRequest, QueryGroupPolicyInfoResponse,
+    ThresholdDecisionPolicy,
 };
+use manifest_std::google::protobuf::Any;
+use manifest_std::liftedinit::manifest::v1::MsgBurnHeldBalance;
+use manifest_std::osmosis::tokenfactory::v1beta1::MsgMint;
+use prost::Message;
 use rstest::*;
+use serde::de::DeserializeOwned;
 use serde::Serialize;
 use serde_json::{json, Value};
+use std::sync::OnceLock;
 use strum_macros::{AsRefStr, IntoStaticStr};
 
 // Default values for instantiation
@@ -19,7 +35,9 @@ pub const DEFAULT_POA_ADMIN: &str =
     "manifest1afk9zr2hn2jsac63h4hm60vl9z3e5u69gndzf7c99cqge3vzwjzsfmy9qj";
 pub const DEFAULT_SENDER: &str =
     "manifest1pgm8hyk0pvphmlvfjc8wsvk4daluz5tgrw6pu5mfpemk74uxnx9qdtpy2n";
-const DEFAULT_RATE: &str = "0.5";
+pub const DEFAULT_RATE: &str = "0.5";
+// Expected semver of the message API, see tests/api_version.rs
+pub const API_VERSION: &str = "1.37.0";
 pub const DEFAULT_SOURCE_DENOM: &str = "umfx";
 pub const DEFAULT_TARGET_DENOM: &str = "upwr";
 pub const DUMMY_DENOM: &str = "udummy";
@@ -55,20 +73,49 @@ pub const WRONG_BECH32_PREFIX: &str = "Wrong bech32 prefix";
 pub const INVALID_TYPE_NULL: &str = "invalid type: null";
 pub const INVALID_TYPE_INTEGER: &str = "invalid type: integer";
 pub const INVALID_TYPE_STRING: &str = "invalid type: string";
+pub const INVALID_VALUE: &str = "invalid value";
 pub const RATE_IS_ZERO: &str = "rate is zero";
 pub const RATE_PARSE_FAILED: &str = "failed to parse rate";
 pub const RESULT_IS_ZERO: &str = "resulting amount is zero";
 pub const SAME_DENOM: &str = "source and target denom cannot be the same";
+pub const INVALID_EXPONENT: &str = "denom exponent exceeds maximum";
+pub const INCOMPLETE_EXPONENTS: &str = "source and target exponents must be set together";
 pub const EMPTY_DENOM: &str = "denom is empty";
 pub const INVALID_DENOM_FORMAT: &str = "invalid denom format";
 pub const INVALID_IBC_DENOM_FORMAT: &str = "invalid ibc denom format";
 pub const INVALID_FACTORY_DENOM_FORMAT: &str = "invalid factory denom format";
+pub const INVALID_EPOCH_LENGTH: &str = "epoch length must be greater than zero";
+pub const INVALID_SYMBOL: &str = "invalid denom symbol";
+pub const UNKNOWN_DENOM: &str = "denom has no supply and no metadata";
 pub const NON_PAYABLE: &str = "non-payable function called with funds";
 pub const INVALID_FUNDS: &str = "invalid funds sent";
 pub const INVALID_SOURCE_DENOM: &str = "invalid source denom";
 pub const CONTRACT_PAUSED: &str = "contract is paused";
+pub const AUTHZ_DISABLED: &str = "authz usage is disabled";
+pub const SENDER_NOT_ALLOWED: &str = "sender is not allowed to convert";
+pub const INVALID_CALLBACK_CONTRACT: &str = "callback target is not a contract";
 pub const ONLY_ADMIN: &str = "only admin can perform this action";
 pub const CANNOT_RENOUNCE: &str = "cannot renounce admin role";
+pub const NO_PENDING_ADMIN: &str = "no admin transfer pending";
+pub const NOT_PENDING_ADMIN: &str = "only the proposed admin can accept the transfer";
+pub const MISSING_PAUSER_ROLE: &str = "sender does not have the pauser role";
+pub const UNKNOWN_ROLE: &str = "unknown role";
+pub const QUORUM_NO_MEMBERS: &str = "quorum must have at least one member";
+pub const QUORUM_DUPLICATE_MEMBER: &str = "quorum members must be unique";
+pub const QUORUM_INVALID_THRESHOLD: &str = "threshold must be between 1 and the number of members";
+pub const QUORUM_ALREADY_CONFIGURED: &str = "quorum is already configured";
+pub const QUORUM_NOT_CONFIGURED: &str = "quorum is not configured";
+pub const QUORUM_APPROVAL_REQUIRED: &str = "action requires quorum approval";
+pub const QUORUM_NOT_MEMBER: &str = "only quorum members can perform this action";
+pub const QUORUM_ALREADY_APPROVED: &str = "action already approved by sender";
+pub const QUORUM_ACTION_NOT_FOUND: &str = "pending action not found";
+pub const QUORUM_NOT_ENOUGH_APPROVALS: &str = "not enough approvals";
+pub const QUORUM_ADMIN_ONLY_APPROVAL: &str =
+    "rate bound changes need the approval of a member other than the admin";
+pub const CONTRACT_FROZEN: &str = "contract is frozen";
+pub const AMOUNT_IS_ZERO: &str = "amount is zero";
+pub const EXCEEDS_UNRECORDED: &str = "amount exceeds unrecorded balance";
+pub const NOTHING_TO_RETURN: &str = "no unsolicited funds recorded for depositor";
 
 // The following errors are not defined in the contract, but are common CosmWasm errors
 
@@ -76,6 +123,93 @@ pub const CANNOT_RENOUNCE: &str = "cannot renounce admin role";
 // CosmWasm tries to send the funds from the invalid address to the contract, which fails
 pub const CANNOT_SUB: &str = "Cannot Sub with given operands";
 pub const ZERO_FUNDS: &str = "Cannot transfer empty coins amount";
+pub const ZERO_AMOUNT: &str = "funds contain a zero amount coin";
+pub const NO_REFUND: &str = "no refund pending for sender";
+pub const NOT_GROUP_POLICY: &str = "poa admin is not a group policy";
+pub const GROUP_THRESHOLD_TOO_LOW: &str = "group decision policy threshold is below minimum";
+pub const INVALID_BECH32_PREFIX: &str = "invalid bech32 prefix";
+pub const ADDRESS_PREFIX_MISMATCH: &str = "address does not use the configured bech32 prefix";
+pub const INVALID_FLAT_FEE: &str = "flat fee must be non-zero and not in the source denom";
+pub const NOOP_UPDATE: &str = "update does not change the config";
+pub const INVALID_RATE_BOUNDS: &str = "rate floor cannot exceed rate ceiling";
+pub const RATE_OUT_OF_BOUNDS: &str = "rate is outside the configured bounds";
+pub const RATE_BOUNDS_IMMUTABLE: &str = "rate bounds can only be changed through a quorum action";
+pub const INVALID_TIERS: &str = "tiers must be non-empty, with increasing non-zero minimum amounts";
+pub const INVALID_SCHEDULE: &str = "schedule must be non-empty, with increasing start times";
+pub const INVALID_CURVE: &str = "curve volume must be greater than zero";
+pub const ORACLE_NOT_CONTRACT: &str = "rate oracle is not a contract";
+pub const RESOLVER_NOT_CONTRACT: &str = "name resolver is not a contract";
+pub const UNRESOLVED_NAME: &str = "name is not registered with the resolver";
+pub const BECH32_PREFIX_IMMUTABLE: &str = "bech32 prefix cannot be changed";
+pub const INVALID_RECIPIENTS: &str = "recipients must be unique, with non-zero weights";
+pub const RECIPIENTS_WITH_CALLBACK: &str = "recipients cannot be combined with a callback";
+pub const SHARE_IS_ZERO: &str = "minted amount is too small to split among recipients";
+pub const INVALID_FEE: &str = "fee coin does not match the flat fee";
+pub const INVALID_PUBKEY: &str = "invalid secp256k1 public key";
+pub const PUBKEY_MISMATCH: &str = "public key does not match the deposit";
+pub const INVALID_SIGNATURE: &str = "invalid signature";
+pub const SIGNATURE_EXPIRED: &str = "signature deadline has passed";
+pub const INVALID_NONCE: &str = "nonce does not match the deposit";
+pub const NO_DEPOSIT: &str = "no deposit for address";
+pub const STREAM_INVALID_DURATION: &str = "duration must be greater than zero";
+pub const STREAM_NOT_FOUND: &str = "stream not found";
+pub const STREAM_NOT_OWNER: &str = "only the stream owner can perform this action";
+pub const NOTHING_TO_CLAIM: &str = "nothing to claim yet";
+pub const INVALID_AUCTION: &str = "invalid auction parameters";
+pub const AUCTION_NOT_CONFIGURED: &str = "auction mode is not enabled";
+pub const BATCH_MODE: &str = "conversions are batched in auction rounds";
+pub const ROUND_OPEN: &str = "round is still open";
+pub const ROUND_ENDED: &str = "round has ended and must be settled";
+pub const PENDING_COMMITMENTS: &str = "current round has commitments";
+pub const NOT_SETTLED: &str = "round is not settled";
+pub const NO_COMMITMENT: &str = "no commitment for sender in round";
+pub const INVALID_COMMITMENT_HASH: &str = "hash must be a 32 bytes SHA-256 digest";
+pub const ALREADY_COMMITTED: &str = "sender already has a pending commitment";
+pub const NO_COMMITMENT_PENDING: &str = "no pending commitment for sender";
+pub const COMMITMENT_SAME_BLOCK: &str = "commitment can only be revealed in a later block";
+pub const COMMITMENT_EXPIRED: &str = "commitment has expired";
+pub const COMMITMENT_HASH_MISMATCH: &str = "revealed values do not match the commitment";
+pub const COMMITMENT_EXCEEDS_FUNDS: &str = "amount exceeds the committed funds";
+pub const NOTIFY_TARGET_NOT_CONTRACT: &str = "notify target is not a contract";
+pub const INVALID_BATCH: &str = "batch must hold between 1 and 16 operations";
+pub const INVALID_LABEL: &str = "label must be non-empty";
+pub const INVALID_DESCRIPTION: &str = "description must be at most";
+pub const INVALID_URL: &str = "url must be an http(s) url";
+pub const INVALID_FUNDING: &str = "funding must be non-zero coins with unique denoms";
+pub const FUNDING_MISMATCH: &str = "funds do not match the declared funding";
+pub const INVALID_PAUSE_MESSAGE: &str = "pause message must be at most 256 bytes";
+pub const SENDER_NOT_VERIFIED: &str = "sender is not verified by the attestation contract";
+pub const VERIFIER_NOT_CONTRACT: &str = "verifier is not a contract";
+pub const INVALID_DELEGATION: &str = "delegation must list known config fields, without duplicates";
+pub const RATE_CHANGE_EXCEEDS_LIMIT: &str = "rate change exceeds the delegated limit";
+pub const NOTHING_TO_MINT: &str = "rounding reserve is below one base unit";
+pub const INVALID_FLOAT_FUNDS: &str = "float must be funded with the target denom";
+pub const INSUFFICIENT_FLOAT: &str = "float is insufficient";
+pub const INVALID_REPORT_RANGE: &str = "report range must have from_height at most to_height";
+pub const INVALID_MEMO: &str = "memo must be at most 256 bytes";
+pub const POA_ADMIN_IS_GRANTEE: &str = "poa admin cannot be the contract itself";
+pub const GRANTEE_PREFIX_MISMATCH: &str =
+    "contract address does not use the configured bech32 prefix";
+pub const NOTHING_TO_BURN: &str = "no residual target tokens to burn";
+pub const DEPOSIT_DENOM_MISMATCH: &str = "deposit is in another denom, withdraw it first";
+pub const INSUFFICIENT_DEPOSIT: &str = "deposit is insufficient";
+pub const INVALID_FEE_MODEL: &str =
+    "fee percentages must be at most 100, flat fees non-zero and tiers increasing";
+pub const CONFLICTING_FEES: &str = "flat fee cannot be combined with a fee model";
+pub const FEE_EXCEEDS_AMOUNT: &str = "fee exceeds the converted amount";
+pub const SLIPPAGE_EXCEEDED: &str = "minted amount is below min_receive";
+pub const BELOW_MIN_AMOUNT: &str = "amount is below the minimum conversion amount";
+pub const ABOVE_MAX_AMOUNT: &str = "amount is above the maximum conversion amount";
+pub const QUOTA_EXCEEDED: &str = "conversion exceeds the remaining daily quota";
+pub const INVALID_DAILY_QUOTA: &str = "daily quota must be non-zero";
+pub const QUOTAS_DISABLED: &str = "daily quotas are not supported by this build";
+pub const EPOCH_CAP_EXCEEDED: &str = "conversion exceeds the remaining epoch cap";
+pub const INVALID_EPOCH_CAP: &str = "epoch cap must be non-zero";
+pub const INVALID_CONVERT_LIMITS: &str =
+    "max conversion amount must be non-zero and at least the min conversion amount";
+pub const SUCCESSOR_NOT_CONTRACT: &str = "successor is not a contract";
+pub const NO_SUCCESSOR: &str = "no successor contract is set";
+pub const NO_POSITION: &str = "sender has no position to migrate";
 pub const UNKNOWN_VARIANT: &str = "unknown variant";
 pub const EXPECTED_VALUE: &str = "expected value";
 
@@ -94,12 +228,406 @@ pub type AppAccepting<ExecC = Empty, QueryC = Empty> = App<
     StargateAccepting,
 >;
 
+// Default app of the tests, with any stargate keeper
+pub type TestApp<S> = App<
+    BankKeeper,
+    MockApi,
+    MockStorage,
+    FailingModule<Empty, Empty, Empty>,
+    WasmKeeper<Empty, Empty>,
+    StakeKeeper,
+    DistributionKeeper,
+    IbcFailingModule,
+    GovFailingModule,
+    S,
+>;
+
+// Same as AppAccepting, but every stargate/any message fails, e.g. a missing authz grant
+pub type AppFailing<ExecC = Empty, QueryC = Empty> = App<
+    BankKeeper,
+    MockApi,
+    MockStorage,
+    FailingModule<ExecC, QueryC, Empty>,
+    WasmKeeper<ExecC, QueryC>,
+    StakeKeeper,
+    DistributionKeeper,
+    IbcFailingModule,
+    GovFailingModule,
+    StargateFailing,
+>;
+
+// Same as AppAccepting, but the default POA admin is a group policy, see StargateGroup
+pub type AppGroup<ExecC = Empty, QueryC = Empty> = App<
+    BankKeeper,
+    MockApi,
+    MockStorage,
+    FailingModule<ExecC, QueryC, Empty>,
+    WasmKeeper<ExecC, QueryC>,
+    StakeKeeper,
+    DistributionKeeper,
+    IbcFailingModule,
+    GovFailingModule,
+    StargateGroup,
+>;
+
+// Threshold of the default POA admin's group decision policy
+pub const DEFAULT_GROUP_THRESHOLD: u64 = 2;
+
+// Accepts any message like StargateAccepting, and answers x/group policy info queries for the
+// default POA admin only. Other addresses fail the query, as they do on chain
+pub struct StargateGroup;
+
+impl Stargate for StargateGroup {
+    fn execute_any<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        _sender: Addr,
+        _msg: AnyMsg,
+    ) -> StdResult<AppResponse>
+    where
+        ExecC: CustomMsg + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        Ok(AppResponse::default())
+    }
+
+    fn query_grpc(
+        &self,
+        _api: &dyn Api,
+        _storage: &dyn Storage,
+        _querier: &dyn Querier,
+        _block: &BlockInfo,
+        request: GrpcQuery,
+    ) -> StdResult<Binary> {
+        let query = QueryGroupPolicyInfoRequest::decode(request.data.as_slice())
+            .map_err(|e| StdError::msg(e.to_string()))?;
+        if request.path != "/cosmos.group.v1.Query/GroupPolicyInfo"
+            || query.address != DEFAULT_POA_ADMIN
+        {
+            return Err(StdError::msg("not found"));
+        }
+        let policy = ThresholdDecisionPolicy {
+            threshold: DEFAULT_GROUP_THRESHOLD.to_string(),
+            windows: None,
+        };
+        let info = GroupPolicyInfo {
+            address: query.address,
+            group_id: 1,
+            decision_policy: Some(Any {
+                type_url: ThresholdDecisionPolicy::TYPE_URL.to_string(),
+                value: policy.encode_to_vec(),
+            }),
+            ..Default::default()
+        };
+        Ok(QueryGroupPolicyInfoResponse { info: Some(info) }
+            .encode_to_vec()
+            .into())
+    }
+}
+
+// Same as AppAccepting, but authz MsgExec can be made to fail, see StargateAuthz
+pub type AppAuthz<ExecC = Empty, QueryC = Empty> = App<
+    BankKeeper,
+    MockApi,
+    MockStorage,
+    FailingModule<ExecC, QueryC, Empty>,
+    WasmKeeper<ExecC, QueryC>,
+    StakeKeeper,
+    DistributionKeeper,
+    IbcFailingModule,
+    GovFailingModule,
+    StargateAuthz,
+>;
+
+// Authz failures StargateAuthz reproduces, with the error of the x/authz keeper
+#[derive(Copy, Clone, Debug)]
+pub enum AuthzFailure {
+    // The POA admin never granted the burn to the contract, or revoked it
+    MissingGrant,
+    // The grant of the burn expired
+    ExpiredGrant,
+    // The chain does not know the type of the mint, e.g. renamed in a chain upgrade
+    UnexpectedTypeUrl,
+}
+
+pub const AUTHZ_NOT_FOUND: &str = "authorization not found";
+pub const AUTHZ_EXPIRED: &str = "authorization expired";
+pub const UNRESOLVED_TYPE_URL: &str = "unable to resolve type URL";
+
+// Accepts any message like StargateAccepting, but fails authz MsgExec with the configured
+// failure. Other messages, and every MsgExec without a failure, succeed
+pub struct StargateAuthz(pub Option<AuthzFailure>);
+
+impl Stargate for StargateAuthz {
+    fn execute_any<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        _sender: Addr,
+        msg: AnyMsg,
+    ) -> StdResult<AppResponse>
+    where
+        ExecC: CustomMsg + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        let Some(failure) = self.0 else {
+            return Ok(AppResponse::default());
+        };
+        if msg.type_url != MsgExec::TYPE_URL {
+            return Ok(AppResponse::default());
+        }
+        let exec =
+            MsgExec::decode(msg.value.as_slice()).map_err(|e| StdError::msg(e.to_string()))?;
+        let type_url = |expected: &str| {
+            exec.msgs
+                .iter()
+                .map(|msg| msg.type_url.clone())
+                .find(|type_url| type_url == expected)
+                .ok_or_else(|| StdError::msg(format!("no {expected} in MsgExec")))
+        };
+        match failure {
+            AuthzFailure::MissingGrant => Err(StdError::msg(format!(
+                "failed to get grant with given granter: {DEFAULT_POA_ADMIN}, grantee: {}, msgType: {}: {AUTHZ_NOT_FOUND}",
+                exec.grantee,
+                type_url(MsgBurnHeldBalance::TYPE_URL)?,
+            ))),
+            AuthzFailure::ExpiredGrant => Err(StdError::msg(format!(
+                "{AUTHZ_EXPIRED}: {}",
+                type_url(MsgBurnHeldBalance::TYPE_URL)?,
+            ))),
+            AuthzFailure::UnexpectedTypeUrl => Err(StdError::msg(format!(
+                "{UNRESOLVED_TYPE_URL} {}",
+                type_url(MsgMint::TYPE_URL)?,
+            ))),
+        }
+    }
+}
+
+// Bookkeeping surfaces a conversion updates, taken before converting and compared with their
+// state after it by `assert_conversion_consistent`
+pub struct ConversionSnapshot {
+    sender: String,
+    metrics: Value,
+    pair_totals: Value,
+    sender_balances: Vec<Coin>,
+    poa_admin_balances: Vec<Coin>,
+}
+
+pub fn snapshot_conversion<S: Stargate>(
+    app: &TestApp<S>,
+    contract_addr: &Addr,
+    sender: &str,
+) -> ConversionSnapshot {
+    let query = |msg: Value| -> Value { app.wrap().query_wasm_smart(contract_addr, &msg).unwrap() };
+    // Balances of the denoms a conversion moves
+    let config = query(json!({"config": {}}));
+    let denoms: Vec<&str> = [
+        &config["source_denom"],
+        &config["target_denom"],
+        &config["flat_fee"]["denom"],
+    ]
+    .into_iter()
+    .filter_map(Value::as_str)
+    .collect();
+    let balances = |addr: &str| -> Vec<Coin> {
+        denoms
+            .iter()
+            .map(|denom| app.wrap().query_balance(addr, *denom).unwrap())
+            .collect()
+    };
+    ConversionSnapshot {
+        sender: sender.to_string(),
+        metrics: query(json!({"metrics": {}}))["metrics"].clone(),
+        pair_totals: query(json!({"pair_totals": {}}))["items"].clone(),
+        sender_balances: balances(sender),
+        poa_admin_balances: balances(DEFAULT_POA_ADMIN),
+    }
+}
+
+// Cross-check the attributes of a successful conversion paid with sent funds against its
+// stored receipt, the metrics and pair totals, and the balances of the sender and the POA
+// admin, compared with the snapshot taken before converting
+pub fn assert_conversion_consistent<S: Stargate>(
+    app: &TestApp<S>,
+    contract_addr: &Addr,
+    before: &ConversionSnapshot,
+    res: &AppResponse,
+) {
+    let event = res
+        .events
+        .iter()
+        .find(|e| {
+            e.ty == "wasm"
+                && e.attributes
+                    .iter()
+                    .any(|a| a.key == "action" && a.value == "convert")
+        })
+        .expect("no convert event");
+    let attr = |key: &str| {
+        event
+            .attributes
+            .iter()
+            .find(|a| a.key == key)
+            .map(|a| a.value.clone())
+    };
+    let amount = |key: &str| -> u128 { attr(key).unwrap().parse().unwrap() };
+    let (burned, minted) = (amount("burned"), amount("minted"));
+    let (burned_denom, minted_denom) =
+        (attr("burned_denom").unwrap(), attr("minted_denom").unwrap());
+    let id: u64 = attr("conversion_id").unwrap().parse().unwrap();
+
+    // The receipt records what the attributes report, and the reply finalized it
+    let receipt: Value = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &json!({"conversion": {"id": id}}))
+        .unwrap();
+    assert_eq!(receipt["status"], json!("succeeded"), "receipt {id}");
+    assert_eq!(receipt["sender"], json!(before.sender));
+    assert_eq!(receipt["burned"], json!(coin(burned, &burned_denom)));
+    assert_eq!(receipt["minted"], json!(coin(minted, &minted_denom)));
+    let shares: u128 = receipt["mints"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|mint| {
+            mint["amount"]["amount"]
+                .as_str()
+                .unwrap()
+                .parse::<u128>()
+                .unwrap()
+        })
+        .sum();
+    assert_eq!(shares, minted, "mints of receipt {id}");
+    let fee: Option<Coin> = serde_json::from_value(receipt["fee"].clone()).unwrap();
+    assert_eq!(fee.as_ref().map(Coin::to_string), attr("fee"));
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "finalize_conversion")
+            .add_attribute("conversion_id", id.to_string())
+    ));
+
+    // Counters grow by the conversion
+    let metrics: Value = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &json!({"metrics": {}}))
+        .unwrap();
+    let metric = |metrics: &Value, key: &str| -> u128 {
+        metrics[key].as_str().map_or(0, |v| v.parse().unwrap())
+    };
+    for (key, delta) in [
+        ("conversions_total", 1),
+        ("burned_total", burned),
+        ("minted_total", minted),
+    ] {
+        assert_eq!(
+            metric(&metrics["metrics"], key),
+            metric(&before.metrics, key) + delta,
+            "metric {key}"
+        );
+    }
+    let pair_totals: Value = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &json!({"pair_totals": {}}))
+        .unwrap();
+    let pair = |items: &Value, key: &str| -> u128 {
+        items
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|item| {
+                item["source_denom"] == burned_denom && item["target_denom"] == minted_denom
+            })
+            .map_or(0, |item| match &item[key] {
+                Value::String(v) => v.parse().unwrap(),
+                v => v.as_u64().unwrap().into(),
+            })
+    };
+    for (key, delta) in [("conversions", 1), ("burned", burned), ("minted", minted)] {
+        assert_eq!(
+            pair(&pair_totals["items"], key),
+            pair(&before.pair_totals, key) + delta,
+            "pair total {key}"
+        );
+    }
+
+    // The sender paid the burned tokens and the fee, which went to the POA admin
+    let balance = |balances: &[Coin], denom: &str| -> u128 {
+        balances
+            .iter()
+            .find(|c| c.denom == denom)
+            .map_or(0, |c| c.amount.to_string().parse().unwrap())
+    };
+    let now = |addr: &str, denom: &str| -> u128 {
+        app.wrap()
+            .query_balance(addr, denom)
+            .unwrap()
+            .amount
+            .to_string()
+            .parse()
+            .unwrap()
+    };
+    let sender = before.sender.as_str();
+    // A fee model withholds the fee from the source tokens, which the sender paid as well
+    let withheld = fee
+        .as_ref()
+        .filter(|fee| fee.denom == burned_denom)
+        .map_or(0, |fee| fee.amount.to_string().parse().unwrap());
+    assert_eq!(
+        now(sender, &burned_denom),
+        balance(&before.sender_balances, &burned_denom) - burned - withheld,
+        "sender balance"
+    );
+    if let Some(fee) = fee {
+        let amount: u128 = fee.amount.to_string().parse().unwrap();
+        if fee.denom != burned_denom {
+            assert_eq!(
+                now(sender, &fee.denom),
+                balance(&before.sender_balances, &fee.denom) - amount,
+                "sender fee balance"
+            );
+        }
+        assert_eq!(
+            now(DEFAULT_POA_ADMIN, &fee.denom),
+            balance(&before.poa_admin_balances, &fee.denom) + amount,
+            "fee collected"
+        );
+    }
+}
+
+// Execute a conversion paid with the given funds and check its bookkeeping is consistent
+pub fn convert_consistently<S: Stargate>(
+    app: &mut TestApp<S>,
+    contract_addr: &Addr,
+    sender: &str,
+    msg: &Value,
+    funds: &[Coin],
+) -> AppResponse {
+    let before = snapshot_conversion(app, contract_addr, sender);
+    let res = app
+        .execute_contract(Addr::unchecked(sender), contract_addr.clone(), msg, funds)
+        .unwrap();
+    assert_conversion_consistent(app, contract_addr, &before, &res);
+    res
+}
+
 #[derive(Copy, Clone)]
 pub enum Expect<'a> {
     Ok,
     ErrContains(&'a str),
 }
 
+// Generate a valid address with the expected bech32 prefix
+pub fn make_addr(label: &str) -> Addr {
+    MockApi::default()
+        .with_prefix(BECH32_PREFIX)
+        .addr_make(label)
+}
+
 #[fixture]
 pub fn default_sender() -> &'static str {
     DEFAULT_SENDER
@@ -181,7 +709,10 @@ pub fn setup() -> (AppAccepting, u64) {
         .with_stargate(StargateAccepting)
         .build(|_, _, _| {});
     let code_id = app.store_code(Box::new(
-        ContractWrapper::new_with_empty(execute, instantiate, query).with_migrate(migrate),
+        ContractWrapper::new_with_empty(execute, instantiate, query)
+            .with_migrate(migrate)
+            .with_sudo(sudo)
+            .with_reply(reply),
     ));
     (app, code_id)
 }
@@ -202,11 +733,211 @@ pub fn setup_with_funds() -> (AppAccepting, u64) {
                 .expect("failed to init balance");
         });
     let code_id = app.store_code(Box::new(
-        ContractWrapper::new_with_empty(execute, instantiate, query).with_migrate(migrate),
+        ContractWrapper::new_with_empty(execute, instantiate, query)
+            .with_migrate(migrate)
+            .with_sudo(sudo)
+            .with_reply(reply),
     ));
     (app, code_id)
 }
 
+#[fixture]
+pub fn setup_failing_stargate() -> (AppFailing, u64) {
+    let mut app = AppBuilder::default()
+        .with_api(MockApi::default().with_prefix(BECH32_PREFIX))
+        .with_stargate(StargateFailing)
+        .build(|router, _, storage| {
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    &Addr::unchecked(default_sender()),
+                    default_initial_funds(),
+                )
+                .expect("failed to init balance");
+        });
+    let code_id = app.store_code(Box::new(
+        ContractWrapper::new_with_empty(execute, instantiate, query)
+            .with_migrate(migrate)
+            .with_sudo(sudo)
+            .with_reply(reply),
+    ));
+    (app, code_id)
+}
+
+// App with funds whose authz MsgExec fails as configured, e.g.
+// `#[with(Some(AuthzFailure::MissingGrant))] setup_authz: (AppAuthz, u64)`
+#[fixture]
+pub fn setup_authz(#[default(None)] failure: Option<AuthzFailure>) -> (AppAuthz, u64) {
+    let mut app = AppBuilder::default()
+        .with_api(MockApi::default().with_prefix(BECH32_PREFIX))
+        .with_stargate(StargateAuthz(failure))
+        .build(|router, _, storage| {
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    &Addr::unchecked(default_sender()),
+                    default_initial_funds(),
+                )
+                .expect("failed to init balance");
+        });
+    let code_id = app.store_code(Box::new(
+        ContractWrapper::new_with_empty(execute, instantiate, query)
+            .with_migrate(migrate)
+            .with_sudo(sudo)
+            .with_reply(reply),
+    ));
+    (app, code_id)
+}
+
+#[fixture]
+pub fn setup_group() -> (AppGroup, u64) {
+    let mut app = AppBuilder::default()
+        .with_api(MockApi::default().with_prefix(BECH32_PREFIX))
+        .with_stargate(StargateGroup)
+        .build(|_, _, _| {});
+    let code_id = app.store_code(Box::new(
+        ContractWrapper::new_with_empty(execute, instantiate, query)
+            .with_migrate(migrate)
+            .with_sudo(sudo)
+            .with_reply(reply),
+    ));
+    (app, code_id)
+}
+
+// A contract accepting any message, used as a conversion callback target
+fn receiver_instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+fn receiver_execute(_: DepsMut, _: Env, _: MessageInfo, _: Value) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+fn receiver_query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+    to_json_binary(&Empty {})
+}
+
+pub fn instantiate_receiver<S: Stargate>(app: &mut TestApp<S>) -> Addr {
+    let code_id = app.store_code(Box::new(ContractWrapper::new_with_empty(
+        receiver_execute,
+        receiver_instantiate,
+        receiver_query,
+    )));
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(default_sender()),
+        &Empty {},
+        &[],
+        "receiver",
+        None,
+    )
+    .expect("failed to instantiate receiver")
+}
+
+// A contract dispatching any message it is given, used to act as a contract sender
+fn proxy_execute(_: DepsMut, _: Env, _: MessageInfo, msg: CosmosMsg) -> StdResult<Response> {
+    Ok(Response::new().add_message(msg))
+}
+
+pub fn instantiate_proxy(app: &mut AppAccepting) -> Addr {
+    let code_id = app.store_code(Box::new(ContractWrapper::new_with_empty(
+        proxy_execute,
+        receiver_instantiate,
+        receiver_query,
+    )));
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(default_sender()),
+        &Empty {},
+        &[],
+        "proxy",
+        None,
+    )
+    .expect("failed to instantiate proxy")
+}
+
+// Storage and block of an App after an expensive setup. Restoring it into a fresh App shares
+// the setup between test cases without sharing their changes
+#[derive(Clone)]
+pub struct Snapshot {
+    entries: Vec<Record>,
+    block: BlockInfo,
+}
+
+impl Snapshot {
+    pub fn take<S: Stargate>(app: &TestApp<S>) -> Self {
+        Snapshot {
+            entries: app.storage().range(None, None, Order::Ascending).collect(),
+            block: app.block_info(),
+        }
+    }
+
+    // Replace the storage and block of the app. Code is not part of the snapshot, the app
+    // must have stored the same code in the same order as the one it was taken from
+    pub fn restore<S: Stargate>(&self, app: &mut TestApp<S>) {
+        let storage = app.storage_mut();
+        let keys: Vec<_> = storage.range_keys(None, None, Order::Ascending).collect();
+        for key in keys {
+            storage.remove(&key);
+        }
+        for (key, value) in &self.entries {
+            storage.set(key, value);
+        }
+        app.set_block(self.block.clone());
+    }
+}
+
+// Run `setup` once per test binary on a `setup_with_funds` app, and give every caller a fresh
+// app restored from its result, along with what `setup` returned. `setup` must not store code
+pub fn cached_setup<T: Clone + Send + Sync>(
+    cache: &'static OnceLock<(Snapshot, T)>,
+    setup: impl FnOnce(&mut AppAccepting, u64) -> T,
+) -> (AppAccepting, u64, T) {
+    let (snapshot, value) = cache.get_or_init(|| {
+        let (mut app, code_id) = setup_with_funds();
+        let value = setup(&mut app, code_id);
+        (Snapshot::take(&app), value)
+    });
+    let (mut app, code_id) = setup_with_funds();
+    snapshot.restore(&mut app);
+    (app, code_id, value.clone())
+}
+
+// Seconds between two blocks when advancing by blocks, the block time of cw-multi-test
+pub const BLOCK_TIME_SECONDS: u64 = 5;
+
+// Move the chain forward by `n` blocks, `BLOCK_TIME_SECONDS` apart
+pub fn advance_blocks<S: Stargate>(app: &mut TestApp<S>, n: u64) {
+    app.update_block(|block| {
+        block.height += n;
+        block.time = block.time.plus_seconds(n * BLOCK_TIME_SECONDS);
+    });
+}
+
+// Move the chain forward by `seconds` in a single block, e.g. past an expiry. The height
+// still increases, for the checks requiring a later block
+pub fn advance_seconds<S: Stargate>(app: &mut TestApp<S>, seconds: u64) {
+    app.update_block(|block| {
+        block.height += 1;
+        block.time = block.time.plus_seconds(seconds);
+    });
+}
+
+// Instantiate the converter with the default message
+pub fn instantiate_default(app: &mut AppAccepting, code_id: u64) -> Addr {
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(default_sender()),
+        &default_instantiate(),
+        &[],
+        "converter",
+        None,
+    )
+    .expect("failed to instantiate")
+}
+
 pub fn run_instantiate(
     mut app: AppAccepting,
     code_id: u64,
@@ -241,7 +972,7 @@ pub fn run_instantiate(
     }
 }
 
-fn run_execute(
+pub fn run_execute(
     app: &mut AppAccepting,
     sender: &str,
     contract_addr: &str,
@@ -314,6 +1045,34 @@ pub enum Field {
     SourceDenom,
     TargetDenom,
     Paused,
+    StrictDenoms,
+    SourceExponent,
+    TargetExponent,
+    SourceSymbol,
+    TargetSymbol,
+    AllowedSenders,
+    EpochLength,
+    RequireGroupPolicy,
+    MinGroupThreshold,
+    Bech32Prefix,
+    FlatFee,
+    RateFloor,
+    RateCeiling,
+    Verifier,
+    RedactSenders,
+    RateStrategy,
+    FloatPayouts,
+    Resolver,
+    HashAuthzPayloads,
+    FeeModel,
+    MinConvertAmount,
+    MaxConvertAmount,
+    DailyQuota,
+    EpochCap,
+    FeesToCommunityPool,
+    // Instantiate only
+    Metadata,
+    Funding,
 }
 
 pub fn modify_config(field: Field, value: impl serde::Serialize) -> Value {
@@ -336,10 +1095,18 @@ pub fn create_msg_update_config(field: Field, value: impl serde::Serialize) -> V
     json!({"update_config": {"config": modify_config(field, value)}})
 }
 
+pub fn create_msg_replace_config(config: &impl Serialize) -> Value {
+    json!({"replace_config": {"config": config}})
+}
+
 pub fn create_msg_update_config_noop() -> Value {
     json!({"update_config": {"config": {}}})
 }
 
+pub fn create_msg_convert_with_callback(contract: &str, msg: Value) -> Value {
+    json!({"convert": {"callback": {"contract": contract, "msg": to_json_binary(&msg).unwrap()}}})
+}
+
 pub fn create_msg_update_admin(new_admin: Option<&str>) -> Value {
     json!({"update_admin": {"admin": new_admin}})
 }
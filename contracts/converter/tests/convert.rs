@@ -1,6 +1,14 @@
 use crate::common::*;
-use cosmwasm_std::{coin, Coin};
+use converter::msg::ReceiptPayload;
+use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env, MockApi};
+use cosmwasm_std::{
+    coin, coins, to_json_binary, to_json_vec, Addr, Binary, Coin, CosmosMsg, Empty, Event, Uint256,
+    WasmMsg,
+};
+use cw_multi_test::Executor;
 use rstest::*;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
 
 mod common;
 
@@ -27,6 +35,226 @@ fn execute_convert_invalid_funds(
     );
 }
 
+// The bank module of the test app rejects zero amount funds before they reach the contract,
+// so they are sent to the entry point directly
+#[rstest]
+#[case::zero(coins(0, DEFAULT_SOURCE_DENOM))]
+#[case::zero_and_source(vec![coin(0, DUMMY_DENOM), default_convert_amount()])]
+fn execute_convert_zero_amount(#[case] funds: Vec<Coin>) {
+    let mut deps = mock_dependencies();
+    deps.api = MockApi::default().with_prefix("manifest");
+    // The contract address must use the configured prefix to be an authz grantee
+    let mut env = mock_env();
+    env.contract.address = deps.api.addr_make("cosmos2contract");
+    let sender = Addr::unchecked(DEFAULT_SENDER);
+    let msg = serde_json::from_value(default_instantiate()).unwrap();
+    converter::instantiate(deps.as_mut(), env.clone(), message_info(&sender, &[]), msg).unwrap();
+
+    let msg = serde_json::from_value(default_convert()).unwrap();
+    let err =
+        converter::execute(deps.as_mut(), env, message_info(&sender, &funds), msg).unwrap_err();
+    assert!(err.to_string().contains(ZERO_AMOUNT));
+}
+
+fn flat_fee_instantiate() -> Value {
+    modify_instantiate(Field::FlatFee, coin(10, DUMMY_DENOM))
+}
+
+#[rstest]
+#[case::exact_fee(&[default_convert_amount(), coin(10, DUMMY_DENOM)], Expect::Ok)]
+#[case::missing_fee(&[default_convert_amount()], Expect::ErrContains(INVALID_FEE))]
+#[case::wrong_fee_amount(&[default_convert_amount(), coin(11, DUMMY_DENOM)], Expect::ErrContains(INVALID_FEE))]
+#[case::fee_only(&[coin(10, DUMMY_DENOM)], Expect::ErrContains(INVALID_FUNDS))]
+#[case::extra_coin(&[default_convert_amount(), coin(10, DUMMY_DENOM), coin(10, DEFAULT_TARGET_DENOM)], Expect::ErrContains(INVALID_FUNDS))]
+fn execute_convert_flat_fee(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] funds: &[Coin],
+    #[case] expect: Expect<'_>,
+) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &flat_fee_instantiate(),
+        &[],
+        default_sender(),
+        &default_convert(),
+        funds,
+        expect,
+    );
+}
+
+#[rstest]
+fn execute_convert_flat_fee_paid(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &flat_fee_instantiate(),
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+    convert_consistently(
+        &mut app,
+        &contract_addr,
+        DEFAULT_SENDER,
+        &default_convert(),
+        &[default_convert_amount(), coin(10, DUMMY_DENOM)],
+    );
+    let balance = app
+        .wrap()
+        .query_balance(DEFAULT_POA_ADMIN, DUMMY_DENOM)
+        .unwrap();
+    assert_eq!(balance, coin(10, DUMMY_DENOM));
+}
+
+// A flat fee given in another case than the chain's is stored canonical, so the fee sent along
+// conversions still matches it
+#[rstest]
+fn execute_convert_flat_fee_canonical(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let fee = coin(10, VALID_IBC_DENOM);
+    app.init_modules(|router, _, storage| {
+        let mut funds = default_initial_funds();
+        funds.push(coin(1_000, VALID_IBC_DENOM));
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked(default_sender()), funds)
+    })
+    .unwrap();
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &modify_instantiate(Field::FlatFee, coin(10, VALID_IBC_DENOM.to_lowercase())),
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+    let config: Value = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &json!({"config": {}}))
+        .unwrap();
+    assert_eq!(config["flat_fee"], json!(fee));
+
+    convert_consistently(
+        &mut app,
+        &contract_addr,
+        DEFAULT_SENDER,
+        &default_convert(),
+        &[default_convert_amount(), fee.clone()],
+    );
+    let balance = app
+        .wrap()
+        .query_balance(DEFAULT_POA_ADMIN, VALID_IBC_DENOM)
+        .unwrap();
+    assert_eq!(balance, fee);
+}
+
+fn fee_model_instantiate(model: Value) -> Value {
+    modify_instantiate(Field::FeeModel, model)
+}
+
+// The fee is withheld from the source tokens, only the rest is converted
+#[rstest]
+#[case::percent(json!({"percent": {"percent": "1.5"}}), 15)]
+#[case::flat(json!({"flat": {"amount": "25"}}), 25)]
+#[case::percent_plus_flat(json!({"percent_plus_flat": {"percent": "1", "amount": "5"}}), 15)]
+#[case::tiered(json!({"tiered": {"tiers": [{"min_amount": "100", "percent": "2"}, {"min_amount": "5000", "percent": "1"}]}}), 20)]
+#[case::below_tiers(json!({"tiered": {"tiers": [{"min_amount": "5000", "percent": "1"}]}}), 0)]
+#[case::none(json!({"none": {}}), 0)]
+fn execute_convert_fee_model(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] model: Value,
+    #[case] fee: u128,
+) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &fee_model_instantiate(model),
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+    let res = convert_consistently(
+        &mut app,
+        &contract_addr,
+        DEFAULT_SENDER,
+        &default_convert(),
+        &[default_convert_amount()],
+    );
+    let burned = 1_000 - fee;
+    assert!(res.has_event(&Event::new("wasm").add_attribute("burned", burned.to_string())));
+    let balance = app
+        .wrap()
+        .query_balance(DEFAULT_POA_ADMIN, DEFAULT_SOURCE_DENOM)
+        .unwrap();
+    assert_eq!(balance, coin(fee, DEFAULT_SOURCE_DENOM));
+}
+
+// The fee funds the community pool through an any message instead of being sent to the POA
+// admin
+#[rstest]
+fn execute_convert_fee_to_community_pool(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let mut msg = fee_model_instantiate(json!({"percent": {"percent": "1.5"}}));
+    msg["fees_to_community_pool"] = json!(true);
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &msg,
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+    let res = app
+        .execute_contract(
+            Addr::unchecked(DEFAULT_SENDER),
+            contract_addr.clone(),
+            &default_convert(),
+            &[default_convert_amount()],
+        )
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("burned", "985")
+            .add_attribute("fee", format!("15{DEFAULT_SOURCE_DENOM}"))
+    ));
+    let balance = app
+        .wrap()
+        .query_balance(DEFAULT_POA_ADMIN, DEFAULT_SOURCE_DENOM)
+        .unwrap();
+    assert!(balance.amount.is_zero());
+}
+
+#[rstest]
+#[case::flat_exceeds(json!({"flat": {"amount": "1000"}}), FEE_EXCEEDS_AMOUNT)]
+#[case::full_percent(json!({"percent": {"percent": "100"}}), FEE_EXCEEDS_AMOUNT)]
+fn execute_convert_fee_model_invalid(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] model: Value,
+    #[case] err: &str,
+) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &fee_model_instantiate(model),
+        &[],
+        default_sender(),
+        &default_convert(),
+        &[default_convert_amount()],
+        Expect::ErrContains(err),
+    );
+}
+
 #[rstest]
 #[case::invalid_sender(INVALID_MANIFEST_ADDRESS, Expect::ErrContains(CANNOT_SUB))]
 #[case::empty_sender("", Expect::ErrContains(CANNOT_SUB))]
@@ -86,14 +314,666 @@ fn execute_convert_when_paused(setup_with_funds: (AppAccepting, u64)) {
 #[rstest]
 #[case::ten(coin(10, DEFAULT_SOURCE_DENOM))]
 fn execute_convert_ok(setup_with_funds: (AppAccepting, u64), #[case] funds: Coin) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    convert_consistently(
+        &mut app,
+        &contract_addr,
+        DEFAULT_SENDER,
+        &default_convert(),
+        &[funds],
+    );
+}
+
+#[rstest]
+#[case::not_a_contract(DEFAULT_SENDER, Expect::ErrContains(INVALID_CALLBACK_CONTRACT))]
+#[case::invalid_address(INVALID_MANIFEST_ADDRESS, Expect::ErrContains(INVALID_CHECKSUM))]
+fn execute_convert_invalid_callback(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] contract: &str,
+    #[case] expect: Expect<'_>,
+) {
     prepare_and_execute(
         setup_with_funds,
         default_sender(),
         &default_instantiate(),
         &[],
         default_sender(),
+        &create_msg_convert_with_callback(contract, serde_json::json!({})),
+        &[default_convert_amount()],
+        expect,
+    );
+}
+
+#[rstest]
+fn execute_convert_with_callback(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let converter = instantiate_default(&mut app, code_id);
+    let receiver = instantiate_receiver(&mut app);
+
+    // The mock stargate keeper does not mint, so pre-fund the converter with the
+    // target tokens it forwards to the callback contract
+    app.send_tokens(
+        Addr::unchecked(default_sender()),
+        converter.clone(),
+        &[coin(500, DEFAULT_TARGET_DENOM)],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(default_sender()),
+        converter.clone(),
+        &create_msg_convert_with_callback(receiver.as_str(), serde_json::json!({"hook": {}})),
+        &[default_convert_amount()],
+    )
+    .unwrap();
+
+    // 1_000 * 0.5 = 500 minted and forwarded
+    let balance = app
+        .wrap()
+        .query_balance(&receiver, DEFAULT_TARGET_DENOM)
+        .unwrap();
+    assert_eq!(balance.amount, Uint256::from(500u32));
+    let balance = app
+        .wrap()
+        .query_balance(&converter, DEFAULT_TARGET_DENOM)
+        .unwrap();
+    assert!(balance.amount.is_zero());
+}
+
+#[rstest]
+// 1_000 * 0.5 * 10^(8 - 6)
+#[case::scale_up(6, 8, Expect::Ok)]
+// 1_000 * 0.5 / 10^(12 - 6) rounds down to zero
+#[case::scale_down_zero(12, 6, Expect::ErrContains(RESULT_IS_ZERO))]
+fn execute_convert_with_exponents(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] source_exponent: u32,
+    #[case] target_exponent: u32,
+    #[case] expect: Expect<'_>,
+) {
+    let mut instantiate_msg = default_instantiate();
+    instantiate_msg["source_exponent"] = source_exponent.into();
+    instantiate_msg["target_exponent"] = target_exponent.into();
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &instantiate_msg,
+        &[],
+        default_sender(),
+        &default_convert(),
+        &[default_convert_amount()],
+        expect,
+    );
+}
+
+#[rstest]
+fn execute_convert_scaled_amount(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let mut instantiate_msg = default_instantiate();
+    instantiate_msg["source_exponent"] = 6.into();
+    instantiate_msg["target_exponent"] = 8.into();
+    let converter = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &instantiate_msg,
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(default_sender()),
+            converter,
+            &default_convert(),
+            &[default_convert_amount()],
+        )
+        .unwrap();
+    assert!(res.has_event(&Event::new("wasm").add_attribute("minted", "50000")));
+}
+
+#[rstest]
+#[case::any("any", Expect::Ok)]
+#[case::accounts_only("accounts_only", Expect::Ok)]
+#[case::contracts_only("contracts_only", Expect::ErrContains(SENDER_NOT_ALLOWED))]
+fn execute_convert_from_account(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] allowed_senders: &str,
+    #[case] expect: Expect<'_>,
+) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &modify_instantiate(Field::AllowedSenders, allowed_senders),
+        &[],
+        default_sender(),
         &default_convert(),
+        &[default_convert_amount()],
+        expect,
+    );
+}
+
+#[rstest]
+#[case::any("any", Expect::Ok)]
+#[case::accounts_only("accounts_only", Expect::ErrContains(SENDER_NOT_ALLOWED))]
+#[case::contracts_only("contracts_only", Expect::Ok)]
+fn execute_convert_from_contract(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] allowed_senders: &str,
+    #[case] expect: Expect<'_>,
+) {
+    let (mut app, code_id) = setup_with_funds;
+    let converter = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &modify_instantiate(Field::AllowedSenders, allowed_senders),
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+    let proxy = instantiate_proxy(&mut app);
+    app.send_tokens(
+        Addr::unchecked(default_sender()),
+        proxy.clone(),
+        &[default_convert_amount()],
+    )
+    .unwrap();
+
+    // The proxy forwards the conversion, making the converter see a contract sender
+    let forward = WasmMsg::Execute {
+        contract_addr: converter.to_string(),
+        msg: to_json_binary(&default_convert()).unwrap(),
+        funds: vec![default_convert_amount()],
+    };
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        proxy.as_str(),
+        &CosmosMsg::<Empty>::Wasm(forward),
+        &[],
+        expect,
+    );
+}
+
+#[rstest]
+fn execute_convert_finalized(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let converter = instantiate_default(&mut app, code_id);
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(default_sender()),
+            converter,
+            &default_convert(),
+            &[default_convert_amount()],
+        )
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "finalize_conversion")
+            .add_attribute("conversion_id", "1")
+            .add_attribute("burned", default_convert_amount().to_string())
+    ));
+}
+
+#[rstest]
+fn execute_convert_receipt(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let res = app
+        .execute_contract(
+            Addr::unchecked(default_sender()),
+            contract_addr.clone(),
+            &default_convert(),
+            &[default_convert_amount()],
+        )
+        .unwrap();
+    assert_eq!(
+        serde_json::from_slice::<Value>(&res.data.unwrap()).unwrap(),
+        json!({"conversion_id": 1})
+    );
+
+    // The receipt hash is the SHA-256 hash of the canonical payload
+    let payload = ReceiptPayload {
+        chain_id: app.block_info().chain_id,
+        contract: contract_addr.to_string(),
+        id: 1,
+        sender: default_sender().to_string(),
+        burned: default_convert_amount(),
+        minted: coin(500, DEFAULT_TARGET_DENOM),
+        rate: default_rate().parse().unwrap(),
+        height: app.block_info().height,
+    };
+    let hash = Binary::from(Sha256::digest(to_json_vec(&payload).unwrap()).to_vec());
+
+    let receipt: Value = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &json!({"conversion": {"id": 1}}))
+        .unwrap();
+    assert_eq!(
+        receipt,
+        json!({
+            "id": 1,
+            "sender": default_sender(),
+            "burned": default_convert_amount(),
+            "minted": coin(500, DEFAULT_TARGET_DENOM),
+            "mints": [{"recipient": default_sender(), "amount": coin(500, DEFAULT_TARGET_DENOM)}],
+            "status": "succeeded",
+            "height": app.block_info().height,
+            "time": app.block_info().time,
+            "rate": DEFAULT_RATE,
+            "hash": hash,
+        })
+    );
+    assert!(app
+        .wrap()
+        .query_wasm_smart::<Value>(&contract_addr, &json!({"conversion": {"id": 2}}))
+        .is_err());
+
+    let verified: Value = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &json!({"verify_receipt": {"id": 1}}))
+        .unwrap();
+    assert_eq!(
+        verified,
+        json!({"id": 1, "hash": hash, "computed": hash, "valid": true})
+    );
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::ascii("ledger-42".to_string())]
+#[case::utf8("ключ-✓".to_string())]
+#[case::longest("a".repeat(256))]
+fn execute_convert_memo(setup_with_funds: (AppAccepting, u64), #[case] memo: String) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let res = app
+        .execute_contract(
+            Addr::unchecked(default_sender()),
+            contract_addr.clone(),
+            &json!({"convert": {"callback": null, "memo": memo}}),
+            &[default_convert_amount()],
+        )
+        .unwrap();
+    assert!(res.has_event(&Event::new("wasm").add_attribute("memo", &memo)));
+
+    let receipt: Value = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &json!({"conversion": {"id": 1}}))
+        .unwrap();
+    assert_eq!(receipt["memo"], json!(memo));
+}
+
+#[rstest]
+fn execute_convert_memo_too_long(setup_with_funds: (AppAccepting, u64)) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        DEFAULT_SENDER,
+        &json!({"convert": {"callback": null, "memo": "a".repeat(257)}}),
+        &[default_convert_amount()],
+        Expect::ErrContains(INVALID_MEMO),
+    );
+}
+
+#[rstest]
+fn execute_convert_redact_senders(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &modify_instantiate(Field::RedactSenders, true),
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+    let res = app
+        .execute_contract(
+            Addr::unchecked(default_sender()),
+            contract_addr.clone(),
+            &default_convert(),
+            &[default_convert_amount()],
+        )
+        .unwrap();
+
+    // Neither the convert nor the finalize_conversion attributes carry the sender
+    let wasm_events = res.events.iter().filter(|e| e.ty.starts_with("wasm"));
+    for event in wasm_events {
+        assert!(
+            event.attributes.iter().all(|a| a.key != "sender"),
+            "sender not redacted from {event:?}"
+        );
+    }
+    assert!(res.has_event(&Event::new("wasm").add_attribute("action", "finalize_conversion")));
+
+    // The receipt still records it
+    let receipt: Value = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &json!({"conversion": {"id": 1}}))
+        .unwrap();
+    assert_eq!(receipt["sender"], json!(default_sender()));
+}
+
+fn create_msg_convert_split(recipients: &[(&str, u64)]) -> Value {
+    let recipients: Vec<_> = recipients
+        .iter()
+        .map(|(address, weight)| json!({"address": address, "weight": weight}))
+        .collect();
+    json!({"convert": {"recipients": recipients}})
+}
+
+fn recipient(i: usize) -> String {
+    make_addr(&format!("recipient{i}")).to_string()
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::empty(create_msg_convert_split(&[]), default_convert_amount(), Expect::ErrContains(INVALID_RECIPIENTS))]
+#[case::zero_weight(create_msg_convert_split(&[(&recipient(0), 1), (&recipient(1), 0)]), default_convert_amount(), Expect::ErrContains(INVALID_RECIPIENTS))]
+#[case::duplicate(create_msg_convert_split(&[(&recipient(0), 1), (&recipient(0), 1)]), default_convert_amount(), Expect::ErrContains(INVALID_RECIPIENTS))]
+#[case::too_many(create_msg_convert_split(&(0..11).map(|_| (DEFAULT_SENDER, 1)).collect::<Vec<_>>()), default_convert_amount(), Expect::ErrContains(INVALID_RECIPIENTS))]
+#[case::invalid_address(create_msg_convert_split(&[("invalid", 1)]), default_convert_amount(), Expect::ErrContains(PARSE_FAILED))]
+#[case::share_is_zero(create_msg_convert_split(&[(&recipient(0), 1), (&recipient(1), 1)]), coin(2, DEFAULT_SOURCE_DENOM), Expect::ErrContains(SHARE_IS_ZERO))]
+#[case::with_callback(json!({"convert": {"callback": {"contract": DEFAULT_SENDER, "msg": ""}, "recipients": [{"address": DEFAULT_SENDER, "weight": 1}]}}), default_convert_amount(), Expect::ErrContains(RECIPIENTS_WITH_CALLBACK))]
+#[case::recipient_and_recipients(json!({"convert": {"recipient": recipient(0), "recipients": [{"address": recipient(1), "weight": 1}]}}), default_convert_amount(), Expect::ErrContains(INVALID_RECIPIENTS))]
+#[case::recipient_invalid_address(json!({"convert": {"recipient": "invalid"}}), default_convert_amount(), Expect::ErrContains(PARSE_FAILED))]
+#[case::recipient_with_callback(json!({"convert": {"callback": {"contract": DEFAULT_SENDER, "msg": ""}, "recipient": recipient(0)}}), default_convert_amount(), Expect::ErrContains(RECIPIENTS_WITH_CALLBACK))]
+fn execute_convert_split_invalid(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] exec_msg: Value,
+    #[case] funds: Coin,
+    #[case] expect: Expect<'_>,
+) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        default_sender(),
+        &exec_msg,
         &[funds],
+        expect,
+    );
+}
+
+#[rstest]
+fn execute_convert_split(setup_with_funds: (AppAccepting, u64)) {
+    let (app, contract_addr, _) = prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        default_sender(),
+        &create_msg_convert_split(&[(&recipient(0), 1), (&recipient(1), 2)]),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+
+    // 500 minted, the rounding leftover goes to the last recipient
+    let receipt: Value = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &json!({"conversion": {"id": 1}}))
+        .unwrap();
+    assert_eq!(
+        receipt["mints"],
+        json!([
+            {"recipient": recipient(0), "amount": coin(166, DEFAULT_TARGET_DENOM)},
+            {"recipient": recipient(1), "amount": coin(334, DEFAULT_TARGET_DENOM)},
+        ])
+    );
+}
+
+#[rstest]
+#[case::exact("500", Expect::Ok)]
+#[case::below("499", Expect::Ok)]
+#[case::above("501", Expect::ErrContains(SLIPPAGE_EXCEEDED))]
+fn execute_convert_min_receive(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] min_receive: &str,
+    #[case] expect: Expect<'_>,
+) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        default_sender(),
+        &json!({"convert": {"min_receive": min_receive}}),
+        &[default_convert_amount()],
+        expect,
+    );
+}
+
+// The minimum applies to the amount sent, before any fee is withheld
+#[rstest]
+#[case::equal(1_000, Expect::Ok)]
+#[case::above(1_001, Expect::Ok)]
+#[case::below(999, Expect::ErrContains(BELOW_MIN_AMOUNT))]
+fn execute_convert_min_amount(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] amount: u128,
+    #[case] expect: Expect<'_>,
+) {
+    let mut msg = modify_instantiate(Field::MinConvertAmount, "1000");
+    msg["fee_model"] = json!({"flat": {"amount": "10"}});
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &msg,
+        &[],
+        default_sender(),
+        &default_convert(),
+        &[coin(amount, DEFAULT_SOURCE_DENOM)],
+        expect,
+    );
+}
+
+#[rstest]
+#[case::equal(1_000, Expect::Ok)]
+#[case::below(999, Expect::Ok)]
+#[case::above(1_001, Expect::ErrContains(ABOVE_MAX_AMOUNT))]
+fn execute_convert_max_amount(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] amount: u128,
+    #[case] expect: Expect<'_>,
+) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &modify_instantiate(Field::MaxConvertAmount, "1000"),
+        &[],
+        default_sender(),
+        &default_convert(),
+        &[coin(amount, DEFAULT_SOURCE_DENOM)],
+        expect,
+    );
+}
+
+// Conversions drawn from a deposit are held to the minimum as well
+#[rstest]
+fn execute_convert_min_amount_from_deposit(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr, _) = prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &modify_instantiate(Field::MinConvertAmount, "100"),
+        &[],
+        default_sender(),
+        &json!({"deposit": {}}),
+        &[default_convert_amount()],
         Expect::Ok,
     );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &json!({"convert": {"from_deposit": "99"}}),
+        &[],
+        Expect::ErrContains(BELOW_MIN_AMOUNT),
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &json!({"convert": {"from_deposit": "100"}}),
+        &[],
+        Expect::Ok,
+    );
+}
+
+// A rate update landing before the conversion makes it fail instead of minting less
+#[rstest]
+fn execute_convert_min_receive_rate_updated(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr, _) = prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        default_admin(),
+        &create_msg_update_config(Field::Rate, "0.4"),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &json!({"convert": {"min_receive": "500"}}),
+        &[default_convert_amount()],
+        Expect::ErrContains(SLIPPAGE_EXCEEDED),
+    );
+}
+
+// The minted tokens go to the recipient, the sender still paying the source tokens
+#[rstest]
+fn execute_convert_recipient(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let res = app
+        .execute_contract(
+            Addr::unchecked(DEFAULT_SENDER),
+            contract_addr.clone(),
+            &json!({"convert": {"recipient": recipient(0)}}),
+            &[default_convert_amount()],
+        )
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("sender", DEFAULT_SENDER)
+            .add_attribute("recipient", recipient(0))
+    ));
+    let receipt: Value = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &json!({"conversion": {"id": 1}}))
+        .unwrap();
+    assert_eq!(receipt["sender"], json!(DEFAULT_SENDER));
+    assert_eq!(
+        receipt["mints"],
+        json!([{"recipient": recipient(0), "amount": coin(500, DEFAULT_TARGET_DENOM)}])
+    );
+}
+
+#[rstest]
+fn execute_convert_exec_failed(setup_failing_stargate: (AppFailing, u64)) {
+    let (mut app, code_id) = setup_failing_stargate;
+    let converter = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &default_instantiate(),
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+
+    // The failed conversion is escrowed for a refund instead of reverting
+    let res = app
+        .execute_contract(
+            Addr::unchecked(default_sender()),
+            converter.clone(),
+            &default_convert(),
+            &[default_convert_amount()],
+        )
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "fail_conversion")
+            .add_attribute("conversion_id", "1")
+            .add_attribute("refundable", default_convert_amount().to_string())
+    ));
+
+    // The POA admin never received the funds, the contract holds them
+    let balance = app
+        .wrap()
+        .query_balance(DEFAULT_POA_ADMIN, DEFAULT_SOURCE_DENOM)
+        .unwrap();
+    assert!(balance.amount.is_zero());
+
+    let refunds: Value = app
+        .wrap()
+        .query_wasm_smart(
+            &converter,
+            &json!({"refunds": {"address": default_sender()}}),
+        )
+        .unwrap();
+    assert_eq!(
+        refunds,
+        json!({"refunds": [{"denom": DEFAULT_SOURCE_DENOM, "amount": "1000"}]})
+    );
+    let unsolicited: Value = app
+        .wrap()
+        .query_wasm_smart(
+            &converter,
+            &json!({"unsolicited_balance": {"denom": DEFAULT_SOURCE_DENOM}}),
+        )
+        .unwrap();
+    assert_eq!(unsolicited["escrowed"], json!("1000"));
+    assert_eq!(unsolicited["unrecorded"], json!("0"));
+
+    let receipt: Value = app
+        .wrap()
+        .query_wasm_smart(&converter, &json!({"conversion": {"id": 1}}))
+        .unwrap();
+    assert!(receipt["status"]["failed"]["error"].is_string());
+
+    let before = app
+        .wrap()
+        .query_balance(default_sender(), DEFAULT_SOURCE_DENOM)
+        .unwrap();
+    app.execute_contract(
+        Addr::unchecked(default_sender()),
+        converter.clone(),
+        &json!({"claim_refund": {}}),
+        &[],
+    )
+    .unwrap();
+    let after = app
+        .wrap()
+        .query_balance(default_sender(), DEFAULT_SOURCE_DENOM)
+        .unwrap();
+    assert_eq!(
+        after.amount - before.amount,
+        default_convert_amount().amount
+    );
+
+    // Refunds can only be claimed once
+    let err = app
+        .execute_contract(
+            Addr::unchecked(default_sender()),
+            converter,
+            &json!({"claim_refund": {}}),
+            &[],
+        )
+        .unwrap_err();
+    assert!(format!("{err:#}").contains(NO_REFUND));
 }
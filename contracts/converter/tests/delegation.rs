@@ -0,0 +1,161 @@
+use crate::common::*;
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+const DAY: u64 = 86_400;
+
+fn ops() -> String {
+    make_addr("ops").to_string()
+}
+
+fn delegation() -> Value {
+    json!({"fields": ["paused", "rate"], "max_rate_change": "0.05"})
+}
+
+fn create_msg_set_delegation(delegate: &str, delegation: Value) -> Value {
+    json!({"set_delegation": {"delegate": delegate, "delegation": delegation}})
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::ok(default_admin(), delegation(), Expect::Ok)]
+#[case::unlimited_rate(default_admin(), json!({"fields": ["rate"]}), Expect::Ok)]
+#[case::unauthorized(DEFAULT_SENDER, delegation(), Expect::ErrContains(ONLY_ADMIN))]
+#[case::no_fields(default_admin(), json!({"fields": []}), Expect::ErrContains(INVALID_DELEGATION))]
+#[case::unknown_field(default_admin(), json!({"fields": ["admin"]}), Expect::ErrContains(INVALID_DELEGATION))]
+#[case::duplicate_field(default_admin(), json!({"fields": ["paused", "paused"]}), Expect::ErrContains(INVALID_DELEGATION))]
+fn set_delegation(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] exec_sender: &str,
+    #[case] delegation: Value,
+    #[case] expect: Expect<'_>,
+) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        exec_sender,
+        &create_msg_set_delegation(&ops(), delegation),
+        &[],
+        expect,
+    );
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::paused(create_msg_update_config(Field::Paused, true), Expect::Ok)]
+#[case::rate_within_limit(create_msg_update_config(Field::Rate, "0.525"), Expect::Ok)]
+#[case::rate_above_limit(create_msg_update_config(Field::Rate, "0.53"), Expect::ErrContains(RATE_CHANGE_EXCEEDS_LIMIT))]
+#[case::rate_below_limit(create_msg_update_config(Field::Rate, "0.47"), Expect::ErrContains(RATE_CHANGE_EXCEEDS_LIMIT))]
+#[case::source_denom(create_msg_update_config(Field::SourceDenom, DUMMY_DENOM), Expect::ErrContains("config field source_denom is not delegated"))]
+#[case::noop_poa_admin(create_msg_update_config(Field::PoaAdmin, DEFAULT_POA_ADMIN), Expect::Ok)]
+fn delegate_update_config(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] exec_msg: Value,
+    #[case] expect: Expect<'_>,
+) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let contract = contract_addr.as_str();
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_set_delegation(&ops(), delegation()),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(&mut app, &ops(), contract, &exec_msg, &[], expect);
+}
+
+#[rstest]
+fn revoke_delegation(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let contract = contract_addr.as_str();
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_set_delegation(&ops(), delegation()),
+        &[],
+        Expect::Ok,
+    );
+
+    let res: Value = app
+        .wrap()
+        .query_wasm_smart(contract, &json!({"delegations": {}}))
+        .unwrap();
+    assert_eq!(
+        res,
+        json!({"items": [{"delegate": ops(), "delegation": delegation()}], "next_key": null})
+    );
+
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_set_delegation(&ops(), Value::Null),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        &ops(),
+        contract,
+        &create_msg_update_config(Field::Paused, true),
+        &[],
+        Expect::ErrContains(ONLY_ADMIN),
+    );
+}
+
+// The limit applies to the rate before the first delegated change of the day, so updates
+// cannot be chained past it. It starts over the next day, or from a rate set by the admin
+#[rstest]
+fn delegate_rate_changes_within_day(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let contract = contract_addr.as_str();
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_set_delegation(&ops(), delegation()),
+        &[],
+        Expect::Ok,
+    );
+    let update_rate = |app: &mut AppAccepting, sender: &str, rate: &str, expect| {
+        let msg = create_msg_update_config(Field::Rate, rate);
+        run_execute(app, sender, contract, &msg, &[], expect);
+    };
+
+    update_rate(&mut app, &ops(), "0.52", Expect::Ok);
+    update_rate(
+        &mut app,
+        &ops(),
+        "0.54",
+        Expect::ErrContains(RATE_CHANGE_EXCEEDS_LIMIT),
+    );
+    update_rate(&mut app, &ops(), "0.525", Expect::Ok);
+    update_rate(
+        &mut app,
+        &ops(),
+        "0.47",
+        Expect::ErrContains(RATE_CHANGE_EXCEEDS_LIMIT),
+    );
+
+    advance_seconds(&mut app, DAY);
+    update_rate(&mut app, &ops(), "0.55", Expect::Ok);
+    update_rate(
+        &mut app,
+        &ops(),
+        "0.56",
+        Expect::ErrContains(RATE_CHANGE_EXCEEDS_LIMIT),
+    );
+
+    update_rate(&mut app, default_admin(), "0.6", Expect::Ok);
+    update_rate(&mut app, &ops(), "0.625", Expect::Ok);
+}
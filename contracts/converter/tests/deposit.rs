@@ -0,0 +1,242 @@
+use crate::common::*;
+use cosmwasm_std::{coin, Addr, Coin};
+use cw_multi_test::Executor;
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn create_msg_deposit() -> Value {
+    json!({"deposit": {}})
+}
+
+fn create_msg_withdraw(amount: &str) -> Value {
+    json!({"withdraw": {"amount": amount}})
+}
+
+fn create_msg_convert_from_deposit(amount: &str) -> Value {
+    json!({"convert": {"from_deposit": amount}})
+}
+
+fn flat_fee_instantiate() -> Value {
+    modify_instantiate(Field::FlatFee, coin(10, DUMMY_DENOM))
+}
+
+fn query_deposit(app: &AppAccepting, contract_addr: &Addr, address: &str) -> Value {
+    app.wrap()
+        .query_wasm_smart(contract_addr, &json!({"deposit": {"address": address}}))
+        .unwrap()
+}
+
+// Instantiate the contract and deposit the default convert amount for the default sender
+fn setup_deposit(
+    setup_with_funds: (AppAccepting, u64),
+    instantiate_msg: &Value,
+) -> (AppAccepting, Addr) {
+    let (app, contract_addr, _) = prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        instantiate_msg,
+        &[],
+        default_sender(),
+        &create_msg_deposit(),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+    (app, contract_addr)
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::ok(default_instantiate(), "600", vec![], Expect::Ok)]
+#[case::whole_deposit(default_instantiate(), "1000", vec![], Expect::Ok)]
+#[case::exceeds_deposit(default_instantiate(), "1001", vec![], Expect::ErrContains(INSUFFICIENT_DEPOSIT))]
+#[case::zero(default_instantiate(), "0", vec![], Expect::ErrContains(AMOUNT_IS_ZERO))]
+#[case::with_funds(default_instantiate(), "600", vec![default_convert_amount()], Expect::ErrContains(NON_PAYABLE))]
+#[case::flat_fee(flat_fee_instantiate(), "600", vec![coin(10, DUMMY_DENOM)], Expect::Ok)]
+#[case::flat_fee_missing(flat_fee_instantiate(), "600", vec![], Expect::ErrContains(INVALID_FEE))]
+fn convert_from_deposit(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] instantiate_msg: Value,
+    #[case] amount: &str,
+    #[case] funds: Vec<Coin>,
+    #[case] expect: Expect<'_>,
+) {
+    let (mut app, contract_addr) = setup_deposit(setup_with_funds, &instantiate_msg);
+    run_execute(&mut app, DEFAULT_SENDER, contract_addr.as_str(), &create_msg_convert_from_deposit(amount), &funds, expect);
+}
+
+#[rstest]
+fn convert_from_deposit_debits(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr) = setup_deposit(setup_with_funds, &default_instantiate());
+    let res = app
+        .execute_contract(
+            Addr::unchecked(DEFAULT_SENDER),
+            contract_addr.clone(),
+            &create_msg_convert_from_deposit("600"),
+            &[],
+        )
+        .unwrap();
+    assert!(res.has_event(
+        &cosmwasm_std::Event::new("wasm")
+            .add_attribute("action", "convert")
+            .add_attribute("burned", "600")
+            .add_attribute("minted", "300")
+    ));
+    assert_eq!(
+        query_deposit(&app, &contract_addr, DEFAULT_SENDER),
+        json!({"amount": coin(400, DEFAULT_SOURCE_DENOM), "nonce": 0})
+    );
+}
+
+#[rstest]
+fn convert_from_deposit_none(setup_with_funds: (AppAccepting, u64)) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        default_sender(),
+        &create_msg_convert_from_deposit("600"),
+        &[],
+        Expect::ErrContains(NO_DEPOSIT),
+    );
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::partial("400", vec![], Expect::Ok)]
+#[case::whole("1000", vec![], Expect::Ok)]
+#[case::exceeds_deposit("1001", vec![], Expect::ErrContains(INSUFFICIENT_DEPOSIT))]
+#[case::zero("0", vec![], Expect::ErrContains(AMOUNT_IS_ZERO))]
+#[case::with_funds("400", vec![default_convert_amount()], Expect::ErrContains(NON_PAYABLE))]
+fn withdraw(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] amount: &str,
+    #[case] funds: Vec<Coin>,
+    #[case] expect: Expect<'_>,
+) {
+    let (mut app, contract_addr) = setup_deposit(setup_with_funds, &default_instantiate());
+    run_execute(&mut app, DEFAULT_SENDER, contract_addr.as_str(), &create_msg_withdraw(amount), &funds, expect);
+}
+
+#[rstest]
+fn withdraw_sends_funds(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr) = setup_deposit(setup_with_funds, &default_instantiate());
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &create_msg_withdraw("400"),
+        &[],
+        Expect::Ok,
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance(DEFAULT_SENDER, DEFAULT_SOURCE_DENOM)
+            .unwrap(),
+        coin(999_400, DEFAULT_SOURCE_DENOM)
+    );
+    assert_eq!(
+        query_deposit(&app, &contract_addr, DEFAULT_SENDER),
+        json!({"amount": coin(600, DEFAULT_SOURCE_DENOM), "nonce": 0})
+    );
+}
+
+#[rstest]
+fn deposit_denom_mismatch(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr) = setup_deposit(setup_with_funds, &default_instantiate());
+    let contract = contract_addr.as_str();
+
+    // The deposit stays in the previous source denom until withdrawn
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_update_config(Field::SourceDenom, DUMMY_DENOM),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_deposit(),
+        &[coin(1_000, DUMMY_DENOM)],
+        Expect::ErrContains(DEPOSIT_DENOM_MISMATCH),
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_convert_from_deposit("600"),
+        &[],
+        Expect::ErrContains(INSUFFICIENT_DEPOSIT),
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_withdraw("1000"),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_deposit(),
+        &[coin(1_000, DUMMY_DENOM)],
+        Expect::Ok,
+    );
+}
+
+#[rstest]
+fn query_deposits(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr) = setup_deposit(setup_with_funds, &default_instantiate());
+    let other = make_addr("other");
+    app.send_tokens(
+        Addr::unchecked(DEFAULT_SENDER),
+        other.clone(),
+        &[coin(500, DEFAULT_SOURCE_DENOM)],
+    )
+    .unwrap();
+    run_execute(
+        &mut app,
+        other.as_str(),
+        contract_addr.as_str(),
+        &create_msg_deposit(),
+        &[coin(500, DEFAULT_SOURCE_DENOM)],
+        Expect::Ok,
+    );
+
+    let page: Value = app
+        .wrap()
+        .query_wasm_smart(
+            &contract_addr,
+            &json!({"deposits": {"pagination": {"limit": 1}}}),
+        )
+        .unwrap();
+    assert_eq!(page["items"].as_array().unwrap().len(), 1);
+    let page: Value = app
+        .wrap()
+        .query_wasm_smart(
+            &contract_addr,
+            &json!({"deposits": {"pagination": {"start_after": page["next_key"]}}}),
+        )
+        .unwrap();
+    assert_eq!(page["items"].as_array().unwrap().len(), 1);
+    assert_eq!(page["next_key"], Value::Null);
+
+    let page: Value = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &json!({"deposits": {}}))
+        .unwrap();
+    // Deposits are listed in address order
+    let mut expected = vec![
+        json!({"address": DEFAULT_SENDER, "amount": default_convert_amount(), "pubkey": null, "nonce": 0}),
+        json!({"address": other, "amount": coin(500, DEFAULT_SOURCE_DENOM), "pubkey": null, "nonce": 0}),
+    ];
+    expected.sort_by_key(|item| item["address"].as_str().unwrap().to_string());
+    assert_eq!(page["items"], json!(expected));
+}
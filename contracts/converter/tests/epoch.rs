@@ -0,0 +1,273 @@
+use crate::common::*;
+use cosmwasm_std::{coin, Addr};
+use cw_multi_test::Executor;
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+const DAY: u64 = 86_400;
+
+fn setup_epochs(setup_with_funds: (AppAccepting, u64)) -> (AppAccepting, Addr) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &modify_instantiate(Field::EpochLength, DAY),
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+    (app, contract_addr)
+}
+
+fn convert(app: &mut AppAccepting, contract_addr: &Addr) {
+    run_execute(
+        app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &default_convert(),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+}
+
+fn query_current_epoch(app: &AppAccepting, contract_addr: &Addr) -> Value {
+    app.wrap()
+        .query_wasm_smart(contract_addr, &json!({"current_epoch": {}}))
+        .unwrap()
+}
+
+fn query_epochs(app: &AppAccepting, contract_addr: &Addr) -> Vec<Value> {
+    let res: Value = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &json!({"epochs": {"limit": 30}}))
+        .unwrap();
+    res["epochs"].as_array().unwrap().clone()
+}
+
+#[rstest]
+fn epoch_accounting(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr) = setup_epochs(setup_with_funds);
+    let start = app.block_info().time;
+
+    convert(&mut app, &contract_addr);
+    convert(&mut app, &contract_addr);
+    assert_eq!(
+        query_current_epoch(&app, &contract_addr),
+        json!({"id": 0, "start": start, "burned": "2000", "minted": "1000", "conversions": 2})
+    );
+
+    // The next epoch starts empty, even before any conversion happens in it
+    advance_seconds(&mut app, DAY);
+    assert_eq!(
+        query_current_epoch(&app, &contract_addr),
+        json!({"id": 1, "start": start.plus_seconds(DAY), "burned": "0", "minted": "0", "conversions": 0})
+    );
+    assert!(query_epochs(&app, &contract_addr).is_empty());
+
+    convert(&mut app, &contract_addr);
+    assert_eq!(
+        query_current_epoch(&app, &contract_addr)["conversions"],
+        json!(1)
+    );
+    assert_eq!(
+        query_epochs(&app, &contract_addr),
+        vec![
+            json!({"id": 0, "start": start, "burned": "2000", "minted": "1000", "conversions": 2})
+        ]
+    );
+}
+
+#[rstest]
+fn epoch_retention(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr) = setup_epochs(setup_with_funds);
+
+    for _ in 0..26 {
+        convert(&mut app, &contract_addr);
+        advance_seconds(&mut app, DAY);
+    }
+
+    // Epochs 0 to 24 were archived, only the last 24 of them are kept
+    let epochs = query_epochs(&app, &contract_addr);
+    assert_eq!(epochs.len(), 24);
+    assert_eq!(epochs[0]["id"], json!(1));
+    assert_eq!(epochs[23]["id"], json!(24));
+}
+
+#[rstest]
+fn epoch_without_length(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+
+    convert(&mut app, &contract_addr);
+    advance_seconds(&mut app, 365 * DAY);
+    convert(&mut app, &contract_addr);
+
+    let epoch = query_current_epoch(&app, &contract_addr);
+    assert_eq!(epoch["id"], json!(0));
+    assert_eq!(epoch["conversions"], json!(2));
+    assert!(query_epochs(&app, &contract_addr).is_empty());
+}
+
+fn query_epoch_usage(app: &AppAccepting, contract_addr: &Addr) -> Value {
+    app.wrap()
+        .query_wasm_smart(contract_addr, &json!({"epoch_usage": {}}))
+        .unwrap()
+}
+
+fn convert_amount(app: &mut AppAccepting, contract_addr: &Addr, amount: u128, expect: Expect<'_>) {
+    run_execute(
+        app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &default_convert(),
+        &[coin(amount, DEFAULT_SOURCE_DENOM)],
+        expect,
+    );
+}
+
+#[rstest]
+fn epoch_cap(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let mut msg = modify_instantiate(Field::EpochLength, DAY);
+    msg["epoch_cap"] = json!("1000");
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &msg,
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+    let start = app.block_info().time;
+
+    // The cap bounds the minted target tokens, whoever converts
+    convert_amount(&mut app, &contract_addr, 1_500, Expect::Ok);
+    convert_amount(
+        &mut app,
+        &contract_addr,
+        502,
+        Expect::ErrContains(EPOCH_CAP_EXCEEDED),
+    );
+    convert_amount(&mut app, &contract_addr, 500, Expect::Ok);
+    assert_eq!(
+        query_epoch_usage(&app, &contract_addr),
+        json!({
+            "epoch": 0,
+            "cap": "1000",
+            "minted": "1000",
+            "remaining": "0",
+            "ends_at": start.plus_seconds(DAY),
+        })
+    );
+
+    // The next epoch starts with the whole cap
+    advance_seconds(&mut app, DAY);
+    assert_eq!(
+        query_epoch_usage(&app, &contract_addr)["remaining"],
+        json!("1000")
+    );
+    convert_amount(&mut app, &contract_addr, 2_000, Expect::Ok);
+}
+
+// Without an epoch length, the single never-ending epoch makes the cap a lifetime one
+#[rstest]
+fn epoch_cap_without_length(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &modify_instantiate(Field::EpochCap, "500"),
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+    convert_amount(&mut app, &contract_addr, 1_000, Expect::Ok);
+    advance_seconds(&mut app, 365 * DAY);
+    convert_amount(
+        &mut app,
+        &contract_addr,
+        2,
+        Expect::ErrContains(EPOCH_CAP_EXCEEDED),
+    );
+    assert_eq!(
+        query_epoch_usage(&app, &contract_addr),
+        json!({"epoch": 0, "cap": "500", "minted": "500", "remaining": "0", "ends_at": null})
+    );
+}
+
+#[rstest]
+fn epoch_usage_without_cap(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr) = setup_epochs(setup_with_funds);
+    convert(&mut app, &contract_addr);
+    let res = query_epoch_usage(&app, &contract_addr);
+    assert_eq!(res["cap"], Value::Null);
+    assert_eq!(res["minted"], json!("500"));
+    assert_eq!(res["remaining"], Value::Null);
+}
+
+#[rstest]
+fn pair_totals(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr) = setup_epochs(setup_with_funds);
+    convert(&mut app, &contract_addr);
+    convert(&mut app, &contract_addr);
+
+    // Converting another source denom accounts for another pair
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract_addr.as_str(),
+        &create_msg_update_config(Field::SourceDenom, DUMMY_DENOM),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &default_convert(),
+        &[coin(500, DUMMY_DENOM)],
+        Expect::Ok,
+    );
+
+    let res: Value = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &json!({"pair_totals": {}}))
+        .unwrap();
+    // Composite keys are length-prefixed, shorter denoms come first
+    assert_eq!(
+        res,
+        json!({
+            "items": [
+                {"source_denom": DEFAULT_SOURCE_DENOM, "target_denom": DEFAULT_TARGET_DENOM, "burned": "2000", "minted": "1000", "conversions": 2},
+                {"source_denom": DUMMY_DENOM, "target_denom": DEFAULT_TARGET_DENOM, "burned": "500", "minted": "250", "conversions": 1},
+            ],
+            "next_key": null,
+        })
+    );
+
+    let res: Value = app
+        .wrap()
+        .query_wasm_smart(
+            &contract_addr,
+            &json!({"pair_totals": {"pagination": {"limit": 1}}}),
+        )
+        .unwrap();
+    assert_eq!(res["items"].as_array().unwrap().len(), 1);
+    let res: Value = app
+        .wrap()
+        .query_wasm_smart(
+            &contract_addr,
+            &json!({"pair_totals": {"pagination": {"start_after": res["next_key"], "limit": 1}}}),
+        )
+        .unwrap();
+    assert_eq!(res["items"][0]["source_denom"], DUMMY_DENOM);
+    assert_eq!(res["next_key"], Value::Null);
+}
@@ -0,0 +1,232 @@
+use crate::common::*;
+use cosmwasm_std::{coin, Addr, Coin};
+use cw_multi_test::Executor;
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+// The default sender administers the contract, so it can fund the float from its balance
+fn float_instantiate() -> Value {
+    let mut msg = modify_instantiate(Field::Admin, DEFAULT_SENDER);
+    msg[Field::FloatPayouts.as_ref()] = json!(true);
+    msg
+}
+
+fn create_msg_withdraw_float(amount: Coin) -> Value {
+    json!({"withdraw_float": {"amount": amount}})
+}
+
+fn query_float(app: &AppAccepting, contract_addr: &Addr) -> Value {
+    app.wrap()
+        .query_wasm_smart(contract_addr, &json!({"float": {}}))
+        .unwrap()
+}
+
+fn query_receipt(app: &AppAccepting, contract_addr: &Addr, id: u64) -> Value {
+    app.wrap()
+        .query_wasm_smart(contract_addr, &json!({"conversion": {"id": id}}))
+        .unwrap()
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::fund(DEFAULT_SENDER, json!({"fund_float": {}}), &[coin(1_000, DEFAULT_TARGET_DENOM)], Expect::Ok)]
+#[case::fund_unauthorized(DEFAULT_POA_ADMIN, json!({"fund_float": {}}), &[], Expect::ErrContains(ONLY_ADMIN))]
+#[case::fund_source_denom(DEFAULT_SENDER, json!({"fund_float": {}}), &[coin(1_000, DEFAULT_SOURCE_DENOM)], Expect::ErrContains(INVALID_FLOAT_FUNDS))]
+#[case::fund_nothing(DEFAULT_SENDER, json!({"fund_float": {}}), &[], Expect::ErrContains(INVALID_FLOAT_FUNDS))]
+#[case::withdraw_empty(DEFAULT_SENDER, create_msg_withdraw_float(coin(1, DEFAULT_TARGET_DENOM)), &[], Expect::ErrContains(INSUFFICIENT_FLOAT))]
+#[case::withdraw_unauthorized(DEFAULT_POA_ADMIN, create_msg_withdraw_float(coin(1, DEFAULT_TARGET_DENOM)), &[], Expect::ErrContains(ONLY_ADMIN))]
+fn execute_float(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] exec_sender: &str,
+    #[case] exec_msg: Value,
+    #[case] funds: &[Coin],
+    #[case] expect: Expect<'_>,
+) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &float_instantiate(),
+        &[],
+        exec_sender,
+        &exec_msg,
+        funds,
+        expect,
+    );
+}
+
+// Target tokens sent along instantiate fund the float right away
+#[rstest]
+fn convert_from_bootstrap_float(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let funds = vec![coin(600, DEFAULT_TARGET_DENOM)];
+    let mut msg = float_instantiate();
+    msg[Field::Funding.as_ref()] = json!(funds);
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &msg,
+            &funds,
+            "converter",
+            None,
+        )
+        .unwrap();
+    assert_eq!(
+        query_float(&app, &contract_addr)["float"]["balance"],
+        json!("600")
+    );
+
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &default_convert(),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+    assert_eq!(
+        query_receipt(&app, &contract_addr, 1)["from_float"],
+        json!(true)
+    );
+    assert_eq!(
+        query_float(&app, &contract_addr)["float"]["balance"],
+        json!("100")
+    );
+    let unsolicited: Value = app
+        .wrap()
+        .query_wasm_smart(
+            &contract_addr,
+            &json!({"unsolicited_funds": {"depositor": default_sender()}}),
+        )
+        .unwrap();
+    assert_eq!(unsolicited, json!({"funds": []}));
+}
+
+#[rstest]
+fn convert_from_float(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &float_instantiate(),
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+    let contract = contract_addr.as_str();
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &json!({"fund_float": {}}),
+        &[coin(600, DEFAULT_TARGET_DENOM)],
+        Expect::Ok,
+    );
+
+    // The float covers the first conversion, the second one is minted as usual
+    for _ in 0..2 {
+        run_execute(
+            &mut app,
+            DEFAULT_SENDER,
+            contract,
+            &default_convert(),
+            &[default_convert_amount()],
+            Expect::Ok,
+        );
+    }
+    assert_eq!(
+        query_receipt(&app, &contract_addr, 1)["from_float"],
+        json!(true)
+    );
+    assert_eq!(
+        query_receipt(&app, &contract_addr, 2)["from_float"],
+        Value::Null
+    );
+    assert_eq!(
+        query_float(&app, &contract_addr),
+        json!({
+            "denom": DEFAULT_TARGET_DENOM,
+            "enabled": true,
+            "float": {
+                "balance": "100",
+                "to_replenish": "500",
+                "funded": "600",
+                "paid_out": "500",
+                "withdrawn": "0",
+            },
+        })
+    );
+
+    // Funding replenishes the payouts, withdrawing leaves them due
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &json!({"fund_float": {}}),
+        &[coin(400, DEFAULT_TARGET_DENOM)],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_withdraw_float(coin(300, DEFAULT_TARGET_DENOM)),
+        &[],
+        Expect::Ok,
+    );
+    let float = query_float(&app, &contract_addr);
+    assert_eq!(float["float"]["balance"], json!("200"));
+    assert_eq!(float["float"]["to_replenish"], json!("100"));
+
+    // The float is escrowed, not residual tokens to burn
+    let unsolicited: Value = app
+        .wrap()
+        .query_wasm_smart(
+            contract,
+            &json!({"unsolicited_balance": {"denom": DEFAULT_TARGET_DENOM}}),
+        )
+        .unwrap();
+    assert_eq!(unsolicited["escrowed"], json!("200"));
+}
+
+#[rstest]
+fn convert_from_float_failed(setup_failing_stargate: (AppFailing, u64)) {
+    let (mut app, code_id) = setup_failing_stargate;
+    let converter = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &float_instantiate(),
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+    app.execute_contract(
+        Addr::unchecked(default_sender()),
+        converter.clone(),
+        &json!({"fund_float": {}}),
+        &[coin(600, DEFAULT_TARGET_DENOM)],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(default_sender()),
+        converter.clone(),
+        &default_convert(),
+        &[default_convert_amount()],
+    )
+    .unwrap();
+
+    // The payout of the failed conversion is back in the float
+    let float: Value = app
+        .wrap()
+        .query_wasm_smart(&converter, &json!({"float": {}}))
+        .unwrap();
+    assert_eq!(float["float"]["balance"], json!("600"));
+    assert_eq!(float["float"]["paid_out"], json!("0"));
+    assert_eq!(float["float"]["to_replenish"], json!("0"));
+}
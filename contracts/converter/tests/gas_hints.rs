@@ -0,0 +1,338 @@
+use converter::msg::{ConversionSignDoc, ExecuteMsg, QueryMsg, RevealPreimage, SignedConversion};
+use converter::{execute, instantiate, query, reply};
+use cosmwasm_std::testing::{message_info, mock_env, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    coin, coins, from_json, to_json_vec, Addr, Binary, Coin, CosmosMsg, Env, Order, OwnedDeps,
+    Record, Reply, Response, Storage, SubMsgResult, Uint256,
+};
+use k256::ecdsa::signature::hazmat::PrehashSigner;
+use k256::ecdsa::{Signature, SigningKey};
+use manifest_std::cosmos::authz::v1beta1::MsgExec;
+use prost::Message;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+
+const SOURCE_DENOM: &str = "umfx";
+const TARGET_DENOM: &str = "upwr";
+const AMOUNT: u128 = 1000;
+
+// KV store gas of the SDK, the default `KVGasConfig`. wasmd prefixes the keys of a contract
+// with its address
+const READ_FLAT: u64 = 1_000;
+const READ_PER_BYTE: u64 = 3;
+const WRITE_FLAT: u64 = 2_000;
+const WRITE_PER_BYTE: u64 = 30;
+const DELETE: u64 = 1_000;
+const ITER_NEXT_FLAT: u64 = 30;
+const KEY_PREFIX_LEN: u64 = 33;
+
+// Gas of a transaction before its storage accesses: the ante handler, the signature check,
+// the transaction bytes and loading the contract
+const TX_GAS: u64 = 100_000;
+
+// Gas of each bank or token factory message dispatched by the contract, and of the grant
+// check of the authz MsgExec wrapping them
+const MSG_GAS: u64 = 30_000;
+const EXEC_GAS: u64 = 20_000;
+
+// How far above the estimate a hint may be before it is considered stale
+const HEADROOM: u64 = 2;
+
+// Storage charging the KV store gas of the SDK for every access
+#[derive(Default)]
+struct MeteredStorage {
+    inner: MockStorage,
+    gas: Cell<u64>,
+}
+
+impl MeteredStorage {
+    fn charge(&self, gas: u64) {
+        self.gas.set(self.gas.get() + gas);
+    }
+
+    fn read_gas(key: &[u8], value: &[u8]) -> u64 {
+        READ_PER_BYTE * (KEY_PREFIX_LEN + key.len() as u64 + value.len() as u64)
+    }
+}
+
+impl Storage for MeteredStorage {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        let value = self.inner.get(key);
+        self.charge(READ_FLAT + Self::read_gas(key, value.as_deref().unwrap_or_default()));
+        value
+    }
+
+    fn range<'a>(
+        &'a self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Record> + 'a> {
+        Box::new(self.inner.range(start, end, order).inspect(|(key, value)| {
+            self.charge(ITER_NEXT_FLAT + Self::read_gas(key, value));
+        }))
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        self.charge(
+            WRITE_FLAT + WRITE_PER_BYTE * (KEY_PREFIX_LEN + key.len() as u64 + value.len() as u64),
+        );
+        self.inner.set(key, value);
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.charge(DELETE);
+        self.inner.remove(key);
+    }
+}
+
+type Deps = OwnedDeps<MeteredStorage, MockApi, MockQuerier>;
+
+fn user(deps: &Deps) -> Addr {
+    deps.api.addr_make("user")
+}
+
+fn signing_key() -> SigningKey {
+    SigningKey::from_slice(&[1; 32]).unwrap()
+}
+
+fn pubkey() -> Binary {
+    Binary::from(
+        signing_key()
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes(),
+    )
+}
+
+fn setup() -> Deps {
+    let mut deps = OwnedDeps {
+        storage: MeteredStorage::default(),
+        api: MockApi::default(),
+        querier: MockQuerier::default(),
+        custom_query_type: PhantomData,
+    };
+    let admin = deps.api.addr_make("admin");
+    let msg = serde_json::from_value(json!({
+        "admin": admin,
+        "poa_admin": deps.api.addr_make("poa_admin"),
+        "rate": "0.5",
+        "source_denom": SOURCE_DENOM,
+        "target_denom": TARGET_DENOM,
+        "paused": false,
+        "bech32_prefix": "cosmwasm",
+    }))
+    .unwrap();
+    instantiate(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+    deps
+}
+
+fn exec(deps: &mut Deps, env: Env, sender: &Addr, msg: Value, funds: &[Coin]) -> Response {
+    let msg: ExecuteMsg = serde_json::from_value(msg).unwrap();
+    execute(deps.as_mut(), env, message_info(sender, funds), msg).unwrap()
+}
+
+fn exec_user(deps: &mut Deps, env: Env, msg: Value, funds: &[Coin]) -> Response {
+    let user = user(deps);
+    exec(deps, env, &user, msg, funds)
+}
+
+fn later(blocks: u64) -> Env {
+    let mut env = mock_env();
+    env.block.height += blocks;
+    env.block.time = env.block.time.plus_seconds(blocks * 5);
+    env
+}
+
+fn commitment_hash(deps: &Deps) -> Binary {
+    let preimage = RevealPreimage {
+        sender: user(deps).to_string(),
+        amount: Uint256::from(AMOUNT),
+        salt: Binary::from(b"salt"),
+    };
+    Binary::from(Sha256::digest(to_json_vec(&preimage).unwrap()).as_slice())
+}
+
+fn signed_conversion(deps: &Deps) -> Value {
+    let payload = SignedConversion {
+        owner: user(deps).to_string(),
+        amount: coin(AMOUNT, SOURCE_DENOM),
+        nonce: 0,
+        deadline: mock_env().block.time.plus_seconds(3600),
+    };
+    let sign_doc = ConversionSignDoc {
+        chain_id: mock_env().block.chain_id,
+        contract: mock_env().contract.address.to_string(),
+        payload: payload.clone(),
+    };
+    let hash = Sha256::digest(to_json_vec(&sign_doc).unwrap());
+    let signature: Signature = signing_key().sign_prehash(&hash).unwrap();
+    json!({"convert_with_signature": {
+        "payload": payload,
+        "signature": Binary::from(signature.to_bytes().as_slice()),
+        "pubkey": pubkey(),
+    }})
+}
+
+fn auction(deps: &mut Deps) {
+    let admin = deps.api.addr_make("admin");
+    let msg = json!({"set_auction": {"auction": {
+        "round_length": 100,
+        "target_demand": "1000",
+        "min_fraction": "0.25",
+    }}});
+    exec(deps, mock_env(), &admin, msg, &[]);
+    exec_user(
+        deps,
+        mock_env(),
+        json!({"auction_commit": {}}),
+        &coins(AMOUNT, SOURCE_DENOM),
+    );
+}
+
+// Bring a fresh contract to the state the message needs, then run it with the storage
+// metered. Returns the gas the message would use on chain
+fn run(message: &str) -> u64 {
+    let mut deps = setup();
+    let funds = coins(AMOUNT, SOURCE_DENOM);
+    let (env, msg, funds) = match message {
+        "convert" => (mock_env(), json!({"convert": {}}), funds),
+        "convert_with_signature" => {
+            let deposit = json!({"deposit": {"pubkey": pubkey()}});
+            exec_user(&mut deps, mock_env(), deposit, &funds);
+            (mock_env(), signed_conversion(&deps), vec![])
+        }
+        "deposit" => (mock_env(), json!({"deposit": {}}), funds),
+        "withdraw" => {
+            exec_user(&mut deps, mock_env(), json!({"deposit": {}}), &funds);
+            let msg = json!({"withdraw": {"amount": AMOUNT.to_string()}});
+            (mock_env(), msg, vec![])
+        }
+        "claim_refund" => {
+            let res = exec_user(&mut deps, mock_env(), json!({"convert": {}}), &funds);
+            let submsg = &res.messages[0];
+            let failed = Reply {
+                id: submsg.id,
+                payload: submsg.payload.clone(),
+                gas_used: 0,
+                result: SubMsgResult::Err("mint failed".to_string()),
+            };
+            reply(deps.as_mut(), mock_env(), failed).unwrap();
+            (mock_env(), json!({"claim_refund": {}}), vec![])
+        }
+        "start_streaming_convert" => {
+            let msg = json!({"start_streaming_convert": {
+                "total": AMOUNT.to_string(),
+                "duration": 100,
+            }});
+            (mock_env(), msg, funds)
+        }
+        "claim_stream" | "cancel_stream" => {
+            let msg = json!({"start_streaming_convert": {
+                "total": AMOUNT.to_string(),
+                "duration": 100,
+            }});
+            exec_user(&mut deps, mock_env(), msg, &funds);
+            (later(10), json!({ message: {"id": 1} }), vec![])
+        }
+        "commit_convert" => {
+            let msg = json!({"commit_convert": {"hash": commitment_hash(&deps)}});
+            (mock_env(), msg, funds)
+        }
+        "reveal_convert" | "cancel_commitment" => {
+            let msg = json!({"commit_convert": {"hash": commitment_hash(&deps)}});
+            exec_user(&mut deps, mock_env(), msg, &funds);
+            let msg = match message {
+                "reveal_convert" => json!({"reveal_convert": {
+                    "amount": AMOUNT.to_string(),
+                    "salt": Binary::from(b"salt"),
+                }}),
+                _ => json!({"cancel_commitment": {}}),
+            };
+            (later(1), msg, vec![])
+        }
+        "auction_commit" => {
+            auction(&mut deps);
+            (mock_env(), json!({"auction_commit": {}}), funds)
+        }
+        "claim_auction" | "cancel_auction_commitment" => {
+            auction(&mut deps);
+            exec_user(&mut deps, later(100), json!({"settle_auction": {}}), &[]);
+            (later(100), json!({ message: {"round": 1} }), vec![])
+        }
+        _ => panic!("no gas scenario for {message}"),
+    };
+    measure(&mut deps, env, msg, &funds)
+}
+
+fn measure(deps: &mut Deps, env: Env, msg: Value, funds: &[Coin]) -> u64 {
+    deps.storage.gas.set(0);
+    let res = exec_user(deps, env, msg, funds);
+    TX_GAS + deps.storage.gas.get() + dispatch_gas(&res)
+}
+
+fn split_gas(recipients: usize) -> u64 {
+    let mut deps = setup();
+    let recipients: Vec<_> = (0..recipients)
+        .map(|i| json!({"address": deps.api.addr_make(&format!("recipient{i}")), "weight": 1}))
+        .collect();
+    let msg = json!({"convert": {"recipients": recipients}});
+    measure(&mut deps, mock_env(), msg, &coins(AMOUNT, SOURCE_DENOM))
+}
+
+fn dispatch_gas(res: &Response) -> u64 {
+    res.messages
+        .iter()
+        .map(|submsg| match &submsg.msg {
+            CosmosMsg::Any(any) if any.type_url == MsgExec::TYPE_URL => {
+                let exec = MsgExec::decode(any.value.as_slice()).unwrap();
+                EXEC_GAS + MSG_GAS * exec.msgs.len() as u64
+            }
+            _ => MSG_GAS,
+        })
+        .sum()
+}
+
+fn gas_hints() -> (BTreeMap<String, u64>, u64) {
+    let deps = setup();
+    let res: Value =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::GasHints {}).unwrap()).unwrap();
+    let hints = res["hints"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|hint| {
+            (
+                hint["message"].as_str().unwrap().to_string(),
+                hint["gas"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    (hints, res["per_recipient"].as_u64().unwrap())
+}
+
+// The hints are handwritten. Each one must cover the estimate of its message and stay
+// within the headroom above it, so a handler that grows or shrinks fails here until its hint
+// is revised
+#[test]
+fn gas_hints_match_estimates() {
+    let (hints, per_recipient) = gas_hints();
+    for (message, hint) in hints {
+        assert_within_headroom(&message, hint, run(&message));
+    }
+    assert_within_headroom("recipient", per_recipient, split_gas(3) - split_gas(2));
+}
+
+fn assert_within_headroom(name: &str, hint: u64, estimate: u64) {
+    assert!(
+        estimate <= hint,
+        "the gas hint of {name} is {hint}, below its estimate of {estimate}"
+    );
+    assert!(
+        hint <= estimate * HEADROOM,
+        "the gas hint of {name} is {hint}, more than {HEADROOM} times its estimate of {estimate}"
+    );
+}
@@ -0,0 +1,100 @@
+use crate::common::*;
+use cosmwasm_std::{Addr, StdResult};
+use cw_multi_test::Executor;
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn check(res: StdResult<impl std::fmt::Debug>, expect: Expect<'_>) {
+    match expect {
+        Expect::Ok => {
+            res.expect("expected Ok");
+        }
+        Expect::ErrContains(s) => {
+            let text = format!("{:#}", res.unwrap_err());
+            assert!(text.contains(s), "expected {s:?} in {text:?}");
+        }
+    }
+}
+
+fn instantiate_group(app: &mut AppGroup, code_id: u64, msg: &Value) -> StdResult<Addr> {
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(default_sender()),
+        msg,
+        &[],
+        "converter",
+        None,
+    )
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::not_required(modify_instantiate(Field::RequireGroupPolicy, false), Expect::Ok)]
+#[case::required(modify_instantiate(Field::RequireGroupPolicy, true), Expect::Ok)]
+#[case::min_threshold_met(modify_instantiate(Field::MinGroupThreshold, DEFAULT_GROUP_THRESHOLD), Expect::Ok)]
+#[case::min_threshold_not_met(modify_instantiate(Field::MinGroupThreshold, DEFAULT_GROUP_THRESHOLD + 1), Expect::ErrContains(GROUP_THRESHOLD_TOO_LOW))]
+#[case::not_a_group(json!({"admin": default_admin(), "poa_admin": DEFAULT_SENDER, "rate": "0.5", "source_denom": DEFAULT_SOURCE_DENOM, "target_denom": DEFAULT_TARGET_DENOM, "paused": false, "require_group_policy": true}), Expect::ErrContains(NOT_GROUP_POLICY))]
+fn instantiate_group_policy(
+    setup_group: (AppGroup, u64),
+    #[case] msg: Value,
+    #[case] expect: Expect<'_>,
+) {
+    let (mut app, code_id) = setup_group;
+    check(instantiate_group(&mut app, code_id, &msg), expect);
+}
+
+#[rstest]
+fn instantiate_no_group_module(setup: (AppAccepting, u64)) {
+    // The accepting stargate module answers every query with an empty response
+    let (app, code_id) = setup;
+    run_instantiate(
+        app,
+        code_id,
+        default_sender(),
+        &modify_instantiate(Field::RequireGroupPolicy, true),
+        &[],
+        Expect::ErrContains(NOT_GROUP_POLICY),
+    );
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::required(create_msg_update_config(Field::RequireGroupPolicy, true), Expect::Ok)]
+#[case::min_threshold_met(create_msg_update_config(Field::MinGroupThreshold, DEFAULT_GROUP_THRESHOLD), Expect::Ok)]
+#[case::min_threshold_not_met(create_msg_update_config(Field::MinGroupThreshold, DEFAULT_GROUP_THRESHOLD + 1), Expect::ErrContains(GROUP_THRESHOLD_TOO_LOW))]
+fn update_config_group_policy(
+    setup_group: (AppGroup, u64),
+    #[case] msg: Value,
+    #[case] expect: Expect<'_>,
+) {
+    let (mut app, code_id) = setup_group;
+    let contract = instantiate_group(&mut app, code_id, &default_instantiate()).unwrap();
+    check(
+        app.execute_contract(Addr::unchecked(default_admin()), contract, &msg, &[]),
+        expect,
+    );
+}
+
+#[rstest]
+fn update_poa_admin_not_a_group(setup_group: (AppGroup, u64)) {
+    let (mut app, code_id) = setup_group;
+    let contract = instantiate_group(
+        &mut app,
+        code_id,
+        &modify_instantiate(Field::RequireGroupPolicy, true),
+    )
+    .unwrap();
+
+    // Once required, the POA admin can't be changed to an account that is not a group policy
+    check(
+        app.execute_contract(
+            Addr::unchecked(default_admin()),
+            contract,
+            &json!({"update_config": {"config": {"poa_admin": DEFAULT_SENDER}}}),
+            &[],
+        ),
+        Expect::ErrContains(NOT_GROUP_POLICY),
+    );
+}
@@ -0,0 +1,134 @@
+use crate::common::*;
+use cosmwasm_std::{coin, coins, Addr, Event, Order, Record};
+use cw_multi_test::Executor;
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn create_msg_health_check() -> Value {
+    json!({"health_check": {}})
+}
+
+fn health_event(healthy: bool, checks: [(&str, &str); 4]) -> Event {
+    Event::new("wasm-health")
+        .add_attribute("healthy", healthy.to_string())
+        .add_attributes(checks)
+}
+
+#[rstest]
+fn health_check_healthy(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let storage = |app: &AppAccepting| -> Vec<Record> {
+        app.contract_storage(&contract_addr)
+            .range(None, None, Order::Ascending)
+            .collect()
+    };
+    let before = storage(&app);
+
+    // Anyone can run it
+    let res = app
+        .execute_contract(
+            Addr::unchecked(DEFAULT_SENDER),
+            contract_addr.clone(),
+            &create_msg_health_check(),
+            &[],
+        )
+        .unwrap();
+    assert!(res.has_event(&health_event(
+        true,
+        [
+            ("config", "ok"),
+            ("denoms", "ok"),
+            ("authz", "ok"),
+            ("escrow", "ok"),
+        ],
+    )));
+    // Nothing is changed
+    assert_eq!(storage(&app), before);
+}
+
+#[rstest]
+fn health_check_authz_disabled(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr, _) = prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        default_admin(),
+        &json!({"set_authz_enabled": {"enabled": false}}),
+        &[],
+        Expect::Ok,
+    );
+    let res = app
+        .execute_contract(
+            Addr::unchecked(DEFAULT_SENDER),
+            contract_addr,
+            &create_msg_health_check(),
+            &[],
+        )
+        .unwrap();
+    assert!(res.has_event(&health_event(
+        false,
+        [
+            ("config", "ok"),
+            ("denoms", "ok"),
+            ("authz", "authz is disabled"),
+            ("escrow", "ok"),
+        ],
+    )));
+}
+
+// Funds leaving the contract outside of its own messages no longer cover the escrow
+#[rstest]
+fn health_check_escrow_not_covered(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr, _) = prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        default_sender(),
+        &json!({"deposit": {}}),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+    app.send_tokens(
+        contract_addr.clone(),
+        Addr::unchecked(DEFAULT_SENDER),
+        &coins(1, DEFAULT_SOURCE_DENOM),
+    )
+    .unwrap();
+    let res = app
+        .execute_contract(
+            Addr::unchecked(DEFAULT_SENDER),
+            contract_addr,
+            &create_msg_health_check(),
+            &[],
+        )
+        .unwrap();
+    let escrow = format!("{DEFAULT_SOURCE_DENOM} balance 999 is below escrowed 1000");
+    assert!(res.has_event(&health_event(
+        false,
+        [
+            ("config", "ok"),
+            ("denoms", "ok"),
+            ("authz", "ok"),
+            ("escrow", escrow.as_str()),
+        ],
+    )));
+}
+
+#[rstest]
+fn health_check_non_payable(setup_with_funds: (AppAccepting, u64)) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        default_sender(),
+        &create_msg_health_check(),
+        &[coin(1, DEFAULT_SOURCE_DENOM)],
+        Expect::ErrContains(NON_PAYABLE),
+    );
+}
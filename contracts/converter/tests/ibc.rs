@@ -0,0 +1,482 @@
+#![cfg(feature = "ibc")]
+
+use converter::msg::{ExecuteMsg, QueryMsg};
+use converter::{
+    execute, ibc_channel_close, ibc_channel_connect, ibc_channel_open, ibc_packet_ack,
+    ibc_packet_receive, ibc_packet_timeout, instantiate, query, reply,
+};
+use cosmwasm_std::testing::{
+    message_info, mock_dependencies, mock_env, mock_ibc_channel, mock_ibc_packet_ack,
+    mock_ibc_packet_recv, mock_ibc_packet_timeout, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, Addr, BankMsg, CosmosMsg, IbcAcknowledgement,
+    IbcChannelCloseMsg, IbcChannelConnectMsg, IbcChannelOpenMsg, IbcMsg, IbcOrder,
+    IbcReceiveResponse, OwnedDeps, Reply, StdAck, SubMsgResponse, SubMsgResult,
+};
+use rstest::*;
+use serde_json::{json, Value};
+
+type Deps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+const VERSION: &str = "manifest-converter-1";
+const CHANNEL_ID: &str = "channel-0";
+const SOURCE_DENOM: &str = "umfx";
+const TARGET_DENOM: &str = "upwr";
+const REMOTE_SENDER: &str = "remote1sender";
+
+fn admin(deps: &Deps) -> Addr {
+    deps.api.addr_make("admin")
+}
+
+fn user(deps: &Deps) -> Addr {
+    deps.api.addr_make("user")
+}
+
+fn query_json(deps: &Deps, msg: QueryMsg) -> Value {
+    from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+}
+
+fn transfer_remote(deps: &mut Deps, denom: &str, amount: u128) -> Result<IbcMsg, String> {
+    let user = user(deps);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&user, &coins(amount, denom)),
+        ExecuteMsg::TransferRemote {
+            channel_id: CHANNEL_ID.to_string(),
+            receiver: REMOTE_SENDER.to_string(),
+            timeout_seconds: None,
+        },
+    )
+    .map_err(|err| err.to_string())?;
+    let CosmosMsg::Ibc(msg) = res.messages[0].msg.clone() else {
+        panic!("expected an ibc message");
+    };
+    Ok(msg)
+}
+
+fn receive(deps: &mut Deps, packet: Value) -> IbcReceiveResponse {
+    let msg = mock_ibc_packet_recv(CHANNEL_ID, &packet).unwrap();
+    ibc_packet_receive(deps.as_mut(), mock_env(), msg).unwrap()
+}
+
+fn transfer_packet(deps: &Deps, denom: &str, amount: u128) -> Value {
+    json!({"transfer": {
+        "denom": denom,
+        "amount": amount.to_string(),
+        "sender": REMOTE_SENDER,
+        "receiver": user(deps),
+    }})
+}
+
+fn convert_packet(deps: &Deps, amount: u128) -> Value {
+    json!({"convert": {
+        "amount": amount.to_string(),
+        "sender": REMOTE_SENDER,
+        "receiver": user(deps),
+    }})
+}
+
+fn escrow(deps: &Deps) -> Value {
+    query_json(deps, QueryMsg::Channels { pagination: None })["items"][0]["escrow"].clone()
+}
+
+#[fixture]
+fn setup() -> Deps {
+    let mut deps = mock_dependencies();
+    let admin = admin(&deps);
+    let msg = serde_json::from_value(json!({
+        "admin": admin,
+        "poa_admin": deps.api.addr_make("poa_admin"),
+        "rate": "0.5",
+        "source_denom": SOURCE_DENOM,
+        "target_denom": TARGET_DENOM,
+        "paused": false,
+        "bech32_prefix": "cosmwasm",
+    }))
+    .unwrap();
+    instantiate(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+    deps
+}
+
+#[fixture]
+fn connected(mut setup: Deps) -> Deps {
+    let channel = mock_ibc_channel(CHANNEL_ID, IbcOrder::Unordered, VERSION);
+    ibc_channel_open(
+        setup.as_mut(),
+        mock_env(),
+        IbcChannelOpenMsg::new_init(channel.clone()),
+    )
+    .unwrap();
+    ibc_channel_connect(
+        setup.as_mut(),
+        mock_env(),
+        IbcChannelConnectMsg::new_ack(channel, VERSION),
+    )
+    .unwrap();
+    setup
+}
+
+// Source tokens transferred to the counterparty chain, escrowed on the channel
+#[fixture]
+fn funded(mut connected: Deps) -> Deps {
+    transfer_remote(&mut connected, SOURCE_DENOM, 1000).unwrap();
+    connected
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::init(IbcChannelOpenMsg::new_init(mock_ibc_channel(CHANNEL_ID, IbcOrder::Unordered, VERSION)), None)]
+#[case::try_open(IbcChannelOpenMsg::new_try(mock_ibc_channel(CHANNEL_ID, IbcOrder::Unordered, VERSION), VERSION), None)]
+#[case::ordered(IbcChannelOpenMsg::new_init(mock_ibc_channel(CHANNEL_ID, IbcOrder::Ordered, VERSION)), Some("channel must be unordered"))]
+#[case::invalid_version(IbcChannelOpenMsg::new_init(mock_ibc_channel(CHANNEL_ID, IbcOrder::Unordered, "ics20-1")), Some("invalid channel version"))]
+#[case::invalid_counterparty_version(IbcChannelOpenMsg::new_try(mock_ibc_channel(CHANNEL_ID, IbcOrder::Unordered, VERSION), "ics20-1"), Some("invalid channel version"))]
+fn channel_open(mut setup: Deps, #[case] msg: IbcChannelOpenMsg, #[case] err: Option<&str>) {
+    let res = ibc_channel_open(setup.as_mut(), mock_env(), msg);
+    match err {
+        None => assert_eq!(res.unwrap().unwrap().version, VERSION),
+        Some(err) => assert!(res.unwrap_err().to_string().contains(err)),
+    }
+}
+
+#[rstest]
+fn channel_connect(connected: Deps) {
+    assert_eq!(
+        query_json(&connected, QueryMsg::Channels { pagination: None }),
+        json!({"items": [{
+            "channel_id": CHANNEL_ID,
+            "connection_id": "connection-2",
+            "counterparty_port_id": "their_port",
+            "counterparty_channel_id": "channel-7",
+            "escrow": [],
+        }], "next_key": null})
+    );
+}
+
+#[rstest]
+fn channel_close(mut connected: Deps) {
+    let channel = mock_ibc_channel(CHANNEL_ID, IbcOrder::Unordered, VERSION);
+    let err = ibc_channel_close(
+        connected.as_mut(),
+        mock_env(),
+        IbcChannelCloseMsg::new_init(channel.clone()),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("channel cannot be closed"));
+
+    ibc_channel_close(
+        connected.as_mut(),
+        mock_env(),
+        IbcChannelCloseMsg::new_confirm(channel),
+    )
+    .unwrap();
+    assert_eq!(
+        query_json(&connected, QueryMsg::Channels { pagination: None }),
+        json!({"items": [], "next_key": null})
+    );
+}
+
+#[rstest]
+fn transfer_remote_escrows(mut connected: Deps) {
+    let msg = transfer_remote(&mut connected, SOURCE_DENOM, 1000).unwrap();
+    let IbcMsg::SendPacket {
+        channel_id,
+        data,
+        timeout,
+    } = msg
+    else {
+        panic!("expected a send packet message");
+    };
+    assert_eq!(channel_id, CHANNEL_ID);
+    assert_eq!(
+        from_json::<Value>(&data).unwrap(),
+        json!({"transfer": {
+            "denom": SOURCE_DENOM,
+            "amount": "1000",
+            "sender": user(&connected),
+            "receiver": REMOTE_SENDER,
+        }})
+    );
+    assert_eq!(
+        timeout.timestamp(),
+        Some(mock_env().block.time.plus_seconds(600))
+    );
+    assert_eq!(
+        escrow(&connected),
+        json!([{"denom": SOURCE_DENOM, "amount": "1000"}])
+    );
+
+    // Escrowed tokens are not unsolicited funds
+    let balance = query_json(
+        &connected,
+        QueryMsg::UnsolicitedBalance {
+            denom: SOURCE_DENOM.to_string(),
+        },
+    );
+    assert_eq!(balance["escrowed"], json!("1000"));
+}
+
+#[rstest]
+fn transfer_remote_invalid(mut setup: Deps) {
+    let err = transfer_remote(&mut setup, SOURCE_DENOM, 1000).unwrap_err();
+    assert!(err.contains("unknown channel"));
+
+    let mut connected = connected(setup);
+    let err = transfer_remote(&mut connected, "uother", 1000).unwrap_err();
+    assert!(err.contains("denom cannot be transferred"));
+}
+
+#[rstest]
+fn receive_transfer(mut funded: Deps) {
+    let packet = transfer_packet(&funded, SOURCE_DENOM, 400);
+    let res = receive(&mut funded, packet);
+    assert_eq!(res.acknowledgement, Some(StdAck::success(b"\x01").into()));
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: user(&funded).to_string(),
+            amount: coins(400, SOURCE_DENOM),
+        })
+    );
+    assert_eq!(
+        escrow(&funded),
+        json!([{"denom": SOURCE_DENOM, "amount": "600"}])
+    );
+}
+
+#[rstest]
+fn receive_errors(mut funded: Deps) {
+    let packets = [
+        (
+            transfer_packet(&funded, SOURCE_DENOM, 1001),
+            "insufficient channel escrow",
+        ),
+        (
+            transfer_packet(&funded, "uother", 1),
+            "denom cannot be transferred",
+        ),
+        (convert_packet(&funded, 1001), "insufficient channel escrow"),
+        (json!({"unknown": {}}), "invalid packet data"),
+    ];
+    for (packet, err) in packets {
+        let res = receive(&mut funded, packet);
+        let ack: StdAck = from_json(res.acknowledgement.unwrap()).unwrap();
+        assert!(ack.is_error());
+        assert!(res.attributes.iter().any(|a| a.value.contains(err)));
+        assert!(res.messages.is_empty());
+    }
+    assert_eq!(
+        escrow(&funded),
+        json!([{"denom": SOURCE_DENOM, "amount": "1000"}])
+    );
+}
+
+#[rstest]
+fn receive_convert(mut funded: Deps) {
+    let packet = convert_packet(&funded, 400);
+    let res = receive(&mut funded, packet);
+    assert_eq!(
+        res.acknowledgement,
+        Some(StdAck::success(to_json_binary(&json!({"minted": "200"})).unwrap()).into())
+    );
+    assert_eq!(res.messages.len(), 1);
+    let CosmosMsg::Any(msg) = &res.messages[0].msg else {
+        panic!("expected an any message");
+    };
+    assert_eq!(msg.type_url, "/cosmos.authz.v1beta1.MsgExec");
+    assert_eq!(
+        escrow(&funded),
+        json!([
+            {"denom": SOURCE_DENOM, "amount": "600"},
+            {"denom": TARGET_DENOM, "amount": "200"},
+        ])
+    );
+
+    // The conversion is accounted once its submessage succeeded
+    let submsg = &res.messages[0];
+    reply(
+        funded.as_mut(),
+        mock_env(),
+        Reply {
+            id: submsg.id,
+            payload: submsg.payload.clone(),
+            gas_used: 0,
+            #[allow(deprecated)]
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+                msg_responses: vec![],
+            }),
+        },
+    )
+    .unwrap();
+    let epoch = query_json(&funded, QueryMsg::CurrentEpoch {});
+    assert_eq!(epoch["burned"], json!("400"));
+    assert_eq!(epoch["minted"], json!("200"));
+}
+
+#[rstest]
+fn receive_convert_failed(mut funded: Deps) {
+    let packet = convert_packet(&funded, 400);
+    let res = receive(&mut funded, packet);
+    let submsg = &res.messages[0];
+    let res = reply(
+        funded.as_mut(),
+        mock_env(),
+        Reply {
+            id: submsg.id,
+            payload: submsg.payload.clone(),
+            gas_used: 0,
+            result: SubMsgResult::Err("mint failed".to_string()),
+        },
+    )
+    .unwrap();
+
+    // The acknowledgement is replaced by an error and the escrow restored
+    assert_eq!(res.data, Some(StdAck::error("mint failed").into()));
+    assert_eq!(
+        escrow(&funded),
+        json!([
+            {"denom": SOURCE_DENOM, "amount": "1000"},
+            {"denom": TARGET_DENOM, "amount": "0"},
+        ])
+    );
+}
+
+#[rstest]
+fn receive_convert_paused(mut funded: Deps) {
+    let admin = admin(&funded);
+    execute(
+        funded.as_mut(),
+        mock_env(),
+        message_info(&admin, &[]),
+        serde_json::from_value(json!({"update_config": {"config": {"paused": true}}})).unwrap(),
+    )
+    .unwrap();
+
+    let packet = convert_packet(&funded, 400);
+    let res = receive(&mut funded, packet);
+    assert_eq!(
+        res.acknowledgement,
+        Some(StdAck::error("contract is paused").into())
+    );
+}
+
+#[rstest]
+fn receive_convert_max_amount(mut funded: Deps) {
+    let admin = admin(&funded);
+    execute(
+        funded.as_mut(),
+        mock_env(),
+        message_info(&admin, &[]),
+        serde_json::from_value(json!({"update_config": {"config": {"max_convert_amount": "300"}}}))
+            .unwrap(),
+    )
+    .unwrap();
+
+    let packet = convert_packet(&funded, 400);
+    let res = receive(&mut funded, packet);
+    assert_eq!(
+        res.acknowledgement,
+        Some(
+            StdAck::error("conversion error: amount is above the maximum conversion amount").into()
+        )
+    );
+    assert_eq!(
+        escrow(&funded),
+        json!([{"denom": SOURCE_DENOM, "amount": "1000"}])
+    );
+}
+
+// Remote senders have a daily quota of their own, keyed by channel
+#[cfg(feature = "quotas")]
+#[rstest]
+fn receive_convert_daily_quota(mut funded: Deps) {
+    let admin = admin(&funded);
+    execute(
+        funded.as_mut(),
+        mock_env(),
+        message_info(&admin, &[]),
+        serde_json::from_value(json!({"update_config": {"config": {"daily_quota": "500"}}}))
+            .unwrap(),
+    )
+    .unwrap();
+
+    let packet = convert_packet(&funded, 400);
+    let res = receive(&mut funded, packet);
+    assert_eq!(res.messages.len(), 1);
+    let quota = query_json(
+        &funded,
+        QueryMsg::RemainingQuota {
+            address: format!("{CHANNEL_ID}/{REMOTE_SENDER}"),
+        },
+    );
+    assert_eq!(quota["remaining"], json!("100"));
+
+    let packet = convert_packet(&funded, 400);
+    let res = receive(&mut funded, packet);
+    assert_eq!(
+        res.acknowledgement,
+        Some(
+            StdAck::error("conversion error: conversion exceeds the remaining daily quota").into()
+        )
+    );
+}
+
+#[rstest]
+fn receive_unknown_channel(mut setup: Deps) {
+    let packet = transfer_packet(&setup, SOURCE_DENOM, 1);
+    let res = receive(&mut setup, packet);
+    assert_eq!(
+        res.acknowledgement,
+        Some(StdAck::error("ibc error: unknown channel").into())
+    );
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::success(StdAck::success(b"\x01"), false)]
+#[case::error(StdAck::error("invalid receiver"), true)]
+fn packet_ack(mut connected: Deps, #[case] ack: StdAck, #[case] refunded: bool) {
+    let IbcMsg::SendPacket { data, .. } = transfer_remote(&mut connected, SOURCE_DENOM, 1000).unwrap() else {
+        panic!("expected a send packet message");
+    };
+    let msg = mock_ibc_packet_ack(CHANNEL_ID, &from_json::<Value>(&data).unwrap(), IbcAcknowledgement::new(ack)).unwrap();
+    let res = ibc_packet_ack(connected.as_mut(), mock_env(), msg).unwrap();
+    if refunded {
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: user(&connected).to_string(),
+                amount: coins(1000, SOURCE_DENOM),
+            })
+        );
+        assert_eq!(escrow(&connected), json!([{"denom": SOURCE_DENOM, "amount": "0"}]));
+    } else {
+        assert!(res.messages.is_empty());
+        assert_eq!(escrow(&connected), json!([{"denom": SOURCE_DENOM, "amount": "1000"}]));
+    }
+}
+
+#[rstest]
+fn packet_timeout(mut connected: Deps) {
+    let IbcMsg::SendPacket { data, .. } =
+        transfer_remote(&mut connected, TARGET_DENOM, 1000).unwrap()
+    else {
+        panic!("expected a send packet message");
+    };
+    let msg = mock_ibc_packet_timeout(CHANNEL_ID, &from_json::<Value>(&data).unwrap()).unwrap();
+    let res = ibc_packet_timeout(connected.as_mut(), mock_env(), msg).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: user(&connected).to_string(),
+            amount: coins(1000, TARGET_DENOM),
+        })
+    );
+    assert_eq!(
+        escrow(&connected),
+        json!([{"denom": TARGET_DENOM, "amount": "0"}])
+    );
+}
@@ -1,5 +1,10 @@
 use crate::common::*;
+use converter::consts;
+use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env, MockApi};
+use cosmwasm_std::{coin, Addr, Coin};
+use cw_multi_test::Executor;
 use rstest::*;
+use serde_json::{json, Value};
 
 mod common;
 
@@ -25,7 +30,7 @@ mod common;
 #[case::poa_admin_checksum(Field::PoaAdmin, INVALID_MANIFEST_ADDRESS, Expect::ErrContains(INVALID_CHECKSUM))]
 #[case::poa_admin_unicode(Field::PoaAdmin, "😀", Expect::ErrContains(PARSE_FAILED))]
 #[case::poa_admin_wrong_prefix(Field::PoaAdmin, VALID_OSMOSIS_ADDRESS, Expect::ErrContains(WRONG_BECH32_PREFIX))]
-#[case::poa_admin_null(Field::PoaAdmin, serde_json::Value::Null, Expect::ErrContains(INVALID_TYPE_NULL))]
+#[case::poa_admin_null(Field::PoaAdmin, serde_json::Value::Null, Expect::Ok)]
 #[case::poa_admin_number(Field::PoaAdmin, 1, Expect::ErrContains(INVALID_TYPE_INTEGER))]
 // --- rate: OK ---
 #[case::rate_one(Field::Rate, "1", Expect::Ok)]
@@ -55,7 +60,7 @@ mod common;
 #[case::src_denom_factory_empty_subdenom( Field::SourceDenom, INVALID_FACTORY_DENOM_NO_SUBDENOM, Expect::ErrContains(INVALID_FACTORY_DENOM_FORMAT))]
 #[case::src_denom_invalid_format(Field::SourceDenom, "a", Expect::ErrContains(INVALID_DENOM_FORMAT))]
 #[case::src_denom_too_long(Field::SourceDenom, "a".repeat(256), Expect::ErrContains(INVALID_DENOM_FORMAT))]
-#[case::src_denom_null(Field::SourceDenom, serde_json::Value::Null, Expect::ErrContains(INVALID_TYPE_NULL))]
+#[case::src_denom_null(Field::SourceDenom, serde_json::Value::Null, Expect::Ok)]
 #[case::src_denom_number(Field::SourceDenom, 1, Expect::ErrContains(INVALID_TYPE_INTEGER))]
 // --- tgt_denom: OK ---
 #[case::tgt_denom_default(Field::TargetDenom, DEFAULT_TARGET_DENOM, Expect::Ok)]
@@ -73,7 +78,7 @@ mod common;
 #[case::tgt_denom_factory_empty_subdenom(Field::TargetDenom, INVALID_FACTORY_DENOM_NO_SUBDENOM, Expect::ErrContains(INVALID_FACTORY_DENOM_FORMAT))]
 #[case::tgt_denom_invalid_format(Field::TargetDenom, "invalid_format", Expect::ErrContains(INVALID_DENOM_FORMAT))]
 #[case::tgt_denom_too_long(Field::TargetDenom, "a".repeat(256), Expect::ErrContains(INVALID_DENOM_FORMAT))]
-#[case::tgt_denom_null(Field::TargetDenom, serde_json::Value::Null, Expect::ErrContains(INVALID_TYPE_NULL))]
+#[case::tgt_denom_null(Field::TargetDenom, serde_json::Value::Null, Expect::Ok)]
 #[case::tgt_denom_number(Field::TargetDenom, 1, Expect::ErrContains(INVALID_TYPE_INTEGER))]
 // --- paused: OK ---
 #[case::paused_true(Field::Paused, true, Expect::Ok)]
@@ -81,8 +86,42 @@ mod common;
 // --- paused: invalid ---
 #[case::paused_invalid(Field::Paused, "a", Expect::ErrContains(INVALID_TYPE_STRING))]
 #[case::paused_unicode(Field::Paused, "😀", Expect::ErrContains(INVALID_TYPE_STRING))]
-#[case::paused_null(Field::Paused, serde_json::Value::Null, Expect::ErrContains(INVALID_TYPE_NULL))]
+#[case::paused_null(Field::Paused, serde_json::Value::Null, Expect::Ok)]
 #[case::paused_number(Field::Paused, 1, Expect::ErrContains(INVALID_TYPE_INTEGER))]
+// --- strict_denoms: OK ---
+#[case::strict_denoms_false(Field::StrictDenoms, false, Expect::Ok)]
+#[case::strict_denoms_null(Field::StrictDenoms, serde_json::Value::Null, Expect::Ok)]
+// --- strict_denoms: invalid ---
+// No funds were minted in this setup, none of the denoms exist
+#[case::strict_denoms_unknown(Field::StrictDenoms, true, Expect::ErrContains(UNKNOWN_DENOM))]
+#[case::strict_denoms_invalid(Field::StrictDenoms, "a", Expect::ErrContains(INVALID_TYPE_STRING))]
+// --- exponents: invalid (both must be set together) ---
+#[case::source_exponent_alone(Field::SourceExponent, 6, Expect::ErrContains(INCOMPLETE_EXPONENTS))]
+#[case::target_exponent_alone(Field::TargetExponent, 18, Expect::ErrContains(INCOMPLETE_EXPONENTS))]
+#[case::source_exponent_negative(Field::SourceExponent, -1, Expect::ErrContains(INVALID_VALUE))]
+
+#[case::bech32_prefix_default(Field::Bech32Prefix, "manifest", Expect::Ok)]
+#[case::bech32_prefix_mismatch(Field::Bech32Prefix, "osmo", Expect::ErrContains(ADDRESS_PREFIX_MISMATCH))]
+#[case::bech32_prefix_empty(Field::Bech32Prefix, "", Expect::ErrContains(INVALID_BECH32_PREFIX))]
+#[case::bech32_prefix_uppercase(Field::Bech32Prefix, "Manifest", Expect::ErrContains(INVALID_BECH32_PREFIX))]
+#[case::bech32_prefix_invalid(Field::Bech32Prefix, "mani fest", Expect::ErrContains(INVALID_BECH32_PREFIX))]
+// --- flat_fee ---
+#[case::flat_fee(Field::FlatFee, coin(10, DUMMY_DENOM), Expect::Ok)]
+#[case::flat_fee_zero(Field::FlatFee, coin(0, DUMMY_DENOM), Expect::ErrContains(INVALID_FLAT_FEE))]
+#[case::flat_fee_source_denom(Field::FlatFee, coin(10, DEFAULT_SOURCE_DENOM), Expect::ErrContains(INVALID_FLAT_FEE))]
+// --- fee_model ---
+#[case::fee_model(Field::FeeModel, json!({"percent_plus_flat": {"percent": "0.5", "amount": "10"}}), Expect::Ok)]
+#[case::fee_model_above_100(Field::FeeModel, json!({"percent": {"percent": "100.5"}}), Expect::ErrContains(INVALID_FEE_MODEL))]
+#[case::fee_model_zero_flat(Field::FeeModel, json!({"flat": {"amount": "0"}}), Expect::ErrContains(INVALID_FEE_MODEL))]
+#[case::fee_model_empty_tiers(Field::FeeModel, json!({"tiered": {"tiers": []}}), Expect::ErrContains(INVALID_FEE_MODEL))]
+#[case::fee_model_unsorted_tiers(Field::FeeModel, json!({"tiered": {"tiers": [{"min_amount": "10", "percent": "1"}, {"min_amount": "5", "percent": "1"}]}}), Expect::ErrContains(INVALID_FEE_MODEL))]
+// --- rate bounds ---
+#[case::rate_floor(Field::RateFloor, "0.4", Expect::Ok)]
+#[case::rate_floor_equal(Field::RateFloor, DEFAULT_RATE, Expect::Ok)]
+#[case::rate_floor_above_rate(Field::RateFloor, "0.6", Expect::ErrContains(RATE_OUT_OF_BOUNDS))]
+#[case::rate_ceiling(Field::RateCeiling, "0.6", Expect::Ok)]
+#[case::rate_ceiling_below_rate(Field::RateCeiling, "0.4", Expect::ErrContains(RATE_OUT_OF_BOUNDS))]
+#[case::rate_ceiling_zero(Field::RateCeiling, "0", Expect::ErrContains(RATE_IS_ZERO))]
 fn instantiate_field_variations(
     setup: (AppAccepting, u64),
     #[case] field: Field,
@@ -93,6 +132,21 @@ fn instantiate_field_variations(
     run_instantiate(app, code_id, default_sender(), &modify_instantiate(field, val), no_funds(), expect);
 }
 
+#[rstest]
+fn instantiate_conflicting_fees(setup: (AppAccepting, u64)) {
+    let (app, code_id) = setup;
+    let mut msg = modify_instantiate(Field::FlatFee, coin(10, DUMMY_DENOM));
+    msg["fee_model"] = json!({"percent": {"percent": "1"}});
+    run_instantiate(
+        app,
+        code_id,
+        default_sender(),
+        &msg,
+        &[],
+        Expect::ErrContains(CONFLICTING_FEES),
+    );
+}
+
 #[rstest]
 fn instantiate_with_funds(setup_with_funds: (AppAccepting, u64)) {
     let (app, code_id) = setup_with_funds;
@@ -105,3 +159,147 @@ fn instantiate_with_funds(setup_with_funds: (AppAccepting, u64)) {
         Expect::ErrContains(NON_PAYABLE),
     );
 }
+
+#[rustfmt::skip]
+#[rstest]
+#[case::exact(vec![coin(100, DEFAULT_SOURCE_DENOM), coin(5, DUMMY_DENOM)], vec![coin(100, DEFAULT_SOURCE_DENOM), coin(5, DUMMY_DENOM)], Expect::Ok)]
+#[case::any_order(vec![coin(5, DUMMY_DENOM), coin(100, DEFAULT_SOURCE_DENOM)], vec![coin(100, DEFAULT_SOURCE_DENOM), coin(5, DUMMY_DENOM)], Expect::Ok)]
+#[case::missing_funds(vec![coin(100, DEFAULT_SOURCE_DENOM)], vec![], Expect::ErrContains(FUNDING_MISMATCH))]
+#[case::less_funds(vec![coin(100, DEFAULT_SOURCE_DENOM)], vec![coin(99, DEFAULT_SOURCE_DENOM)], Expect::ErrContains(FUNDING_MISMATCH))]
+#[case::extra_denom(vec![coin(100, DEFAULT_SOURCE_DENOM)], vec![coin(100, DEFAULT_SOURCE_DENOM), coin(5, DUMMY_DENOM)], Expect::ErrContains(FUNDING_MISMATCH))]
+#[case::empty(vec![], vec![], Expect::ErrContains(INVALID_FUNDING))]
+#[case::zero(vec![coin(0, DEFAULT_SOURCE_DENOM)], vec![], Expect::ErrContains(INVALID_FUNDING))]
+#[case::duplicate(vec![coin(1, DUMMY_DENOM), coin(2, DUMMY_DENOM)], vec![coin(3, DUMMY_DENOM)], Expect::ErrContains(INVALID_FUNDING))]
+fn instantiate_with_funding(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] funding: Vec<Coin>,
+    #[case] funds: Vec<Coin>,
+    #[case] expect: Expect<'_>,
+) {
+    let (app, code_id) = setup_with_funds;
+    run_instantiate(
+        app,
+        code_id,
+        default_sender(),
+        &modify_instantiate(Field::Funding, funding),
+        &funds,
+        expect,
+    );
+}
+
+// Bootstrap funds outside of the target denom are recorded as unsolicited funds of the
+// instantiator
+#[rstest]
+fn instantiate_funding_recorded(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let funds = vec![coin(100, DEFAULT_SOURCE_DENOM)];
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &modify_instantiate(Field::Funding, &funds),
+            &funds,
+            "converter",
+            None,
+        )
+        .unwrap();
+
+    let recorded: Value = app
+        .wrap()
+        .query_wasm_smart(
+            &contract_addr,
+            &json!({"unsolicited_funds": {"depositor": default_sender()}}),
+        )
+        .unwrap();
+    assert_eq!(recorded, json!({"funds": funds}));
+    let balance: Value = app
+        .wrap()
+        .query_wasm_smart(
+            &contract_addr,
+            &json!({"unsolicited_balance": {"denom": DEFAULT_SOURCE_DENOM}}),
+        )
+        .unwrap();
+    assert_eq!(balance["recorded"], json!("100"));
+    assert_eq!(balance["unrecorded"], json!("0"));
+}
+
+#[rstest]
+fn instantiate_strict_denoms_with_supply(setup_with_funds: (AppAccepting, u64)) {
+    let (app, code_id) = setup_with_funds;
+    run_instantiate(
+        app,
+        code_id,
+        default_sender(),
+        &modify_instantiate(Field::StrictDenoms, true),
+        no_funds(),
+        Expect::Ok,
+    );
+}
+
+#[rstest]
+fn instantiate_minimal(setup: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup;
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &json!({"admin": default_admin(), "rate": "0.5"}),
+            no_funds(),
+            "converter",
+            None,
+        )
+        .unwrap();
+    let config: Value = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &json!({"config": {}}))
+        .unwrap();
+    assert_eq!(
+        config,
+        json!({
+            "poa_admin": consts::DEFAULT_POA_ADMIN,
+            "rate": "0.5",
+            "source_denom": "umfx",
+            "target_denom": format!("factory/{}/upwr", consts::DEFAULT_POA_ADMIN),
+            "paused": false,
+        })
+    );
+}
+
+// Denoms are stored in the case the chain reports IBC hashes in, without surrounding spaces
+#[rstest]
+fn instantiate_canonical_denoms(setup: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup;
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &json!({
+                "admin": default_admin(),
+                "rate": "0.5",
+                "source_denom": format!(" {} ", VALID_IBC_DENOM.to_lowercase()),
+            }),
+            no_funds(),
+            "converter",
+            None,
+        )
+        .unwrap();
+    let config: Value = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &json!({"config": {}}))
+        .unwrap();
+    assert_eq!(config["source_denom"], VALID_IBC_DENOM);
+}
+
+// Grants of the POA admin, a manifest address, can only be made to a manifest address
+#[rstest]
+fn instantiate_grantee_prefix_mismatch() {
+    let mut deps = mock_dependencies();
+    deps.api = MockApi::default().with_prefix("manifest");
+    let env = mock_env();
+    assert!(env.contract.address.as_str().starts_with("cosmwasm1"));
+    let sender = Addr::unchecked(DEFAULT_SENDER);
+    let msg = serde_json::from_value(default_instantiate()).unwrap();
+    let err =
+        converter::instantiate(deps.as_mut(), env, message_info(&sender, &[]), msg).unwrap_err();
+    assert!(err.to_string().contains(GRANTEE_PREFIX_MISMATCH));
+}
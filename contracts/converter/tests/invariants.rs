@@ -0,0 +1,185 @@
+use crate::common::*;
+use cosmwasm_std::{coin, Addr, Coin, Decimal256, Uint256, Uint512};
+use cw_multi_test::Executor;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::{json, Value};
+
+mod common;
+
+// Random cases per run. The seed can be overridden to reproduce a failure or widen coverage
+const CASES: usize = 64;
+const DEFAULT_SEED: u64 = 0x6d66_7870_7772;
+
+fn rng() -> StdRng {
+    let seed = std::env::var("CONVERTER_INVARIANT_SEED")
+        .ok()
+        .and_then(|seed| seed.parse().ok())
+        .unwrap_or(DEFAULT_SEED);
+    println!("invariant seed: {seed}");
+    StdRng::seed_from_u64(seed)
+}
+
+// A value of random magnitude, from 1 up to 10^max_digits
+fn random_magnitude(rng: &mut StdRng, max_digits: u32) -> u128 {
+    let digits = rng.gen_range(0..=max_digits);
+    rng.gen_range(1..=10u128.pow(digits))
+}
+
+fn pow10(exp: u32) -> Uint512 {
+    Uint512::from(10u8).pow(exp)
+}
+
+// Exact floor of amount * rate * 10^(target_exponent - source_exponent)
+fn exact_mint(
+    amount: Uint256,
+    rate: Decimal256,
+    source_exponent: u32,
+    target_exponent: u32,
+) -> Uint512 {
+    let numerator = Uint512::from(amount) * Uint512::from(rate.atomics()) * pow10(target_exponent);
+    numerator / (pow10(Decimal256::DECIMAL_PLACES) * pow10(source_exponent))
+}
+
+// The converter and a reverse converter, between the same denoms in the other direction
+struct Converters {
+    app: AppAccepting,
+    forward: Addr,
+    reverse: Addr,
+}
+
+fn setup(setup_with_funds: (AppAccepting, u64)) -> Converters {
+    let (mut app, code_id) = setup_with_funds;
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(
+                storage,
+                &Addr::unchecked(default_sender()),
+                vec![coin(u128::MAX, DEFAULT_SOURCE_DENOM)],
+            )
+            .unwrap()
+    });
+    let forward = instantiate_default(&mut app, code_id);
+    let mut msg = default_instantiate();
+    msg["source_denom"] = json!(DEFAULT_TARGET_DENOM);
+    msg["target_denom"] = json!(DEFAULT_SOURCE_DENOM);
+    let reverse = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &msg,
+            &[],
+            "reverse",
+            None,
+        )
+        .unwrap();
+    Converters {
+        app,
+        forward,
+        reverse,
+    }
+}
+
+fn set_rate(app: &mut AppAccepting, contract: &Addr, rate: Decimal256, exponents: (u32, u32)) {
+    let config = json!({
+        "rate": rate.to_string(),
+        "source_exponent": exponents.0,
+        "target_exponent": exponents.1,
+    });
+    run_execute(
+        app,
+        default_admin(),
+        contract.as_str(),
+        &create_msg_update_config_from_config(&config),
+        &[],
+        Expect::Ok,
+    );
+}
+
+// Minted coin the contract predicts for a conversion, None when it would fail
+fn simulate(app: &AppAccepting, contract: &Addr, amount: Uint256) -> Option<Coin> {
+    app.wrap()
+        .query_wasm_smart::<Value>(
+            contract,
+            &json!({"simulate_execution": {"sender": DEFAULT_SENDER, "amount": amount}}),
+        )
+        .ok()
+        .map(|res| serde_json::from_value(res["minted"].clone()).unwrap())
+}
+
+// Minted coin of an actual conversion, None when it failed
+fn convert(app: &mut AppAccepting, contract: &Addr, amount: Uint256) -> Option<Coin> {
+    let res = app
+        .execute_contract(
+            Addr::unchecked(default_sender()),
+            contract.clone(),
+            &default_convert(),
+            &[Coin::new(amount, DEFAULT_SOURCE_DENOM)],
+        )
+        .ok()?;
+    let id: Value = serde_json::from_slice(&res.data.unwrap()).unwrap();
+    let receipt: Value = app
+        .wrap()
+        .query_wasm_smart(
+            contract,
+            &json!({"conversion": {"id": id["conversion_id"]}}),
+        )
+        .unwrap();
+    Some(serde_json::from_value(receipt["minted"].clone()).unwrap())
+}
+
+#[rstest::rstest]
+fn conversion_invariants(setup_with_funds: (AppAccepting, u64)) {
+    let mut rng = rng();
+    let Converters {
+        mut app,
+        forward,
+        reverse,
+    } = setup(setup_with_funds);
+
+    let mut converted = 0;
+    for case in 0..CASES {
+        let rate = Decimal256::new(Uint256::from(random_magnitude(&mut rng, 24)));
+        let exponents = (rng.gen_range(0..=18), rng.gen_range(0..=18));
+        let amount = Uint256::from(random_magnitude(&mut rng, 24));
+        let context = format!("case {case}: rate {rate}, exponents {exponents:?}, amount {amount}");
+
+        set_rate(&mut app, &forward, rate, exponents);
+        let predicted = simulate(&app, &forward, amount);
+        let minted = convert(&mut app, &forward, amount);
+
+        // No path mints more than simulate predicts, and both fail together
+        assert_eq!(minted, predicted, "{context}");
+        let Some(minted) = minted else {
+            continue;
+        };
+        converted += 1;
+
+        // minted <= rate * burned, with the rate in display units
+        let exact = exact_mint(amount, rate, exponents.0, exponents.1);
+        assert!(
+            Uint512::from(minted.amount) <= exact,
+            "{context}: minted {minted}"
+        );
+
+        // Converting the minted tokens back at the inverse rate never yields more than burned
+        let inverse = Decimal256::one()
+            .checked_div(rate)
+            .ok()
+            .filter(|inverse| !inverse.is_zero());
+        let Some(inverse) = inverse else {
+            continue;
+        };
+        set_rate(&mut app, &reverse, inverse, (exponents.1, exponents.0));
+        if let Some(back) = simulate(&app, &reverse, minted.amount) {
+            assert!(back.amount <= amount, "{context}: converted back {back}");
+        }
+    }
+
+    // Guard against the cases degenerating into failed conversions only
+    assert!(
+        converted >= CASES / 4,
+        "only {converted} of {CASES} cases converted"
+    );
+}
@@ -0,0 +1,370 @@
+use crate::common::*;
+use converter::msg::ExecuteMsg;
+use cosmwasm_schema::schema_for;
+use cosmwasm_std::{coin, Addr, Coin};
+use cw_multi_test::Executor;
+use rstest::*;
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+
+mod common;
+
+// Specification of the states of the contract, checked against every execute message. A
+// frozen contract rejects everything, a paused one the messages converting tokens
+#[derive(Clone, Copy, Debug)]
+enum State {
+    Active,
+    Paused,
+    Frozen,
+    FrozenPaused,
+}
+
+// Whether a message is allowed while the contract is paused
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum WhenPaused {
+    Allowed,
+    Rejected,
+}
+
+impl State {
+    // Error of the state for the message, None when it is allowed
+    fn rejects(self, when_paused: WhenPaused) -> Option<&'static str> {
+        match (self, when_paused) {
+            (State::Frozen | State::FrozenPaused, _) => Some(CONTRACT_FROZEN),
+            (State::Paused, WhenPaused::Rejected) => Some(CONTRACT_PAUSED),
+            _ => None,
+        }
+    }
+
+    fn enter(self, app: &mut AppAccepting, contract_addr: &Addr) {
+        if matches!(self, State::Paused | State::FrozenPaused) {
+            run_execute(
+                app,
+                default_admin(),
+                contract_addr.as_str(),
+                &json!({"pause": {"reason": "maintenance", "message": null}}),
+                &[],
+                Expect::Ok,
+            );
+        }
+        if matches!(self, State::Frozen | State::FrozenPaused) {
+            app.wasm_sudo(contract_addr.clone(), &json!({"freeze": {}}))
+                .expect("failed to freeze");
+        }
+    }
+}
+
+// One message of every execute variant, with its sender, funds and whether it is allowed
+// while paused
+fn messages() -> Vec<(&'static str, Value, Vec<Coin>, WhenPaused)> {
+    let admin = default_admin();
+    let sender = DEFAULT_SENDER;
+    let funds = vec![default_convert_amount()];
+    let one = coin(1, DEFAULT_SOURCE_DENOM);
+    #[allow(unused_mut)]
+    let mut messages = vec![
+        (
+            sender,
+            default_convert(),
+            funds.clone(),
+            WhenPaused::Rejected,
+        ),
+        (
+            admin,
+            json!({"update_config": {"config": {"rate": "0.6"}}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            admin,
+            json!({"replace_config": {"config": {"rate": "0.6"}}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            admin,
+            json!({"refresh_denom_info": {}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            admin,
+            json!({"update_admin": {"admin": sender}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            admin,
+            json!({"access": {"propose_admin": {"admin": sender}}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            admin,
+            json!({"set_authz_enabled": {"enabled": true}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            admin,
+            json!({"emit_expected_grants": {}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            admin,
+            json!({"health_check": {}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            admin,
+            json!({"set_delegation": {"delegate": sender, "delegation": null}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            admin,
+            json!({"set_notify_target": {"target": null}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            admin,
+            json!({"pause": {"reason": "incident", "message": null}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            admin,
+            json!({"update_metadata": {"metadata": null}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            admin,
+            json!({"batch": {"ops": [{"update_metadata": {"metadata": null}}]}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            admin,
+            json!({"set_quorum": {"members": [sender], "threshold": 1}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            sender,
+            json!({"propose_action": {"action": {"update_admin": {"admin": sender}}}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            sender,
+            json!({"approve_action": {"id": 1}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            sender,
+            json!({"execute_action": {"id": 1}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            admin,
+            json!({"set_auction": {"auction": null}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            admin,
+            json!({"set_successor": {"addr": null}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            admin,
+            json!({"record_unsolicited": {"depositor": sender, "amount": one}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            admin,
+            json!({"return_unsolicited": {"depositor": sender, "denom": DEFAULT_SOURCE_DENOM}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            admin,
+            json!({"burn_residual": {}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            admin,
+            json!({"mint_rounding_reserve": {"recipient": null}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            sender,
+            json!({"claim_refund": {}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            sender,
+            json!({"deposit": {}}),
+            funds.clone(),
+            WhenPaused::Allowed,
+        ),
+        (
+            sender,
+            json!({"withdraw": {"amount": "1"}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            admin,
+            json!({"fund_float": {}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            admin,
+            json!({"withdraw_float": {"amount": coin(1, DEFAULT_TARGET_DENOM)}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            sender,
+            json!({"cancel_stream": {"id": 1}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            sender,
+            json!({"cancel_commitment": {}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            sender,
+            json!({"cancel_auction_commitment": {"round": 0}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            sender,
+            json!({"settle_auction": {}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            sender,
+            json!({"migrate_my_position": {"streams": null}}),
+            vec![],
+            WhenPaused::Allowed,
+        ),
+        (
+            sender,
+            json!({"convert_with_signature": {
+                "payload": {"owner": sender, "amount": one, "nonce": 0, "deadline": "0"},
+                "signature": "",
+                "pubkey": "",
+            }}),
+            vec![],
+            WhenPaused::Rejected,
+        ),
+        (
+            sender,
+            json!({"start_streaming_convert": {"total": "1000", "duration": 10}}),
+            funds.clone(),
+            WhenPaused::Rejected,
+        ),
+        (
+            sender,
+            json!({"claim_stream": {"id": 1}}),
+            vec![],
+            WhenPaused::Rejected,
+        ),
+        (
+            sender,
+            json!({"commit_convert": {"hash": "AA=="}}),
+            funds.clone(),
+            WhenPaused::Rejected,
+        ),
+        (
+            sender,
+            json!({"reveal_convert": {"amount": "1", "salt": ""}}),
+            vec![],
+            WhenPaused::Rejected,
+        ),
+        (
+            sender,
+            json!({"auction_commit": {}}),
+            funds.clone(),
+            WhenPaused::Rejected,
+        ),
+        (
+            sender,
+            json!({"claim_auction": {"round": 0}}),
+            vec![],
+            WhenPaused::Rejected,
+        ),
+    ];
+    #[cfg(feature = "ibc")]
+    messages.push((
+        sender,
+        json!({"transfer_remote": {"channel_id": "channel-0", "receiver": sender, "timeout_seconds": null}}),
+        funds.clone(),
+        WhenPaused::Allowed,
+    ));
+    messages
+}
+
+// Names of the execute variants of the build, from its schema
+fn variants() -> BTreeSet<String> {
+    let schema = serde_json::to_value(schema_for!(ExecuteMsg)).unwrap();
+    schema["oneOf"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|variant| variant["required"][0].as_str().unwrap().to_string())
+        .collect()
+}
+
+// The specification must cover every execute message, new ones included
+#[test]
+fn messages_cover_every_variant() {
+    let covered: BTreeSet<_> = messages()
+        .into_iter()
+        .map(|(_, msg, _, _)| msg.as_object().unwrap().keys().next().unwrap().clone())
+        .collect();
+    assert_eq!(covered, variants());
+}
+
+#[rstest]
+fn every_message_in_every_state(
+    #[values(State::Active, State::Paused, State::Frozen, State::FrozenPaused)] state: State,
+) {
+    for (sender, msg, funds, when_paused) in messages() {
+        let (mut app, code_id) = setup_with_funds();
+        let contract_addr = instantiate_default(&mut app, code_id);
+        state.enter(&mut app, &contract_addr);
+
+        let res = app.execute_contract(Addr::unchecked(sender), contract_addr, &msg, &funds);
+        let text = res.err().map(|err| format!("{err:#}")).unwrap_or_default();
+        match state.rejects(when_paused) {
+            Some(expected) => assert!(
+                text.contains(expected),
+                "{state:?} should reject {msg} with {expected:?}, got {text:?}"
+            ),
+            None => assert!(
+                !text.contains(CONTRACT_FROZEN) && !text.contains(CONTRACT_PAUSED),
+                "{state:?} should allow {msg}, got {text:?}"
+            ),
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use crate::common::*;
+use cosmwasm_std::Addr;
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn metadata() -> Value {
+    json!({
+        "label": "MFX to PWR",
+        "description": "Converts MFX to PWR at a fixed rate",
+        "url": "https://manifest.network",
+    })
+}
+
+fn metadata_with(field: &str, value: Value) -> Value {
+    let mut metadata = metadata();
+    metadata[field] = value;
+    metadata
+}
+
+fn create_msg_update_metadata(metadata: Value) -> Value {
+    json!({"update_metadata": {"metadata": metadata}})
+}
+
+fn query_contract_info(app: &AppAccepting, contract_addr: &Addr) -> Value {
+    app.wrap()
+        .query_wasm_smart(contract_addr, &json!({"contract_info": {}}))
+        .unwrap()
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::full(metadata(), Expect::Ok)]
+#[case::label_only(json!({"label": "converter"}), Expect::Ok)]
+#[case::empty_label(metadata_with("label", json!(" ")), Expect::ErrContains(INVALID_LABEL))]
+#[case::long_label(metadata_with("label", json!("a".repeat(65))), Expect::ErrContains(INVALID_LABEL))]
+#[case::long_description(metadata_with("description", json!("a".repeat(513))), Expect::ErrContains(INVALID_DESCRIPTION))]
+#[case::url_scheme(metadata_with("url", json!("ipfs://manifest")), Expect::ErrContains(INVALID_URL))]
+#[case::url_whitespace(metadata_with("url", json!("https://manifest .network")), Expect::ErrContains(INVALID_URL))]
+#[case::missing_label(json!({"url": "https://manifest.network"}), Expect::ErrContains("missing field `label`"))]
+fn instantiate_metadata(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] metadata: Value,
+    #[case] expect: Expect<'_>,
+) {
+    let (app, code_id) = setup_with_funds;
+    run_instantiate(
+        app,
+        code_id,
+        default_sender(),
+        &modify_instantiate(Field::Metadata, metadata),
+        &[],
+        expect,
+    );
+}
+
+#[rstest]
+fn query_contract_info_metadata(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    assert_eq!(
+        query_contract_info(&app, &contract_addr),
+        json!({
+            "contract_name": "manifest/converter",
+            "contract_version": env!("CARGO_PKG_VERSION"),
+            "api_version": API_VERSION,
+            "metadata": null,
+        })
+    );
+
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract_addr.as_str(),
+        &create_msg_update_metadata(metadata()),
+        &[],
+        Expect::Ok,
+    );
+    assert_eq!(
+        query_contract_info(&app, &contract_addr)["metadata"],
+        metadata()
+    );
+
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract_addr.as_str(),
+        &create_msg_update_metadata(Value::Null),
+        &[],
+        Expect::Ok,
+    );
+    assert_eq!(
+        query_contract_info(&app, &contract_addr)["metadata"],
+        Value::Null
+    );
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::unauthorized(DEFAULT_SENDER, create_msg_update_metadata(metadata()), Expect::ErrContains(ONLY_ADMIN))]
+#[case::invalid(default_admin(), create_msg_update_metadata(metadata_with("label", json!(""))), Expect::ErrContains(INVALID_LABEL))]
+fn update_metadata_invalid(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] exec_sender: &str,
+    #[case] exec_msg: Value,
+    #[case] expect: Expect<'_>,
+) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        exec_sender,
+        &exec_msg,
+        &[],
+        expect,
+    );
+}
@@ -0,0 +1,158 @@
+use crate::common::*;
+use cosmwasm_std::Addr;
+use cw_multi_test::Executor;
+use rstest::*;
+use serde_json::json;
+
+mod common;
+
+// Raw storage keys of the current and legacy config namespaces
+const CONFIG_KEY: &[u8] = b"config_v2";
+const LEGACY_CONFIG_KEY: &[u8] = b"config";
+
+fn instantiate_migratable(app: &mut AppAccepting, code_id: u64) -> Addr {
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(default_sender()),
+        &default_instantiate(),
+        &[],
+        "converter",
+        Some(default_sender().to_string()),
+    )
+    .expect("failed to instantiate")
+}
+
+// Rewrite the stored config into the legacy namespace, as a pre-v2 deployment would have it
+fn move_config_to_legacy(app: &mut AppAccepting, contract_addr: &Addr) {
+    let mut storage = app.contract_storage_mut(contract_addr);
+    let raw = storage.get(CONFIG_KEY).expect("config not found");
+    storage.remove(CONFIG_KEY);
+    storage.set(LEGACY_CONFIG_KEY, &raw);
+}
+
+#[rstest]
+#[case::legacy_layout(true, "true")]
+#[case::current_layout(false, "false")]
+fn migrate_config_namespace(
+    setup: (AppAccepting, u64),
+    #[case] legacy: bool,
+    #[case] migrated: &str,
+) {
+    let (mut app, code_id) = setup;
+    let contract_addr = instantiate_migratable(&mut app, code_id);
+    if legacy {
+        move_config_to_legacy(&mut app, &contract_addr);
+    }
+
+    // The config is readable before migrating, whatever the layout
+    let query_msg = json!({"config": {}});
+    let res: serde_json::Value = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &query_msg)
+        .unwrap();
+    assert_eq!(res, default_config());
+
+    let res = app
+        .migrate_contract(
+            Addr::unchecked(default_sender()),
+            contract_addr.clone(),
+            &json!({}),
+            code_id,
+        )
+        .expect("failed to migrate");
+    assert!(
+        res.has_event(&cosmwasm_std::Event::new("wasm").add_attribute("config_migrated", migrated))
+    );
+
+    let storage = app.contract_storage(&contract_addr);
+    assert!(storage.get(LEGACY_CONFIG_KEY).is_none());
+    assert!(storage.get(CONFIG_KEY).is_some());
+    drop(storage);
+
+    let res: serde_json::Value = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &query_msg)
+        .unwrap();
+    assert_eq!(res, default_config());
+}
+
+#[cfg(feature = "history")]
+#[rstest]
+fn migrate_seeds_admin_history(setup: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup;
+    let contract_addr = instantiate_migratable(&mut app, code_id);
+
+    // Drop the history, as a deployment predating it would have none
+    let mut storage = app.contract_storage_mut(&contract_addr);
+    let mut entry_key = b"\x00\x0dadmin_history".to_vec();
+    entry_key.extend_from_slice(&1u64.to_be_bytes());
+    storage.remove(&entry_key);
+    storage.remove(b"admin_change_count");
+    drop(storage);
+
+    app.migrate_contract(
+        Addr::unchecked(default_sender()),
+        contract_addr.clone(),
+        &json!({}),
+        code_id,
+    )
+    .expect("failed to migrate");
+
+    let res: serde_json::Value = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &json!({"admin_history": {}}))
+        .unwrap();
+    let items = res["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["admin"], DEFAULT_POA_ADMIN);
+}
+
+#[cfg(feature = "history")]
+#[rstest]
+fn migrate_records_upgrade(setup: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup;
+    let contract_addr = instantiate_migratable(&mut app, code_id);
+    let version = env!("CARGO_PKG_VERSION");
+
+    let res = app
+        .migrate_contract(
+            Addr::unchecked(default_sender()),
+            contract_addr.clone(),
+            &json!({}),
+            code_id,
+        )
+        .expect("failed to migrate");
+    assert!(res.has_event(
+        &cosmwasm_std::Event::new("wasm-migrated")
+            .add_attribute("from_version", version)
+            .add_attribute("to_version", version)
+            .add_attribute("initiator", default_sender())
+    ));
+
+    advance_blocks(&mut app, 1);
+    app.migrate_contract(
+        Addr::unchecked(default_sender()),
+        contract_addr.clone(),
+        &json!({}),
+        code_id,
+    )
+    .expect("failed to migrate");
+
+    let height = app.block_info().height;
+    let res: serde_json::Value = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &json!({"upgrade_history": {}}))
+        .unwrap();
+    let upgrade = |height: u64| {
+        json!({
+            "from_version": version,
+            "to_version": version,
+            "height": height,
+            "initiator": default_sender(),
+        })
+    };
+    assert_eq!(
+        res,
+        json!({"items": [upgrade(height - 1), upgrade(height)], "next_key": null})
+    );
+}
@@ -0,0 +1,212 @@
+use crate::common::*;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, Event, MessageInfo, Response,
+    StdError, StdResult,
+};
+use cw_multi_test::{ContractWrapper, Executor, Stargate};
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn target_instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+fn target_execute(_: DepsMut, _: Env, _: MessageInfo, msg: Value) -> StdResult<Response> {
+    // Only alerts are expected
+    if msg.get("converter_alert").is_none() {
+        return Err(StdError::msg("unexpected message"));
+    }
+    Ok(Response::default())
+}
+
+fn failing_execute(_: DepsMut, _: Env, _: MessageInfo, _: Value) -> StdResult<Response> {
+    Err(StdError::msg("target failed"))
+}
+
+fn target_query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+    to_json_binary(&Empty {})
+}
+
+// Instantiate a notify target, accepting alerts or failing on all of them
+fn instantiate_target<S: Stargate>(app: &mut TestApp<S>, failing: bool) -> Addr {
+    let execute = if failing {
+        failing_execute
+    } else {
+        target_execute
+    };
+    let code_id = app.store_code(Box::new(ContractWrapper::new_with_empty(
+        execute,
+        target_instantiate,
+        target_query,
+    )));
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(default_sender()),
+        &Empty {},
+        &[],
+        "target",
+        None,
+    )
+    .expect("failed to instantiate target")
+}
+
+fn create_msg_set_notify_target(target: Option<&str>) -> Value {
+    json!({"set_notify_target": {"target": target}})
+}
+
+fn alerted(res: &cw_multi_test::AppResponse, target: &Addr) -> bool {
+    res.has_event(&Event::new("execute").add_attribute("_contract_address", target.as_str()))
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::unauthorized(DEFAULT_SENDER, false, Expect::ErrContains(ONLY_ADMIN))]
+#[case::not_contract(default_admin(), true, Expect::ErrContains(NOTIFY_TARGET_NOT_CONTRACT))]
+fn set_notify_target_invalid(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] exec_sender: &str,
+    #[case] account_target: bool,
+    #[case] expect: Expect<'_>,
+) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let target = if account_target {
+        make_addr("account")
+    } else {
+        instantiate_target(&mut app, false)
+    };
+    run_execute(
+        &mut app,
+        exec_sender,
+        contract_addr.as_str(),
+        &create_msg_set_notify_target(Some(target.as_str())),
+        &[],
+        expect,
+    );
+}
+
+#[rstest]
+fn set_and_clear_notify_target(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let target = instantiate_target(&mut app, false);
+
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract_addr.as_str(),
+        &create_msg_set_notify_target(Some(target.as_str())),
+        &[],
+        Expect::Ok,
+    );
+    let query: Value = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &json!({"notify_target": {}}))
+        .unwrap();
+    assert_eq!(query, json!(target));
+
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract_addr.as_str(),
+        &create_msg_set_notify_target(None),
+        &[],
+        Expect::Ok,
+    );
+    let query: Value = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &json!({"notify_target": {}}))
+        .unwrap();
+    assert_eq!(query, Value::Null);
+
+    // Without a target nothing is alerted
+    let res = app
+        .wasm_sudo(contract_addr.clone(), &json!({"freeze": {}}))
+        .unwrap();
+    assert!(!alerted(&res, &target));
+}
+
+#[rstest]
+fn freeze_alerts_target(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let target = instantiate_target(&mut app, false);
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract_addr.as_str(),
+        &create_msg_set_notify_target(Some(target.as_str())),
+        &[],
+        Expect::Ok,
+    );
+
+    let res = app
+        .wasm_sudo(contract_addr.clone(), &json!({"freeze": {}}))
+        .unwrap();
+    assert!(alerted(&res, &target));
+    let res = app
+        .wasm_sudo(contract_addr.clone(), &json!({"unfreeze": {}}))
+        .unwrap();
+    assert!(alerted(&res, &target));
+}
+
+#[rstest]
+fn failing_target_does_not_revert(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let target = instantiate_target(&mut app, true);
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract_addr.as_str(),
+        &create_msg_set_notify_target(Some(target.as_str())),
+        &[],
+        Expect::Ok,
+    );
+
+    let res = app
+        .wasm_sudo(contract_addr.clone(), &json!({"freeze": {}}))
+        .unwrap();
+    assert!(res.has_event(&Event::new("wasm").add_attribute("action", "notify_failed")));
+    let frozen: bool = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &json!({"frozen": {}}))
+        .unwrap();
+    assert!(frozen);
+}
+
+#[rstest]
+fn failed_conversion_alerts_target(setup_failing_stargate: (AppFailing, u64)) {
+    let (mut app, code_id) = setup_failing_stargate;
+    let converter = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &default_instantiate(),
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+    let target = instantiate_target(&mut app, false);
+    app.execute_contract(
+        Addr::unchecked(default_admin()),
+        converter.clone(),
+        &create_msg_set_notify_target(Some(target.as_str())),
+        &[],
+    )
+    .unwrap();
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(default_sender()),
+            converter,
+            &default_convert(),
+            &[default_convert_amount()],
+        )
+        .unwrap();
+    assert!(res.has_event(&Event::new("wasm").add_attribute("action", "fail_conversion")));
+    assert!(alerted(&res, &target));
+}
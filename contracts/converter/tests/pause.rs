@@ -0,0 +1,113 @@
+use crate::common::*;
+use cosmwasm_std::Addr;
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn create_msg_pause(reason: &str, message: Option<&str>) -> Value {
+    json!({"pause": {"reason": reason, "message": message}})
+}
+
+fn query_status(app: &AppAccepting, contract_addr: &Addr) -> Value {
+    app.wrap()
+        .query_wasm_smart(contract_addr, &json!({"status": {}}))
+        .unwrap()
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::maintenance(default_admin(), create_msg_pause("maintenance", Some("upgrading the relayer")), Expect::Ok)]
+#[case::no_message(default_admin(), create_msg_pause("incident", None), Expect::Ok)]
+#[case::unauthorized(DEFAULT_SENDER, create_msg_pause("maintenance", None), Expect::ErrContains(MISSING_PAUSER_ROLE))]
+#[case::long_message(default_admin(), create_msg_pause("other", Some(&"a".repeat(257))), Expect::ErrContains(INVALID_PAUSE_MESSAGE))]
+#[case::unknown_reason(default_admin(), create_msg_pause("holiday", None), Expect::ErrContains("unknown variant `holiday`"))]
+fn execute_pause(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] exec_sender: &str,
+    #[case] exec_msg: Value,
+    #[case] expect: Expect<'_>,
+) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        exec_sender,
+        &exec_msg,
+        &[],
+        expect,
+    );
+}
+
+#[rstest]
+fn pause_reason_in_error_and_status(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let contract = contract_addr.as_str();
+    assert_eq!(
+        query_status(&app, &contract_addr),
+        json!({"paused": false, "frozen": false, "authz_enabled": true, "pause": null})
+    );
+
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_pause("incident", Some("investigating a relayer outage")),
+        &[],
+        Expect::Ok,
+    );
+    let since = app.block_info().time.nanos().to_string();
+    assert_eq!(
+        query_status(&app, &contract_addr),
+        json!({
+            "paused": true,
+            "frozen": false,
+            "authz_enabled": true,
+            "pause": {"reason": "incident", "message": "investigating a relayer outage", "since": since},
+        })
+    );
+    run_execute(
+        &mut app,
+        default_sender(),
+        contract,
+        &default_convert(),
+        &[default_convert_amount()],
+        Expect::ErrContains("contract is paused (incident: investigating a relayer outage)"),
+    );
+
+    // Updating the reason keeps the start of the pause
+    advance_seconds(&mut app, 60);
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_pause("maintenance", None),
+        &[],
+        Expect::Ok,
+    );
+    assert_eq!(
+        query_status(&app, &contract_addr)["pause"],
+        json!({"reason": "maintenance", "since": since})
+    );
+
+    // Unpausing through a config update clears the reason
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_update_config(Field::Paused, false),
+        &[],
+        Expect::Ok,
+    );
+    assert_eq!(query_status(&app, &contract_addr)["pause"], Value::Null);
+    run_execute(
+        &mut app,
+        default_sender(),
+        contract,
+        &default_convert(),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+}
@@ -0,0 +1,45 @@
+use converter::prelude::*;
+use cosmwasm_std::Uint256;
+use serde_json::{json, to_value};
+
+// Downstream contracts build messages from the prelude alone
+#[test]
+fn prelude_builds_messages() {
+    let msg = ExecuteMsg::Batch {
+        ops: vec![
+            AdminOp::UpdateConfig {
+                config: Box::new(UpdateConfig {
+                    rate_strategy: Some(RateStrategy::Tiered {
+                        tiers: vec![RateTier {
+                            min_amount: Uint256::from(1_000u32),
+                            rate: Rate::parse("0.6").unwrap(),
+                        }],
+                    }),
+                    ..Default::default()
+                }),
+                strict: None,
+            },
+            AdminOp::Pause {
+                reason: PauseReason::Maintenance,
+                message: None,
+            },
+        ],
+    };
+    assert_eq!(
+        to_value(&msg).unwrap(),
+        json!({"batch": {"ops": [
+            {"update_config": {"config": {"rate_strategy": {"tiered": {"tiers": [{"min_amount": "1000", "rate": "0.6"}]}}}}},
+            {"pause": {"reason": "maintenance", "message": null}},
+        ]}})
+    );
+}
+
+// Public enums are non-exhaustive, errors are matched by variant with a fallback
+#[test]
+fn prelude_errors_are_matchable() {
+    let err = Rate::parse("0").unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::RateError(RateError::InvalidRateZero)
+    ));
+}
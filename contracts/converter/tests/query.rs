@@ -1,6 +1,9 @@
 use crate::common::*;
+use converter::consts;
+use converter::msg::ExecuteMsg;
+use cosmwasm_std::{coin, Addr, AnyMsg, Coin, DenomMetadata, DenomUnit, StdResult};
 use rstest::*;
-use serde_json::to_value;
+use serde_json::{json, to_value, Value};
 
 mod common;
 
@@ -54,3 +57,538 @@ fn query_admin(setup_with_funds: (AppAccepting, u64), #[case] admin: &str) {
         .unwrap();
     assert_eq!(res, serde_json::json!({"admin": default_admin()}));
 }
+
+#[rstest]
+#[case::raw(json!({}), json!({
+    "display_rate": "0.5", "raw_rate": "0.5", "source_symbol": DEFAULT_SOURCE_DENOM, "target_symbol": DEFAULT_TARGET_DENOM,
+    "source_exponent": 0, "target_exponent": 0, "display": "1 umfx = 0.5 upwr",
+}))]
+#[case::display(json!({"source_exponent": 6, "target_exponent": 18, "source_symbol": "MFX", "target_symbol": "PWR"}), json!({
+    "display_rate": "0.5", "raw_rate": "500000000000", "source_symbol": "MFX", "target_symbol": "PWR",
+    "source_exponent": 6, "target_exponent": 18, "display": "1 MFX = 0.5 PWR",
+}))]
+fn query_display_rate(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] config: serde_json::Value,
+    #[case] expected: serde_json::Value,
+) {
+    let (app, contract_addr, _code_id) = prepare_and_execute(
+        setup_with_funds,
+        default_admin(),
+        &default_instantiate(),
+        &[],
+        default_admin(),
+        &create_msg_update_config_from_config(&config),
+        &[],
+        Expect::Ok,
+    );
+
+    let res: serde_json::Value = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &json!({"display_rate": {}}))
+        .unwrap();
+    assert_eq!(res, expected);
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::source_symbol("1.5mfx", Ok(coin(1_500_000, DEFAULT_SOURCE_DENOM)))]
+#[case::target_symbol("2 PWR", Ok(coin(2_000_000_000_000_000_000, DEFAULT_TARGET_DENOM)))]
+#[case::base_units("1500umfx", Ok(coin(1_500, DEFAULT_SOURCE_DENOM)))]
+#[case::too_many_decimals("1.0000001mfx", Err("failed to parse amount"))]
+#[case::unknown_unit("1.5atom", Err("unknown amount unit"))]
+fn query_parse_amount(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] input: &str,
+    #[case] expected: Result<Coin, &str>,
+) {
+    let config = json!({"source_exponent": 6, "target_exponent": 18, "source_symbol": "MFX", "target_symbol": "PWR"});
+    let (app, contract_addr, _code_id) = prepare_and_execute(
+        setup_with_funds,
+        default_admin(),
+        &default_instantiate(),
+        &[],
+        default_admin(),
+        &create_msg_update_config_from_config(&config),
+        &[],
+        Expect::Ok,
+    );
+
+    let res = app
+        .wrap()
+        .query_wasm_smart::<serde_json::Value>(contract_addr, &json!({"parse_amount": {"input": input}}));
+    match expected {
+        Ok(amount) => assert_eq!(res.unwrap(), json!({"amount": amount})),
+        Err(err) => assert!(res.unwrap_err().to_string().contains(err)),
+    }
+}
+
+#[rstest]
+fn query_metrics(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr, _code_id) = prepare_and_execute(
+        setup_with_funds,
+        default_admin(),
+        &default_instantiate(),
+        &[],
+        default_sender(),
+        &default_convert(),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract_addr.as_str(),
+        &create_msg_update_config(Field::Paused, true),
+        &[],
+        Expect::Ok,
+    );
+
+    let res: serde_json::Value = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &json!({"metrics": {}}))
+        .unwrap();
+    assert_eq!(
+        res,
+        json!({"metrics": {
+            "authz_enabled": "1",
+            "burned_total": "1000",
+            "conversions_total": "1",
+            "epoch_burned": "1000",
+            "epoch_conversions": "1",
+            "epoch_id": "0",
+            "epoch_minted": "500",
+            "frozen": "0",
+            "minted_total": "500",
+            "paused": "1",
+            "rate": "0.5",
+            "rounding_reserve": "0",
+        }})
+    );
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::empty(json!({}), Ok(default_config()))]
+#[case::rate(json!({"rate": "1.5"}), Ok(modify_config(Field::Rate, "1.5")))]
+#[case::paused(json!({"paused": true}), Ok(modify_config(Field::Paused, true)))]
+#[case::invalid_rate(json!({"rate": "abc"}), Err(RATE_PARSE_FAILED))]
+#[case::same_denom(json!({"target_denom": DEFAULT_SOURCE_DENOM}), Err(SAME_DENOM))]
+fn query_validate_config_update(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] update: serde_json::Value,
+    #[case] expected: Result<serde_json::Value, &str>,
+) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let res = app.wrap().query_wasm_smart::<serde_json::Value>(
+        &contract_addr,
+        &json!({"validate_config_update": {"config": update}}),
+    );
+    match expected {
+        Ok(config) => assert_eq!(res.unwrap(), config),
+        Err(msg) => assert!(res.unwrap_err().to_string().contains(msg)),
+    }
+
+    // The stored config is left untouched
+    let res: serde_json::Value = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &json!({"config": {}}))
+        .unwrap();
+    assert_eq!(res, default_config());
+}
+
+#[rstest]
+fn query_config_diff(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let query = |proposed: serde_json::Value| -> serde_json::Value {
+        app.wrap()
+            .query_wasm_smart(
+                &contract_addr,
+                &json!({"config_diff": {"proposed": proposed}}),
+            )
+            .unwrap()
+    };
+
+    // Unchanged fields are left out
+    let res = query(json!({"rate": "1.5", "paused": false, "epoch_length": 86_400}));
+    assert_eq!(
+        res,
+        json!({
+            "changes": [
+                {"field": "rate", "old": "0.5", "new": "1.5"},
+                {"field": "epoch_length", "old": null, "new": "86400"},
+            ],
+            "error": null,
+        })
+    );
+
+    // Invalid updates still list their changes
+    let res = query(json!({"target_denom": DEFAULT_SOURCE_DENOM}));
+    assert_eq!(
+        res,
+        json!({
+            "changes": [
+                {"field": "target_denom", "old": DEFAULT_TARGET_DENOM, "new": DEFAULT_SOURCE_DENOM},
+            ],
+            "error": format!("configuration error: {SAME_DENOM}"),
+        })
+    );
+}
+
+#[rstest]
+fn query_defaults(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let res: serde_json::Value = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &json!({"defaults": {}}))
+        .unwrap();
+    assert_eq!(
+        res,
+        json!({
+            "contract_name": "manifest/converter",
+            "contract_version": env!("CARGO_PKG_VERSION"),
+            "profile": consts::PROFILE,
+            "poa_admin": consts::DEFAULT_POA_ADMIN,
+            "source_denom": "umfx",
+            "target_denom": format!("factory/{}/upwr", consts::DEFAULT_POA_ADMIN),
+            "bech32_prefix": "manifest",
+        })
+    );
+}
+
+#[rstest]
+fn query_gas_hints(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let res: serde_json::Value = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &json!({"gas_hints": {}}))
+        .unwrap();
+    assert_eq!(res["per_recipient"], json!(50_000));
+    let hints = res["hints"].as_array().unwrap();
+    assert!(hints.contains(&json!({"message": "convert", "gas": 300_000})));
+    // Every hint names an execute message, once
+    let names: Vec<&str> = hints
+        .iter()
+        .map(|hint| hint["message"].as_str().unwrap())
+        .collect();
+    let mut unique = names.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(unique.len(), names.len());
+    for name in names {
+        let msg = json!({ name: {} });
+        let err = serde_json::from_value::<ExecuteMsg>(msg).err();
+        assert!(
+            err.is_none_or(|err| !err.to_string().contains("unknown variant")),
+            "{name} is not an execute message"
+        );
+    }
+}
+
+#[rstest]
+fn query_error_catalog(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let res: serde_json::Value = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &json!({"error_catalog": {}}))
+        .unwrap();
+    let errors = res["errors"].as_array().unwrap();
+    for expected in [
+        json!({"code": 1005, "message": "unauthorized: sender does not have the {0} role"}),
+        json!({"code": 2004, "message": format!("invalid amount: {NON_PAYABLE}")}),
+        json!({"code": 6001, "message": format!("conversion error: {INVALID_FUNDS}")}),
+        json!({"code": 7001, "message": format!("configuration error: {SAME_DENOM}")}),
+        json!({"code": 18001, "message": CONTRACT_PAUSED}),
+        json!({"code": 18002, "message": CONTRACT_FROZEN}),
+    ] {
+        assert!(errors.contains(&expected), "missing {expected}");
+    }
+    // Codes are unique, and listed in order
+    let codes: Vec<u64> = errors
+        .iter()
+        .map(|entry| entry["code"].as_u64().unwrap())
+        .collect();
+    assert!(codes.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+fn set_pwr_metadata(app: &mut AppAccepting) {
+    app.init_modules(|router, _, storage| {
+        router.bank.set_denom_metadata(
+            storage,
+            DEFAULT_TARGET_DENOM.to_string(),
+            DenomMetadata {
+                base: DEFAULT_TARGET_DENOM.to_string(),
+                display: "pwr".to_string(),
+                symbol: "PWR".to_string(),
+                denom_units: vec![
+                    DenomUnit {
+                        denom: DEFAULT_TARGET_DENOM.to_string(),
+                        exponent: 0,
+                        aliases: vec![],
+                    },
+                    DenomUnit {
+                        denom: "pwr".to_string(),
+                        exponent: 6,
+                        aliases: vec![],
+                    },
+                ],
+                ..Default::default()
+            },
+        )
+    })
+    .unwrap();
+}
+
+fn query_denom_info(
+    app: &AppAccepting,
+    contract_addr: &Addr,
+    denom: &str,
+) -> StdResult<serde_json::Value> {
+    app.wrap()
+        .query_wasm_smart(contract_addr, &json!({"denom_info": {"denom": denom}}))
+}
+
+#[rstest]
+fn query_denom_info_cached(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    set_pwr_metadata(&mut app);
+    let contract_addr = instantiate_default(&mut app, code_id);
+
+    assert_eq!(
+        query_denom_info(&app, &contract_addr, DEFAULT_TARGET_DENOM).unwrap(),
+        json!({"denom": DEFAULT_TARGET_DENOM, "symbol": "PWR", "exponent": 6})
+    );
+    // Denoms without metadata are cached too
+    assert_eq!(
+        query_denom_info(&app, &contract_addr, DEFAULT_SOURCE_DENOM).unwrap(),
+        json!({"denom": DEFAULT_SOURCE_DENOM, "symbol": null, "exponent": null})
+    );
+    assert!(query_denom_info(&app, &contract_addr, DUMMY_DENOM).is_err());
+}
+
+#[rstest]
+fn refresh_denom_info(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    set_pwr_metadata(&mut app);
+
+    // The metadata set after instantiate is only picked up once refreshed
+    assert_eq!(
+        query_denom_info(&app, &contract_addr, DEFAULT_TARGET_DENOM).unwrap()["symbol"],
+        serde_json::Value::Null
+    );
+    let refresh = json!({"refresh_denom_info": {}});
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &refresh,
+        &[],
+        Expect::ErrContains(ONLY_ADMIN),
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_POA_ADMIN,
+        contract_addr.as_str(),
+        &refresh,
+        &[],
+        Expect::Ok,
+    );
+    assert_eq!(
+        query_denom_info(&app, &contract_addr, DEFAULT_TARGET_DENOM).unwrap()["symbol"],
+        "PWR"
+    );
+
+    // Denoms no longer configured are dropped
+    run_execute(
+        &mut app,
+        DEFAULT_POA_ADMIN,
+        contract_addr.as_str(),
+        &create_msg_update_config(Field::SourceDenom, "uatom"),
+        &[],
+        Expect::Ok,
+    );
+    assert!(query_denom_info(&app, &contract_addr, DEFAULT_SOURCE_DENOM).is_err());
+    assert!(query_denom_info(&app, &contract_addr, "uatom").is_ok());
+}
+
+#[rstest]
+fn query_simulate_execution(setup_with_funds: (AppAccepting, u64)) {
+    use converter::msg::SimulateExecutionResponse;
+    use cw_multi_test::Executor;
+    use manifest_std::cosmos::authz::v1beta1::MsgExec;
+    use manifest_std::cosmos::bank::v1beta1::MsgSend;
+    use manifest_std::liftedinit::manifest::v1::MsgBurnHeldBalance;
+    use manifest_std::osmosis::tokenfactory::v1beta1::MsgMint;
+    use prost::Message;
+
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &modify_instantiate(Field::FlatFee, coin(10, DUMMY_DENOM)),
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+    let res: SimulateExecutionResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &contract_addr,
+            &json!({"simulate_execution": {"sender": DEFAULT_SENDER, "amount": "1000"}}),
+        )
+        .unwrap();
+    assert_eq!(res.minted, coin(500, DEFAULT_TARGET_DENOM));
+
+    // The MsgExec wraps the authz messages, followed by the flat fee transfer
+    let type_urls = |msgs: &[AnyMsg]| msgs.iter().map(|m| m.type_url.clone()).collect::<Vec<_>>();
+    assert_eq!(type_urls(&res.msgs), [MsgExec::TYPE_URL, MsgSend::TYPE_URL]);
+    assert_eq!(
+        type_urls(&res.authz_msgs),
+        [
+            MsgSend::TYPE_URL,
+            MsgBurnHeldBalance::TYPE_URL,
+            MsgMint::TYPE_URL
+        ]
+    );
+    let exec = MsgExec::decode(res.msgs[0].value.as_slice()).unwrap();
+    assert_eq!(exec.grantee, contract_addr.as_str());
+    assert_eq!(exec.msgs.len(), res.authz_msgs.len());
+    for (wrapped, msg) in exec.msgs.iter().zip(&res.authz_msgs) {
+        assert_eq!(wrapped.value, msg.value.as_slice());
+    }
+
+    let fee = MsgSend::decode(res.msgs[1].value.as_slice()).unwrap();
+    assert_eq!(fee.from_address, contract_addr.as_str());
+    assert_eq!(fee.to_address, DEFAULT_POA_ADMIN);
+    assert_eq!(fee.amount[0].amount, "10");
+    let mint = MsgMint::decode(res.authz_msgs[2].value.as_slice()).unwrap();
+    assert_eq!(mint.sender, DEFAULT_POA_ADMIN);
+    assert_eq!(mint.mint_to_address, DEFAULT_SENDER);
+    assert_eq!(mint.amount.unwrap().amount, "500");
+
+    let err = app
+        .wrap()
+        .query_wasm_smart::<SimulateExecutionResponse>(
+            &contract_addr,
+            &json!({"simulate_execution": {"sender": DEFAULT_SENDER, "amount": "0"}}),
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains(AMOUNT_IS_ZERO));
+}
+
+#[rstest]
+fn query_simulate_execution_community_pool(setup_with_funds: (AppAccepting, u64)) {
+    use converter::msg::SimulateExecutionResponse;
+    use cw_multi_test::Executor;
+    use manifest_std::cosmos::distribution::v1beta1::MsgFundCommunityPool;
+    use prost::Message;
+
+    let (mut app, code_id) = setup_with_funds;
+    let mut msg = modify_instantiate(Field::FlatFee, coin(10, DUMMY_DENOM));
+    msg["fees_to_community_pool"] = json!(true);
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &msg,
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+    let res: SimulateExecutionResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &contract_addr,
+            &json!({"simulate_execution": {"sender": DEFAULT_SENDER, "amount": "1000"}}),
+        )
+        .unwrap();
+    assert_eq!(res.msgs[1].type_url, MsgFundCommunityPool::TYPE_URL);
+    let fee = MsgFundCommunityPool::decode(res.msgs[1].value.as_slice()).unwrap();
+    assert_eq!(fee.depositor, contract_addr.as_str());
+    assert_eq!(fee.amount[0].amount, "10");
+    assert_eq!(fee.amount[0].denom, DUMMY_DENOM);
+}
+
+// The amount found mints at least the desired amount, one unit less does not
+#[rstest]
+#[case::default(Field::FeeModel, json!(null), "501", 1_002, None)]
+#[case::exact(Field::FeeModel, json!(null), "500", 1_000, None)]
+#[case::percent_fee(Field::FeeModel, json!({"percent": {"percent": "1"}}), "495", 999, Some(9))]
+#[case::flat_fee(Field::FeeModel, json!({"flat": {"amount": "25"}}), "500", 1_025, Some(25))]
+#[case::tiered_rate(Field::RateStrategy, json!({"tiered": {"tiers": [{"min_amount": "1000", "rate": "0.6"}]}}), "600", 1_000, None)]
+fn query_reverse_simulate(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] field: Field,
+    #[case] value: Value,
+    #[case] desired: &str,
+    #[case] amount: u128,
+    #[case] fee: Option<u128>,
+) {
+    use cw_multi_test::Executor;
+
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &modify_instantiate(field, value),
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+    let res: Value = app
+        .wrap()
+        .query_wasm_smart(
+            &contract_addr,
+            &json!({"reverse_simulate": {"desired": desired}}),
+        )
+        .unwrap();
+    assert_eq!(res["amount"], json!(coin(amount, DEFAULT_SOURCE_DENOM)));
+    assert_eq!(
+        res["fee"],
+        json!(fee.map(|fee| coin(fee, DEFAULT_SOURCE_DENOM)))
+    );
+    assert_eq!(res["flat_fee"], json!(null));
+
+    let minted = |amount: u128| -> Option<u128> {
+        let res: Value = app
+            .wrap()
+            .query_wasm_smart(
+                &contract_addr,
+                &json!({"simulate_execution": {"sender": DEFAULT_SENDER, "amount": amount.to_string()}}),
+            )
+            .ok()?;
+        res["minted"]["amount"].as_str()?.parse().ok()
+    };
+    let desired: u128 = desired.parse().unwrap();
+    assert_eq!(
+        res["minted"]["amount"],
+        json!(minted(amount).unwrap().to_string())
+    );
+    assert!(minted(amount).unwrap() >= desired);
+    assert!(minted(amount - 1).is_none_or(|minted| minted < desired));
+}
+
+#[rstest]
+fn query_reverse_simulate_zero(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let err = app
+        .wrap()
+        .query_wasm_smart::<Value>(
+            &contract_addr,
+            &json!({"reverse_simulate": {"desired": "0"}}),
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains(AMOUNT_IS_ZERO));
+}
@@ -0,0 +1,429 @@
+use crate::common::*;
+use cosmwasm_std::Addr;
+use rstest::*;
+use serde_json::{json, Value};
+use std::sync::OnceLock;
+
+mod common;
+
+fn members() -> Vec<String> {
+    vec![
+        make_addr("member1").to_string(),
+        make_addr("member2").to_string(),
+        make_addr("member3").to_string(),
+    ]
+}
+
+fn member(i: usize) -> String {
+    members()[i].clone()
+}
+
+fn create_msg_set_quorum(members: Vec<String>, threshold: u32) -> Value {
+    json!({"set_quorum": {"members": members, "threshold": threshold}})
+}
+
+fn create_msg_propose_update_admin(admin: &str) -> Value {
+    json!({"propose_action": {"action": {"update_admin": {"admin": admin}}}})
+}
+
+fn create_msg_approve(id: u64) -> Value {
+    json!({"approve_action": {"id": id}})
+}
+
+fn create_msg_execute(id: u64) -> Value {
+    json!({"execute_action": {"id": id}})
+}
+
+static QUORUM_SETUP: OnceLock<(Snapshot, Addr)> = OnceLock::new();
+
+// Instantiate the contract and configure a 2-of-3 quorum, once for all the tests
+fn setup_quorum() -> (AppAccepting, Addr) {
+    let (app, _, contract_addr) = cached_setup(&QUORUM_SETUP, |app, code_id| {
+        let contract_addr = instantiate_default(app, code_id);
+        run_execute(
+            app,
+            default_admin(),
+            contract_addr.as_str(),
+            &create_msg_set_quorum(members(), 2),
+            &[],
+            Expect::Ok,
+        );
+        contract_addr
+    });
+    (app, contract_addr)
+}
+
+fn query_admin(app: &AppAccepting, contract_addr: &Addr) -> Value {
+    app.wrap()
+        .query_wasm_smart(contract_addr, &json!({"admin": {}}))
+        .unwrap()
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::ok(default_admin(), create_msg_set_quorum(members(), 2), Expect::Ok)]
+#[case::unauthorized(DEFAULT_SENDER, create_msg_set_quorum(members(), 2), Expect::ErrContains(ONLY_ADMIN))]
+#[case::no_members(default_admin(), create_msg_set_quorum(vec![], 1), Expect::ErrContains(QUORUM_NO_MEMBERS))]
+#[case::duplicate_member(default_admin(), create_msg_set_quorum(vec![member(0), member(0)], 1), Expect::ErrContains(QUORUM_DUPLICATE_MEMBER))]
+#[case::zero_threshold(default_admin(), create_msg_set_quorum(members(), 0), Expect::ErrContains(QUORUM_INVALID_THRESHOLD))]
+#[case::threshold_too_high(default_admin(), create_msg_set_quorum(members(), 4), Expect::ErrContains(QUORUM_INVALID_THRESHOLD))]
+#[case::invalid_member(default_admin(), create_msg_set_quorum(vec!["invalid".to_string()], 1), Expect::ErrContains(PARSE_FAILED))]
+fn set_quorum(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] exec_sender: &str,
+    #[case] exec_msg: Value,
+    #[case] expect: Expect<'_>,
+) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        exec_sender,
+        &exec_msg,
+        &[],
+        expect,
+    );
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::set_quorum_twice(default_admin(), create_msg_set_quorum(members(), 1), Expect::ErrContains(QUORUM_ALREADY_CONFIGURED))]
+#[case::update_admin(default_admin(), create_msg_update_admin(Some(DEFAULT_SENDER)), Expect::ErrContains(QUORUM_APPROVAL_REQUIRED))]
+#[case::propose_admin(default_admin(), json!({"access": {"propose_admin": {"admin": DEFAULT_SENDER}}}), Expect::ErrContains(QUORUM_APPROVAL_REQUIRED))]
+#[case::propose_not_member(DEFAULT_SENDER, create_msg_propose_update_admin(DEFAULT_SENDER), Expect::ErrContains(QUORUM_NOT_MEMBER))]
+#[case::propose_invalid_admin(&member(0), create_msg_propose_update_admin("invalid"), Expect::ErrContains(PARSE_FAILED))]
+#[case::approve_not_found(&member(0), create_msg_approve(1), Expect::ErrContains(QUORUM_ACTION_NOT_FOUND))]
+#[case::execute_not_found(&member(0), create_msg_execute(1), Expect::ErrContains(QUORUM_ACTION_NOT_FOUND))]
+fn quorum_configured(
+    #[case] exec_sender: &str,
+    #[case] exec_msg: Value,
+    #[case] expect: Expect<'_>,
+) {
+    let (mut app, contract_addr) = setup_quorum();
+    run_execute(
+        &mut app,
+        exec_sender,
+        contract_addr.as_str(),
+        &exec_msg,
+        &[],
+        expect,
+    );
+}
+
+#[rstest]
+fn propose_without_quorum(setup_with_funds: (AppAccepting, u64)) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        default_admin(),
+        &create_msg_propose_update_admin(DEFAULT_SENDER),
+        &[],
+        Expect::ErrContains(QUORUM_NOT_CONFIGURED),
+    );
+}
+
+#[rstest]
+fn quorum_update_admin() {
+    let (mut app, contract_addr) = setup_quorum();
+    let contract = contract_addr.as_str();
+
+    run_execute(
+        &mut app,
+        &member(0),
+        contract,
+        &create_msg_propose_update_admin(DEFAULT_SENDER),
+        &[],
+        Expect::Ok,
+    );
+
+    // The proposer already approved, one more approval is required
+    run_execute(
+        &mut app,
+        &member(0),
+        contract,
+        &create_msg_approve(1),
+        &[],
+        Expect::ErrContains(QUORUM_ALREADY_APPROVED),
+    );
+    run_execute(
+        &mut app,
+        &member(0),
+        contract,
+        &create_msg_execute(1),
+        &[],
+        Expect::ErrContains(QUORUM_NOT_ENOUGH_APPROVALS),
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_approve(1),
+        &[],
+        Expect::ErrContains(QUORUM_NOT_MEMBER),
+    );
+    run_execute(
+        &mut app,
+        &member(1),
+        contract,
+        &create_msg_approve(1),
+        &[],
+        Expect::Ok,
+    );
+
+    let pending: Value = app
+        .wrap()
+        .query_wasm_smart(contract, &json!({"pending_actions": {}}))
+        .unwrap();
+    assert_eq!(
+        pending,
+        json!({"actions": [{
+            "id": 1,
+            "action": {"update_admin": {"admin": DEFAULT_SENDER}},
+            "proposer": member(0),
+            "approvals": [member(0), member(1)],
+        }]})
+    );
+
+    run_execute(
+        &mut app,
+        &member(2),
+        contract,
+        &create_msg_execute(1),
+        &[],
+        Expect::Ok,
+    );
+    assert_eq!(
+        query_admin(&app, &contract_addr),
+        json!({"admin": DEFAULT_SENDER})
+    );
+
+    // Executed actions are removed
+    run_execute(
+        &mut app,
+        &member(2),
+        contract,
+        &create_msg_execute(1),
+        &[],
+        Expect::ErrContains(QUORUM_ACTION_NOT_FOUND),
+    );
+    let pending: Value = app
+        .wrap()
+        .query_wasm_smart(contract, &json!({"pending_actions": {}}))
+        .unwrap();
+    assert_eq!(pending, json!({"actions": []}));
+}
+
+#[rstest]
+fn quorum_update_quorum() {
+    let (mut app, contract_addr) = setup_quorum();
+    let contract = contract_addr.as_str();
+
+    let new_members = vec![member(0), DEFAULT_SENDER.to_string()];
+    let propose = json!({"propose_action": {"action": {"update_quorum": {"members": new_members, "threshold": 1}}}});
+    run_execute(&mut app, &member(0), contract, &propose, &[], Expect::Ok);
+    run_execute(
+        &mut app,
+        &member(1),
+        contract,
+        &create_msg_approve(1),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        &member(0),
+        contract,
+        &create_msg_execute(1),
+        &[],
+        Expect::Ok,
+    );
+
+    let quorum: Value = app
+        .wrap()
+        .query_wasm_smart(contract, &json!({"quorum": {}}))
+        .unwrap();
+    assert_eq!(quorum, json!({"members": new_members, "threshold": 1}));
+
+    // Removed members lose their rights, new members can act alone with a threshold of 1
+    run_execute(
+        &mut app,
+        &member(1),
+        contract,
+        &create_msg_propose_update_admin(DEFAULT_SENDER),
+        &[],
+        Expect::ErrContains(QUORUM_NOT_MEMBER),
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_propose_update_admin(DEFAULT_SENDER),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_execute(2),
+        &[],
+        Expect::Ok,
+    );
+    assert_eq!(
+        query_admin(&app, &contract_addr),
+        json!({"admin": DEFAULT_SENDER})
+    );
+}
+
+fn create_msg_propose_update_rate_bounds(floor: Option<&str>, ceiling: Option<&str>) -> Value {
+    json!({"propose_action": {"action": {"update_rate_bounds": {"rate_floor": floor, "rate_ceiling": ceiling}}}})
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::floor_above_rate(create_msg_propose_update_rate_bounds(Some("0.6"), None), Expect::ErrContains(RATE_OUT_OF_BOUNDS))]
+#[case::ceiling_below_rate(create_msg_propose_update_rate_bounds(None, Some("0.4")), Expect::ErrContains(RATE_OUT_OF_BOUNDS))]
+#[case::inverted(create_msg_propose_update_rate_bounds(Some("2"), Some("1")), Expect::ErrContains(INVALID_RATE_BOUNDS))]
+#[case::invalid(create_msg_propose_update_rate_bounds(Some("a"), None), Expect::ErrContains(RATE_PARSE_FAILED))]
+fn propose_invalid_rate_bounds(#[case] exec_msg: Value, #[case] expect: Expect<'_>) {
+    let (mut app, contract_addr) = setup_quorum();
+    run_execute(&mut app, &member(0), contract_addr.as_str(), &exec_msg, &[], expect);
+}
+
+#[rstest]
+fn quorum_update_rate_bounds() {
+    let (mut app, contract_addr) = setup_quorum();
+    let contract = contract_addr.as_str();
+
+    run_execute(
+        &mut app,
+        &member(0),
+        contract,
+        &create_msg_propose_update_rate_bounds(Some("0.4"), Some("0.6")),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        &member(1),
+        contract,
+        &create_msg_approve(1),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        &member(0),
+        contract,
+        &create_msg_execute(1),
+        &[],
+        Expect::Ok,
+    );
+
+    let config: Value = app
+        .wrap()
+        .query_wasm_smart(contract, &json!({"config": {}}))
+        .unwrap();
+    assert_eq!(config["rate_floor"], json!("0.4"));
+    assert_eq!(config["rate_ceiling"], json!("0.6"));
+
+    // Routine rate updates by the admin must now stay within the bounds
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_update_config(Field::Rate, "0.7"),
+        &[],
+        Expect::ErrContains(RATE_OUT_OF_BOUNDS),
+    );
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_update_config(Field::Rate, "0.6"),
+        &[],
+        Expect::Ok,
+    );
+}
+
+// A quorum the admin can meet alone does not separate the bounds from routine updates
+#[rstest]
+fn quorum_update_rate_bounds_admin_only(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let contract = contract_addr.as_str();
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_set_quorum(vec![default_admin().to_string(), member(0)], 1),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_propose_update_rate_bounds(Some("0.1"), None),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_execute(1),
+        &[],
+        Expect::ErrContains(QUORUM_ADMIN_ONLY_APPROVAL),
+    );
+
+    // The approval of another member lets the bounds through
+    run_execute(
+        &mut app,
+        &member(0),
+        contract,
+        &create_msg_approve(1),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        &member(0),
+        contract,
+        &create_msg_execute(1),
+        &[],
+        Expect::Ok,
+    );
+
+    // Other actions only need the threshold
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_propose_update_admin(&member(1)),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_execute(2),
+        &[],
+        Expect::Ok,
+    );
+}
+
+#[rstest]
+fn query_quorum_unset(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let quorum: Value = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &json!({"quorum": {}}))
+        .unwrap();
+    assert_eq!(quorum, Value::Null);
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "quotas")]
+
+use crate::common::*;
+use cosmwasm_std::{coin, Addr};
+use cw_multi_test::Executor;
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+const DAY: u64 = 86_400;
+
+fn query_remaining_quota(app: &AppAccepting, contract_addr: &Addr, address: &str) -> Value {
+    app.wrap()
+        .query_wasm_smart(
+            contract_addr,
+            &json!({"remaining_quota": {"address": address}}),
+        )
+        .unwrap()
+}
+
+fn convert(app: &mut AppAccepting, contract_addr: &Addr, amount: u128, expect: Expect<'_>) {
+    run_execute(
+        app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &default_convert(),
+        &[coin(amount, DEFAULT_SOURCE_DENOM)],
+        expect,
+    );
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::ok(create_msg_update_config(Field::DailyQuota, "1000"), Expect::Ok)]
+#[case::zero(create_msg_update_config(Field::DailyQuota, "0"), Expect::ErrContains(INVALID_DAILY_QUOTA))]
+fn update_daily_quota(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] exec_msg: Value,
+    #[case] expect: Expect<'_>,
+) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        DEFAULT_POA_ADMIN,
+        &exec_msg,
+        &[],
+        expect,
+    );
+}
+
+#[rstest]
+fn daily_quota(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &modify_instantiate(Field::DailyQuota, "1500"),
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+    convert(&mut app, &contract_addr, 1_000, Expect::Ok);
+    convert(
+        &mut app,
+        &contract_addr,
+        501,
+        Expect::ErrContains(QUOTA_EXCEEDED),
+    );
+    convert(&mut app, &contract_addr, 500, Expect::Ok);
+
+    let res = query_remaining_quota(&app, &contract_addr, DEFAULT_SENDER);
+    let resets_at = (app.block_info().time.seconds() / DAY + 1) * DAY;
+    assert_eq!(
+        res,
+        json!({
+            "quota": "1500",
+            "used": "1500",
+            "remaining": "0",
+            "resets_at": (resets_at * 1_000_000_000).to_string(),
+        })
+    );
+    // Each sender has a quota of their own
+    let res = query_remaining_quota(&app, &contract_addr, DEFAULT_POA_ADMIN);
+    assert_eq!(res["remaining"], json!("1500"));
+
+    // The quota is restored at the start of the next day
+    let now = app.block_info().time.seconds();
+    advance_seconds(&mut app, resets_at - now);
+    let res = query_remaining_quota(&app, &contract_addr, DEFAULT_SENDER);
+    assert_eq!(res["used"], json!("0"));
+    assert_eq!(res["remaining"], json!("1500"));
+    convert(&mut app, &contract_addr, 1_500, Expect::Ok);
+}
+
+#[rstest]
+fn daily_quota_unset(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    convert(&mut app, &contract_addr, 1_000, Expect::Ok);
+    let res = query_remaining_quota(&app, &contract_addr, DEFAULT_SENDER);
+    assert_eq!(res["quota"], json!(null));
+    assert_eq!(res["remaining"], json!(null));
+    assert_eq!(res["used"], json!("0"));
+}
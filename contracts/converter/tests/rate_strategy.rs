@@ -0,0 +1,230 @@
+use crate::common::*;
+use cosmwasm_std::{
+    coin, to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
+    StdResult,
+};
+use cw_multi_test::{ContractWrapper, Executor};
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+// Oracle answering every pair with the rate it was instantiated with
+fn oracle_instantiate(deps: DepsMut, _: Env, _: MessageInfo, msg: Value) -> StdResult<Response> {
+    deps.storage
+        .set(b"rate", &serde_json::to_vec(&msg["rate"]).unwrap());
+    Ok(Response::default())
+}
+
+fn oracle_execute(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    Err(StdError::msg("unexpected message"))
+}
+
+fn oracle_query(deps: Deps, _: Env, msg: Value) -> StdResult<Binary> {
+    if msg["rate"]["source_denom"] != DEFAULT_SOURCE_DENOM {
+        return Err(StdError::msg("unexpected query"));
+    }
+    let rate: Value = serde_json::from_slice(&deps.storage.get(b"rate").unwrap()).unwrap();
+    to_json_binary(&json!({"rate": rate}))
+}
+
+fn instantiate_oracle(app: &mut AppAccepting, rate: &str) -> Addr {
+    let code_id = app.store_code(Box::new(ContractWrapper::new_with_empty(
+        oracle_execute,
+        oracle_instantiate,
+        oracle_query,
+    )));
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(default_sender()),
+        &json!({"rate": rate}),
+        &[],
+        "oracle",
+        None,
+    )
+    .expect("failed to instantiate oracle")
+}
+
+fn tiered() -> Value {
+    json!({"tiered": {"tiers": [
+        {"min_amount": "1000", "rate": "0.6"},
+        {"min_amount": "10000", "rate": "0.7"},
+    ]}})
+}
+
+fn instantiate_with_strategy(app: &mut AppAccepting, code_id: u64, strategy: Value) -> Addr {
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(default_sender()),
+        &modify_instantiate(Field::RateStrategy, strategy),
+        &[],
+        "converter",
+        None,
+    )
+    .unwrap()
+}
+
+fn query_current_rate(app: &AppAccepting, contract_addr: &Addr, amount: Option<&str>) -> Value {
+    app.wrap()
+        .query_wasm_smart(contract_addr, &json!({"current_rate": {"amount": amount}}))
+        .unwrap()
+}
+
+fn query_minted(app: &AppAccepting, contract_addr: &Addr, id: u64) -> Value {
+    let receipt: Value = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &json!({"conversion": {"id": id}}))
+        .unwrap();
+    json!({"minted": receipt["minted"], "rate": receipt["rate"]})
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::fixed(json!({"fixed": {}}), Expect::Ok)]
+#[case::tiered(tiered(), Expect::Ok)]
+#[case::tiers_empty(json!({"tiered": {"tiers": []}}), Expect::ErrContains(INVALID_TIERS))]
+#[case::tiers_unordered(json!({"tiered": {"tiers": [{"min_amount": "2", "rate": "0.6"}, {"min_amount": "1", "rate": "0.7"}]}}), Expect::ErrContains(INVALID_TIERS))]
+#[case::tier_zero(json!({"tiered": {"tiers": [{"min_amount": "0", "rate": "0.6"}]}}), Expect::ErrContains(INVALID_TIERS))]
+#[case::schedule_empty(json!({"scheduled": {"steps": []}}), Expect::ErrContains(INVALID_SCHEDULE))]
+#[case::curve_zero_volume(json!({"curve": {"end_rate": "1", "volume": "0"}}), Expect::ErrContains(INVALID_CURVE))]
+#[case::oracle_not_contract(json!({"oracle_backed": {"oracle": DEFAULT_SENDER}}), Expect::ErrContains(ORACLE_NOT_CONTRACT))]
+fn instantiate_rate_strategy(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] strategy: Value,
+    #[case] expect: Expect<'_>,
+) {
+    let (app, code_id) = setup_with_funds;
+    run_instantiate(
+        app,
+        code_id,
+        default_sender(),
+        &modify_instantiate(Field::RateStrategy, strategy),
+        &[],
+        expect,
+    );
+}
+
+#[rstest]
+fn convert_tiered(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_with_strategy(&mut app, code_id, tiered());
+
+    assert_eq!(
+        query_current_rate(&app, &contract_addr, None),
+        json!({"kind": "tiered", "rate": DEFAULT_RATE})
+    );
+    assert_eq!(
+        query_current_rate(&app, &contract_addr, Some("1000")),
+        json!({"kind": "tiered", "rate": "0.6"})
+    );
+
+    // The receipt records the resolved rate rather than the configured one
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &default_convert(),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+    assert_eq!(
+        query_minted(&app, &contract_addr, 1),
+        json!({"minted": coin(600, DEFAULT_TARGET_DENOM), "rate": "0.6"})
+    );
+}
+
+#[rstest]
+fn convert_curve(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let strategy = json!({"curve": {"end_rate": "1", "volume": "2000"}});
+    let contract_addr = instantiate_with_strategy(&mut app, code_id, strategy);
+
+    convert_consistently(
+        &mut app,
+        &contract_addr,
+        DEFAULT_SENDER,
+        &default_convert(),
+        &[default_convert_amount()],
+    );
+    assert_eq!(
+        query_minted(&app, &contract_addr, 1),
+        json!({"minted": coin(500, DEFAULT_TARGET_DENOM), "rate": DEFAULT_RATE})
+    );
+
+    // Half of the volume converted, the rate is halfway to the end rate
+    assert_eq!(
+        query_current_rate(&app, &contract_addr, None),
+        json!({"kind": "curve", "rate": "0.75"})
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &default_convert(),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+    assert_eq!(
+        query_minted(&app, &contract_addr, 2),
+        json!({"minted": coin(750, DEFAULT_TARGET_DENOM), "rate": "0.75"})
+    );
+}
+
+#[rstest]
+fn convert_oracle_backed(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let oracle = instantiate_oracle(&mut app, "0.8");
+    let contract_addr = instantiate_default(&mut app, code_id);
+
+    // Switched on through a config update like any other field
+    run_execute(
+        &mut app,
+        DEFAULT_POA_ADMIN,
+        contract_addr.as_str(),
+        &create_msg_update_config(
+            Field::RateStrategy,
+            json!({"oracle_backed": {"oracle": oracle}}),
+        ),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &default_convert(),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+    assert_eq!(
+        query_minted(&app, &contract_addr, 1),
+        json!({"minted": coin(800, DEFAULT_TARGET_DENOM), "rate": "0.8"})
+    );
+}
+
+#[rstest]
+fn convert_oracle_out_of_bounds(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let oracle = instantiate_oracle(&mut app, "2");
+    let mut msg = modify_instantiate(Field::RateCeiling, "1");
+    msg[Field::RateStrategy.as_ref()] = json!({"oracle_backed": {"oracle": oracle}});
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &msg,
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &default_convert(),
+        &[default_convert_amount()],
+        Expect::ErrContains(RATE_OUT_OF_BOUNDS),
+    );
+}
@@ -0,0 +1,122 @@
+use crate::common::*;
+use cosmwasm_std::{coin, Addr};
+use cw_multi_test::Executor;
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn query_report(
+    app: &AppAccepting,
+    contract_addr: &Addr,
+    from_height: u64,
+    to_height: u64,
+    pagination: Value,
+) -> Value {
+    app.wrap()
+        .query_wasm_smart(
+            contract_addr,
+            &json!({"report": {"from_height": from_height, "to_height": to_height, "pagination": pagination}}),
+        )
+        .unwrap()
+}
+
+// One conversion per block, with the flat fee, returning the height of the first one
+fn convert_over_blocks(app: &mut AppAccepting, contract_addr: &Addr, blocks: u64) -> u64 {
+    let start = app.block_info().height + 1;
+    for _ in 0..blocks {
+        advance_blocks(app, 1);
+        convert_consistently(
+            app,
+            contract_addr,
+            DEFAULT_SENDER,
+            &default_convert(),
+            &[default_convert_amount(), coin(10, DUMMY_DENOM)],
+        );
+    }
+    start
+}
+
+#[rstest]
+fn report_range(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &modify_instantiate(Field::FlatFee, coin(10, DUMMY_DENOM)),
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+    let start = convert_over_blocks(&mut app, &contract_addr, 4);
+
+    // The middle two blocks of the four
+    assert_eq!(
+        query_report(&app, &contract_addr, start + 1, start + 2, Value::Null),
+        json!({
+            "from_height": start + 1,
+            "to_height": start + 2,
+            "conversions": 2,
+            "burned": [coin(2_000, DEFAULT_SOURCE_DENOM)],
+            "minted": [coin(1_000, DEFAULT_TARGET_DENOM)],
+            "fees": [coin(20, DUMMY_DENOM)],
+            "failed": 0,
+            "senders": [DEFAULT_SENDER],
+            "next_key": null,
+        })
+    );
+
+    // Nothing was converted before the first block
+    let report = query_report(&app, &contract_addr, 0, start - 1, Value::Null);
+    assert_eq!(report["conversions"], json!(0));
+    assert_eq!(report["burned"], json!([]));
+}
+
+#[rstest]
+fn report_pages(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &modify_instantiate(Field::FlatFee, coin(10, DUMMY_DENOM)),
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+    let start = convert_over_blocks(&mut app, &contract_addr, 5);
+    let to_height = start + 3;
+
+    let first = query_report(&app, &contract_addr, start, to_height, json!({"limit": 3}));
+    assert_eq!(first["conversions"], json!(3));
+    assert!(first["next_key"].is_string());
+
+    // The last page stops at the end of the range, before the fifth conversion
+    let second = query_report(
+        &app,
+        &contract_addr,
+        start,
+        to_height,
+        json!({"start_after": first["next_key"], "limit": 3}),
+    );
+    assert_eq!(second["conversions"], json!(1));
+    assert_eq!(second["burned"], json!([coin(1_000, DEFAULT_SOURCE_DENOM)]));
+    assert_eq!(second["next_key"], Value::Null);
+}
+
+#[rstest]
+fn report_invalid_range(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let err = app
+        .wrap()
+        .query_wasm_smart::<Value>(
+            &contract_addr,
+            &json!({"report": {"from_height": 2, "to_height": 1}}),
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains(INVALID_REPORT_RANGE), "{err}");
+}
@@ -0,0 +1,166 @@
+use crate::common::*;
+use cosmwasm_std::{
+    coin, to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, Event, MessageInfo, Response,
+    StdError, StdResult,
+};
+use cw_multi_test::{ContractWrapper, Executor};
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+const TREASURY: &str = "treasury";
+
+// Name service answering the names it was instantiated with, and null for any other
+fn resolver_instantiate(deps: DepsMut, _: Env, _: MessageInfo, msg: Value) -> StdResult<Response> {
+    deps.storage
+        .set(b"names", &serde_json::to_vec(&msg["names"]).unwrap());
+    Ok(Response::default())
+}
+
+fn resolver_execute(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    Err(StdError::msg("unexpected message"))
+}
+
+fn resolver_query(deps: Deps, _: Env, msg: Value) -> StdResult<Binary> {
+    let Some(name) = msg["resolve"]["name"].as_str() else {
+        return Err(StdError::msg("unexpected query"));
+    };
+    let names: Value = serde_json::from_slice(&deps.storage.get(b"names").unwrap()).unwrap();
+    to_json_binary(&json!({"address": names.get(name)}))
+}
+
+fn notify_target_execute(_: DepsMut, _: Env, _: MessageInfo, _: Value) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+fn notify_target_query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+    to_json_binary(&Empty {})
+}
+
+fn instantiate_resolver(app: &mut AppAccepting, names: Value) -> Addr {
+    let code_id = app.store_code(Box::new(ContractWrapper::new_with_empty(
+        resolver_execute,
+        resolver_instantiate,
+        resolver_query,
+    )));
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(default_sender()),
+        &json!({"names": names}),
+        &[],
+        "resolver",
+        None,
+    )
+    .expect("failed to instantiate resolver")
+}
+
+fn instantiate_notify_target(app: &mut AppAccepting) -> Addr {
+    let code_id = app.store_code(Box::new(ContractWrapper::new_with_empty(
+        notify_target_execute,
+        resolver_instantiate,
+        notify_target_query,
+    )));
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(default_sender()),
+        &json!({}),
+        &[],
+        "target",
+        None,
+    )
+    .expect("failed to instantiate notify target")
+}
+
+// Converter resolving names through a resolver knowing the given names
+fn instantiate_with_resolver(app: &mut AppAccepting, code_id: u64, names: Value) -> Addr {
+    let resolver = instantiate_resolver(app, names);
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(default_sender()),
+        &modify_instantiate(Field::Resolver, resolver),
+        &[],
+        "converter",
+        None,
+    )
+    .unwrap()
+}
+
+fn create_msg_convert_split(recipients: &[(&str, u64)]) -> Value {
+    let recipients: Vec<_> = recipients
+        .iter()
+        .map(|(address, weight)| json!({"address": address, "weight": weight}))
+        .collect();
+    json!({"convert": {"recipients": recipients}})
+}
+
+#[rstest]
+fn instantiate_resolver_not_contract(setup_with_funds: (AppAccepting, u64)) {
+    let (app, code_id) = setup_with_funds;
+    run_instantiate(
+        app,
+        code_id,
+        default_sender(),
+        &modify_instantiate(Field::Resolver, DEFAULT_SENDER),
+        &[],
+        Expect::ErrContains(RESOLVER_NOT_CONTRACT),
+    );
+}
+
+#[rstest]
+fn convert_to_resolved_names(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let treasury = make_addr(TREASURY).to_string();
+    let contract_addr =
+        instantiate_with_resolver(&mut app, code_id, json!({TREASURY: treasury.clone()}));
+
+    // Names and addresses can be mixed, only names are recorded as resolved
+    let res = convert_consistently(
+        &mut app,
+        &contract_addr,
+        DEFAULT_SENDER,
+        &create_msg_convert_split(&[(TREASURY, 1), (DEFAULT_SENDER, 1)]),
+        &[default_convert_amount()],
+    );
+    assert!(res.has_event(
+        &Event::new("wasm").add_attribute("resolved", format!("{TREASURY}={treasury}"))
+    ));
+    let receipt: Value = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &json!({"conversion": {"id": 1}}))
+        .unwrap();
+    assert_eq!(
+        receipt["mints"][0],
+        json!({"recipient": treasury, "amount": coin(250, DEFAULT_TARGET_DENOM)})
+    );
+
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &create_msg_convert_split(&[("nobody", 1)]),
+        &[default_convert_amount()],
+        Expect::ErrContains(UNRESOLVED_NAME),
+    );
+}
+
+#[rstest]
+fn set_notify_target_by_name(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let target = instantiate_notify_target(&mut app);
+    let contract_addr = instantiate_with_resolver(&mut app, code_id, json!({"alerts": target}));
+
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract_addr.as_str(),
+        &json!({"set_notify_target": {"target": "alerts"}}),
+        &[],
+        Expect::Ok,
+    );
+    let res: Value = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &json!({"notify_target": {}}))
+        .unwrap();
+    assert_eq!(res, json!(target));
+}
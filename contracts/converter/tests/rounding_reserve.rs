@@ -0,0 +1,102 @@
+use crate::common::*;
+use cosmwasm_std::{coin, Addr, Event};
+use cw_multi_test::Executor;
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn create_msg_mint_rounding_reserve(recipient: Option<&str>) -> Value {
+    json!({"mint_rounding_reserve": {"recipient": recipient}})
+}
+
+fn query_rounding_reserve(app: &AppAccepting, contract_addr: &Addr) -> Value {
+    let res: Value = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &json!({"metrics": {}}))
+        .unwrap();
+    res["metrics"]["rounding_reserve"].clone()
+}
+
+// Each conversion of 1000 source tokens at this rate floors 333.3 target tokens
+fn instantiate_lossy(app: &mut AppAccepting, code_id: u64) -> Addr {
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(default_sender()),
+        &modify_instantiate(Field::Rate, "0.3333"),
+        &[],
+        "converter",
+        None,
+    )
+    .unwrap()
+}
+
+fn convert_times(app: &mut AppAccepting, contract_addr: &Addr, times: usize) {
+    for _ in 0..times {
+        convert_consistently(
+            app,
+            contract_addr,
+            DEFAULT_SENDER,
+            &default_convert(),
+            &[default_convert_amount()],
+        );
+    }
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::admin(default_admin(), create_msg_mint_rounding_reserve(None), Expect::ErrContains(NOTHING_TO_MINT))]
+#[case::unauthorized(DEFAULT_SENDER, create_msg_mint_rounding_reserve(None), Expect::ErrContains(ONLY_ADMIN))]
+#[case::invalid_recipient(default_admin(), create_msg_mint_rounding_reserve(Some("invalid")), Expect::ErrContains(PARSE_FAILED))]
+fn execute_mint_rounding_reserve(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] exec_sender: &str,
+    #[case] exec_msg: Value,
+    #[case] expect: Expect<'_>,
+) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        exec_sender,
+        &exec_msg,
+        &[],
+        expect,
+    );
+}
+
+#[rstest]
+fn rounding_reserve_accumulates(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_lossy(&mut app, code_id);
+
+    convert_times(&mut app, &contract_addr, 3);
+    assert_eq!(query_rounding_reserve(&app, &contract_addr), json!("0.9"));
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract_addr.as_str(),
+        &create_msg_mint_rounding_reserve(None),
+        &[],
+        Expect::ErrContains(NOTHING_TO_MINT),
+    );
+
+    // The whole base unit is minted, the fraction left stays in the reserve
+    convert_times(&mut app, &contract_addr, 1);
+    let res = app
+        .execute_contract(
+            Addr::unchecked(default_admin()),
+            contract_addr.clone(),
+            &create_msg_mint_rounding_reserve(Some(DEFAULT_SENDER)),
+            &[],
+        )
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("recipient", DEFAULT_SENDER)
+            .add_attribute("minted", coin(1, DEFAULT_TARGET_DENOM).to_string())
+            .add_attribute("remaining", "0.2")
+    ));
+    assert_eq!(query_rounding_reserve(&app, &contract_addr), json!("0.2"));
+}
@@ -0,0 +1,358 @@
+use crate::common::*;
+use converter::msg::{ConversionSignDoc, SignedConversion};
+use cosmwasm_std::{coin, to_json_vec, Addr, Binary, Coin, Event, Timestamp};
+use cw_multi_test::Executor;
+use k256::ecdsa::signature::hazmat::PrehashSigner;
+use k256::ecdsa::{Signature, SigningKey};
+use rstest::*;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+mod common;
+
+fn signing_key(seed: u8) -> SigningKey {
+    SigningKey::from_slice(&[seed; 32]).unwrap()
+}
+
+fn pubkey(key: &SigningKey) -> Binary {
+    Binary::from(key.verifying_key().to_encoded_point(true).as_bytes())
+}
+
+fn relayer() -> String {
+    make_addr("relayer").to_string()
+}
+
+fn payload(amount: Coin, nonce: u64, deadline: Timestamp) -> SignedConversion {
+    SignedConversion {
+        owner: DEFAULT_SENDER.to_string(),
+        amount,
+        nonce,
+        deadline,
+    }
+}
+
+fn sign(
+    app: &AppAccepting,
+    contract_addr: &Addr,
+    payload: &SignedConversion,
+    key: &SigningKey,
+) -> Binary {
+    let sign_doc = ConversionSignDoc {
+        chain_id: app.block_info().chain_id,
+        contract: contract_addr.to_string(),
+        payload: payload.clone(),
+    };
+    let hash = Sha256::digest(to_json_vec(&sign_doc).unwrap());
+    let signature: Signature = key.sign_prehash(&hash).unwrap();
+    Binary::from(signature.to_bytes().as_slice())
+}
+
+fn create_msg_deposit(pubkey: &Binary) -> Value {
+    json!({"deposit": {"pubkey": pubkey}})
+}
+
+fn create_msg_convert_with_signature(
+    payload: &SignedConversion,
+    signature: &Binary,
+    pubkey: &Binary,
+) -> Value {
+    json!({"convert_with_signature": {"payload": payload, "signature": signature, "pubkey": pubkey}})
+}
+
+fn query_deposit(app: &AppAccepting, contract_addr: &Addr) -> Value {
+    app.wrap()
+        .query_wasm_smart(
+            contract_addr,
+            &json!({"deposit": {"address": DEFAULT_SENDER}}),
+        )
+        .unwrap()
+}
+
+// Instantiate the contract and deposit the default convert amount for the default sender
+fn setup_deposit(setup_with_funds: (AppAccepting, u64)) -> (AppAccepting, Addr) {
+    let (app, contract_addr, _) = prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        default_sender(),
+        &create_msg_deposit(&pubkey(&signing_key(1))),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+    (app, contract_addr)
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::ok(create_msg_deposit(&pubkey(&signing_key(1))), vec![default_convert_amount()], Expect::Ok)]
+#[case::uncompressed(json!({"deposit": {"pubkey": Binary::from(signing_key(1).verifying_key().to_encoded_point(false).as_bytes())}}), vec![default_convert_amount()], Expect::Ok)]
+#[case::invalid_pubkey(json!({"deposit": {"pubkey": Binary::from([2u8; 32])}}), vec![default_convert_amount()], Expect::ErrContains(INVALID_PUBKEY))]
+#[case::no_funds(create_msg_deposit(&pubkey(&signing_key(1))), vec![], Expect::ErrContains(INVALID_FUNDS))]
+#[case::wrong_denom(create_msg_deposit(&pubkey(&signing_key(1))), vec![coin(1_000, DUMMY_DENOM)], Expect::ErrContains(INVALID_SOURCE_DENOM))]
+fn deposit(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] exec_msg: Value,
+    #[case] funds: Vec<Coin>,
+    #[case] expect: Expect<'_>,
+) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        default_sender(),
+        &exec_msg,
+        &funds,
+        expect,
+    );
+}
+
+#[rstest]
+fn deposit_excluded_from_unsolicited(setup_with_funds: (AppAccepting, u64)) {
+    let (app, contract_addr) = setup_deposit(setup_with_funds);
+    assert_eq!(
+        query_deposit(&app, &contract_addr),
+        json!({"amount": default_convert_amount(), "pubkey": pubkey(&signing_key(1)), "nonce": 0})
+    );
+    let unsolicited: Value = app
+        .wrap()
+        .query_wasm_smart(
+            &contract_addr,
+            &json!({"unsolicited_balance": {"denom": DEFAULT_SOURCE_DENOM}}),
+        )
+        .unwrap();
+    assert_eq!(unsolicited["unrecorded"], json!("0"));
+}
+
+#[rstest]
+fn convert_with_signature(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr) = setup_deposit(setup_with_funds);
+    let key = signing_key(1);
+    let deadline = app.block_info().time.plus_seconds(60);
+    let payload = payload(coin(600, DEFAULT_SOURCE_DENOM), 0, deadline);
+    let signature = sign(&app, &contract_addr, &payload, &key);
+    let msg = create_msg_convert_with_signature(&payload, &signature, &pubkey(&key));
+
+    let res = app
+        .execute_contract(Addr::unchecked(relayer()), contract_addr.clone(), &msg, &[])
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "convert")
+            .add_attribute("sender", DEFAULT_SENDER)
+            .add_attribute("burned", "600")
+            .add_attribute("minted", "300")
+            .add_attribute("relayer", relayer())
+            .add_attribute("nonce", "0")
+    ));
+    assert_eq!(
+        query_deposit(&app, &contract_addr),
+        json!({"amount": coin(400, DEFAULT_SOURCE_DENOM), "pubkey": pubkey(&key), "nonce": 1})
+    );
+
+    // The nonce was consumed, the same signature cannot be replayed
+    run_execute(
+        &mut app,
+        &relayer(),
+        contract_addr.as_str(),
+        &msg,
+        &[],
+        Expect::ErrContains(INVALID_NONCE),
+    );
+
+    // The remaining deposit does not cover another conversion of the same amount
+    let payload = payload_with_nonce(&payload, 1);
+    let signature = sign(&app, &contract_addr, &payload, &key);
+    run_execute(
+        &mut app,
+        &relayer(),
+        contract_addr.as_str(),
+        &create_msg_convert_with_signature(&payload, &signature, &pubkey(&key)),
+        &[],
+        Expect::ErrContains(INSUFFICIENT_DEPOSIT),
+    );
+}
+
+fn payload_with_nonce(payload: &SignedConversion, nonce: u64) -> SignedConversion {
+    SignedConversion {
+        nonce,
+        ..payload.clone()
+    }
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::wrong_signer(signing_key(2), signing_key(1), 0, 60, Expect::ErrContains(INVALID_SIGNATURE))]
+#[case::pubkey_mismatch(signing_key(2), signing_key(2), 0, 60, Expect::ErrContains(PUBKEY_MISMATCH))]
+#[case::invalid_nonce(signing_key(1), signing_key(1), 1, 60, Expect::ErrContains(INVALID_NONCE))]
+#[case::expired(signing_key(1), signing_key(1), 0, 0, Expect::ErrContains(SIGNATURE_EXPIRED))]
+fn convert_with_signature_invalid(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] signer: SigningKey,
+    #[case] key: SigningKey,
+    #[case] nonce: u64,
+    #[case] validity: u64,
+    #[case] expect: Expect<'_>,
+) {
+    let (mut app, contract_addr) = setup_deposit(setup_with_funds);
+    let deadline = app.block_info().time.plus_seconds(validity);
+    let payload = payload(default_convert_amount(), nonce, deadline);
+    let signature = sign(&app, &contract_addr, &payload, &signer);
+    run_execute(
+        &mut app,
+        &relayer(),
+        contract_addr.as_str(),
+        &create_msg_convert_with_signature(&payload, &signature, &pubkey(&key)),
+        &[],
+        expect,
+    );
+}
+
+#[rstest]
+fn convert_with_signature_no_deposit(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let key = signing_key(1);
+    let payload = payload(
+        default_convert_amount(),
+        0,
+        app.block_info().time.plus_seconds(60),
+    );
+    let signature = sign(&app, &contract_addr, &payload, &key);
+    run_execute(
+        &mut app,
+        &relayer(),
+        contract_addr.as_str(),
+        &create_msg_convert_with_signature(&payload, &signature, &pubkey(&key)),
+        &[],
+        Expect::ErrContains(NO_DEPOSIT),
+    );
+}
+
+// Relayed conversions are held to the minimum conversion amount like direct ones
+#[rstest]
+fn convert_with_signature_min_amount(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr, _) = prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &modify_instantiate(Field::MinConvertAmount, "700"),
+        &[],
+        default_sender(),
+        &create_msg_deposit(&pubkey(&signing_key(1))),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+    let key = signing_key(1);
+    let deadline = app.block_info().time.plus_seconds(60);
+    let payload = payload(coin(600, DEFAULT_SOURCE_DENOM), 0, deadline);
+    let signature = sign(&app, &contract_addr, &payload, &key);
+    run_execute(
+        &mut app,
+        &relayer(),
+        contract_addr.as_str(),
+        &create_msg_convert_with_signature(&payload, &signature, &pubkey(&key)),
+        &[],
+        Expect::ErrContains(BELOW_MIN_AMOUNT),
+    );
+
+    let payload = payload_with_nonce(
+        &SignedConversion {
+            amount: coin(700, DEFAULT_SOURCE_DENOM),
+            ..payload
+        },
+        0,
+    );
+    let signature = sign(&app, &contract_addr, &payload, &key);
+    run_execute(
+        &mut app,
+        &relayer(),
+        contract_addr.as_str(),
+        &create_msg_convert_with_signature(&payload, &signature, &pubkey(&key)),
+        &[],
+        Expect::Ok,
+    );
+}
+
+// Relayed conversions count against the daily quota of the deposit owner
+#[cfg(feature = "quotas")]
+#[rstest]
+fn convert_with_signature_daily_quota(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr, _) = prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &modify_instantiate(Field::DailyQuota, "500"),
+        &[],
+        default_sender(),
+        &create_msg_deposit(&pubkey(&signing_key(1))),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+    let key = signing_key(1);
+    let deadline = app.block_info().time.plus_seconds(60);
+    let payload = payload(coin(600, DEFAULT_SOURCE_DENOM), 0, deadline);
+    let signature = sign(&app, &contract_addr, &payload, &key);
+    run_execute(
+        &mut app,
+        &relayer(),
+        contract_addr.as_str(),
+        &create_msg_convert_with_signature(&payload, &signature, &pubkey(&key)),
+        &[],
+        Expect::ErrContains(QUOTA_EXCEEDED),
+    );
+}
+
+#[rstest]
+fn withdraw_keeps_nonce(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr) = setup_deposit(setup_with_funds);
+    let contract = contract_addr.as_str();
+    let key = signing_key(1);
+    let payload = payload(
+        default_convert_amount(),
+        0,
+        app.block_info().time.plus_seconds(60),
+    );
+    let signature = sign(&app, &contract_addr, &payload, &key);
+
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &json!({"withdraw": {"amount": "1000"}}),
+        &[],
+        Expect::Ok,
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance(DEFAULT_SENDER, DEFAULT_SOURCE_DENOM)
+            .unwrap(),
+        coin(1_000_000, DEFAULT_SOURCE_DENOM)
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &json!({"withdraw": {"amount": "1000"}}),
+        &[],
+        Expect::ErrContains(INSUFFICIENT_DEPOSIT),
+    );
+
+    // The nonce survives the withdrawal, an unused signature remains valid for a new deposit
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_deposit(&pubkey(&key)),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+    assert_eq!(query_deposit(&app, &contract_addr)["nonce"], json!(0));
+    run_execute(
+        &mut app,
+        &relayer(),
+        contract,
+        &create_msg_convert_with_signature(&payload, &signature, &pubkey(&key)),
+        &[],
+        Expect::Ok,
+    );
+}
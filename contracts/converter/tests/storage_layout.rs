@@ -0,0 +1,77 @@
+use crate::common::*;
+use cosmwasm_std::Addr;
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn query_storage_layout(app: &AppAccepting, contract_addr: &Addr) -> Value {
+    app.wrap()
+        .query_wasm_smart(contract_addr, &json!({"storage_layout": {}}))
+        .unwrap()
+}
+
+fn entry(layout: &Value, name: &str) -> Value {
+    layout["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|entry| entry["name"] == name)
+        .cloned()
+        .unwrap_or_else(|| panic!("{name} missing from the layout"))
+}
+
+// Key of a map entry with a single component: the length-prefixed namespace, then the
+// component as is
+fn map_key(namespace: &str, component: &[u8]) -> Vec<u8> {
+    let mut key = (namespace.len() as u16).to_be_bytes().to_vec();
+    key.extend_from_slice(namespace.as_bytes());
+    key.extend_from_slice(component);
+    key
+}
+
+#[rstest]
+fn storage_layout_decodes_raw_state(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &default_convert(),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+    let layout = query_storage_layout(&app, &contract_addr);
+
+    let config = entry(&layout, "CONFIG");
+    assert_eq!(
+        config,
+        json!({"name": "CONFIG", "namespace": "config_v2", "kind": "item", "key": [], "value": "Config"})
+    );
+    let raw = app
+        .wrap()
+        .query_wasm_raw(
+            &contract_addr,
+            config["namespace"].as_str().unwrap().as_bytes(),
+        )
+        .unwrap()
+        .unwrap();
+    let stored: Value = serde_json::from_slice(&raw).unwrap();
+    assert_eq!(stored["rate"], json!(DEFAULT_RATE));
+
+    let conversions = entry(&layout, "CONVERSIONS");
+    assert_eq!(conversions["kind"], json!("map"));
+    assert_eq!(conversions["key"], json!(["u64"]));
+    let key = map_key(
+        conversions["namespace"].as_str().unwrap(),
+        &1u64.to_be_bytes(),
+    );
+    let raw = app
+        .wrap()
+        .query_wasm_raw(&contract_addr, key)
+        .unwrap()
+        .unwrap();
+    let stored: Value = serde_json::from_slice(&raw).unwrap();
+    assert_eq!(stored["sender"], json!(DEFAULT_SENDER));
+}
@@ -0,0 +1,269 @@
+use crate::common::*;
+use cosmwasm_std::{coin, Addr, Coin, Event};
+use cw_multi_test::Executor;
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn create_msg_start_stream(total: &str, duration: u64) -> Value {
+    json!({"start_streaming_convert": {"total": total, "duration": duration}})
+}
+
+fn create_msg_claim_stream(id: u64) -> Value {
+    json!({"claim_stream": {"id": id}})
+}
+
+fn create_msg_cancel_stream(id: u64) -> Value {
+    json!({"cancel_stream": {"id": id}})
+}
+
+fn query_stream(app: &AppAccepting, contract_addr: &Addr, id: u64) -> Value {
+    app.wrap()
+        .query_wasm_smart(contract_addr, &json!({"stream": {"id": id}}))
+        .unwrap()
+}
+
+// Instantiate the contract and stream the default convert amount over 100 seconds
+fn setup_stream(setup_with_funds: (AppAccepting, u64)) -> (AppAccepting, Addr) {
+    let (app, contract_addr, _) = prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        default_sender(),
+        &create_msg_start_stream("1000", 100),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+    (app, contract_addr)
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::ok(create_msg_start_stream("1000", 100), vec![default_convert_amount()], Expect::Ok)]
+#[case::total_mismatch(create_msg_start_stream("999", 100), vec![default_convert_amount()], Expect::ErrContains(INVALID_FUNDS))]
+#[case::zero_duration(create_msg_start_stream("1000", 0), vec![default_convert_amount()], Expect::ErrContains(STREAM_INVALID_DURATION))]
+#[case::no_funds(create_msg_start_stream("1000", 100), vec![], Expect::ErrContains(INVALID_FUNDS))]
+#[case::wrong_denom(create_msg_start_stream("1000", 100), vec![coin(1_000, DUMMY_DENOM)], Expect::ErrContains(INVALID_SOURCE_DENOM))]
+fn start_streaming_convert(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] exec_msg: Value,
+    #[case] funds: Vec<Coin>,
+    #[case] expect: Expect<'_>,
+) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        default_sender(),
+        &exec_msg,
+        &funds,
+        expect,
+    );
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::claim_not_owner(VALID_MANIFEST_ADDRESS, create_msg_claim_stream(1), Expect::ErrContains(STREAM_NOT_OWNER))]
+#[case::claim_not_found(DEFAULT_SENDER, create_msg_claim_stream(2), Expect::ErrContains(STREAM_NOT_FOUND))]
+#[case::claim_nothing_vested(DEFAULT_SENDER, create_msg_claim_stream(1), Expect::ErrContains(NOTHING_TO_CLAIM))]
+#[case::cancel_not_owner(VALID_MANIFEST_ADDRESS, create_msg_cancel_stream(1), Expect::ErrContains(STREAM_NOT_OWNER))]
+#[case::cancel_not_found(DEFAULT_SENDER, create_msg_cancel_stream(2), Expect::ErrContains(STREAM_NOT_FOUND))]
+fn stream_invalid(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] exec_sender: &str,
+    #[case] exec_msg: Value,
+    #[case] expect: Expect<'_>,
+) {
+    let (mut app, contract_addr) = setup_stream(setup_with_funds);
+    run_execute(&mut app, exec_sender, contract_addr.as_str(), &exec_msg, &[], expect);
+}
+
+#[rstest]
+fn claim_stream(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr) = setup_stream(setup_with_funds);
+    let start = app.block_info().time;
+
+    advance_seconds(&mut app, 25);
+    assert_eq!(
+        query_stream(&app, &contract_addr, 1),
+        json!({
+            "stream": {
+                "id": 1,
+                "owner": DEFAULT_SENDER,
+                "total": default_convert_amount(),
+                "claimed": "0",
+                "start": start,
+                "duration": 100,
+            },
+            "claimable": "250",
+        })
+    );
+    let res = app
+        .execute_contract(
+            Addr::unchecked(DEFAULT_SENDER),
+            contract_addr.clone(),
+            &create_msg_claim_stream(1),
+            &[],
+        )
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "convert")
+            .add_attribute("burned", "250")
+            .add_attribute("minted", "125")
+            .add_attribute("stream_id", "1")
+            .add_attribute("stream_remaining", "750")
+    ));
+
+    // The rest vests at the rate in effect at the next claim
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract_addr.as_str(),
+        &create_msg_update_config(Field::Rate, "2"),
+        &[],
+        Expect::Ok,
+    );
+    advance_seconds(&mut app, 1_000);
+    let res = app
+        .execute_contract(
+            Addr::unchecked(DEFAULT_SENDER),
+            contract_addr.clone(),
+            &create_msg_claim_stream(1),
+            &[],
+        )
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("burned", "750")
+            .add_attribute("minted", "1500")
+            .add_attribute("stream_remaining", "0")
+    ));
+
+    // Fully claimed streams are removed
+    assert!(app
+        .wrap()
+        .query_wasm_smart::<Value>(&contract_addr, &json!({"stream": {"id": 1}}))
+        .is_err());
+}
+
+// Claims are capped at the maximum conversion amount, the rest stays vested
+#[rstest]
+fn claim_stream_max_amount(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr, _) = prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &modify_instantiate(Field::MaxConvertAmount, "300"),
+        &[],
+        default_sender(),
+        &create_msg_start_stream("1000", 100),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+    advance_seconds(&mut app, 50);
+    let res = app
+        .execute_contract(
+            Addr::unchecked(DEFAULT_SENDER),
+            contract_addr.clone(),
+            &create_msg_claim_stream(1),
+            &[],
+        )
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("burned", "300")
+            .add_attribute("stream_remaining", "700")
+    ));
+    assert_eq!(
+        query_stream(&app, &contract_addr, 1)["claimable"],
+        json!("200")
+    );
+}
+
+// Claims count against the daily quota of the stream owner
+#[cfg(feature = "quotas")]
+#[rstest]
+fn claim_stream_daily_quota(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr, _) = prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &modify_instantiate(Field::DailyQuota, "300"),
+        &[],
+        default_sender(),
+        &create_msg_start_stream("1000", 100),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+    advance_seconds(&mut app, 25);
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &create_msg_claim_stream(1),
+        &[],
+        Expect::Ok,
+    );
+    advance_seconds(&mut app, 25);
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &create_msg_claim_stream(1),
+        &[],
+        Expect::ErrContains(QUOTA_EXCEEDED),
+    );
+}
+
+#[rstest]
+fn cancel_stream(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr) = setup_stream(setup_with_funds);
+    advance_seconds(&mut app, 40);
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &create_msg_claim_stream(1),
+        &[],
+        Expect::Ok,
+    );
+    advance_seconds(&mut app, 10);
+
+    // Vested tokens not claimed yet are refunded as well
+    let res = app
+        .execute_contract(
+            Addr::unchecked(DEFAULT_SENDER),
+            contract_addr.clone(),
+            &create_msg_cancel_stream(1),
+            &[],
+        )
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm").add_attribute("refunded", coin(600, DEFAULT_SOURCE_DENOM).to_string())
+    ));
+    assert_eq!(
+        app.wrap()
+            .query_balance(DEFAULT_SENDER, DEFAULT_SOURCE_DENOM)
+            .unwrap(),
+        coin(999_600, DEFAULT_SOURCE_DENOM)
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &create_msg_claim_stream(1),
+        &[],
+        Expect::ErrContains(STREAM_NOT_FOUND),
+    );
+
+    let unsolicited: Value = app
+        .wrap()
+        .query_wasm_smart(
+            &contract_addr,
+            &json!({"unsolicited_balance": {"denom": DEFAULT_SOURCE_DENOM}}),
+        )
+        .unwrap();
+    assert_eq!(unsolicited["escrowed"], json!("0"));
+}
@@ -0,0 +1,221 @@
+use crate::common::*;
+use cosmwasm_std::{coin, Addr, Binary, Event};
+use cw_multi_test::Executor;
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn create_msg_set_successor(addr: Option<&str>) -> Value {
+    json!({"set_successor": {"addr": addr}})
+}
+
+fn create_msg_migrate_my_position(streams: &[u64]) -> Value {
+    json!({"migrate_my_position": {"streams": streams}})
+}
+
+fn query_successor(app: &AppAccepting, contract_addr: &Addr) -> Value {
+    app.wrap()
+        .query_wasm_smart(contract_addr, &json!({"successor": {}}))
+        .unwrap()
+}
+
+// Instantiate the contract and set a receiver contract as its successor
+fn setup_successor(app: &mut AppAccepting, code_id: u64) -> (Addr, Addr) {
+    let contract_addr = instantiate_default(app, code_id);
+    let successor = instantiate_receiver(app);
+    run_execute(
+        app,
+        default_admin(),
+        contract_addr.as_str(),
+        &create_msg_set_successor(Some(successor.as_str())),
+        &[],
+        Expect::Ok,
+    );
+    (contract_addr, successor)
+}
+
+#[rstest]
+#[case::not_admin(DEFAULT_SENDER, Some(DEFAULT_SENDER), Expect::ErrContains(ONLY_ADMIN))]
+#[case::not_contract(
+    DEFAULT_POA_ADMIN,
+    Some(DEFAULT_SENDER),
+    Expect::ErrContains(SUCCESSOR_NOT_CONTRACT)
+)]
+#[case::clear(DEFAULT_POA_ADMIN, None, Expect::Ok)]
+fn set_successor_invalid(
+    setup: (AppAccepting, u64),
+    #[case] sender: &str,
+    #[case] addr: Option<&str>,
+    #[case] expect: Expect<'_>,
+) {
+    prepare_and_execute(
+        setup,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        sender,
+        &create_msg_set_successor(addr),
+        &[],
+        expect,
+    );
+}
+
+#[rstest]
+fn set_successor(setup: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    assert_eq!(
+        query_successor(&app, &contract_addr),
+        json!({"successor": null, "migrated_positions": 0})
+    );
+
+    let successor = instantiate_receiver(&mut app);
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract_addr.as_str(),
+        &create_msg_set_successor(Some(successor.as_str())),
+        &[],
+        Expect::Ok,
+    );
+    assert_eq!(
+        query_successor(&app, &contract_addr),
+        json!({"successor": successor, "migrated_positions": 0})
+    );
+
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract_addr.as_str(),
+        &create_msg_set_successor(None),
+        &[],
+        Expect::Ok,
+    );
+    assert_eq!(
+        query_successor(&app, &contract_addr)["successor"],
+        json!(null)
+    );
+}
+
+#[rstest]
+fn migrate_my_position_without_successor(setup_with_funds: (AppAccepting, u64)) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        default_sender(),
+        &create_msg_migrate_my_position(&[]),
+        &[],
+        Expect::ErrContains(NO_SUCCESSOR),
+    );
+}
+
+#[rstest]
+fn migrate_my_position_empty(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let (contract_addr, _) = setup_successor(&mut app, code_id);
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &json!({"migrate_my_position": {}}),
+        &[],
+        Expect::ErrContains(NO_POSITION),
+    );
+}
+
+// The deposit, commitment and stream of the sender move to the successor with their funds,
+// and the escrow of the contract is released
+#[rstest]
+fn migrate_my_position(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let (contract_addr, successor) = setup_successor(&mut app, code_id);
+    let contract = contract_addr.as_str();
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &json!({"deposit": {}}),
+        &[coin(100, DEFAULT_SOURCE_DENOM)],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &json!({"commit_convert": {"hash": Binary::from([1u8; 32])}}),
+        &[coin(200, DEFAULT_SOURCE_DENOM)],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &json!({"start_streaming_convert": {"total": "300", "duration": 100}}),
+        &[coin(300, DEFAULT_SOURCE_DENOM)],
+        Expect::Ok,
+    );
+
+    // Only the owner can move a stream
+    run_execute(
+        &mut app,
+        DEFAULT_POA_ADMIN,
+        contract,
+        &create_msg_migrate_my_position(&[1]),
+        &[],
+        Expect::ErrContains(STREAM_NOT_OWNER),
+    );
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(DEFAULT_SENDER),
+            contract_addr.clone(),
+            &create_msg_migrate_my_position(&[1]),
+            &[],
+        )
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "migrate_my_position")
+            .add_attribute("successor", successor.as_str())
+            .add_attribute("migrated", format!("600{DEFAULT_SOURCE_DENOM}"))
+    ));
+    let received = app
+        .wrap()
+        .query_balance(&successor, DEFAULT_SOURCE_DENOM)
+        .unwrap();
+    assert_eq!(received, coin(600, DEFAULT_SOURCE_DENOM));
+    assert_eq!(
+        query_successor(&app, &contract_addr),
+        json!({"successor": successor, "migrated_positions": 1})
+    );
+
+    // Nothing is left behind, and the balance of the contract still covers its escrow
+    let deposit: Value = app
+        .wrap()
+        .query_wasm_smart(
+            &contract_addr,
+            &json!({"deposit": {"address": DEFAULT_SENDER}}),
+        )
+        .unwrap();
+    assert_eq!(deposit["amount"]["amount"], json!("0"));
+    let res = app
+        .execute_contract(
+            Addr::unchecked(DEFAULT_SENDER),
+            contract_addr.clone(),
+            &json!({"health_check": {}}),
+            &[],
+        )
+        .unwrap();
+    assert!(res.has_event(&Event::new("wasm-health").add_attribute("healthy", "true")));
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &create_msg_migrate_my_position(&[]),
+        &[],
+        Expect::ErrContains(NO_POSITION),
+    );
+}
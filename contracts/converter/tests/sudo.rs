@@ -0,0 +1,182 @@
+use crate::common::*;
+use cosmwasm_std::{Addr, Coin, Event};
+use cw_multi_test::Executor;
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+fn freeze(app: &mut AppAccepting, contract_addr: &Addr) {
+    app.wasm_sudo(contract_addr.clone(), &json!({"freeze": {}}))
+        .expect("failed to freeze");
+}
+
+fn query_frozen(app: &AppAccepting, contract_addr: &Addr) -> bool {
+    app.wrap()
+        .query_wasm_smart(contract_addr, &json!({"frozen": {}}))
+        .unwrap()
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::convert(DEFAULT_SENDER, default_convert(), vec![default_convert_amount()])]
+#[case::update_config(default_admin(), create_msg_update_config(Field::Paused, true), vec![])]
+#[case::update_admin(default_admin(), create_msg_update_admin(Some(DEFAULT_SENDER)), vec![])]
+#[case::set_quorum(default_admin(), json!({"set_quorum": {"members": [DEFAULT_SENDER], "threshold": 1}}), vec![])]
+fn frozen_blocks_execute(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] exec_sender: &str,
+    #[case] exec_msg: Value,
+    #[case] funds: Vec<Coin>,
+) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    freeze(&mut app, &contract_addr);
+    run_execute(
+        &mut app,
+        exec_sender,
+        contract_addr.as_str(),
+        &exec_msg,
+        &funds,
+        Expect::ErrContains(CONTRACT_FROZEN),
+    );
+}
+
+#[rstest]
+fn freeze_and_unfreeze(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    assert!(!query_frozen(&app, &contract_addr));
+
+    freeze(&mut app, &contract_addr);
+    assert!(query_frozen(&app, &contract_addr));
+
+    app.wasm_sudo(contract_addr.clone(), &json!({"unfreeze": {}}))
+        .expect("failed to unfreeze");
+    assert!(!query_frozen(&app, &contract_addr));
+
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &default_convert(),
+        &[default_convert_amount()],
+        Expect::Ok,
+    );
+}
+
+#[rstest]
+fn force_admin(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract_addr.as_str(),
+        &json!({"access": {"propose_admin": {"admin": make_addr("candidate")}}}),
+        &[],
+        Expect::Ok,
+    );
+
+    // Governance sets the admin even on a frozen contract, dropping the pending transfer
+    freeze(&mut app, &contract_addr);
+    app.wasm_sudo(
+        contract_addr.clone(),
+        &json!({"access": {"force_admin": {"admin": DEFAULT_SENDER}}}),
+    )
+    .expect("failed to force admin");
+
+    let admin: Value = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &json!({"admin": {}}))
+        .unwrap();
+    assert_eq!(admin, json!({"admin": DEFAULT_SENDER}));
+    let pending: Value = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &json!({"access": {"pending_admin": {}}}))
+        .unwrap();
+    assert_eq!(pending, json!({"pending_admin": null}));
+}
+
+#[rstest]
+fn cancel_scheduled(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let now = app.block_info().time;
+    let steps = json!([
+        {"start": now.nanos().to_string(), "rate": "0.6"},
+        {"start": now.plus_seconds(3_600).nanos().to_string(), "rate": "0.7"},
+    ]);
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &modify_instantiate(Field::RateStrategy, json!({"scheduled": {"steps": steps}})),
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+    let contract = contract_addr.as_str();
+    // Admin transfers go through the quorum once it is configured
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &json!({"access": {"propose_admin": {"admin": make_addr("candidate")}}}),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &json!({"set_quorum": {"members": [DEFAULT_SENDER, default_admin()], "threshold": 2}}),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &json!({"propose_action": {"action": {"update_rate_bounds": {"rate_floor": "0.1", "rate_ceiling": null}}}}),
+        &[],
+        Expect::Ok,
+    );
+
+    let res = app
+        .wasm_sudo(contract_addr.clone(), &json!({"cancel_scheduled": {}}))
+        .expect("failed to cancel");
+    assert!(res.has_event(&Event::new("wasm").add_attribute("cancelled", "3")));
+    assert!(res.has_event(
+        &Event::new("wasm-cancelled")
+            .add_attribute("kind", "quorum_action")
+            .add_attribute("id", "1")
+            .add_attribute("quorum_action", "update_rate_bounds")
+    ));
+    assert!(res.has_event(
+        &Event::new("wasm-cancelled")
+            .add_attribute("kind", "rate_step")
+            .add_attribute("rate", "0.7")
+    ));
+
+    let query = |msg: Value| -> Value { app.wrap().query_wasm_smart(contract, &msg).unwrap() };
+    assert_eq!(
+        query(json!({"pending_actions": {}})),
+        json!({"actions": []})
+    );
+    assert_eq!(
+        query(json!({"access": {"pending_admin": {}}})),
+        json!({"pending_admin": null})
+    );
+    // The step already started keeps applying
+    assert_eq!(
+        query(json!({"config": {}}))["rate_strategy"],
+        json!({"scheduled": {"steps": [{"start": now.nanos().to_string(), "rate": "0.6"}]}})
+    );
+
+    // Nothing left to cancel
+    let res = app
+        .wasm_sudo(contract_addr.clone(), &json!({"cancel_scheduled": {}}))
+        .expect("failed to cancel");
+    assert!(res.has_event(&Event::new("wasm").add_attribute("cancelled", "0")));
+}
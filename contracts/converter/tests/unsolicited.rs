@@ -0,0 +1,218 @@
+use crate::common::*;
+use cosmwasm_std::{coin, Addr, Event, Uint256};
+use cw_multi_test::Executor;
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+const UNSOLICITED_AMOUNT: u128 = 100;
+
+fn create_msg_record(depositor: &str, amount: u128) -> Value {
+    json!({"record_unsolicited": {"depositor": depositor, "amount": coin(amount, DUMMY_DENOM)}})
+}
+
+fn create_msg_return(depositor: &str) -> Value {
+    json!({"return_unsolicited": {"depositor": depositor, "denom": DUMMY_DENOM}})
+}
+
+// Instantiate the contract and send it funds through a plain bank transfer
+fn setup_unsolicited(setup_with_funds: (AppAccepting, u64)) -> (AppAccepting, Addr) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    app.send_tokens(
+        Addr::unchecked(DEFAULT_SENDER),
+        contract_addr.clone(),
+        &[coin(UNSOLICITED_AMOUNT, DUMMY_DENOM)],
+    )
+    .unwrap();
+    (app, contract_addr)
+}
+
+fn query_unsolicited_balance(app: &AppAccepting, contract_addr: &Addr) -> Value {
+    app.wrap()
+        .query_wasm_smart(
+            contract_addr,
+            &json!({"unsolicited_balance": {"denom": DUMMY_DENOM}}),
+        )
+        .unwrap()
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::ok(default_admin(), create_msg_record(DEFAULT_SENDER, UNSOLICITED_AMOUNT), Expect::Ok)]
+#[case::partial(default_admin(), create_msg_record(DEFAULT_SENDER, 1), Expect::Ok)]
+#[case::unauthorized(DEFAULT_SENDER, create_msg_record(DEFAULT_SENDER, UNSOLICITED_AMOUNT), Expect::ErrContains(ONLY_ADMIN))]
+#[case::exceeds_balance(default_admin(), create_msg_record(DEFAULT_SENDER, UNSOLICITED_AMOUNT + 1), Expect::ErrContains(EXCEEDS_UNRECORDED))]
+#[case::zero(default_admin(), create_msg_record(DEFAULT_SENDER, 0), Expect::ErrContains(AMOUNT_IS_ZERO))]
+#[case::invalid_depositor(default_admin(), create_msg_record("invalid", UNSOLICITED_AMOUNT), Expect::ErrContains(PARSE_FAILED))]
+#[case::return_nothing(default_admin(), create_msg_return(DEFAULT_SENDER), Expect::ErrContains(NOTHING_TO_RETURN))]
+#[case::return_unauthorized(DEFAULT_SENDER, create_msg_return(DEFAULT_SENDER), Expect::ErrContains(ONLY_ADMIN))]
+fn unsolicited(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] exec_sender: &str,
+    #[case] exec_msg: Value,
+    #[case] expect: Expect<'_>,
+) {
+    let (mut app, contract_addr) = setup_unsolicited(setup_with_funds);
+    run_execute(
+        &mut app,
+        exec_sender,
+        contract_addr.as_str(),
+        &exec_msg,
+        &[],
+        expect,
+    );
+}
+
+#[rstest]
+fn unsolicited_record_and_return(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr) = setup_unsolicited(setup_with_funds);
+    let contract = contract_addr.as_str();
+    let other = make_addr("other").to_string();
+
+    assert_eq!(
+        query_unsolicited_balance(&app, &contract_addr),
+        json!({"balance": "100", "recorded": "0", "escrowed": "0", "unrecorded": "100"})
+    );
+
+    // Recorded funds can't be attributed twice
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_record(DEFAULT_SENDER, 60),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_record(&other, 41),
+        &[],
+        Expect::ErrContains(EXCEEDS_UNRECORDED),
+    );
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_record(DEFAULT_SENDER, 40),
+        &[],
+        Expect::Ok,
+    );
+    assert_eq!(
+        query_unsolicited_balance(&app, &contract_addr),
+        json!({"balance": "100", "recorded": "100", "escrowed": "0", "unrecorded": "0"})
+    );
+
+    let funds: Value = app
+        .wrap()
+        .query_wasm_smart(
+            contract,
+            &json!({"unsolicited_funds": {"depositor": DEFAULT_SENDER}}),
+        )
+        .unwrap();
+    assert_eq!(
+        funds,
+        json!({"funds": [{"denom": DUMMY_DENOM, "amount": "100"}]})
+    );
+
+    let before = app
+        .wrap()
+        .query_balance(DEFAULT_SENDER, DUMMY_DENOM)
+        .unwrap();
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_return(DEFAULT_SENDER),
+        &[],
+        Expect::Ok,
+    );
+    let after = app
+        .wrap()
+        .query_balance(DEFAULT_SENDER, DUMMY_DENOM)
+        .unwrap();
+    assert_eq!(
+        after.amount - before.amount,
+        Uint256::from(UNSOLICITED_AMOUNT)
+    );
+    assert_eq!(
+        query_unsolicited_balance(&app, &contract_addr),
+        json!({"balance": "0", "recorded": "0", "escrowed": "0", "unrecorded": "0"})
+    );
+
+    // Funds are only returned once
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_return(DEFAULT_SENDER),
+        &[],
+        Expect::ErrContains(NOTHING_TO_RETURN),
+    );
+}
+
+fn create_msg_burn_residual() -> Value {
+    json!({"burn_residual": {}})
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::unauthorized(DEFAULT_SENDER, Expect::ErrContains(ONLY_ADMIN))]
+#[case::nothing_to_burn(default_admin(), Expect::ErrContains(NOTHING_TO_BURN))]
+fn burn_residual_invalid(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] exec_sender: &str,
+    #[case] expect: Expect<'_>,
+) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        exec_sender,
+        &create_msg_burn_residual(),
+        &[],
+        expect,
+    );
+}
+
+#[rstest]
+fn burn_residual(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    app.send_tokens(
+        Addr::unchecked(DEFAULT_SENDER),
+        contract_addr.clone(),
+        &[coin(100, DEFAULT_TARGET_DENOM)],
+    )
+    .unwrap();
+
+    // Recorded unsolicited funds are kept for their depositor
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract_addr.as_str(),
+        &json!({"record_unsolicited": {"depositor": DEFAULT_SENDER, "amount": coin(30, DEFAULT_TARGET_DENOM)}}),
+        &[],
+        Expect::Ok,
+    );
+    let res = app
+        .execute_contract(
+            Addr::unchecked(default_admin()),
+            contract_addr,
+            &create_msg_burn_residual(),
+            &[],
+        )
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "burn_residual")
+            .add_attribute("balance", "100")
+            .add_attribute("escrowed", "0")
+            .add_attribute("recorded", "30")
+            .add_attribute("burned", coin(70, DEFAULT_TARGET_DENOM).to_string())
+    ));
+}
@@ -1,4 +1,6 @@
 use crate::common::*;
+use cosmwasm_std::{Addr, Event};
+use cw_multi_test::Executor;
 use rstest::*;
 
 mod common;
@@ -45,3 +47,82 @@ fn update_admin(
         expect,
     );
 }
+
+#[rstest]
+#[case::changed(DEFAULT_SENDER, true)]
+#[case::same_admin(DEFAULT_POA_ADMIN, false)]
+fn update_admin_attributes(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] new_admin: &str,
+    #[case] changed: bool,
+) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let res = app
+        .execute_contract(
+            Addr::unchecked(DEFAULT_POA_ADMIN),
+            contract_addr,
+            &create_msg_update_admin(Some(new_admin)),
+            &[],
+        )
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("old_admin", DEFAULT_POA_ADMIN)
+            .add_attribute("new_admin", new_admin)
+            .add_attribute("changed", changed.to_string())
+    ));
+}
+
+#[cfg(feature = "history")]
+#[rstest]
+fn query_admin_history(setup_with_funds: (AppAccepting, u64)) {
+    use serde_json::{json, Value};
+
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let height = app.block_info().height;
+    for (sender, new_admin) in [
+        (DEFAULT_POA_ADMIN, DEFAULT_SENDER),
+        // Noops are not recorded
+        (DEFAULT_SENDER, DEFAULT_SENDER),
+    ] {
+        advance_blocks(&mut app, 1);
+        app.execute_contract(
+            Addr::unchecked(sender),
+            contract_addr.clone(),
+            &create_msg_update_admin(Some(new_admin)),
+            &[],
+        )
+        .unwrap();
+    }
+
+    let history: Value = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &json!({"admin_history": {}}))
+        .unwrap();
+    let summary: Vec<_> = history["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|change| (change["admin"].clone(), change["height"].clone()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (json!(DEFAULT_POA_ADMIN), json!(height)),
+            (json!(DEFAULT_SENDER), json!(height + 1)),
+        ]
+    );
+    assert_eq!(history["next_key"], Value::Null);
+
+    let page: Value = app
+        .wrap()
+        .query_wasm_smart(
+            &contract_addr,
+            &json!({"admin_history": {"pagination": {"limit": 1}}}),
+        )
+        .unwrap();
+    assert_eq!(page["items"].as_array().unwrap().len(), 1);
+    assert_ne!(page["next_key"], Value::Null);
+}
@@ -1,5 +1,8 @@
 use crate::common::*;
+use cosmwasm_std::{coin, Addr, DenomMetadata, Event};
+use cw_multi_test::Executor;
 use rstest::*;
+use serde_json::json;
 
 mod common;
 
@@ -55,6 +58,36 @@ mod common;
 // --- invalid paused
 #[case::invalid_paused_string(DEFAULT_POA_ADMIN, create_msg_update_config(Field::Paused, Some("a")), Expect::ErrContains(INVALID_TYPE_STRING))]
 #[case::invalid_paused_unicode(DEFAULT_POA_ADMIN, create_msg_update_config(Field::Paused, Some("😀")), Expect::ErrContains(INVALID_TYPE_STRING))]
+// --- strict denoms
+#[case::strict_denoms(DEFAULT_POA_ADMIN, create_msg_update_config(Field::StrictDenoms, true), Expect::Ok)]
+#[case::strict_denoms_unknown_tgt(DEFAULT_POA_ADMIN, create_msg_update_config_from_config(&json!({"target_denom": VALID_FACTORY_DENOM, "strict_denoms": true})), Expect::ErrContains(UNKNOWN_DENOM))]
+#[case::redact_senders(DEFAULT_POA_ADMIN, create_msg_update_config(Field::RedactSenders, true), Expect::Ok)]
+#[case::invalid_strict_denoms_string(DEFAULT_POA_ADMIN, create_msg_update_config(Field::StrictDenoms, Some("a")), Expect::ErrContains(INVALID_TYPE_STRING))]
+// --- exponents
+#[case::exponents(DEFAULT_POA_ADMIN, create_msg_update_config_from_config(&json!({"source_exponent": 6, "target_exponent": 18})), Expect::Ok)]
+#[case::exponents_max(DEFAULT_POA_ADMIN, create_msg_update_config_from_config(&json!({"source_exponent": 18, "target_exponent": 0})), Expect::Ok)]
+#[case::invalid_exponents_incomplete(DEFAULT_POA_ADMIN, create_msg_update_config(Field::TargetExponent, 18), Expect::ErrContains(INCOMPLETE_EXPONENTS))]
+#[case::invalid_exponents_too_large(DEFAULT_POA_ADMIN, create_msg_update_config_from_config(&json!({"source_exponent": 6, "target_exponent": 19})), Expect::ErrContains(INVALID_EXPONENT))]
+// --- symbols
+#[case::symbols(DEFAULT_POA_ADMIN, create_msg_update_config_from_config(&json!({"source_symbol": "MFX", "target_symbol": "PWR"})), Expect::Ok)]
+#[case::invalid_source_symbol(DEFAULT_POA_ADMIN, create_msg_update_config(Field::SourceSymbol, "M FX"), Expect::ErrContains(INVALID_SYMBOL))]
+#[case::invalid_target_symbol_empty(DEFAULT_POA_ADMIN, create_msg_update_config(Field::TargetSymbol, ""), Expect::ErrContains(INVALID_SYMBOL))]
+// --- allowed senders
+#[case::allowed_senders_accounts(DEFAULT_POA_ADMIN, create_msg_update_config(Field::AllowedSenders, "accounts_only"), Expect::Ok)]
+#[case::allowed_senders_contracts(DEFAULT_POA_ADMIN, create_msg_update_config(Field::AllowedSenders, "contracts_only"), Expect::Ok)]
+#[case::invalid_allowed_senders(DEFAULT_POA_ADMIN, create_msg_update_config(Field::AllowedSenders, "humans"), Expect::ErrContains(UNKNOWN_VARIANT))]
+// --- epoch length
+#[case::epoch_length(DEFAULT_POA_ADMIN, create_msg_update_config(Field::EpochLength, 86_400), Expect::Ok)]
+#[case::invalid_epoch_length_zero(DEFAULT_POA_ADMIN, create_msg_update_config(Field::EpochLength, 0), Expect::ErrContains(INVALID_EPOCH_LENGTH))]
+// --- conversion limits
+#[case::min_convert_amount(DEFAULT_POA_ADMIN, create_msg_update_config(Field::MinConvertAmount, "1000"), Expect::Ok)]
+#[case::max_convert_amount(DEFAULT_POA_ADMIN, create_msg_update_config(Field::MaxConvertAmount, "1000"), Expect::Ok)]
+#[case::convert_limits_equal(DEFAULT_POA_ADMIN, create_msg_update_config_from_config(&json!({"min_convert_amount": "1000", "max_convert_amount": "1000"})), Expect::Ok)]
+#[case::invalid_convert_limits(DEFAULT_POA_ADMIN, create_msg_update_config_from_config(&json!({"min_convert_amount": "1001", "max_convert_amount": "1000"})), Expect::ErrContains(INVALID_CONVERT_LIMITS))]
+#[case::epoch_cap(DEFAULT_POA_ADMIN, create_msg_update_config(Field::EpochCap, "1000"), Expect::Ok)]
+#[case::fees_to_community_pool(DEFAULT_POA_ADMIN, create_msg_update_config(Field::FeesToCommunityPool, true), Expect::Ok)]
+#[case::invalid_epoch_cap_zero(DEFAULT_POA_ADMIN, create_msg_update_config(Field::EpochCap, "0"), Expect::ErrContains(INVALID_EPOCH_CAP))]
+#[case::invalid_max_convert_amount_zero(DEFAULT_POA_ADMIN, create_msg_update_config(Field::MaxConvertAmount, "0"), Expect::ErrContains(INVALID_CONVERT_LIMITS))]
 fn update_config(
     setup_with_funds: (AppAccepting, u64),
     #[case] exec_sender: &str,
@@ -72,3 +105,202 @@ fn update_config(
         expect,
     );
 }
+
+// Builds without the `quotas` feature reject a daily quota rather than not enforcing it
+#[cfg(not(feature = "quotas"))]
+#[rstest]
+fn update_config_daily_quota_disabled(setup_with_funds: (AppAccepting, u64)) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        DEFAULT_POA_ADMIN,
+        &create_msg_update_config(Field::DailyQuota, "1000"),
+        &[],
+        Expect::ErrContains(QUOTAS_DISABLED),
+    );
+}
+
+// A config sets either the flat fee or a fee model. Switching from one to the other takes a
+// replace_config, which can leave the previous one unset
+#[rstest]
+fn update_config_conflicting_fees(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, contract_addr, _) = prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &modify_instantiate(Field::FlatFee, coin(10, DUMMY_DENOM)),
+        &[],
+        DEFAULT_POA_ADMIN,
+        &create_msg_update_config(Field::FeeModel, json!({"flat": {"amount": "10"}})),
+        &[],
+        Expect::ErrContains(CONFLICTING_FEES),
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_POA_ADMIN,
+        contract_addr.as_str(),
+        &create_msg_replace_config(&modify_config(
+            Field::FeeModel,
+            json!({"flat": {"amount": "10"}}),
+        )),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_POA_ADMIN,
+        contract_addr.as_str(),
+        &create_msg_update_config(Field::FlatFee, coin(10, DUMMY_DENOM)),
+        &[],
+        Expect::ErrContains(CONFLICTING_FEES),
+    );
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::change(json!({"rate": "1.5"}), Some(true), Expect::Ok)]
+#[case::noop(json!({"rate": DEFAULT_RATE}), Some(true), Expect::ErrContains(NOOP_UPDATE))]
+#[case::empty(json!({}), Some(true), Expect::ErrContains(NOOP_UPDATE))]
+#[case::noop_permissive(json!({"rate": DEFAULT_RATE}), Some(false), Expect::Ok)]
+#[case::noop_default(json!({"rate": DEFAULT_RATE}), None, Expect::Ok)]
+fn update_config_strict(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] config: serde_json::Value,
+    #[case] strict: Option<bool>,
+    #[case] expect: Expect<'_>,
+) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        DEFAULT_POA_ADMIN,
+        &json!({"update_config": {"config": config, "strict": strict}}),
+        &[],
+        expect,
+    );
+}
+
+#[rstest]
+fn update_config_unknown_denom_warning(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let res = app
+        .execute_contract(
+            Addr::unchecked(DEFAULT_POA_ADMIN),
+            contract_addr,
+            &create_msg_update_config(Field::TargetDenom, VALID_FACTORY_DENOM),
+            &[],
+        )
+        .unwrap();
+    assert!(res.has_event(&Event::new("wasm").add_attribute("unknown_denom", VALID_FACTORY_DENOM)));
+}
+
+#[rstest]
+fn update_config_strict_denoms_with_metadata(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    app.init_modules(|router, _, storage| {
+        router.bank.set_denom_metadata(
+            storage,
+            VALID_FACTORY_DENOM.to_string(),
+            DenomMetadata {
+                base: VALID_FACTORY_DENOM.to_string(),
+                ..Default::default()
+            },
+        )
+    })
+    .unwrap();
+    let contract_addr = instantiate_default(&mut app, code_id);
+    run_execute(
+        &mut app,
+        DEFAULT_POA_ADMIN,
+        contract_addr.as_str(),
+        &create_msg_update_config_from_config(
+            &json!({"target_denom": VALID_FACTORY_DENOM, "strict_denoms": true}),
+        ),
+        &[],
+        Expect::Ok,
+    );
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::ok(DEFAULT_POA_ADMIN, create_msg_replace_config(&modify_config(Field::Rate, "2.0")), Expect::Ok)]
+#[case::ok_defaults(DEFAULT_POA_ADMIN, create_msg_replace_config(&json!({"rate": "2.0"})), Expect::Ok)]
+#[case::unauthorized(DEFAULT_SENDER, create_msg_replace_config(&default_config()), Expect::ErrContains(ONLY_ADMIN))]
+#[case::missing_rate(DEFAULT_POA_ADMIN, create_msg_replace_config(&json!({})), Expect::ErrContains("missing field `rate`"))]
+#[case::same_denom(DEFAULT_POA_ADMIN, create_msg_replace_config(&modify_config(Field::TargetDenom, DEFAULT_SOURCE_DENOM)), Expect::ErrContains(SAME_DENOM))]
+#[case::invalid_rate(DEFAULT_POA_ADMIN, create_msg_replace_config(&modify_config(Field::Rate, "invalid_rate")), Expect::ErrContains(RATE_PARSE_FAILED))]
+#[case::bech32_prefix(DEFAULT_POA_ADMIN, create_msg_replace_config(&modify_config(Field::Bech32Prefix, "manifest")), Expect::ErrContains(BECH32_PREFIX_IMMUTABLE))]
+#[case::rate_bounds(DEFAULT_POA_ADMIN, create_msg_replace_config(&modify_config(Field::RateFloor, "0.1")), Expect::ErrContains(RATE_BOUNDS_IMMUTABLE))]
+#[case::invalid_rate_bounds(DEFAULT_POA_ADMIN, create_msg_replace_config(&json!({"rate": "1", "rate_floor": "2", "rate_ceiling": "1.5"})), Expect::ErrContains(INVALID_RATE_BOUNDS))]
+fn replace_config(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] exec_sender: &str,
+    #[case] exec_msg: serde_json::Value,
+    #[case] expect: Expect<'_>,
+) {
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &default_instantiate(),
+        &[],
+        exec_sender,
+        &exec_msg,
+        &[],
+        expect,
+    );
+}
+
+#[rstest]
+fn replace_config_unsets_omitted_fields(setup_with_funds: (AppAccepting, u64)) {
+    let mut instantiate_msg = default_instantiate();
+    instantiate_msg["epoch_length"] = json!(86_400);
+    instantiate_msg["source_symbol"] = json!("MFX");
+    let config = modify_config(Field::Rate, "2");
+    let (app, contract_addr, _) = prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &instantiate_msg,
+        &[],
+        DEFAULT_POA_ADMIN,
+        &create_msg_replace_config(&config),
+        &[],
+        Expect::Ok,
+    );
+    let res: serde_json::Value = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &json!({"config": {}}))
+        .unwrap();
+    assert_eq!(res, config);
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::within(create_msg_update_config(Field::Rate, "0.55"), Expect::Ok)]
+#[case::floor(create_msg_update_config(Field::Rate, "0.4"), Expect::Ok)]
+#[case::below_floor(create_msg_update_config(Field::Rate, "0.3"), Expect::ErrContains(RATE_OUT_OF_BOUNDS))]
+#[case::above_ceiling(create_msg_update_config(Field::Rate, "1.5"), Expect::ErrContains(RATE_OUT_OF_BOUNDS))]
+#[case::update_floor(create_msg_update_config(Field::RateFloor, "0.1"), Expect::ErrContains(RATE_BOUNDS_IMMUTABLE))]
+#[case::update_ceiling(create_msg_update_config(Field::RateCeiling, "0.7"), Expect::ErrContains(RATE_BOUNDS_IMMUTABLE))]
+#[case::replace_same_bounds(create_msg_replace_config(&json!({"rate": "0.45", "rate_floor": "0.4", "rate_ceiling": "0.6"})), Expect::Ok)]
+fn update_config_rate_bounds(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] exec_msg: serde_json::Value,
+    #[case] expect: Expect<'_>,
+) {
+    let mut instantiate_msg = default_instantiate();
+    instantiate_msg["rate_floor"] = json!("0.4");
+    instantiate_msg["rate_ceiling"] = json!("0.6");
+    prepare_and_execute(
+        setup_with_funds,
+        default_sender(),
+        &instantiate_msg,
+        &[],
+        DEFAULT_POA_ADMIN,
+        &exec_msg,
+        &[],
+        expect,
+    );
+}
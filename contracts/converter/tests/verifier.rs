@@ -0,0 +1,124 @@
+use crate::common::*;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
+    StdResult,
+};
+use cw_multi_test::{ContractWrapper, Executor};
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+// Attestation contract verifying the addresses it was instantiated with
+fn verifier_instantiate(deps: DepsMut, _: Env, _: MessageInfo, msg: Value) -> StdResult<Response> {
+    deps.storage
+        .set(b"verified", &serde_json::to_vec(&msg["verified"]).unwrap());
+    Ok(Response::default())
+}
+
+fn verifier_execute(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    Err(StdError::msg("unexpected message"))
+}
+
+fn verifier_query(deps: Deps, _: Env, msg: Value) -> StdResult<Binary> {
+    let address = msg["is_verified"]["address"]
+        .as_str()
+        .ok_or_else(|| StdError::msg("unexpected query"))?;
+    let verified: Vec<String> =
+        serde_json::from_slice(&deps.storage.get(b"verified").unwrap()).unwrap();
+    to_json_binary(&json!({"verified": verified.iter().any(|v| v == address)}))
+}
+
+fn instantiate_verifier(app: &mut AppAccepting, verified: &[&str]) -> Addr {
+    let code_id = app.store_code(Box::new(ContractWrapper::new_with_empty(
+        verifier_execute,
+        verifier_instantiate,
+        verifier_query,
+    )));
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(default_sender()),
+        &json!({"verified": verified}),
+        &[],
+        "verifier",
+        None,
+    )
+    .expect("failed to instantiate verifier")
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::verified(&[DEFAULT_SENDER], Expect::Ok)]
+#[case::not_verified(&[DEFAULT_POA_ADMIN], Expect::ErrContains(SENDER_NOT_VERIFIED))]
+fn convert_with_verifier(
+    setup_with_funds: (AppAccepting, u64),
+    #[case] verified: &[&str],
+    #[case] expect: Expect<'_>,
+) {
+    let (mut app, code_id) = setup_with_funds;
+    let verifier = instantiate_verifier(&mut app, verified);
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(default_sender()),
+            &modify_instantiate(Field::Verifier, verifier.as_str()),
+            &[],
+            "converter",
+            None,
+        )
+        .unwrap();
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract_addr.as_str(),
+        &default_convert(),
+        &[default_convert_amount()],
+        expect,
+    );
+}
+
+#[rstest]
+fn verifier_not_contract(setup_with_funds: (AppAccepting, u64)) {
+    let (app, code_id) = setup_with_funds;
+    run_instantiate(
+        app,
+        code_id,
+        default_sender(),
+        &modify_instantiate(Field::Verifier, make_addr("account").as_str()),
+        &[],
+        Expect::ErrContains(VERIFIER_NOT_CONTRACT),
+    );
+}
+
+#[rstest]
+fn update_config_verifier(setup_with_funds: (AppAccepting, u64)) {
+    let (mut app, code_id) = setup_with_funds;
+    let verifier = instantiate_verifier(&mut app, &[]);
+    let contract_addr = instantiate_default(&mut app, code_id);
+    let contract = contract_addr.as_str();
+
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_update_config(Field::Verifier, make_addr("account").as_str()),
+        &[],
+        Expect::ErrContains(VERIFIER_NOT_CONTRACT),
+    );
+    run_execute(
+        &mut app,
+        default_admin(),
+        contract,
+        &create_msg_update_config(Field::Verifier, verifier.as_str()),
+        &[],
+        Expect::Ok,
+    );
+    run_execute(
+        &mut app,
+        DEFAULT_SENDER,
+        contract,
+        &default_convert(),
+        &[default_convert_amount()],
+        Expect::ErrContains(SENDER_NOT_VERIFIED),
+    );
+}
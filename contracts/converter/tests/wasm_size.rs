@@ -0,0 +1,25 @@
+use std::path::PathBuf;
+
+// Code size limit of wasmd's default upload params. Raising the budget should be a deliberate
+// choice, made in the change growing the contract
+const SIZE_BUDGET: u64 = 800 * 1024;
+
+// The size-focused build of `cargo wasm-min`, skipped when it was not built, e.g. without the
+// wasm32 target installed. CI builds it before running the tests
+#[test]
+fn wasm_min_within_budget() {
+    let target_dir = std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../target"));
+    let wasm = target_dir.join("wasm32-unknown-unknown/wasm-min/converter.wasm");
+    let Ok(metadata) = std::fs::metadata(&wasm) else {
+        eprintln!("{} not found, run `cargo wasm-min` first", wasm.display());
+        return;
+    };
+    assert!(
+        metadata.len() <= SIZE_BUDGET,
+        "{} is {} bytes, over the budget of {SIZE_BUDGET} bytes",
+        wasm.display(),
+        metadata.len()
+    );
+}
@@ -0,0 +1,12 @@
+pub const CONTRACT_NAME: &str = "manifest/ica-controller";
+
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// ICS-27 channel parameters
+pub const ICA_VERSION: &str = "ics27-1";
+pub const ICA_HOST_PORT: &str = "icahost";
+pub const ICA_ENCODING: &str = "proto3";
+pub const ICA_TX_TYPE: &str = "sdk_multi_msg";
+
+// Packets time out after this many seconds unless a timeout is given
+pub const DEFAULT_TIMEOUT_SECONDS: u64 = 600;
@@ -0,0 +1,254 @@
+use crate::consts::{CONTRACT_NAME, CONTRACT_VERSION};
+use crate::error::AmountError::NonPayable;
+use crate::error::ContractError;
+use crate::error::MigrateError::InvalidContractName;
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::state::{Config, ADMIN, CONFIG};
+use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, MigrateInfo, Response, StdResult};
+use cw2::{get_contract_version, set_contract_version};
+use cw_utils::nonpayable;
+
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+    let admin = deps.api.addr_validate(&msg.admin)?;
+
+    let config = Config {
+        connection_id: msg.connection_id,
+        counterparty_connection_id: msg.counterparty_connection_id,
+    };
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    CONFIG.save(deps.storage, &config)?;
+    ADMIN.set(deps, Some(admin))?;
+
+    Ok(Response::new().add_attribute("action", "instantiate"))
+}
+
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    use QueryMsg::*;
+
+    match msg {
+        Config {} => query::config(deps),
+        Admin {} => query::admin(deps),
+        Channel {} => query::channel(deps),
+        Packet {
+            channel_id,
+            sequence,
+        } => query::packet(deps, channel_id, sequence),
+    }
+}
+
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    use ExecuteMsg::*;
+
+    match msg {
+        CreateChannel {} => exec::create_channel(deps, env, info),
+        SendTx {
+            messages,
+            memo,
+            timeout_seconds,
+        } => exec::send_tx(deps, env, info, messages, memo, timeout_seconds),
+        UpdateAdmin { admin } => exec::update_admin(deps, info, admin),
+    }
+}
+
+pub fn migrate(
+    deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+    _info: MigrateInfo,
+) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::MigrateError(InvalidContractName));
+    }
+
+    if stored.version == CONTRACT_VERSION {
+        return Ok(Response::new()
+            .add_attribute("action", "migrate")
+            .add_attribute("note", "already at latest version")
+            .add_attribute("version", CONTRACT_VERSION));
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("contract", CONTRACT_NAME)
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", CONTRACT_VERSION))
+}
+
+mod query {
+    use super::*;
+    use crate::msg::{ChannelResponse, PacketResponse};
+    use crate::state::{CHANNEL, PACKETS};
+    use cosmwasm_std::to_json_binary;
+
+    pub fn config(deps: Deps) -> StdResult<Binary> {
+        to_json_binary(&CONFIG.load(deps.storage)?)
+    }
+
+    pub fn admin(deps: Deps) -> StdResult<Binary> {
+        to_json_binary(&ADMIN.query_admin(deps)?)
+    }
+
+    pub fn channel(deps: Deps) -> StdResult<Binary> {
+        to_json_binary(&ChannelResponse {
+            channel: CHANNEL.may_load(deps.storage)?,
+        })
+    }
+
+    pub fn packet(deps: Deps, channel_id: String, sequence: u64) -> StdResult<Binary> {
+        to_json_binary(&PacketResponse {
+            status: PACKETS.may_load(deps.storage, (&channel_id, sequence))?,
+        })
+    }
+}
+
+mod exec {
+    use super::*;
+    use crate::consts::{DEFAULT_TIMEOUT_SECONDS, ICA_HOST_PORT};
+    use crate::error::AdminError::NotAdmin;
+    use crate::error::ChannelError::{AlreadyOpen, NotOpen};
+    use crate::error::PacketError::{InvalidTimeout, NoMessages};
+    use crate::ica::{Metadata, PacketData};
+    use crate::state::{ChannelStatus, PacketStatus, CHANNEL, NEXT_SEQUENCE, PACKETS};
+    use cosmwasm_std::{to_json_binary, to_json_string, AnyMsg, CosmosMsg, IbcMsg, IbcTimeout};
+    use manifest_std::ibc::core::channel::v1::{
+        Channel, Counterparty, MsgChannelOpenInit, Order, State,
+    };
+    use prost::Message;
+
+    // Start the channel handshake with the host. The handshake completes in the IBC entry
+    // points, where the host provides the interchain account address
+    pub fn create_channel(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        ADMIN
+            .assert_admin(deps.as_ref(), &info.sender)
+            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+
+        if let Some(channel) = CHANNEL.may_load(deps.storage)? {
+            if channel.status == ChannelStatus::Open {
+                return Err(ContractError::ChannelError(AlreadyOpen));
+            }
+        }
+
+        let config = CONFIG.load(deps.storage)?;
+        let metadata = Metadata::new(&config.connection_id, &config.counterparty_connection_id);
+        let port_id = format!("wasm.{}", env.contract.address);
+        let open_init = MsgChannelOpenInit {
+            port_id: port_id.clone(),
+            channel: Some(Channel {
+                state: State::Init.into(),
+                ordering: Order::Ordered.into(),
+                counterparty: Some(Counterparty {
+                    port_id: ICA_HOST_PORT.to_string(),
+                    channel_id: String::new(),
+                }),
+                connection_hops: vec![config.connection_id.clone()],
+                version: to_json_string(&metadata)?,
+            }),
+            signer: env.contract.address.to_string(),
+        };
+        let msg = CosmosMsg::Any(AnyMsg {
+            type_url: MsgChannelOpenInit::TYPE_URL.to_string(),
+            value: open_init.encode_to_vec().into(),
+        });
+
+        Ok(Response::new()
+            .add_message(msg)
+            .add_attribute("action", "create_channel")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("port_id", port_id)
+            .add_attribute("connection_id", config.connection_id))
+    }
+
+    pub fn send_tx(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        messages: Vec<AnyMsg>,
+        memo: String,
+        timeout_seconds: Option<u64>,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        ADMIN
+            .assert_admin(deps.as_ref(), &info.sender)
+            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+
+        if messages.is_empty() {
+            return Err(ContractError::PacketError(NoMessages));
+        }
+        let timeout_seconds = timeout_seconds.unwrap_or(DEFAULT_TIMEOUT_SECONDS);
+        if timeout_seconds == 0 {
+            return Err(ContractError::PacketError(InvalidTimeout));
+        }
+
+        let channel = CHANNEL
+            .may_load(deps.storage)?
+            .filter(|c| c.status == ChannelStatus::Open)
+            .ok_or(ContractError::ChannelError(NotOpen))?;
+
+        let sequence = NEXT_SEQUENCE
+            .may_load(deps.storage, &channel.channel_id)?
+            .unwrap_or(1);
+        NEXT_SEQUENCE.save(deps.storage, &channel.channel_id, &(sequence + 1))?;
+        PACKETS.save(
+            deps.storage,
+            (&channel.channel_id, sequence),
+            &PacketStatus::Pending {},
+        )?;
+
+        let message_count = messages.len();
+        let packet = PacketData::execute_tx(messages, memo);
+        let msg = IbcMsg::SendPacket {
+            channel_id: channel.channel_id.clone(),
+            data: to_json_binary(&packet)?,
+            timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(timeout_seconds)),
+        };
+
+        Ok(Response::new()
+            .add_message(msg)
+            .add_attribute("action", "send_tx")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("channel_id", channel.channel_id)
+            .add_attribute("sequence", sequence.to_string())
+            .add_attribute("message_count", message_count.to_string()))
+    }
+
+    pub fn update_admin(
+        deps: DepsMut,
+        info: MessageInfo,
+        admin: String,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        let new = deps.api.addr_validate(&admin)?;
+        let res = ADMIN
+            .execute_update_admin(deps, info, Some(new))
+            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+        Ok(res
+            .add_attribute("action", "update_admin")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("new_admin", admin))
+    }
+}
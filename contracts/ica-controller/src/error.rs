@@ -0,0 +1,50 @@
+use cosmwasm_std::StdError;
+pub use manifest_errors::{AdminError, AmountError, MigrateError};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[allow(clippy::enum_variant_names)]
+pub enum ContractError {
+    #[error("{0}")]
+    StdError(#[from] StdError),
+    #[error("unauthorized: {0}")]
+    AdminError(#[from] AdminError),
+    #[error("invalid amount: {0}")]
+    AmountError(#[from] AmountError),
+    #[error("channel error: {0}")]
+    ChannelError(#[from] ChannelError),
+    #[error("packet error: {0}")]
+    PacketError(#[from] PacketError),
+    #[error("migration error: {0}")]
+    MigrateError(#[from] MigrateError),
+}
+
+#[derive(Error, Debug)]
+pub enum ChannelError {
+    #[error("channel is already open")]
+    AlreadyOpen,
+    #[error("no open channel")]
+    NotOpen,
+    #[error("channel must be ordered")]
+    InvalidOrder,
+    #[error("counterparty port must be the ica host port")]
+    InvalidCounterpartyPort,
+    #[error("invalid channel version")]
+    InvalidVersion,
+    #[error("channel connection does not match the configured connection")]
+    InvalidConnection,
+    #[error("only the controller can initiate a channel")]
+    OpenTryNotAllowed,
+    #[error("counterparty did not provide an interchain account address")]
+    MissingAddress,
+}
+
+#[derive(Error, Debug)]
+pub enum PacketError {
+    #[error("no messages to send")]
+    NoMessages,
+    #[error("timeout must be greater than zero")]
+    InvalidTimeout,
+    #[error("invalid acknowledgement")]
+    InvalidAcknowledgement,
+}
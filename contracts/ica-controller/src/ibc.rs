@@ -0,0 +1,202 @@
+use crate::consts::{CONTRACT_NAME, CONTRACT_VERSION, ICA_HOST_PORT};
+use crate::error::ChannelError::{
+    AlreadyOpen, InvalidConnection, InvalidCounterpartyPort, InvalidOrder, InvalidVersion,
+    MissingAddress, OpenTryNotAllowed,
+};
+use crate::error::ContractError;
+use crate::error::PacketError::InvalidAcknowledgement;
+use crate::ica::{Acknowledgement, Metadata};
+use crate::state::{
+    ChannelState, ChannelStatus, PacketStatus, CHANNEL, CONFIG, NEXT_SEQUENCE, PACKETS,
+};
+use cosmwasm_std::{
+    from_json, DepsMut, Env, Ibc3ChannelOpenResponse, IbcBasicResponse, IbcChannel,
+    IbcChannelCloseMsg, IbcChannelConnectMsg, IbcChannelOpenMsg, IbcChannelOpenResponse, IbcOrder,
+    IbcPacketAckMsg, IbcPacketReceiveMsg, IbcPacketTimeoutMsg, IbcReceiveResponse, Never, StdAck,
+    Storage,
+};
+
+// Only the controller side of the handshake is supported, and only on the configured
+// connection towards the host's interchain account port
+pub fn channel_open(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelOpenMsg,
+) -> Result<IbcChannelOpenResponse, ContractError> {
+    let channel = match msg {
+        IbcChannelOpenMsg::OpenInit { channel } => channel,
+        IbcChannelOpenMsg::OpenTry { .. } => {
+            return Err(ContractError::ChannelError(OpenTryNotAllowed))
+        }
+    };
+
+    if let Some(current) = CHANNEL.may_load(deps.storage)? {
+        if current.status == ChannelStatus::Open {
+            return Err(ContractError::ChannelError(AlreadyOpen));
+        }
+    }
+
+    let metadata = validate_channel(deps.storage, &channel)?;
+    if metadata.host_connection_id != CONFIG.load(deps.storage)?.counterparty_connection_id {
+        return Err(ContractError::ChannelError(InvalidConnection));
+    }
+
+    Ok(Some(Ibc3ChannelOpenResponse {
+        version: channel.version,
+    }))
+}
+
+// The host acknowledged the channel with the address of the interchain account
+pub fn channel_connect(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelConnectMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let (channel, counterparty_version) = match msg {
+        IbcChannelConnectMsg::OpenAck {
+            channel,
+            counterparty_version,
+        } => (channel, counterparty_version),
+        IbcChannelConnectMsg::OpenConfirm { .. } => {
+            return Err(ContractError::ChannelError(OpenTryNotAllowed))
+        }
+    };
+
+    validate_channel(deps.storage, &channel)?;
+    let metadata: Metadata = from_json(counterparty_version.as_bytes())
+        .map_err(|_| ContractError::ChannelError(InvalidVersion))?;
+    if !metadata.is_supported() {
+        return Err(ContractError::ChannelError(InvalidVersion));
+    }
+    if metadata.address.is_empty() {
+        return Err(ContractError::ChannelError(MissingAddress));
+    }
+
+    let state = ChannelState {
+        channel_id: channel.endpoint.channel_id,
+        port_id: channel.endpoint.port_id,
+        connection_id: channel.connection_id,
+        ica_address: metadata.address,
+        status: ChannelStatus::Open,
+    };
+    CHANNEL.save(deps.storage, &state)?;
+    NEXT_SEQUENCE.save(deps.storage, &state.channel_id, &1)?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "channel_connect")
+        .add_attribute("contract", CONTRACT_NAME)
+        .add_attribute("version", CONTRACT_VERSION)
+        .add_attribute("channel_id", state.channel_id)
+        .add_attribute("ica_address", state.ica_address))
+}
+
+pub fn channel_close(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelCloseMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let channel = msg.channel();
+    close_channel(deps.storage, &channel.endpoint.channel_id)?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "channel_close")
+        .add_attribute("contract", CONTRACT_NAME)
+        .add_attribute("version", CONTRACT_VERSION)
+        .add_attribute("channel_id", channel.endpoint.channel_id.as_str()))
+}
+
+// Interchain account hosts never send packets to their controller
+pub fn packet_receive(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: IbcPacketReceiveMsg,
+) -> Result<IbcReceiveResponse, Never> {
+    Ok(IbcReceiveResponse::new(StdAck::error(
+        "interchain account controllers do not receive packets",
+    )))
+}
+
+pub fn packet_ack(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcPacketAckMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let packet = msg.original_packet;
+    let ack: Acknowledgement = from_json(&msg.acknowledgement.data)
+        .map_err(|_| ContractError::PacketError(InvalidAcknowledgement))?;
+
+    let (status, success) = match ack {
+        Acknowledgement::Result(result) => (PacketStatus::Success { result }, true),
+        Acknowledgement::Error(error) => (PacketStatus::Error { error }, false),
+    };
+    PACKETS.save(
+        deps.storage,
+        (&packet.src.channel_id, packet.sequence),
+        &status,
+    )?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "packet_ack")
+        .add_attribute("contract", CONTRACT_NAME)
+        .add_attribute("version", CONTRACT_VERSION)
+        .add_attribute("channel_id", packet.src.channel_id)
+        .add_attribute("sequence", packet.sequence.to_string())
+        .add_attribute("success", success.to_string()))
+}
+
+// A timeout closes the ordered channel, it must be created again before sending more packets
+pub fn packet_timeout(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcPacketTimeoutMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let packet = msg.packet;
+    PACKETS.save(
+        deps.storage,
+        (&packet.src.channel_id, packet.sequence),
+        &PacketStatus::Timeout {},
+    )?;
+    close_channel(deps.storage, &packet.src.channel_id)?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "packet_timeout")
+        .add_attribute("contract", CONTRACT_NAME)
+        .add_attribute("version", CONTRACT_VERSION)
+        .add_attribute("channel_id", packet.src.channel_id)
+        .add_attribute("sequence", packet.sequence.to_string()))
+}
+
+// Check the channel parameters and return its version metadata
+fn validate_channel(
+    storage: &dyn Storage,
+    channel: &IbcChannel,
+) -> Result<Metadata, ContractError> {
+    if channel.order != IbcOrder::Ordered {
+        return Err(ContractError::ChannelError(InvalidOrder));
+    }
+    if channel.counterparty_endpoint.port_id != ICA_HOST_PORT {
+        return Err(ContractError::ChannelError(InvalidCounterpartyPort));
+    }
+    let metadata: Metadata = from_json(channel.version.as_bytes())
+        .map_err(|_| ContractError::ChannelError(InvalidVersion))?;
+    if !metadata.is_supported() {
+        return Err(ContractError::ChannelError(InvalidVersion));
+    }
+    let config = CONFIG.load(storage)?;
+    if channel.connection_id != config.connection_id
+        || metadata.controller_connection_id != config.connection_id
+    {
+        return Err(ContractError::ChannelError(InvalidConnection));
+    }
+    Ok(metadata)
+}
+
+fn close_channel(storage: &mut dyn Storage, channel_id: &str) -> Result<(), ContractError> {
+    if let Some(mut channel) = CHANNEL.may_load(storage)? {
+        if channel.channel_id == channel_id {
+            channel.status = ChannelStatus::Closed;
+            CHANNEL.save(storage, &channel)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use crate::consts::{ICA_ENCODING, ICA_TX_TYPE, ICA_VERSION};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{AnyMsg, Binary};
+use manifest_std::google::protobuf::Any;
+use prost::Message;
+
+// ICS-27 channel version, negotiated as JSON metadata. The host fills in the address of the
+// interchain account it registered for the channel
+#[cw_serde]
+pub struct Metadata {
+    pub version: String,
+    pub controller_connection_id: String,
+    pub host_connection_id: String,
+    #[serde(default)]
+    pub address: String,
+    pub encoding: String,
+    pub tx_type: String,
+}
+
+impl Metadata {
+    pub fn new(controller_connection_id: &str, host_connection_id: &str) -> Self {
+        Self {
+            version: ICA_VERSION.to_string(),
+            controller_connection_id: controller_connection_id.to_string(),
+            host_connection_id: host_connection_id.to_string(),
+            address: String::new(),
+            encoding: ICA_ENCODING.to_string(),
+            tx_type: ICA_TX_TYPE.to_string(),
+        }
+    }
+
+    // Whether the metadata describes the channel parameters this controller supports
+    pub fn is_supported(&self) -> bool {
+        self.version == ICA_VERSION && self.encoding == ICA_ENCODING && self.tx_type == ICA_TX_TYPE
+    }
+}
+
+// Interchain account packet, its data is a proto encoded CosmosTx
+#[cw_serde]
+pub struct PacketData {
+    pub r#type: String,
+    pub data: Binary,
+    #[serde(default)]
+    pub memo: String,
+}
+
+impl PacketData {
+    pub const EXECUTE_TX: &'static str = "TYPE_EXECUTE_TX";
+
+    pub fn execute_tx(messages: Vec<AnyMsg>, memo: String) -> Self {
+        let tx = CosmosTx {
+            messages: messages
+                .into_iter()
+                .map(|msg| Any {
+                    type_url: msg.type_url,
+                    value: msg.value.to_vec(),
+                })
+                .collect(),
+        };
+        Self {
+            r#type: Self::EXECUTE_TX.to_string(),
+            data: tx.encode_to_vec().into(),
+            memo,
+        }
+    }
+}
+
+// Channel acknowledgement of the host, the result holds the proto encoded tx message responses
+#[cw_serde]
+pub enum Acknowledgement {
+    Result(Binary),
+    Error(String),
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CosmosTx {
+    #[prost(message, repeated, tag = "1")]
+    pub messages: Vec<Any>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{from_json, to_json_string};
+
+    #[test]
+    fn metadata_roundtrip() {
+        let metadata = Metadata::new("connection-0", "connection-1");
+        assert!(metadata.is_supported());
+        let json = to_json_string(&metadata).unwrap();
+        assert_eq!(
+            json,
+            r#"{"version":"ics27-1","controller_connection_id":"connection-0","host_connection_id":"connection-1","address":"","encoding":"proto3","tx_type":"sdk_multi_msg"}"#
+        );
+        assert_eq!(from_json::<Metadata>(json).unwrap(), metadata);
+    }
+
+    #[test]
+    fn metadata_unsupported() {
+        let mut metadata = Metadata::new("connection-0", "connection-1");
+        metadata.encoding = "proto3json".to_string();
+        assert!(!metadata.is_supported());
+    }
+
+    #[test]
+    fn execute_tx() {
+        let msg = AnyMsg {
+            type_url: "/test.Msg".to_string(),
+            value: Binary::from(vec![1, 2, 3]),
+        };
+        let packet = PacketData::execute_tx(vec![msg], "memo".to_string());
+        assert_eq!(packet.r#type, PacketData::EXECUTE_TX);
+        assert_eq!(packet.memo, "memo");
+        let tx = CosmosTx::decode(packet.data.as_slice()).unwrap();
+        assert_eq!(
+            tx.messages,
+            vec![Any {
+                type_url: "/test.Msg".to_string(),
+                value: vec![1, 2, 3],
+            }]
+        );
+        let json = to_json_string(&packet).unwrap();
+        assert!(json.starts_with(r#"{"type":"TYPE_EXECUTE_TX","data":""#));
+    }
+
+    #[test]
+    fn acknowledgement() {
+        assert_eq!(
+            from_json::<Acknowledgement>(r#"{"result":"AQI="}"#).unwrap(),
+            Acknowledgement::Result(Binary::from(vec![1, 2]))
+        );
+        assert_eq!(
+            from_json::<Acknowledgement>(r#"{"error":"failed"}"#).unwrap(),
+            Acknowledgement::Error("failed".to_string())
+        );
+    }
+}
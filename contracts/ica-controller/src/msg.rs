@@ -0,0 +1,50 @@
+use crate::state::{ChannelState, Config, PacketStatus};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::AnyMsg;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub admin: String,
+    pub connection_id: String,
+    pub counterparty_connection_id: String,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    // Open the interchain account channel, or reopen it after it was closed by a timeout
+    CreateChannel {},
+    // Execute the messages as the interchain account on the counterparty chain
+    SendTx {
+        messages: Vec<AnyMsg>,
+        #[serde(default)]
+        memo: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timeout_seconds: Option<u64>,
+    },
+    UpdateAdmin {
+        admin: String,
+    },
+}
+
+#[cw_serde]
+pub enum QueryMsg {
+    Config {},
+    Admin {},
+    Channel {},
+    Packet { channel_id: String, sequence: u64 },
+}
+
+pub type ConfigResponse = Config;
+
+#[cw_serde]
+pub struct ChannelResponse {
+    pub channel: Option<ChannelState>,
+}
+
+#[cw_serde]
+pub struct PacketResponse {
+    pub status: Option<PacketStatus>,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
@@ -0,0 +1,43 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Binary;
+use cw_controllers::Admin;
+use cw_storage_plus::{Item, Map};
+
+#[cw_serde]
+pub struct Config {
+    // Connection the interchain account channel is opened on, and its counterparty
+    pub connection_id: String,
+    pub counterparty_connection_id: String,
+}
+
+#[cw_serde]
+pub enum ChannelStatus {
+    Open,
+    Closed,
+}
+
+#[cw_serde]
+pub struct ChannelState {
+    pub channel_id: String,
+    pub port_id: String,
+    pub connection_id: String,
+    // Address of the interchain account on the counterparty chain
+    pub ica_address: String,
+    pub status: ChannelStatus,
+}
+
+#[cw_serde]
+pub enum PacketStatus {
+    Pending {},
+    Success { result: Binary },
+    Error { error: String },
+    Timeout {},
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const ADMIN: Admin = Admin::new("admin");
+pub const CHANNEL: Item<ChannelState> = Item::new("channel");
+// Sequence of the next packet sent on a channel. ICA channels are ordered, so sequences are
+// assigned in sending order starting from 1
+pub const NEXT_SEQUENCE: Map<&str, u64> = Map::new("next_sequence");
+pub const PACKETS: Map<(&str, u64), PacketStatus> = Map::new("packets");
@@ -0,0 +1,336 @@
+use cosmwasm_std::testing::{
+    message_info, mock_dependencies, mock_env, mock_ibc_channel, mock_ibc_packet_ack,
+    mock_ibc_packet_recv, mock_ibc_packet_timeout, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    from_json, Addr, AnyMsg, Binary, CosmosMsg, IbcAcknowledgement, IbcChannel, IbcChannelCloseMsg,
+    IbcChannelConnectMsg, IbcChannelOpenMsg, IbcMsg, IbcOrder, OwnedDeps, StdAck,
+};
+use ica_controller::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use ica_controller::{
+    execute, ibc_channel_close, ibc_channel_connect, ibc_channel_open, ibc_packet_ack,
+    ibc_packet_receive, ibc_packet_timeout, instantiate, query,
+};
+use rstest::*;
+use serde_json::{json, Value};
+
+type Deps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+const CONNECTION_ID: &str = "connection-2";
+const COUNTERPARTY_CONNECTION_ID: &str = "connection-5";
+const CHANNEL_ID: &str = "channel-0";
+const ICA_ADDRESS: &str = "host1ica";
+
+fn admin(deps: &Deps) -> Addr {
+    deps.api.addr_make("admin")
+}
+
+fn metadata(address: &str) -> String {
+    json!({
+        "version": "ics27-1",
+        "controller_connection_id": CONNECTION_ID,
+        "host_connection_id": COUNTERPARTY_CONNECTION_ID,
+        "address": address,
+        "encoding": "proto3",
+        "tx_type": "sdk_multi_msg",
+    })
+    .to_string()
+}
+
+fn ica_channel(version: &str) -> IbcChannel {
+    let mut channel = mock_ibc_channel(CHANNEL_ID, IbcOrder::Ordered, version);
+    channel.counterparty_endpoint.port_id = "icahost".to_string();
+    channel
+}
+
+fn query_json(deps: &Deps, msg: QueryMsg) -> Value {
+    from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+}
+
+fn send_tx_msg() -> ExecuteMsg {
+    ExecuteMsg::SendTx {
+        messages: vec![AnyMsg {
+            type_url: "/liftedinit.manifest.v1.MsgBurnHeldBalance".to_string(),
+            value: Binary::from(vec![1, 2, 3]),
+        }],
+        memo: String::new(),
+        timeout_seconds: None,
+    }
+}
+
+#[fixture]
+fn setup() -> Deps {
+    let mut deps = mock_dependencies();
+    let admin = admin(&deps);
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&admin, &[]),
+        InstantiateMsg {
+            admin: admin.to_string(),
+            connection_id: CONNECTION_ID.to_string(),
+            counterparty_connection_id: COUNTERPARTY_CONNECTION_ID.to_string(),
+        },
+    )
+    .unwrap();
+    deps
+}
+
+// Run the controller side of the handshake up to an open channel
+#[fixture]
+fn connected(mut setup: Deps) -> Deps {
+    ibc_channel_open(
+        setup.as_mut(),
+        mock_env(),
+        IbcChannelOpenMsg::new_init(ica_channel(&metadata(""))),
+    )
+    .unwrap();
+    ibc_channel_connect(
+        setup.as_mut(),
+        mock_env(),
+        IbcChannelConnectMsg::new_ack(ica_channel(&metadata("")), metadata(ICA_ADDRESS)),
+    )
+    .unwrap();
+    setup
+}
+
+#[rstest]
+fn create_channel(mut setup: Deps) {
+    let admin = admin(&setup);
+    let res = execute(
+        setup.as_mut(),
+        mock_env(),
+        message_info(&admin, &[]),
+        ExecuteMsg::CreateChannel {},
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    let CosmosMsg::Any(msg) = &res.messages[0].msg else {
+        panic!("expected an any message");
+    };
+    assert_eq!(msg.type_url, "/ibc.core.channel.v1.MsgChannelOpenInit");
+}
+
+#[rstest]
+fn create_channel_unauthorized(mut setup: Deps) {
+    let sender = setup.api.addr_make("sender");
+    let err = execute(
+        setup.as_mut(),
+        mock_env(),
+        message_info(&sender, &[]),
+        ExecuteMsg::CreateChannel {},
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("only admin"));
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::ok(IbcChannelOpenMsg::new_init(ica_channel(&metadata(""))), None)]
+#[case::open_try(IbcChannelOpenMsg::new_try(ica_channel(&metadata("")), metadata("")), Some("only the controller can initiate a channel"))]
+#[case::unordered(IbcChannelOpenMsg::new_init(mock_ibc_channel(CHANNEL_ID, IbcOrder::Unordered, &metadata(""))), Some("channel must be ordered"))]
+#[case::wrong_port(IbcChannelOpenMsg::new_init(mock_ibc_channel(CHANNEL_ID, IbcOrder::Ordered, &metadata(""))), Some("counterparty port must be the ica host port"))]
+#[case::invalid_version(IbcChannelOpenMsg::new_init(ica_channel("ics20-1")), Some("invalid channel version"))]
+#[case::wrong_connection(IbcChannelOpenMsg::new_init(ica_channel(&metadata("").replace(CONNECTION_ID, "connection-9"))), Some("channel connection does not match"))]
+fn channel_open(mut setup: Deps, #[case] msg: IbcChannelOpenMsg, #[case] err: Option<&str>) {
+    let res = ibc_channel_open(setup.as_mut(), mock_env(), msg);
+    match err {
+        None => assert!(res.unwrap().is_some()),
+        Some(err) => assert!(res.unwrap_err().to_string().contains(err)),
+    }
+}
+
+#[rstest]
+fn channel_connect(connected: Deps) {
+    assert_eq!(
+        query_json(&connected, QueryMsg::Channel {}),
+        json!({"channel": {
+            "channel_id": CHANNEL_ID,
+            "port_id": "my_port",
+            "connection_id": CONNECTION_ID,
+            "ica_address": ICA_ADDRESS,
+            "status": "open",
+        }})
+    );
+}
+
+#[rstest]
+fn channel_connect_without_address(mut setup: Deps) {
+    let err = ibc_channel_connect(
+        setup.as_mut(),
+        mock_env(),
+        IbcChannelConnectMsg::new_ack(ica_channel(&metadata("")), metadata("")),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("interchain account address"));
+}
+
+#[rstest]
+fn channel_already_open(mut connected: Deps) {
+    let admin = admin(&connected);
+    let err = execute(
+        connected.as_mut(),
+        mock_env(),
+        message_info(&admin, &[]),
+        ExecuteMsg::CreateChannel {},
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("channel is already open"));
+    let err = ibc_channel_open(
+        connected.as_mut(),
+        mock_env(),
+        IbcChannelOpenMsg::new_init(ica_channel(&metadata(""))),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("channel is already open"));
+}
+
+#[rstest]
+fn send_tx_without_channel(mut setup: Deps) {
+    let admin = admin(&setup);
+    let err = execute(
+        setup.as_mut(),
+        mock_env(),
+        message_info(&admin, &[]),
+        send_tx_msg(),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("no open channel"));
+}
+
+#[rstest]
+fn send_tx_and_ack(mut connected: Deps) {
+    let admin = admin(&connected);
+    let res = execute(
+        connected.as_mut(),
+        mock_env(),
+        message_info(&admin, &[]),
+        send_tx_msg(),
+    )
+    .unwrap();
+    let CosmosMsg::Ibc(IbcMsg::SendPacket {
+        channel_id, data, ..
+    }) = &res.messages[0].msg
+    else {
+        panic!("expected a packet");
+    };
+    assert_eq!(channel_id, CHANNEL_ID);
+    let packet: Value = from_json(data).unwrap();
+    assert_eq!(packet["type"], "TYPE_EXECUTE_TX");
+
+    let packet_query = || QueryMsg::Packet {
+        channel_id: CHANNEL_ID.to_string(),
+        sequence: 1,
+    };
+    assert_eq!(
+        query_json(&connected, packet_query()),
+        json!({"status": {"pending": {}}})
+    );
+
+    let mut ack = mock_ibc_packet_ack(
+        CHANNEL_ID,
+        &packet,
+        IbcAcknowledgement::new(br#"{"result":"AQI="}"#),
+    )
+    .unwrap();
+    ack.original_packet.sequence = 1;
+    ibc_packet_ack(connected.as_mut(), mock_env(), ack).unwrap();
+    assert_eq!(
+        query_json(&connected, packet_query()),
+        json!({"status": {"success": {"result": "AQI="}}})
+    );
+}
+
+#[rstest]
+fn send_tx_error_ack(mut connected: Deps) {
+    let ack = mock_ibc_packet_ack(
+        CHANNEL_ID,
+        &json!({}),
+        IbcAcknowledgement::new(br#"{"error":"ABCI code: 5"}"#),
+    )
+    .unwrap();
+    let sequence = ack.original_packet.sequence;
+    ibc_packet_ack(connected.as_mut(), mock_env(), ack).unwrap();
+    assert_eq!(
+        query_json(
+            &connected,
+            QueryMsg::Packet {
+                channel_id: CHANNEL_ID.to_string(),
+                sequence,
+            }
+        ),
+        json!({"status": {"error": {"error": "ABCI code: 5"}}})
+    );
+}
+
+#[rstest]
+fn invalid_ack(mut connected: Deps) {
+    let ack =
+        mock_ibc_packet_ack(CHANNEL_ID, &json!({}), IbcAcknowledgement::new(b"garbage")).unwrap();
+    let err = ibc_packet_ack(connected.as_mut(), mock_env(), ack).unwrap_err();
+    assert!(err.to_string().contains("invalid acknowledgement"));
+}
+
+#[rstest]
+fn timeout_closes_channel(mut connected: Deps) {
+    let timeout = mock_ibc_packet_timeout(CHANNEL_ID, &json!({})).unwrap();
+    ibc_packet_timeout(connected.as_mut(), mock_env(), timeout).unwrap();
+    assert_eq!(
+        query_json(&connected, QueryMsg::Channel {})["channel"]["status"],
+        "closed"
+    );
+
+    // Sending requires reopening the channel, which is now allowed
+    let admin = admin(&connected);
+    let err = execute(
+        connected.as_mut(),
+        mock_env(),
+        message_info(&admin, &[]),
+        send_tx_msg(),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("no open channel"));
+    execute(
+        connected.as_mut(),
+        mock_env(),
+        message_info(&admin, &[]),
+        ExecuteMsg::CreateChannel {},
+    )
+    .unwrap();
+}
+
+#[rstest]
+fn channel_close(mut connected: Deps) {
+    ibc_channel_close(
+        connected.as_mut(),
+        mock_env(),
+        IbcChannelCloseMsg::new_confirm(ica_channel(&metadata(""))),
+    )
+    .unwrap();
+    assert_eq!(
+        query_json(&connected, QueryMsg::Channel {})["channel"]["status"],
+        "closed"
+    );
+}
+
+#[rstest]
+fn packet_receive(mut connected: Deps) {
+    let msg = mock_ibc_packet_recv(CHANNEL_ID, &json!({})).unwrap();
+    let res = ibc_packet_receive(connected.as_mut(), mock_env(), msg).unwrap();
+    let ack: StdAck = from_json(res.acknowledgement.unwrap()).unwrap();
+    assert!(!ack.is_success());
+}
+
+#[rstest]
+#[case::no_messages(ExecuteMsg::SendTx { messages: vec![], memo: String::new(), timeout_seconds: None }, "no messages to send")]
+#[case::zero_timeout(ExecuteMsg::SendTx { messages: vec![AnyMsg { type_url: "/a".to_string(), value: Binary::default() }], memo: String::new(), timeout_seconds: Some(0) }, "timeout must be greater than zero")]
+fn send_tx_invalid(mut connected: Deps, #[case] msg: ExecuteMsg, #[case] err: &str) {
+    let admin = admin(&connected);
+    let res = execute(
+        connected.as_mut(),
+        mock_env(),
+        message_info(&admin, &[]),
+        msg,
+    );
+    assert!(res.unwrap_err().to_string().contains(err));
+}
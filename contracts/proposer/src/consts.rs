@@ -0,0 +1,3 @@
+pub const CONTRACT_NAME: &str = "manifest/proposer";
+
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -0,0 +1,189 @@
+use crate::consts::{CONTRACT_NAME, CONTRACT_VERSION};
+use crate::error::AmountError::NonPayable;
+use crate::error::ContractError;
+use crate::error::MigrateError::InvalidContractName;
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::state::{Config, ADMIN, CONFIG};
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, MigrateInfo, Response, StdResult,
+};
+use cw2::{get_contract_version, set_contract_version};
+use cw_utils::nonpayable;
+
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+    let admin = deps.api.addr_validate(&msg.admin)?;
+
+    let config = Config {
+        group_policy: deps.api.addr_validate(&msg.group_policy)?,
+        converter: deps.api.addr_validate(&msg.converter)?,
+    };
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    CONFIG.save(deps.storage, &config)?;
+    ADMIN.set(deps, Some(admin))?;
+
+    Ok(Response::new().add_attribute("action", "instantiate"))
+}
+
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    use QueryMsg::*;
+
+    match msg {
+        Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
+        Admin {} => to_json_binary(&ADMIN.query_admin(deps)?),
+    }
+}
+
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    use ExecuteMsg::*;
+
+    match msg {
+        Propose {
+            action,
+            title,
+            summary,
+            try_exec,
+        } => exec::propose(deps, env, info, action, title, summary, try_exec),
+        UpdateConfig {
+            group_policy,
+            converter,
+        } => exec::update_config(deps, info, group_policy, converter),
+        UpdateAdmin { admin } => exec::update_admin(deps, info, admin),
+    }
+}
+
+pub fn migrate(
+    deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+    _info: MigrateInfo,
+) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::MigrateError(InvalidContractName));
+    }
+
+    if stored.version == CONTRACT_VERSION {
+        return Ok(Response::new()
+            .add_attribute("action", "migrate")
+            .add_attribute("note", "already at latest version")
+            .add_attribute("version", CONTRACT_VERSION));
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("contract", CONTRACT_NAME)
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", CONTRACT_VERSION))
+}
+
+mod exec {
+    use super::*;
+    use crate::error::AdminError::NotAdmin;
+    use crate::error::ProposalError::{EmptyTitle, NothingToUpdate};
+    use crate::msg::ConverterAction;
+    use crate::proposal::{converter_msg, submit_proposal};
+
+    // Submit the action as a proposal to the group policy, on behalf of the sender
+    pub fn propose(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        action: ConverterAction,
+        title: String,
+        summary: String,
+        try_exec: bool,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        if title.trim().is_empty() {
+            return Err(ContractError::ProposalError(EmptyTitle));
+        }
+
+        let config = CONFIG.load(deps.storage)?;
+        let message = converter_msg(&action, &config.group_policy, &config.converter)?;
+        let msg = submit_proposal(
+            &env.contract.address,
+            &info.sender,
+            &config.group_policy,
+            message,
+            title,
+            summary,
+            try_exec,
+        );
+
+        Ok(Response::new()
+            .add_message(msg)
+            .add_attribute("action", "propose")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("proposer", info.sender)
+            .add_attribute("group_policy", config.group_policy)
+            .add_attribute("converter", config.converter)
+            .add_attribute("converter_action", action.name())
+            .add_attribute("try_exec", try_exec.to_string()))
+    }
+
+    pub fn update_config(
+        deps: DepsMut,
+        info: MessageInfo,
+        group_policy: Option<String>,
+        converter: Option<String>,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        ADMIN
+            .assert_admin(deps.as_ref(), &info.sender)
+            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+
+        if group_policy.is_none() && converter.is_none() {
+            return Err(ContractError::ProposalError(NothingToUpdate));
+        }
+
+        let mut config = CONFIG.load(deps.storage)?;
+        if let Some(group_policy) = group_policy {
+            config.group_policy = deps.api.addr_validate(&group_policy)?;
+        }
+        if let Some(converter) = converter {
+            config.converter = deps.api.addr_validate(&converter)?;
+        }
+        CONFIG.save(deps.storage, &config)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "update_config")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("group_policy", config.group_policy)
+            .add_attribute("converter", config.converter))
+    }
+
+    pub fn update_admin(
+        deps: DepsMut,
+        info: MessageInfo,
+        admin: String,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info).map_err(|_| ContractError::AmountError(NonPayable))?;
+        let new = deps.api.addr_validate(&admin)?;
+        let res = ADMIN
+            .execute_update_admin(deps, info, Some(new))
+            .map_err(|_| ContractError::AdminError(NotAdmin))?;
+        Ok(res
+            .add_attribute("action", "update_admin")
+            .add_attribute("contract", CONTRACT_NAME)
+            .add_attribute("version", CONTRACT_VERSION)
+            .add_attribute("new_admin", admin))
+    }
+}
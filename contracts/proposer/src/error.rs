@@ -0,0 +1,26 @@
+use cosmwasm_std::StdError;
+pub use manifest_errors::{AdminError, AmountError, MigrateError};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[allow(clippy::enum_variant_names)]
+pub enum ContractError {
+    #[error("{0}")]
+    StdError(#[from] StdError),
+    #[error("unauthorized: {0}")]
+    AdminError(#[from] AdminError),
+    #[error("invalid amount: {0}")]
+    AmountError(#[from] AmountError),
+    #[error("proposal error: {0}")]
+    ProposalError(#[from] ProposalError),
+    #[error("migration error: {0}")]
+    MigrateError(#[from] MigrateError),
+}
+
+#[derive(Error, Debug)]
+pub enum ProposalError {
+    #[error("proposal title cannot be empty")]
+    EmptyTitle,
+    #[error("nothing to update")]
+    NothingToUpdate,
+}
@@ -0,0 +1,66 @@
+use crate::state::Config;
+use cosmwasm_schema::cw_serde;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub admin: String,
+    pub group_policy: String,
+    pub converter: String,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    // Submit a proposal executing the action on the converter to the group policy. The sender
+    // is the proposer and must be a group member that granted the contract a
+    // MsgSubmitProposal authorization
+    Propose {
+        action: ConverterAction,
+        title: String,
+        #[serde(default)]
+        summary: String,
+        // Try to execute the proposal right away, which succeeds when the proposer's vote is
+        // enough to pass it
+        #[serde(default)]
+        try_exec: bool,
+    },
+    UpdateConfig {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        group_policy: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        converter: Option<String>,
+    },
+    UpdateAdmin {
+        admin: String,
+    },
+}
+
+// Converter admin actions that can be proposed
+#[cw_serde]
+pub enum ConverterAction {
+    UpdateRate { rate: String },
+    Pause {},
+    Unpause {},
+    Migrate { code_id: u64 },
+}
+
+impl ConverterAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConverterAction::UpdateRate { .. } => "update_rate",
+            ConverterAction::Pause {} => "pause",
+            ConverterAction::Unpause {} => "unpause",
+            ConverterAction::Migrate { .. } => "migrate",
+        }
+    }
+}
+
+#[cw_serde]
+pub enum QueryMsg {
+    Config {},
+    Admin {},
+}
+
+pub type ConfigResponse = Config;
+
+#[cw_serde]
+pub struct MigrateMsg {}
@@ -0,0 +1,207 @@
+use crate::msg::ConverterAction;
+use crate::proto::{MsgExecuteContract, MsgMigrateContract};
+use converter::msg::{ExecuteMsg as ConverterExecuteMsg, MigrateMsg, UpdateConfig};
+use cosmwasm_std::{to_json_vec, Addr, AnyMsg, CosmosMsg, StdResult};
+use manifest_std::cosmos::authz::v1beta1::MsgExec;
+use manifest_std::cosmos::group::v1::{Exec, MsgSubmitProposal};
+use manifest_std::google::protobuf::Any;
+use prost::Message;
+
+// The message the group policy executes on the converter once the proposal passes
+pub fn converter_msg(
+    action: &ConverterAction,
+    group_policy: &Addr,
+    converter: &Addr,
+) -> StdResult<Any> {
+    let update_config = |config: UpdateConfig| -> StdResult<Any> {
+        let msg = MsgExecuteContract {
+            sender: group_policy.to_string(),
+            contract: converter.to_string(),
+            msg: to_json_vec(&ConverterExecuteMsg::UpdateConfig {
+                config: Box::new(config),
+                strict: None,
+            })?,
+            funds: vec![],
+        };
+        Ok(Any {
+            type_url: MsgExecuteContract::TYPE_URL.to_string(),
+            value: msg.encode_to_vec(),
+        })
+    };
+
+    match action {
+        ConverterAction::UpdateRate { rate } => update_config(UpdateConfig {
+            rate: Some(rate.clone()),
+            ..Default::default()
+        }),
+        ConverterAction::Pause {} => update_config(UpdateConfig {
+            paused: Some(true),
+            ..Default::default()
+        }),
+        ConverterAction::Unpause {} => update_config(UpdateConfig {
+            paused: Some(false),
+            ..Default::default()
+        }),
+        ConverterAction::Migrate { code_id } => {
+            let msg = MsgMigrateContract {
+                sender: group_policy.to_string(),
+                contract: converter.to_string(),
+                code_id: *code_id,
+                msg: to_json_vec(&MigrateMsg {})?,
+            };
+            Ok(Any {
+                type_url: MsgMigrateContract::TYPE_URL.to_string(),
+                value: msg.encode_to_vec(),
+            })
+        }
+    }
+}
+
+// Submit the proposal on behalf of the proposer through the authz grant it gave the contract
+pub fn submit_proposal(
+    grantee: &Addr,
+    proposer: &Addr,
+    group_policy: &Addr,
+    message: Any,
+    title: String,
+    summary: String,
+    try_exec: bool,
+) -> CosmosMsg {
+    let exec = if try_exec {
+        Exec::Try
+    } else {
+        Exec::Unspecified
+    };
+    let submit = MsgSubmitProposal {
+        group_policy_address: group_policy.to_string(),
+        proposers: vec![proposer.to_string()],
+        metadata: String::new(),
+        messages: vec![message],
+        exec: exec.into(),
+        title,
+        summary,
+    };
+    let msg_exec = MsgExec {
+        grantee: grantee.to_string(),
+        msgs: vec![Any {
+            type_url: MsgSubmitProposal::TYPE_URL.to_string(),
+            value: submit.encode_to_vec(),
+        }],
+    };
+    CosmosMsg::Any(AnyMsg {
+        type_url: MsgExec::TYPE_URL.to_string(),
+        value: msg_exec.encode_to_vec().into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::from_json;
+
+    fn decode_execute(any: &Any) -> (MsgExecuteContract, ConverterExecuteMsg) {
+        assert_eq!(any.type_url, MsgExecuteContract::TYPE_URL);
+        let msg = MsgExecuteContract::decode(any.value.as_slice()).unwrap();
+        let inner = from_json(&msg.msg).unwrap();
+        (msg, inner)
+    }
+
+    #[test]
+    fn update_rate() {
+        let policy = Addr::unchecked("policy");
+        let converter = Addr::unchecked("converter");
+        let any = converter_msg(
+            &ConverterAction::UpdateRate {
+                rate: "0.5".to_string(),
+            },
+            &policy,
+            &converter,
+        )
+        .unwrap();
+        let (msg, inner) = decode_execute(&any);
+        assert_eq!(msg.sender, "policy");
+        assert_eq!(msg.contract, "converter");
+        assert!(msg.funds.is_empty());
+        assert_eq!(
+            inner,
+            ConverterExecuteMsg::UpdateConfig {
+                config: Box::new(UpdateConfig {
+                    rate: Some("0.5".to_string()),
+                    ..Default::default()
+                }),
+                strict: None,
+            }
+        );
+    }
+
+    #[test]
+    fn pause_and_unpause() {
+        let policy = Addr::unchecked("policy");
+        let converter = Addr::unchecked("converter");
+        for (action, paused) in [
+            (ConverterAction::Pause {}, true),
+            (ConverterAction::Unpause {}, false),
+        ] {
+            let any = converter_msg(&action, &policy, &converter).unwrap();
+            let (_, inner) = decode_execute(&any);
+            assert_eq!(
+                inner,
+                ConverterExecuteMsg::UpdateConfig {
+                    config: Box::new(UpdateConfig {
+                        paused: Some(paused),
+                        ..Default::default()
+                    }),
+                    strict: None,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn migrate() {
+        let any = converter_msg(
+            &ConverterAction::Migrate { code_id: 7 },
+            &Addr::unchecked("policy"),
+            &Addr::unchecked("converter"),
+        )
+        .unwrap();
+        assert_eq!(any.type_url, MsgMigrateContract::TYPE_URL);
+        let msg = MsgMigrateContract::decode(any.value.as_slice()).unwrap();
+        assert_eq!(msg.sender, "policy");
+        assert_eq!(msg.contract, "converter");
+        assert_eq!(msg.code_id, 7);
+        assert_eq!(msg.msg, b"{}");
+    }
+
+    #[test]
+    fn submit() {
+        let message = Any {
+            type_url: "/test".to_string(),
+            value: vec![1],
+        };
+        let msg = submit_proposal(
+            &Addr::unchecked("proposer_contract"),
+            &Addr::unchecked("member"),
+            &Addr::unchecked("policy"),
+            message.clone(),
+            "title".to_string(),
+            "summary".to_string(),
+            true,
+        );
+        let CosmosMsg::Any(any) = msg else {
+            panic!("expected an any message");
+        };
+        assert_eq!(any.type_url, MsgExec::TYPE_URL);
+        let exec = MsgExec::decode(any.value.as_slice()).unwrap();
+        assert_eq!(exec.grantee, "proposer_contract");
+        assert_eq!(exec.msgs.len(), 1);
+        assert_eq!(exec.msgs[0].type_url, MsgSubmitProposal::TYPE_URL);
+        let submit = MsgSubmitProposal::decode(exec.msgs[0].value.as_slice()).unwrap();
+        assert_eq!(submit.group_policy_address, "policy");
+        assert_eq!(submit.proposers, vec!["member".to_string()]);
+        assert_eq!(submit.messages, vec![message]);
+        assert_eq!(submit.exec, i32::from(Exec::Try));
+        assert_eq!(submit.title, "title");
+        assert_eq!(submit.summary, "summary");
+    }
+}
@@ -0,0 +1,33 @@
+// Messages of the wasm module, which manifest-std does not ship
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgExecuteContract {
+    #[prost(string, tag = "1")]
+    pub sender: String,
+    #[prost(string, tag = "2")]
+    pub contract: String,
+    #[prost(bytes = "vec", tag = "3")]
+    pub msg: Vec<u8>,
+    #[prost(message, repeated, tag = "5")]
+    pub funds: Vec<manifest_std::cosmos::base::v1beta1::Coin>,
+}
+
+impl MsgExecuteContract {
+    pub const TYPE_URL: &'static str = "/cosmwasm.wasm.v1.MsgExecuteContract";
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgMigrateContract {
+    #[prost(string, tag = "1")]
+    pub sender: String,
+    #[prost(string, tag = "2")]
+    pub contract: String,
+    #[prost(uint64, tag = "3")]
+    pub code_id: u64,
+    #[prost(bytes = "vec", tag = "4")]
+    pub msg: Vec<u8>,
+}
+
+impl MsgMigrateContract {
+    pub const TYPE_URL: &'static str = "/cosmwasm.wasm.v1.MsgMigrateContract";
+}
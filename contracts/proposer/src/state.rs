@@ -0,0 +1,15 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Addr;
+use cw_controllers::Admin;
+use cw_storage_plus::Item;
+
+#[cw_serde]
+pub struct Config {
+    // The x/group policy account administering the converter, proposals are submitted to it
+    pub group_policy: Addr,
+    // The converter contract the proposed actions target
+    pub converter: Addr,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const ADMIN: Admin = Admin::new("admin");
@@ -0,0 +1,157 @@
+#![allow(dead_code)] // Allow dead code since not all helpers are used in every test file
+
+use cosmwasm_std::testing::{MockApi, MockStorage};
+use cosmwasm_std::{Addr, Empty};
+use cw_multi_test::{
+    App, AppBuilder, BankKeeper, ContractWrapper, DistributionKeeper, Executor, FailingModule,
+    GovFailingModule, IbcFailingModule, StakeKeeper, StargateAccepting, StargateFailing,
+    WasmKeeper,
+};
+use proposer::{execute, instantiate, migrate, query};
+use rstest::*;
+use serde_json::{json, Value};
+
+const BECH32_PREFIX: &str = "manifest";
+
+// Error messages
+pub const ONLY_ADMIN: &str = "only admin can perform this action";
+pub const NON_PAYABLE: &str = "non-payable function called with funds";
+pub const EMPTY_TITLE: &str = "proposal title cannot be empty";
+pub const NOTHING_TO_UPDATE: &str = "nothing to update";
+pub const PARSE_FAILED: &str = "parse failed";
+
+// One can't use the `App` type directly when `.with_stargate(StargateAccepting)` is used
+// See https://github.com/CosmWasm/cw-multi-test/issues/285
+pub type AppAccepting<ExecC = Empty, QueryC = Empty> = App<
+    BankKeeper,
+    MockApi,
+    MockStorage,
+    FailingModule<ExecC, QueryC, Empty>,
+    WasmKeeper<ExecC, QueryC>,
+    StakeKeeper,
+    DistributionKeeper,
+    IbcFailingModule,
+    GovFailingModule,
+    StargateAccepting,
+>;
+
+// Same as AppAccepting, but every stargate/any message fails, e.g. a missing authz grant
+pub type AppFailing<ExecC = Empty, QueryC = Empty> = App<
+    BankKeeper,
+    MockApi,
+    MockStorage,
+    FailingModule<ExecC, QueryC, Empty>,
+    WasmKeeper<ExecC, QueryC>,
+    StakeKeeper,
+    DistributionKeeper,
+    IbcFailingModule,
+    GovFailingModule,
+    StargateFailing,
+>;
+
+#[derive(Copy, Clone)]
+pub enum Expect<'a> {
+    Ok,
+    ErrContains(&'a str),
+}
+
+// Generate a valid address with the expected bech32 prefix
+pub fn make_addr(label: &str) -> Addr {
+    MockApi::default()
+        .with_prefix(BECH32_PREFIX)
+        .addr_make(label)
+}
+
+pub fn admin() -> Addr {
+    make_addr("admin")
+}
+
+pub fn group_policy() -> Addr {
+    make_addr("group_policy")
+}
+
+pub fn converter() -> Addr {
+    make_addr("converter")
+}
+
+pub fn member() -> Addr {
+    make_addr("member")
+}
+
+pub fn default_instantiate() -> Value {
+    json!({
+        "admin": admin(),
+        "group_policy": group_policy(),
+        "converter": converter(),
+    })
+}
+
+pub fn create_msg_propose(action: Value, title: &str) -> Value {
+    json!({"propose": {"action": action, "title": title, "summary": "summary"}})
+}
+
+#[fixture]
+pub fn setup() -> (AppAccepting, Addr) {
+    let mut app = AppBuilder::default()
+        .with_api(MockApi::default().with_prefix(BECH32_PREFIX))
+        .with_stargate(StargateAccepting)
+        .build(|_, _, _| {});
+    let code_id = app.store_code(Box::new(
+        ContractWrapper::new_with_empty(execute, instantiate, query).with_migrate(migrate),
+    ));
+    let contract = app
+        .instantiate_contract(
+            code_id,
+            admin(),
+            &default_instantiate(),
+            &[],
+            "proposer",
+            None,
+        )
+        .expect("failed to instantiate");
+    (app, contract)
+}
+
+#[fixture]
+pub fn setup_failing_stargate() -> (AppFailing, Addr) {
+    let mut app = AppBuilder::default()
+        .with_api(MockApi::default().with_prefix(BECH32_PREFIX))
+        .with_stargate(StargateFailing)
+        .build(|_, _, _| {});
+    let code_id = app.store_code(Box::new(
+        ContractWrapper::new_with_empty(execute, instantiate, query).with_migrate(migrate),
+    ));
+    let contract = app
+        .instantiate_contract(
+            code_id,
+            admin(),
+            &default_instantiate(),
+            &[],
+            "proposer",
+            None,
+        )
+        .expect("failed to instantiate");
+    (app, contract)
+}
+
+pub fn run_execute(
+    app: &mut AppAccepting,
+    sender: &Addr,
+    contract: &Addr,
+    msg: &Value,
+    expect: Expect<'_>,
+) {
+    let res = app.execute_contract(sender.clone(), contract.clone(), msg, &[]);
+    match expect {
+        Expect::Ok => {
+            res.expect("expected Ok");
+        }
+        Expect::ErrContains(s) => {
+            let text = format!("{:#}", res.unwrap_err());
+            assert!(
+                text.contains(s),
+                "error didn't contain expected substring.\nGot:\n{text:#}\nExpected to contain:\n{s:#}"
+            );
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use crate::common::*;
+use cosmwasm_std::{coin, Addr, Event};
+use cw_multi_test::Executor;
+use rstest::*;
+use serde_json::{json, Value};
+
+mod common;
+
+#[rustfmt::skip]
+#[rstest]
+#[case::update_rate(json!({"update_rate": {"rate": "0.5"}}), "update_rate")]
+#[case::pause(json!({"pause": {}}), "pause")]
+#[case::unpause(json!({"unpause": {}}), "unpause")]
+#[case::migrate(json!({"migrate": {"code_id": 2}}), "migrate")]
+fn propose(setup: (AppAccepting, Addr), #[case] action: Value, #[case] name: &str) {
+    let (mut app, contract) = setup;
+    let res = app
+        .execute_contract(member(), contract, &create_msg_propose(action, "title"), &[])
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "propose")
+            .add_attribute("proposer", member())
+            .add_attribute("group_policy", group_policy())
+            .add_attribute("converter", converter())
+            .add_attribute("converter_action", name)
+            .add_attribute("try_exec", "false")
+    ));
+}
+
+#[rstest]
+fn propose_empty_title(setup: (AppAccepting, Addr)) {
+    let (mut app, contract) = setup;
+    run_execute(
+        &mut app,
+        &member(),
+        &contract,
+        &create_msg_propose(json!({"pause": {}}), " "),
+        Expect::ErrContains(EMPTY_TITLE),
+    );
+}
+
+#[rstest]
+fn propose_without_grant(setup_failing_stargate: (AppFailing, Addr)) {
+    // Without an authz grant from the proposer, the submission fails
+    let (mut app, contract) = setup_failing_stargate;
+    app.execute_contract(
+        member(),
+        contract,
+        &create_msg_propose(json!({"pause": {}}), "title"),
+        &[],
+    )
+    .unwrap_err();
+}
+
+#[rstest]
+fn propose_with_funds(setup: (AppAccepting, Addr)) {
+    let (mut app, contract) = setup;
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &member(), vec![coin(10, "umfx")])
+            .unwrap();
+    });
+    let err = app
+        .execute_contract(
+            member(),
+            contract,
+            &create_msg_propose(json!({"pause": {}}), "title"),
+            &[coin(10, "umfx")],
+        )
+        .unwrap_err();
+    assert!(format!("{err:#}").contains(NON_PAYABLE));
+}
+
+#[rustfmt::skip]
+#[rstest]
+#[case::group_policy(admin(), json!({"update_config": {"group_policy": make_addr("new_policy")}}), Expect::Ok)]
+#[case::converter(admin(), json!({"update_config": {"converter": make_addr("new_converter")}}), Expect::Ok)]
+#[case::nothing(admin(), json!({"update_config": {}}), Expect::ErrContains(NOTHING_TO_UPDATE))]
+#[case::invalid(admin(), json!({"update_config": {"converter": "invalid"}}), Expect::ErrContains(PARSE_FAILED))]
+#[case::unauthorized(member(), json!({"update_config": {"converter": make_addr("new_converter")}}), Expect::ErrContains(ONLY_ADMIN))]
+#[case::update_admin(admin(), json!({"update_admin": {"admin": member()}}), Expect::Ok)]
+#[case::update_admin_unauthorized(member(), json!({"update_admin": {"admin": member()}}), Expect::ErrContains(ONLY_ADMIN))]
+fn admin_actions(
+    setup: (AppAccepting, Addr),
+    #[case] sender: Addr,
+    #[case] msg: Value,
+    #[case] expect: Expect<'_>,
+) {
+    let (mut app, contract) = setup;
+    run_execute(&mut app, &sender, &contract, &msg, expect);
+}
+
+#[rstest]
+fn query_config(setup: (AppAccepting, Addr)) {
+    let (app, contract) = setup;
+    let config: Value = app
+        .wrap()
+        .query_wasm_smart(&contract, &json!({"config": {}}))
+        .unwrap();
+    assert_eq!(
+        config,
+        json!({"group_policy": group_policy(), "converter": converter()})
+    );
+    let admin_res: Value = app
+        .wrap()
+        .query_wasm_smart(&contract, &json!({"admin": {}}))
+        .unwrap();
+    assert_eq!(admin_res, json!({"admin": admin()}));
+}
@@ -0,0 +1,114 @@
+use cosmwasm_std::{Coin, OverflowError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CoinsError {
+    #[error("no coins")]
+    Empty,
+    #[error("coin amount is zero")]
+    ZeroAmount,
+    #[error("duplicate denom {0}")]
+    DuplicateDenom(String),
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+}
+
+// Sort coins by denom, the order the bank module keeps them in
+pub fn sort_coins(coins: &mut [Coin]) {
+    coins.sort_by(|a, b| a.denom.cmp(&b.denom));
+}
+
+// Merge the coins of the same denom and drop zero amounts, sorted by denom
+pub fn normalize_coins(coins: impl IntoIterator<Item = Coin>) -> Result<Vec<Coin>, CoinsError> {
+    let mut coins: Vec<_> = coins.into_iter().filter(|c| !c.amount.is_zero()).collect();
+    sort_coins(&mut coins);
+    let mut normalized: Vec<Coin> = Vec::with_capacity(coins.len());
+    for coin in coins {
+        match normalized.last_mut() {
+            Some(last) if last.denom == coin.denom => {
+                last.amount = last.amount.checked_add(coin.amount)?;
+            }
+            _ => normalized.push(coin),
+        }
+    }
+    Ok(normalized)
+}
+
+// Ensure a coin list is non-empty, with non-zero amounts and unique denoms
+pub fn validate_coins(coins: &[Coin]) -> Result<(), CoinsError> {
+    if coins.is_empty() {
+        return Err(CoinsError::Empty);
+    }
+    if coins.iter().any(|c| c.amount.is_zero()) {
+        return Err(CoinsError::ZeroAmount);
+    }
+    let mut denoms: Vec<_> = coins.iter().map(|c| c.denom.as_str()).collect();
+    denoms.sort();
+    match denoms.windows(2).find(|w| w[0] == w[1]) {
+        Some(w) => Err(CoinsError::DuplicateDenom(w[0].to_string())),
+        None => Ok(()),
+    }
+}
+
+// Check sent funds against the expected ones, coin for coin in any order
+pub fn same_coins(funds: &[Coin], expected: &[Coin]) -> bool {
+    let mut funds = funds.to_vec();
+    let mut expected = expected.to_vec();
+    sort_coins(&mut funds);
+    sort_coins(&mut expected);
+    funds == expected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{coin, Uint256};
+
+    #[test]
+    fn normalize() {
+        let coins = vec![
+            coin(2, "uatom"),
+            coin(0, "umfx"),
+            coin(1, "upwr"),
+            coin(3, "uatom"),
+        ];
+        assert_eq!(
+            normalize_coins(coins).unwrap(),
+            vec![coin(5, "uatom"), coin(1, "upwr")]
+        );
+        assert_eq!(normalize_coins(vec![coin(0, "umfx")]).unwrap(), vec![]);
+
+        let max = Coin::new(Uint256::MAX, "umfx");
+        assert!(matches!(
+            normalize_coins(vec![max, coin(1, "umfx")]),
+            Err(CoinsError::Overflow(_))
+        ));
+    }
+
+    #[test]
+    fn validate() {
+        assert_eq!(validate_coins(&[coin(1, "umfx"), coin(1, "upwr")]), Ok(()));
+        assert_eq!(validate_coins(&[]), Err(CoinsError::Empty));
+        assert_eq!(
+            validate_coins(&[coin(1, "umfx"), coin(0, "upwr")]),
+            Err(CoinsError::ZeroAmount)
+        );
+        assert_eq!(
+            validate_coins(&[coin(1, "upwr"), coin(1, "umfx"), coin(2, "upwr")]),
+            Err(CoinsError::DuplicateDenom("upwr".to_string()))
+        );
+    }
+
+    #[test]
+    fn same() {
+        let expected = [coin(1, "umfx"), coin(2, "upwr")];
+        assert!(same_coins(&[coin(2, "upwr"), coin(1, "umfx")], &expected));
+        assert!(!same_coins(&[coin(1, "umfx")], &expected));
+        assert!(!same_coins(&[coin(1, "umfx"), coin(3, "upwr")], &expected));
+        // Funds are not merged, a split coin is a different set of funds
+        assert!(!same_coins(
+            &[coin(1, "umfx"), coin(1, "upwr"), coin(1, "upwr")],
+            &expected
+        ));
+    }
+}
@@ -0,0 +1,144 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Binary, Order, StdResult, Storage};
+use cw_storage_plus::{Bound, KeyDeserialize, Map, PrimaryKey};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+// Page size limits of list queries
+pub const DEFAULT_PAGE_LIMIT: u32 = 10;
+pub const MAX_PAGE_LIMIT: u32 = 30;
+
+// Pagination arguments of list queries. `start_after` is the `next_key` of the previous page
+#[cw_serde]
+#[derive(Default)]
+pub struct PageRequest {
+    pub start_after: Option<Binary>,
+    pub limit: Option<u32>,
+}
+
+impl PageRequest {
+    pub fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize
+    }
+}
+
+// A page of a list query. `next_key` is set when more items follow, and is opaque to clients
+#[cw_serde]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_key: Option<Binary>,
+}
+
+// List a page of a map in ascending key order, mapping each entry to a response item
+pub fn paginate<'a, K, V, T>(
+    storage: &dyn Storage,
+    map: &Map<K, V>,
+    request: &PageRequest,
+    mut item: impl FnMut(K::Output, V) -> StdResult<T>,
+) -> StdResult<Page<T>>
+where
+    K: PrimaryKey<'a> + KeyDeserialize,
+    V: Serialize + DeserializeOwned,
+{
+    let limit = request.limit();
+    let min = request
+        .start_after
+        .as_ref()
+        .map(|key| Bound::ExclusiveRaw(key.to_vec()));
+
+    // Read one more entry than requested to know whether another page follows
+    let mut entries = map
+        .range_raw(storage, min, None, Order::Ascending)
+        .take(limit + 1)
+        .collect::<StdResult<Vec<_>>>()?;
+    let next_key = if entries.len() > limit {
+        entries.truncate(limit);
+        entries.last().map(|(key, _)| Binary::new(key.clone()))
+    } else {
+        None
+    };
+
+    let items = entries
+        .into_iter()
+        .map(|(key, value)| item(K::from_vec(key)?, value))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(Page { items, next_key })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockStorage;
+
+    const NUMBERS: Map<u64, String> = Map::new("numbers");
+    const PAIRS: Map<(&str, &str), u64> = Map::new("pairs");
+
+    fn request(start_after: Option<Binary>, limit: u32) -> PageRequest {
+        PageRequest {
+            start_after,
+            limit: Some(limit),
+        }
+    }
+
+    #[test]
+    fn test_page_limit() {
+        assert_eq!(PageRequest::default().limit(), 10);
+        assert_eq!(request(None, 5).limit(), 5);
+        assert_eq!(request(None, 100).limit(), 30);
+    }
+
+    #[test]
+    fn test_paginate() {
+        let mut storage = MockStorage::new();
+        for i in 1..=5u64 {
+            NUMBERS.save(&mut storage, i, &i.to_string()).unwrap();
+        }
+
+        let page = paginate(&storage, &NUMBERS, &request(None, 2), |k, v| Ok((k, v))).unwrap();
+        assert_eq!(page.items, vec![(1, "1".to_string()), (2, "2".to_string())]);
+        let page = paginate(&storage, &NUMBERS, &request(page.next_key, 2), |k, _| Ok(k)).unwrap();
+        assert_eq!(page.items, vec![3, 4]);
+        let page = paginate(&storage, &NUMBERS, &request(page.next_key, 2), |k, _| Ok(k)).unwrap();
+        assert_eq!(page.items, vec![5]);
+        assert_eq!(page.next_key, None);
+    }
+
+    #[test]
+    fn test_paginate_exact_page() {
+        let mut storage = MockStorage::new();
+        for i in 1..=2u64 {
+            NUMBERS.save(&mut storage, i, &i.to_string()).unwrap();
+        }
+        let page = paginate(&storage, &NUMBERS, &request(None, 2), |k, _| Ok(k)).unwrap();
+        assert_eq!(page.items, vec![1, 2]);
+        assert_eq!(page.next_key, None);
+    }
+
+    #[test]
+    fn test_paginate_composite_key() {
+        let mut storage = MockStorage::new();
+        PAIRS.save(&mut storage, ("a", "x"), &1).unwrap();
+        PAIRS.save(&mut storage, ("a", "y"), &2).unwrap();
+        PAIRS.save(&mut storage, ("b", "x"), &3).unwrap();
+
+        let page = paginate(&storage, &PAIRS, &request(None, 2), |k, v| Ok((k, v))).unwrap();
+        assert_eq!(
+            page.items,
+            vec![
+                (("a".to_string(), "x".to_string()), 1),
+                (("a".to_string(), "y".to_string()), 2),
+            ]
+        );
+        let page = paginate(&storage, &PAIRS, &request(page.next_key, 2), |_, v| Ok(v)).unwrap();
+        assert_eq!(page.items, vec![3]);
+        assert_eq!(page.next_key, None);
+    }
+
+    #[test]
+    fn test_paginate_empty() {
+        let storage = MockStorage::new();
+        let page = paginate(&storage, &NUMBERS, &PageRequest::default(), |k, _| Ok(k)).unwrap();
+        assert!(page.items.is_empty());
+        assert_eq!(page.next_key, None);
+    }
+}
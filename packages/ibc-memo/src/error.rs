@@ -0,0 +1,17 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum MemoError {
+    #[error("receiver is empty")]
+    EmptyReceiver,
+    #[error("invalid channel id")]
+    InvalidChannel,
+    #[error("invalid port id")]
+    InvalidPort,
+    #[error("contract is empty")]
+    EmptyContract,
+    #[error("hook message is not a json object")]
+    InvalidHookMsg,
+    #[error("failed to serialize memo: {0}")]
+    Serialize(#[from] serde_json::Error),
+}
@@ -0,0 +1,115 @@
+use crate::error::MemoError;
+use crate::memo::Memo;
+use serde::{Deserialize, Serialize};
+
+// Port of ICS-20 transfers
+const TRANSFER_PORT: &str = "transfer";
+
+// Instructions for packet-forward-middleware to send the received tokens on to the next
+// chain. `receiver` is the address on the next chain, the receiver of the incoming transfer
+// itself is ignored by the middleware
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Forward {
+    pub receiver: String,
+    pub port: String,
+    pub channel: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<Box<Memo>>,
+}
+
+impl Forward {
+    pub fn new(receiver: impl Into<String>, channel: impl Into<String>) -> Self {
+        Self {
+            receiver: receiver.into(),
+            port: TRANSFER_PORT.to_string(),
+            channel: channel.into(),
+            timeout: None,
+            retries: None,
+            next: None,
+        }
+    }
+
+    pub fn port(mut self, port: impl Into<String>) -> Self {
+        self.port = port.into();
+        self
+    }
+
+    // The middleware parses the timeout as a Go duration
+    pub fn timeout_seconds(mut self, seconds: u64) -> Self {
+        self.timeout = Some(format!("{seconds}s"));
+        self
+    }
+
+    pub fn retries(mut self, retries: u8) -> Self {
+        self.retries = Some(retries);
+        self
+    }
+
+    // Memo of the forwarded transfer, e.g. another hop or a contract call on the next chain
+    pub fn next(mut self, memo: Memo) -> Self {
+        self.next = Some(Box::new(memo));
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), MemoError> {
+        if self.receiver.trim().is_empty() {
+            return Err(MemoError::EmptyReceiver);
+        }
+        if self.port.trim().is_empty() {
+            return Err(MemoError::InvalidPort);
+        }
+        if !is_channel_id(&self.channel) {
+            return Err(MemoError::InvalidChannel);
+        }
+        match &self.next {
+            Some(next) => next.validate(),
+            None => Ok(()),
+        }
+    }
+}
+
+fn is_channel_id(s: &str) -> bool {
+    s.strip_prefix("channel-")
+        .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_channel_id() {
+        assert!(is_channel_id("channel-0"));
+        assert!(is_channel_id("channel-142"));
+        assert!(!is_channel_id("channel-"));
+        assert!(!is_channel_id("channel-1a"));
+        assert!(!is_channel_id("connection-0"));
+        assert!(!is_channel_id("0"));
+    }
+
+    #[test]
+    fn test_forward_defaults() {
+        let forward = Forward::new("osmo1receiver", "channel-0");
+        assert_eq!(forward.port, "transfer");
+        assert_eq!(forward.timeout, None);
+        assert_eq!(forward.retries, None);
+        assert!(forward.validate().is_ok());
+    }
+
+    #[test]
+    fn test_forward_validate() {
+        let err = Forward::new(" ", "channel-0").validate().unwrap_err();
+        assert!(matches!(err, MemoError::EmptyReceiver));
+        let err = Forward::new("osmo1receiver", "0").validate().unwrap_err();
+        assert!(matches!(err, MemoError::InvalidChannel));
+        let err = Forward::new("osmo1receiver", "channel-0")
+            .port("")
+            .validate()
+            .unwrap_err();
+        assert!(matches!(err, MemoError::InvalidPort));
+    }
+}
@@ -0,0 +1,50 @@
+use crate::error::MemoError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Instructions for ibc-hooks to execute a contract with the received tokens attached. The
+// receiver of the incoming transfer must be the contract as well
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct WasmHook {
+    pub contract: String,
+    pub msg: Value,
+}
+
+impl WasmHook {
+    // The execute message is embedded as a JSON object, not as base64 like in a WasmMsg
+    pub fn new(contract: impl Into<String>, msg: &impl Serialize) -> Result<Self, MemoError> {
+        let hook = Self {
+            contract: contract.into(),
+            msg: serde_json::to_value(msg)?,
+        };
+        hook.validate()?;
+        Ok(hook)
+    }
+
+    pub fn validate(&self) -> Result<(), MemoError> {
+        if self.contract.trim().is_empty() {
+            return Err(MemoError::EmptyContract);
+        }
+        if !self.msg.is_object() {
+            return Err(MemoError::InvalidHookMsg);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_wasm_hook_new() {
+        let hook = WasmHook::new("manifest1contract", &json!({"convert": {}})).unwrap();
+        assert_eq!(hook.msg, json!({"convert": {}}));
+
+        let err = WasmHook::new("", &json!({"convert": {}})).unwrap_err();
+        assert!(matches!(err, MemoError::EmptyContract));
+        let err = WasmHook::new("manifest1contract", &"convert").unwrap_err();
+        assert!(matches!(err, MemoError::InvalidHookMsg));
+    }
+}
@@ -0,0 +1,9 @@
+mod error;
+mod forward;
+mod hooks;
+mod memo;
+
+pub use error::MemoError;
+pub use forward::Forward;
+pub use hooks::WasmHook;
+pub use memo::Memo;
@@ -0,0 +1,152 @@
+use crate::error::MemoError;
+use crate::forward::Forward;
+use crate::hooks::WasmHook;
+use serde::{Deserialize, Serialize};
+
+// Memo of an ICS-20 transfer, as expected by packet-forward-middleware and ibc-hooks
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Memo {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forward: Option<Forward>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wasm: Option<WasmHook>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ibc_callback: Option<String>,
+}
+
+impl Memo {
+    pub fn forward(forward: Forward) -> Self {
+        Self {
+            forward: Some(forward),
+            ..Default::default()
+        }
+    }
+
+    pub fn wasm(hook: WasmHook) -> Self {
+        Self {
+            wasm: Some(hook),
+            ..Default::default()
+        }
+    }
+
+    // Contract notified by ibc-hooks of the acknowledgement or timeout of the transfer
+    pub fn with_callback(mut self, contract: impl Into<String>) -> Self {
+        self.ibc_callback = Some(contract.into());
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), MemoError> {
+        if let Some(forward) = &self.forward {
+            forward.validate()?;
+        }
+        if let Some(hook) = &self.wasm {
+            hook.validate()?;
+        }
+        if self
+            .ibc_callback
+            .as_ref()
+            .is_some_and(|contract| contract.trim().is_empty())
+        {
+            return Err(MemoError::EmptyContract);
+        }
+        Ok(())
+    }
+
+    // Validate the memo and serialize it to the memo field of a transfer
+    pub fn to_json_string(&self) -> Result<String, MemoError> {
+        self.validate()?;
+        Ok(serde_json::to_string(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn to_value(memo: &Memo) -> Value {
+        serde_json::from_str(&memo.to_json_string().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_memo_empty() {
+        assert_eq!(Memo::default().to_json_string().unwrap(), "{}");
+    }
+
+    #[test]
+    fn test_memo_forward() {
+        let memo = Memo::forward(
+            Forward::new("osmo1receiver", "channel-3")
+                .timeout_seconds(600)
+                .retries(2),
+        );
+        assert_eq!(
+            to_value(&memo),
+            json!({"forward": {
+                "receiver": "osmo1receiver",
+                "port": "transfer",
+                "channel": "channel-3",
+                "timeout": "600s",
+                "retries": 2,
+            }})
+        );
+    }
+
+    #[test]
+    fn test_memo_multi_hop_wasm() {
+        let hook = WasmHook::new("manifest1contract", &json!({"convert": {}})).unwrap();
+        let memo = Memo::forward(
+            Forward::new("noble1receiver", "channel-1").next(Memo::forward(
+                Forward::new("manifest1contract", "channel-7").next(Memo::wasm(hook)),
+            )),
+        );
+        assert_eq!(
+            to_value(&memo),
+            json!({"forward": {
+                "receiver": "noble1receiver",
+                "port": "transfer",
+                "channel": "channel-1",
+                "next": {"forward": {
+                    "receiver": "manifest1contract",
+                    "port": "transfer",
+                    "channel": "channel-7",
+                    "next": {"wasm": {
+                        "contract": "manifest1contract",
+                        "msg": {"convert": {}},
+                    }},
+                }},
+            }})
+        );
+    }
+
+    #[test]
+    fn test_memo_callback() {
+        let memo = Memo::default().with_callback("manifest1contract");
+        assert_eq!(
+            to_value(&memo),
+            json!({"ibc_callback": "manifest1contract"})
+        );
+        let err = Memo::default()
+            .with_callback("")
+            .to_json_string()
+            .unwrap_err();
+        assert!(matches!(err, MemoError::EmptyContract));
+    }
+
+    #[test]
+    fn test_memo_invalid_nested() {
+        let memo = Memo::forward(
+            Forward::new("noble1receiver", "channel-1")
+                .next(Memo::forward(Forward::new("", "channel-7"))),
+        );
+        let err = memo.to_json_string().unwrap_err();
+        assert!(matches!(err, MemoError::EmptyReceiver));
+    }
+
+    #[test]
+    fn test_memo_roundtrip() {
+        let json = r#"{"forward":{"receiver":"osmo1receiver","port":"transfer","channel":"channel-3","timeout":"10m","retries":2}}"#;
+        let memo: Memo = serde_json::from_str(json).unwrap();
+        assert_eq!(memo.forward.unwrap().timeout.as_deref(), Some("10m"));
+    }
+}
@@ -0,0 +1,123 @@
+use crate::msg::PendingAdminResponse;
+use crate::AccessError;
+use cosmwasm_std::{Addr, Deps, DepsMut, StdResult, Storage};
+use cw_controllers::Admin;
+use cw_storage_plus::Item;
+use manifest_errors::AdminError;
+
+// Candidate of a two-step admin transfer. The admin proposes, the candidate accepts, so the
+// role is never handed to an address nobody controls
+pub struct PendingAdmin(Item<Addr>);
+
+impl PendingAdmin {
+    pub const fn new(namespace: &'static str) -> Self {
+        PendingAdmin(Item::new(namespace))
+    }
+
+    pub fn get(&self, storage: &dyn Storage) -> StdResult<Option<Addr>> {
+        self.0.may_load(storage)
+    }
+
+    // Propose a new admin, replacing the previous candidate. Only the admin can propose
+    pub fn propose(
+        &self,
+        deps: DepsMut,
+        admin: &Admin,
+        sender: &Addr,
+        candidate: &Addr,
+    ) -> Result<(), AccessError> {
+        assert_admin(deps.as_ref(), admin, sender)?;
+        Ok(self.0.save(deps.storage, candidate)?)
+    }
+
+    // Remove the candidate, checking the sender is the candidate. The caller sets the admin,
+    // so it can record the change along with it
+    pub fn accept(&self, storage: &mut dyn Storage, sender: &Addr) -> Result<Addr, AccessError> {
+        let candidate = self.get(storage)?.ok_or(AdminError::NoPendingAdmin)?;
+        if candidate != *sender {
+            return Err(AdminError::NotPendingAdmin.into());
+        }
+        self.0.remove(storage);
+        Ok(candidate)
+    }
+
+    // Withdraw the candidate. Only the admin can cancel
+    pub fn cancel(&self, deps: DepsMut, admin: &Admin, sender: &Addr) -> Result<Addr, AccessError> {
+        assert_admin(deps.as_ref(), admin, sender)?;
+        let candidate = self.get(deps.storage)?.ok_or(AdminError::NoPendingAdmin)?;
+        self.0.remove(deps.storage);
+        Ok(candidate)
+    }
+
+    // Remove the candidate without checks, e.g. when governance sets the admin through sudo
+    pub fn clear(&self, storage: &mut dyn Storage) {
+        self.0.remove(storage);
+    }
+
+    pub fn query(&self, storage: &dyn Storage) -> StdResult<PendingAdminResponse> {
+        Ok(PendingAdminResponse {
+            pending_admin: self.get(storage)?,
+        })
+    }
+}
+
+// Admin::assert_admin fails with its own error type, mapped to the shared category
+pub(crate) fn assert_admin(deps: Deps, admin: &Admin, sender: &Addr) -> Result<(), AccessError> {
+    admin
+        .assert_admin(deps, sender)
+        .map_err(|_| AdminError::NotAdmin.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::admin_error;
+    use cosmwasm_std::testing::mock_dependencies;
+
+    const ADMIN: Admin = Admin::new("admin");
+    const PENDING_ADMIN: PendingAdmin = PendingAdmin::new("pending_admin");
+
+    #[test]
+    fn two_step_transfer() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let candidate = deps.api.addr_make("candidate");
+        ADMIN.set(deps.as_mut(), Some(admin.clone())).unwrap();
+
+        let res = PENDING_ADMIN.propose(deps.as_mut(), &ADMIN, &candidate, &candidate);
+        assert_eq!(admin_error(res), AdminError::NotAdmin);
+        let res = PENDING_ADMIN.accept(&mut deps.storage, &candidate);
+        assert_eq!(admin_error(res), AdminError::NoPendingAdmin);
+
+        PENDING_ADMIN
+            .propose(deps.as_mut(), &ADMIN, &admin, &candidate)
+            .unwrap();
+        let pending = PENDING_ADMIN.get(&deps.storage).unwrap();
+        assert_eq!(pending, Some(candidate.clone()));
+        let res = PENDING_ADMIN.accept(&mut deps.storage, &admin);
+        assert_eq!(admin_error(res), AdminError::NotPendingAdmin);
+        let accepted = PENDING_ADMIN.accept(&mut deps.storage, &candidate).unwrap();
+        assert_eq!(accepted, candidate);
+        assert_eq!(PENDING_ADMIN.get(&deps.storage).unwrap(), None);
+    }
+
+    #[test]
+    fn cancel() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let candidate = deps.api.addr_make("candidate");
+        ADMIN.set(deps.as_mut(), Some(admin.clone())).unwrap();
+
+        let res = PENDING_ADMIN.cancel(deps.as_mut(), &ADMIN, &admin);
+        assert_eq!(admin_error(res), AdminError::NoPendingAdmin);
+        PENDING_ADMIN
+            .propose(deps.as_mut(), &ADMIN, &admin, &candidate)
+            .unwrap();
+        let res = PENDING_ADMIN.cancel(deps.as_mut(), &ADMIN, &candidate);
+        assert_eq!(admin_error(res), AdminError::NotAdmin);
+        let cancelled = PENDING_ADMIN.cancel(deps.as_mut(), &ADMIN, &admin).unwrap();
+        assert_eq!(cancelled, candidate);
+        let res = PENDING_ADMIN.accept(&mut deps.storage, &candidate);
+        assert_eq!(admin_error(res), AdminError::NoPendingAdmin);
+    }
+}
@@ -0,0 +1,31 @@
+mod admin;
+mod msg;
+mod roles;
+
+pub use admin::PendingAdmin;
+pub use msg::{
+    AccessMsg, AccessQueryMsg, AccessSudoMsg, HasRoleResponse, PendingAdminResponse,
+    RoleMembersResponse,
+};
+pub use roles::{validate_role, Roles, PAUSER};
+
+use cosmwasm_std::StdError;
+use manifest_errors::AdminError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AccessError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+    #[error("{0}")]
+    Admin(#[from] AdminError),
+}
+
+// The shared category of an access error, failing the test on anything else
+#[cfg(test)]
+pub(crate) fn admin_error<T: std::fmt::Debug>(res: Result<T, AccessError>) -> AdminError {
+    match res {
+        Err(AccessError::Admin(err)) => err,
+        res => panic!("expected an admin error, got {res:?}"),
+    }
+}
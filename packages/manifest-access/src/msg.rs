@@ -0,0 +1,45 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Addr;
+
+// Execute messages of the access control, nested by contracts under one variant of their own
+// execute message, e.g. `{"access": {"accept_admin": {}}}`
+#[cw_serde]
+pub enum AccessMsg {
+    // Propose a new admin, who becomes admin once they accept. Replaces a previous proposal
+    ProposeAdmin { admin: String },
+    AcceptAdmin {},
+    CancelAdminProposal {},
+    GrantRole { role: String, address: String },
+    RevokeRole { role: String, address: String },
+    // Give up a role held by the sender
+    RenounceRole { role: String },
+}
+
+// Messages only chain governance can send, overriding the admin of a contract whose admin key
+// is lost or compromised
+#[cw_serde]
+pub enum AccessSudoMsg {
+    ForceAdmin { admin: String },
+}
+
+#[cw_serde]
+pub enum AccessQueryMsg {
+    PendingAdmin {},
+    RoleMembers { role: String },
+    HasRole { role: String, address: String },
+}
+
+#[cw_serde]
+pub struct PendingAdminResponse {
+    pub pending_admin: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct RoleMembersResponse {
+    pub members: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct HasRoleResponse {
+    pub has_role: bool,
+}
@@ -0,0 +1,161 @@
+use crate::admin::assert_admin;
+use crate::msg::{HasRoleResponse, RoleMembersResponse};
+use crate::AccessError;
+use cosmwasm_std::{Addr, Deps, Empty, Order, StdResult, Storage};
+use cw_controllers::Admin;
+use cw_storage_plus::Map;
+use manifest_errors::AdminError;
+
+// Role allowed to pause a contract. Resuming is left to the admin
+pub const PAUSER: &str = "pauser";
+
+// Ensure a role is one the contract knows about, so a typo is not granted silently
+pub fn validate_role(role: &str, known: &[&str]) -> Result<(), AccessError> {
+    if !known.contains(&role) {
+        return Err(AdminError::UnknownRole(role.to_string()).into());
+    }
+    Ok(())
+}
+
+// Addresses holding each role, granted and revoked by the admin
+pub struct Roles(Map<(&'static str, &'static Addr), Empty>);
+
+impl Roles {
+    pub const fn new(namespace: &'static str) -> Self {
+        Roles(Map::new(namespace))
+    }
+
+    pub fn has_role(&self, storage: &dyn Storage, role: &str, addr: &Addr) -> bool {
+        self.map().has(storage, (role, addr))
+    }
+
+    pub fn grant(&self, storage: &mut dyn Storage, role: &str, addr: &Addr) -> StdResult<()> {
+        self.map().save(storage, (role, addr), &Empty {})
+    }
+
+    pub fn revoke(&self, storage: &mut dyn Storage, role: &str, addr: &Addr) {
+        self.map().remove(storage, (role, addr));
+    }
+
+    pub fn assert_role(
+        &self,
+        storage: &dyn Storage,
+        role: &str,
+        addr: &Addr,
+    ) -> Result<(), AccessError> {
+        if !self.has_role(storage, role, addr) {
+            return Err(AdminError::MissingRole(role.to_string()).into());
+        }
+        Ok(())
+    }
+
+    // The admin holds every role implicitly
+    pub fn assert_admin_or_role(
+        &self,
+        deps: Deps,
+        admin: &Admin,
+        role: &str,
+        addr: &Addr,
+    ) -> Result<(), AccessError> {
+        if assert_admin(deps, admin, addr).is_ok() {
+            return Ok(());
+        }
+        self.assert_role(deps.storage, role, addr)
+    }
+
+    // Members of a role in address order. Roles are granted by the admin to a handful of
+    // operators, so the list is returned whole
+    pub fn members(&self, storage: &dyn Storage, role: &str) -> StdResult<Vec<Addr>> {
+        self.map()
+            .prefix(role)
+            .keys(storage, None, None, Order::Ascending)
+            .collect()
+    }
+
+    pub fn query_members(
+        &self,
+        storage: &dyn Storage,
+        role: &str,
+    ) -> StdResult<RoleMembersResponse> {
+        Ok(RoleMembersResponse {
+            members: self.members(storage, role)?,
+        })
+    }
+
+    pub fn query_has_role(
+        &self,
+        storage: &dyn Storage,
+        role: &str,
+        addr: &Addr,
+    ) -> HasRoleResponse {
+        HasRoleResponse {
+            has_role: self.has_role(storage, role, addr),
+        }
+    }
+
+    // The keys borrow from the caller, shorten the 'static lifetimes of the constant
+    fn map<'a>(&self) -> &Map<(&'a str, &'a Addr), Empty> {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::admin_error;
+    use cosmwasm_std::testing::mock_dependencies;
+
+    const ADMIN: Admin = Admin::new("admin");
+    const ROLES: Roles = Roles::new("roles");
+
+    #[test]
+    fn grant_and_revoke() {
+        let mut deps = mock_dependencies();
+        let pauser = deps.api.addr_make("pauser");
+        let other = deps.api.addr_make("other");
+
+        ROLES.grant(&mut deps.storage, PAUSER, &pauser).unwrap();
+        ROLES.grant(&mut deps.storage, PAUSER, &other).unwrap();
+        ROLES.grant(&mut deps.storage, "operator", &other).unwrap();
+        assert!(ROLES.has_role(&deps.storage, PAUSER, &pauser));
+        assert!(!ROLES.has_role(&deps.storage, "operator", &pauser));
+
+        let mut expected = vec![pauser.clone(), other.clone()];
+        expected.sort();
+        assert_eq!(ROLES.members(&deps.storage, PAUSER).unwrap(), expected);
+
+        ROLES.revoke(&mut deps.storage, PAUSER, &pauser);
+        assert_eq!(
+            admin_error(ROLES.assert_role(&deps.storage, PAUSER, &pauser)),
+            AdminError::MissingRole(PAUSER.to_string())
+        );
+        assert_eq!(ROLES.members(&deps.storage, PAUSER).unwrap(), vec![other]);
+    }
+
+    #[test]
+    fn admin_holds_every_role() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let pauser = deps.api.addr_make("pauser");
+        let other = deps.api.addr_make("other");
+        ADMIN.set(deps.as_mut(), Some(admin.clone())).unwrap();
+        ROLES.grant(&mut deps.storage, PAUSER, &pauser).unwrap();
+
+        let assert = |addr| ROLES.assert_admin_or_role(deps.as_ref(), &ADMIN, PAUSER, addr);
+        assert(&admin).unwrap();
+        assert(&pauser).unwrap();
+        assert_eq!(
+            admin_error(assert(&other)),
+            AdminError::MissingRole(PAUSER.to_string())
+        );
+    }
+
+    #[test]
+    fn unknown_role() {
+        validate_role(PAUSER, &[PAUSER]).unwrap();
+        assert_eq!(
+            admin_error(validate_role("pauser ", &[PAUSER])),
+            AdminError::UnknownRole("pauser ".to_string())
+        );
+    }
+}
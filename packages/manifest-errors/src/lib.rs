@@ -0,0 +1,137 @@
+use thiserror::Error;
+
+// A stable numeric code identifying an error independently of its message. Codes are grouped
+// by category, and a code is never reused once released
+pub trait ErrorCode {
+    fn code(&self) -> u32;
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum AdminError {
+    #[error("only admin can perform this action")]
+    NotAdmin,
+    #[error("cannot renounce admin role")]
+    CannotRenounce,
+    #[error("no admin transfer pending")]
+    NoPendingAdmin,
+    #[error("only the proposed admin can accept the transfer")]
+    NotPendingAdmin,
+    #[error("sender does not have the {0} role")]
+    MissingRole(String),
+    #[error("unknown role {0}")]
+    UnknownRole(String),
+}
+
+impl ErrorCode for AdminError {
+    fn code(&self) -> u32 {
+        match self {
+            AdminError::NotAdmin => 1001,
+            AdminError::CannotRenounce => 1002,
+            AdminError::NoPendingAdmin => 1003,
+            AdminError::NotPendingAdmin => 1004,
+            AdminError::MissingRole(_) => 1005,
+            AdminError::UnknownRole(_) => 1006,
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum AmountError {
+    #[error("amount is zero")]
+    AmountIsZero,
+    #[error("amount exceeds maximum")]
+    AmountExceedsMax,
+    #[error("failed to parse amount")]
+    InvalidAmountParsing,
+    #[error("non-payable function called with funds")]
+    NonPayable,
+}
+
+impl ErrorCode for AmountError {
+    fn code(&self) -> u32 {
+        match self {
+            AmountError::AmountIsZero => 2001,
+            AmountError::AmountExceedsMax => 2002,
+            AmountError::InvalidAmountParsing => 2003,
+            AmountError::NonPayable => 2004,
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum DenomError {
+    #[error("denom is empty")]
+    EmptyDenom,
+    #[error("invalid ibc denom format")]
+    InvalidIbcDenomFormat,
+    #[error("invalid factory denom format")]
+    InvalidFactoryDenomFormat,
+    #[error("invalid denom format")]
+    InvalidDenomFormat,
+    #[error("denom has no supply and no metadata")]
+    UnknownDenom,
+    #[error("invalid denom symbol")]
+    InvalidSymbol,
+}
+
+impl ErrorCode for DenomError {
+    fn code(&self) -> u32 {
+        match self {
+            DenomError::EmptyDenom => 3001,
+            DenomError::InvalidIbcDenomFormat => 3002,
+            DenomError::InvalidFactoryDenomFormat => 3003,
+            DenomError::InvalidDenomFormat => 3004,
+            DenomError::UnknownDenom => 3005,
+            DenomError::InvalidSymbol => 3006,
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum MigrateError {
+    #[error("invalid contract name")]
+    InvalidContractName,
+}
+
+impl ErrorCode for MigrateError {
+    fn code(&self) -> u32 {
+        match self {
+            MigrateError::InvalidContractName => 4001,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Codes are part of the public interface, changing one is a breaking change
+    #[test]
+    fn codes_are_stable() {
+        assert_eq!(AdminError::NotAdmin.code(), 1001);
+        assert_eq!(AdminError::CannotRenounce.code(), 1002);
+        assert_eq!(AdminError::UnknownRole("pauser".to_string()).code(), 1006);
+        assert_eq!(AmountError::AmountIsZero.code(), 2001);
+        assert_eq!(AmountError::NonPayable.code(), 2004);
+        assert_eq!(DenomError::EmptyDenom.code(), 3001);
+        assert_eq!(DenomError::InvalidSymbol.code(), 3006);
+        assert_eq!(MigrateError::InvalidContractName.code(), 4001);
+    }
+
+    #[test]
+    fn messages_are_stable() {
+        assert_eq!(
+            AdminError::NotAdmin.to_string(),
+            "only admin can perform this action"
+        );
+        assert_eq!(
+            AmountError::NonPayable.to_string(),
+            "non-payable function called with funds"
+        );
+        assert_eq!(DenomError::EmptyDenom.to_string(), "denom is empty");
+        assert_eq!(
+            MigrateError::InvalidContractName.to_string(),
+            "invalid contract name"
+        );
+    }
+}